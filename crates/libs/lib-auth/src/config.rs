@@ -20,6 +20,7 @@ pub struct AuthConfig {
 
 	pub REFRESH_TOKEN_DURATION_SEC: f64,
 	pub ACCESS_TOKEN_DURATION_SEC: f64,
+	pub FILE_TOKEN_DURATION_SEC: f64,
 }
 
 impl AuthConfig {
@@ -32,6 +33,7 @@ impl AuthConfig {
 
 			REFRESH_TOKEN_DURATION_SEC: get_env_parse("SERVICE_REFRESH_TOKEN_DURATION_SEC")?,
 			ACCESS_TOKEN_DURATION_SEC: get_env_parse("SERVICE_ACCESS_TOKEN_DURATION_SEC")?,
+			FILE_TOKEN_DURATION_SEC: get_env_parse("SERVICE_FILE_TOKEN_DURATION_SEC")?,
 		})
 	}
 }
@@ -88,6 +88,23 @@ pub fn validate_web_token(origin_token: &Token, salt: Uuid) -> Result<()> {
 
 // endregion: --- Web Token Gen and Validation
 
+// region:    --- File Token Gen and Validation
+
+/// Short-lived token for a download link, `ident` names the file and the user it was issued to.
+pub fn generate_file_token(ident: &str, salt: Uuid) -> Result<Token> {
+	let config = &auth_config();
+	_generate_token(ident, config.FILE_TOKEN_DURATION_SEC, salt, &config.TOKEN_KEY)
+}
+
+pub fn validate_file_token(origin_token: &Token, salt: Uuid) -> Result<()> {
+	let config = &auth_config();
+	_validate_token_sign_and_exp(origin_token, salt, &config.TOKEN_KEY)?;
+
+	Ok(())
+}
+
+// endregion: --- File Token Gen and Validation
+
 // region:    --- (private) Token Gen and Validation
 
 fn _generate_token(
@@ -0,0 +1,129 @@
+use serde_json::Value;
+
+use crate::{interfaces::exercise::ExerciseResult, models::exercise::{Connection, ConnectionDiff, ConspectDiff, Node, NodeDiff, NodeDiffState, NodeType, StageDiff}};
+
+use super::exercise_checker::ExerciseChecker;
+
+pub struct ConspectDiffBuilder;
+
+impl ConspectDiffBuilder {
+    /// Builds a structural diff of a submitted conspect against the answer.
+    /// A missing submission is treated as an empty conspect.
+    pub fn diff_conspects(answer_conspect: Value, solution_conspect: Option<Value>) -> ExerciseResult<ConspectDiff> {
+        let answer_conspect = ExerciseChecker::parse_conspect(answer_conspect)?;
+
+        let (solution_connections, solution_nodes) = match solution_conspect {
+            Some(solution_conspect) => {
+                let solution_conspect = ExerciseChecker::parse_conspect(solution_conspect)?;
+                (solution_conspect.connections, solution_conspect.nodes)
+            },
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let added_connections = Self::missing_connections(&solution_connections, &answer_conspect.connections);
+        let removed_connections = Self::missing_connections(&answer_conspect.connections, &solution_connections);
+        let nodes = Self::diff_nodes(&answer_conspect.nodes, &solution_nodes)?;
+
+        Ok(ConspectDiff {
+            added_connections,
+            removed_connections,
+            nodes,
+        })
+    }
+
+    fn missing_connections(connections: &[Connection], other_connections: &[Connection]) -> Vec<ConnectionDiff> {
+        connections
+            .iter()
+            .filter(|connection| ExerciseChecker::find_connection(connection, other_connections).is_none())
+            .map(|connection| ConnectionDiff {
+                from: connection.from.clone(),
+                to: connection.to.clone(),
+            })
+            .collect()
+    }
+
+    fn diff_nodes(answer_nodes: &[Node], solution_nodes: &[Node]) -> ExerciseResult<Vec<NodeDiff>> {
+        let mut result = Vec::new();
+
+        for answer_node in answer_nodes {
+            let node_diff = match ExerciseChecker::find_node(&answer_node.id, solution_nodes) {
+                Some(solution_node) => Self::diff_node(answer_node, solution_node)?,
+                None => NodeDiff {
+                    id: answer_node.id.clone(),
+                    x: answer_node.x,
+                    y: answer_node.y,
+                    state: NodeDiffState::Removed,
+                    answer_body: Some(answer_node.body.clone()),
+                    solution_body: None,
+                    stages: Vec::new(),
+                },
+            };
+
+            result.push(node_diff);
+        }
+
+        for solution_node in solution_nodes {
+            if ExerciseChecker::find_node(&solution_node.id, answer_nodes).is_some() {
+                continue;
+            }
+
+            result.push(NodeDiff {
+                id: solution_node.id.clone(),
+                x: solution_node.x,
+                y: solution_node.y,
+                state: NodeDiffState::Added,
+                answer_body: None,
+                solution_body: Some(solution_node.body.clone()),
+                stages: Vec::new(),
+            });
+        }
+
+        Ok(result)
+    }
+
+    fn diff_node(answer_node: &Node, solution_node: &Node) -> ExerciseResult<NodeDiff> {
+        let stages = match (&answer_node.node_type, &solution_node.node_type) {
+            (NodeType::ProcessStages, NodeType::ProcessStages) => Self::diff_stages(answer_node, solution_node)?,
+            _ => Vec::new(),
+        };
+
+        let state = if answer_node.body == solution_node.body {
+            NodeDiffState::Unchanged
+        } else {
+            NodeDiffState::Changed
+        };
+
+        Ok(NodeDiff {
+            id: solution_node.id.clone(),
+            x: solution_node.x,
+            y: solution_node.y,
+            state,
+            answer_body: Some(answer_node.body.clone()),
+            solution_body: Some(solution_node.body.clone()),
+            stages,
+        })
+    }
+
+    fn diff_stages(answer_node: &Node, solution_node: &Node) -> ExerciseResult<Vec<StageDiff>> {
+        let answer_stages = ExerciseChecker::parse_process_stages(answer_node)?;
+        let solution_stages = ExerciseChecker::parse_process_stages(solution_node)?;
+
+        let number_of_stages = answer_stages.len().max(solution_stages.len());
+        let mut result = Vec::new();
+
+        for position in 0..number_of_stages {
+            let answer_stage_id = answer_stages.get(position).map(|stage| stage.id);
+            let solution_stage_id = solution_stages.get(position).map(|stage| stage.id);
+
+            if answer_stage_id != solution_stage_id {
+                result.push(StageDiff {
+                    position,
+                    answer_stage_id,
+                    solution_stage_id,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+}
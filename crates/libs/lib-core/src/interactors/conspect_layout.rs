@@ -0,0 +1,455 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{interfaces::exercise::ExerciseResult, models::exercise::{Connection, ConspectLayoutAlgorithm, MAX_FORCE_DIRECTED_NODES}};
+
+use super::error::ExerciseError;
+
+const NODE_SPACING: f64 = 250.0;
+const LAYER_SPACING: f64 = 200.0;
+const ORDERING_SWEEPS: usize = 8;
+const FORCE_ITERATIONS: usize = 300;
+
+/// The part of a conspect the layout needs, nodes may come without positions.
+#[derive(Deserialize)]
+struct LayoutConspect {
+    connections: Vec<Connection>,
+    nodes: Vec<LayoutNode>,
+}
+
+#[derive(Deserialize)]
+struct LayoutNode {
+    id: String,
+}
+
+struct LayoutGraph {
+    ids: Vec<String>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl LayoutGraph {
+    fn new(conspect: &LayoutConspect) -> Self {
+        let ids: Vec<String> = conspect.nodes.iter().map(|node| node.id.clone()).collect();
+        let indexes: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (id.as_str(), index))
+            .collect();
+
+        let mut edges = Vec::new();
+        let mut seen = HashSet::new();
+
+        for connection in &conspect.connections {
+            let (from, to) = match (indexes.get(connection.from.as_str()), indexes.get(connection.to.as_str())) {
+                (Some(from), Some(to)) => (*from, *to),
+                _ => continue,
+            };
+
+            if from != to && seen.insert((from, to)) {
+                edges.push((from, to));
+            }
+        }
+
+        Self { ids, edges }
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn is_acyclic(&self) -> bool {
+        Self::topological_order(self.len(), &self.edges).len() == self.len()
+    }
+
+    fn topological_order(len: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut in_degree = vec![0; len];
+        let mut successors = vec![Vec::new(); len];
+
+        for &(from, to) in edges {
+            in_degree[to] += 1;
+            successors[from].push(to);
+        }
+
+        let mut queue: VecDeque<usize> = (0..len).filter(|&v| in_degree[v] == 0).collect();
+        let mut result = Vec::with_capacity(len);
+
+        while let Some(v) = queue.pop_front() {
+            result.push(v);
+
+            for &w in &successors[v] {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+pub struct ConspectLayout;
+
+impl ConspectLayout {
+    /// Returns the same body with recomputed `x`/`y` for every node,
+    /// all other fields are left untouched. `Auto` lays out a large graph with cycles
+    /// in layers as well.
+    pub fn layout_conspect(body: Value, algorithm: ConspectLayoutAlgorithm) -> ExerciseResult<Value> {
+        let conspect: LayoutConspect = serde_json::from_value(body.clone())
+            .map_err(|err| ExerciseError::IncorrectExerciseBodyFormat { description: err.to_string() })?;
+
+        let graph = LayoutGraph::new(&conspect);
+        let fits_force_directed = graph.len() <= MAX_FORCE_DIRECTED_NODES;
+
+        let positions = match algorithm {
+            ConspectLayoutAlgorithm::Layered => Self::layered(&graph),
+            ConspectLayoutAlgorithm::ForceDirected if fits_force_directed => Self::force_directed(&graph),
+            ConspectLayoutAlgorithm::ForceDirected => {
+                return Err(ExerciseError::TooManyNodesForLayout {
+                    number_of_nodes: graph.len(),
+                    max_number_of_nodes: MAX_FORCE_DIRECTED_NODES,
+                }.into());
+            },
+            ConspectLayoutAlgorithm::Auto if graph.is_acyclic() || !fits_force_directed => Self::layered(&graph),
+            ConspectLayoutAlgorithm::Auto => Self::force_directed(&graph),
+        };
+
+        Self::apply_positions(body, &graph, &Self::normalize(positions))
+    }
+
+    // region:    --- Layered (Sugiyama)
+
+    fn layered(graph: &LayoutGraph) -> Vec<(f64, f64)> {
+        let edges = Self::remove_cycles(graph);
+        let layers = Self::assign_layers(graph.len(), &edges);
+        let (mut ordering, proper_edges) = Self::insert_dummy_vertices(graph.len(), &edges, &layers);
+
+        Self::reduce_crossings(&mut ordering, &proper_edges);
+
+        let mut positions = vec![(0.0, 0.0); graph.len()];
+
+        for (layer, vertices) in ordering.iter().enumerate() {
+            let offset = (vertices.len() as f64 - 1.0) / 2.0;
+
+            for (position, &v) in vertices.iter().enumerate() {
+                if v < graph.len() {
+                    positions[v] = (
+                        (position as f64 - offset) * NODE_SPACING,
+                        layer as f64 * LAYER_SPACING,
+                    );
+                }
+            }
+        }
+
+        positions
+    }
+
+    /// Reverses the back edges found by a depth-first search so the graph becomes acyclic.
+    fn remove_cycles(graph: &LayoutGraph) -> Vec<(usize, usize)> {
+        let mut successors = vec![Vec::new(); graph.len()];
+        for &(from, to) in &graph.edges {
+            successors[from].push(to);
+        }
+
+        // 0 - not visited, 1 - on stack, 2 - done
+        let mut state = vec![0u8; graph.len()];
+        let mut back_edges = HashSet::new();
+
+        for root in 0..graph.len() {
+            if state[root] != 0 {
+                continue;
+            }
+
+            let mut stack = vec![(root, 0)];
+            state[root] = 1;
+
+            while let Some((v, next)) = stack.pop() {
+                if let Some(&w) = successors[v].get(next) {
+                    stack.push((v, next + 1));
+
+                    match state[w] {
+                        0 => {
+                            state[w] = 1;
+                            stack.push((w, 0));
+                        },
+                        1 => {
+                            back_edges.insert((v, w));
+                        },
+                        _ => {},
+                    }
+                } else {
+                    state[v] = 2;
+                }
+            }
+        }
+
+        graph.edges
+            .iter()
+            .map(|&(from, to)| if back_edges.contains(&(from, to)) { (to, from) } else { (from, to) })
+            .collect()
+    }
+
+    /// Longest path layering: every vertex is placed one layer below its deepest predecessor.
+    fn assign_layers(len: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut predecessors = vec![Vec::new(); len];
+        for &(from, to) in edges {
+            predecessors[to].push(from);
+        }
+
+        let mut layers = vec![0; len];
+        for v in LayoutGraph::topological_order(len, edges) {
+            layers[v] = predecessors[v]
+                .iter()
+                .map(|&u| layers[u] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        layers
+    }
+
+    /// Splits edges spanning several layers with dummy vertices (ids >= `len`),
+    /// so that every edge connects two adjacent layers.
+    fn insert_dummy_vertices(
+        len: usize,
+        edges: &[(usize, usize)],
+        layers: &[usize],
+    ) -> (Vec<Vec<usize>>, Vec<(usize, usize)>) {
+        let number_of_layers = layers.iter().max().map_or(0, |max| max + 1);
+        let mut ordering = vec![Vec::new(); number_of_layers];
+        for (v, &layer) in layers.iter().enumerate() {
+            ordering[layer].push(v);
+        }
+
+        let mut proper_edges = Vec::new();
+        let mut next_vertex = len;
+
+        for &(from, to) in edges {
+            let mut previous = from;
+
+            for layer_vertices in &mut ordering[layers[from] + 1..layers[to]] {
+                layer_vertices.push(next_vertex);
+                proper_edges.push((previous, next_vertex));
+                previous = next_vertex;
+                next_vertex += 1;
+            }
+
+            proper_edges.push((previous, to));
+        }
+
+        (ordering, proper_edges)
+    }
+
+    /// Barycenter heuristic with alternating down and up sweeps, keeps the best ordering found.
+    fn reduce_crossings(ordering: &mut [Vec<usize>], edges: &[(usize, usize)]) {
+        let mut best = ordering.to_vec();
+        let mut best_crossings = Self::count_crossings(ordering, edges);
+
+        for sweep in 0..ORDERING_SWEEPS {
+            if best_crossings == 0 {
+                break;
+            }
+
+            if sweep % 2 == 0 {
+                for layer in 1..ordering.len() {
+                    let (fixed, free) = ordering.split_at_mut(layer);
+                    Self::order_by_barycenter(&mut free[0], &fixed[layer - 1], edges, false);
+                }
+            } else {
+                for layer in (0..ordering.len().saturating_sub(1)).rev() {
+                    let (free, fixed) = ordering.split_at_mut(layer + 1);
+                    Self::order_by_barycenter(&mut free[layer], &fixed[0], edges, true);
+                }
+            }
+
+            let crossings = Self::count_crossings(ordering, edges);
+            if crossings < best_crossings {
+                best_crossings = crossings;
+                best = ordering.to_vec();
+            }
+        }
+
+        ordering.clone_from_slice(&best);
+    }
+
+    fn order_by_barycenter(free: &mut [usize], fixed: &[usize], edges: &[(usize, usize)], by_successors: bool) {
+        let fixed_positions: HashMap<usize, usize> = fixed.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+
+        let mut barycenters: Vec<(f64, usize)> = free
+            .iter()
+            .enumerate()
+            .map(|(position, &v)| {
+                let neighbours: Vec<f64> = edges
+                    .iter()
+                    .filter_map(|&(from, to)| {
+                        let (own, other) = if by_successors { (from, to) } else { (to, from) };
+                        if own == v { fixed_positions.get(&other).map(|&p| p as f64) } else { None }
+                    })
+                    .collect();
+
+                let barycenter = if neighbours.is_empty() {
+                    position as f64
+                } else {
+                    neighbours.iter().sum::<f64>() / neighbours.len() as f64
+                };
+
+                (barycenter, v)
+            })
+            .collect();
+
+        barycenters.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (slot, (_, v)) in free.iter_mut().zip(barycenters) {
+            *slot = v;
+        }
+    }
+
+    fn count_crossings(ordering: &[Vec<usize>], edges: &[(usize, usize)]) -> usize {
+        let positions: HashMap<usize, usize> = ordering
+            .iter()
+            .flat_map(|vertices| vertices.iter().enumerate().map(|(i, &v)| (v, i)))
+            .collect();
+
+        let segments: Vec<(usize, usize)> = edges
+            .iter()
+            .map(|(from, to)| (positions[from], positions[to]))
+            .collect();
+
+        let layer_of: HashMap<usize, usize> = ordering
+            .iter()
+            .enumerate()
+            .flat_map(|(layer, vertices)| vertices.iter().map(move |&v| (v, layer)))
+            .collect();
+
+        let mut crossings = 0;
+        for i in 0..edges.len() {
+            for j in i + 1..edges.len() {
+                if layer_of[&edges[i].0] != layer_of[&edges[j].0] {
+                    continue;
+                }
+
+                let (a, b) = (segments[i], segments[j]);
+                if (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1) {
+                    crossings += 1;
+                }
+            }
+        }
+
+        crossings
+    }
+
+    // endregion: --- Layered (Sugiyama)
+
+    // region:    --- Force directed (Fruchterman-Reingold)
+
+    fn force_directed(graph: &LayoutGraph) -> Vec<(f64, f64)> {
+        let len = graph.len();
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let k = NODE_SPACING;
+        let radius = (k * len as f64 / std::f64::consts::TAU).max(k);
+
+        let mut positions: Vec<(f64, f64)> = (0..len)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / len as f64;
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+
+        let initial_temperature = radius / 2.0;
+
+        for iteration in 0..FORCE_ITERATIONS {
+            let temperature = initial_temperature * (1.0 - iteration as f64 / FORCE_ITERATIONS as f64);
+            let mut displacements = vec![(0.0, 0.0); len];
+
+            for v in 0..len {
+                for u in 0..len {
+                    if u == v {
+                        continue;
+                    }
+
+                    let (dx, dy, distance) = Self::delta(positions[v], positions[u], v);
+                    let force = k * k / distance;
+                    displacements[v].0 += dx / distance * force;
+                    displacements[v].1 += dy / distance * force;
+                }
+            }
+
+            for &(from, to) in &graph.edges {
+                let (dx, dy, distance) = Self::delta(positions[from], positions[to], from);
+                let force = distance * distance / k;
+                displacements[from].0 -= dx / distance * force;
+                displacements[from].1 -= dy / distance * force;
+                displacements[to].0 += dx / distance * force;
+                displacements[to].1 += dy / distance * force;
+            }
+
+            for (position, (dx, dy)) in positions.iter_mut().zip(displacements) {
+                let length = (dx * dx + dy * dy).sqrt();
+                if length > 0.0 {
+                    let step = length.min(temperature);
+                    position.0 += dx / length * step;
+                    position.1 += dy / length * step;
+                }
+            }
+        }
+
+        positions
+    }
+
+    /// Vector from `b` to `a` with its length, coincident vertices are pushed apart deterministically.
+    fn delta(a: (f64, f64), b: (f64, f64), seed: usize) -> (f64, f64, f64) {
+        let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        if distance < 0.01 {
+            let angle = seed as f64;
+            return (0.01 * angle.cos(), 0.01 * angle.sin(), 0.01);
+        }
+
+        (dx, dy, distance)
+    }
+
+    // endregion: --- Force directed (Fruchterman-Reingold)
+
+    fn normalize(positions: Vec<(f64, f64)>) -> Vec<(i64, i64)> {
+        let min_x = positions.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+        let min_y = positions.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+
+        positions
+            .iter()
+            .map(|(x, y)| ((x - min_x).round() as i64, (y - min_y).round() as i64))
+            .collect()
+    }
+
+    fn apply_positions(mut body: Value, graph: &LayoutGraph, positions: &[(i64, i64)]) -> ExerciseResult<Value> {
+        let positions: HashMap<&str, (i64, i64)> = graph.ids
+            .iter()
+            .map(String::as_str)
+            .zip(positions.iter().copied())
+            .collect();
+
+        let nodes = body
+            .get_mut("nodes")
+            .and_then(Value::as_array_mut)
+            .ok_or(ExerciseError::IncorrectExerciseBodyFormat { description: "nodes must be an array".to_string() })?;
+
+        for node in nodes {
+            let position = node
+                .get("id")
+                .and_then(Value::as_str)
+                .and_then(|id| positions.get(id).copied());
+
+            if let (Some((x, y)), Some(node)) = (position, node.as_object_mut()) {
+                node.insert("x".to_string(), x.into());
+                node.insert("y".to_string(), y.into());
+            }
+        }
+
+        Ok(body)
+    }
+}
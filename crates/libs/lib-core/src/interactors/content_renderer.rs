@@ -0,0 +1,99 @@
+use pulldown_cmark::{html, Options, Parser};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{interfaces::lesson::LessonResult, models::content_block::{AttachmentBody, CalloutBody, ContentBlockType, ImageBody, MarkdownBody, VideoBody}};
+
+use super::error::LessonError;
+
+/// Hosts of the players a video block may embed in its iframe.
+const VIDEO_EMBED_HOSTS: [&str; 5] = [
+    "www.youtube.com",
+    "youtube.com",
+    "www.youtube-nocookie.com",
+    "player.vimeo.com",
+    "rutube.ru",
+];
+
+pub struct ContentRenderer;
+
+impl ContentRenderer {
+    /// Checks the body against the block type and returns the HTML shown to students.
+    /// Blocks without text (image, video, attachment) are rendered by the client from the body.
+    pub fn render_block(block_type: &ContentBlockType, body: Value) -> LessonResult<String> {
+        match block_type {
+            ContentBlockType::Markdown => {
+                let body: MarkdownBody = Self::parse_body(body)?;
+
+                Ok(Self::render_markdown(&body.text))
+            },
+            ContentBlockType::Callout => {
+                let body: CalloutBody = Self::parse_body(body)?;
+                let style = body.style.to_string().to_lowercase();
+
+                Ok(format!(
+                    "<aside class=\"callout callout-{}\">{}</aside>", 
+                    style, 
+                    Self::render_markdown(&body.text)
+                ))
+            },
+            ContentBlockType::Image => {
+                let body: ImageBody = Self::parse_body(body)?;
+                Self::validate_url(&body.url)?;
+
+                Ok(String::new())
+            },
+            ContentBlockType::Attachment => {
+                let body: AttachmentBody = Self::parse_body(body)?;
+                Self::validate_url(&body.url)?;
+
+                Ok(String::new())
+            },
+            ContentBlockType::Video => {
+                let body: VideoBody = Self::parse_body(body)?;
+                Self::validate_video_url(&body.url)?;
+
+                Ok(String::new())
+            },
+        }
+    }
+
+    /// Markdown is rendered with tables and strikethrough, raw HTML in it is sanitized.
+    pub fn render_markdown(text: &str) -> String {
+        let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+        let parser = Parser::new_ext(text, options);
+
+        let mut unsafe_html = String::new();
+        html::push_html(&mut unsafe_html, parser);
+
+        ammonia::clean(&unsafe_html)
+    }
+
+    fn parse_body<T: DeserializeOwned>(body: Value) -> LessonResult<T> {
+        serde_json::from_value(body)
+            .map_err(|err| LessonError::IncorrectContentBlockBodyFormat { description: err.to_string() }.into())
+    }
+
+    /// Absolute http(s) URLs, or relative ones as the local file storage hands out.
+    fn validate_url(url: &str) -> LessonResult<()> {
+        let is_relative = !url.is_empty() && !url.contains(':') && !url.starts_with("//") && !url.contains("..");
+        if url.starts_with("https://") || url.starts_with("http://") || is_relative {
+            return Ok(());
+        }
+
+        Err(LessonError::IncorrectContentBlockUrl { url: url.to_string() }.into())
+    }
+
+    /// Embedded players are loaded over https from the allowed hosts only.
+    fn validate_video_url(url: &str) -> LessonResult<()> {
+        let host = url
+            .strip_prefix("https://")
+            .and_then(|rest| rest.split(['/', '?', '#']).next())
+            .map(|host| host.to_lowercase());
+
+        match host {
+            Some(host) if VIDEO_EMBED_HOSTS.contains(&host.as_str()) => Ok(()),
+            _ => Err(LessonError::IncorrectContentBlockUrl { url: url.to_string() }.into()),
+        }
+    }
+}
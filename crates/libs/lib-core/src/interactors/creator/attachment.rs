@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::{error::LessonError, file_key::new_file_key, permission_manager::PermissionManager, revision_stager::RevisionStager}, interfaces::{command_repository_manager::ICommandRepositoryManager, file_storage::PRIVATE_PREFIX, lesson::LessonResult}, models::{attachment::{AttachmentForCreate, AttachmentForCreateCommand, ALLOWED_ATTACHMENT_TYPES, MAX_ATTACHMENT_SIZE}, course::CoursePermission}};
+
+
+pub struct CreatorAttachmentInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CreatorAttachmentInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl CreatorAttachmentInteractor {
+    pub async fn upload_attachment(
+        &self,
+        ctx: &Ctx,
+        attachment: AttachmentForCreate,
+        file_data: &[u8],
+    ) -> LessonResult<i64> {
+        self.permission_manager
+            .check_lesson_permission(ctx, attachment.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, attachment.lesson_id)
+            .await?;
+
+        Self::validate_file(&attachment.content_type, file_data)?;
+
+        let storage_key = new_file_key(PRIVATE_PREFIX, "attachments", &attachment.content_type);
+        let file_storage = self.repository_manager.get_file_storage();
+        file_storage.put(&storage_key, file_data.to_vec(), &attachment.content_type).await?;
+
+        let attachment_for_c = AttachmentForCreateCommand {
+            lesson_id: attachment.lesson_id,
+            file_name: Self::sanitize_file_name(&attachment.file_name),
+            content_type: attachment.content_type,
+            size: file_data.len() as i64,
+            storage_key: storage_key.clone(),
+        };
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let result = lesson_repository.create_attachment(ctx, attachment_for_c).await;
+
+        if result.is_err() {
+            let _ = file_storage.delete(&storage_key).await;
+        }
+
+        result
+    }
+
+    pub async fn delete_attachment(
+        &self,
+        ctx: &Ctx,
+        attachment_id: i64,
+    ) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let attachment = lesson_repository.get_attachment(ctx, attachment_id).await?;
+
+        self.permission_manager
+            .check_lesson_permission(ctx, attachment.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, attachment.lesson_id)
+            .await?;
+
+        lesson_repository.delete_attachment(ctx, attachment_id).await?;
+
+        // A file that failed to delete is no longer referenced and is removed by the upload collector.
+        let _ = self.repository_manager.get_file_storage().delete(&attachment.storage_key).await;
+
+        Ok(())
+    }
+
+    fn validate_file(content_type: &str, file_data: &[u8]) -> LessonResult<()> {
+        if file_data.is_empty() {
+            return Err(LessonError::AttachmentIsEmpty.into());
+        }
+
+        if file_data.len() > MAX_ATTACHMENT_SIZE {
+            return Err(LessonError::AttachmentTooLarge { size: file_data.len(), max_size: MAX_ATTACHMENT_SIZE }.into());
+        }
+
+        let Some((_, magic)) = ALLOWED_ATTACHMENT_TYPES.iter().find(|(allowed_type, _)| *allowed_type == content_type) else {
+            return Err(LessonError::AttachmentTypeNotAllowed { content_type: content_type.to_string() }.into());
+        };
+
+        // The declared content type comes from the client, the file has to back it up.
+        if !file_data.starts_with(magic) {
+            return Err(LessonError::AttachmentContentDoesNotMatchType { content_type: content_type.to_string() }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the last path segment only, the name ends up in the `Content-Disposition` header.
+    fn sanitize_file_name(file_name: &str) -> String {
+        let file_name: String = file_name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| !c.is_control() && *c != '"')
+            .collect();
+
+        if file_name.is_empty() {
+            "attachment".to_string()
+        } else {
+            file_name
+        }
+    }
+}
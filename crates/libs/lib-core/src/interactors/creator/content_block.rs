@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::{order_calculator, content_renderer::ContentRenderer, error::LessonError, permission_manager::PermissionManager, revision_stager::RevisionStager}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::{content_block::{ContentBlockForChangeOrder, ContentBlockForCreate, ContentBlockForCreateCommand, ContentBlockForUpdate, ContentBlockForUpdateCommand, ContentBlockType}, course::CoursePermission, course_revision::{StagedContentBlockEdit, StagedEdit}}};
+
+
+pub struct CreatorContentBlockInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CreatorContentBlockInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl CreatorContentBlockInteractor {
+    pub async fn create_content_block(
+        &self, 
+        ctx: &Ctx,
+        content_block: ContentBlockForCreate
+    ) -> LessonResult<i64> {
+        self.permission_manager
+            .check_lesson_permission(ctx, content_block.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, content_block.lesson_id)
+            .await?;
+
+        let block_type: ContentBlockType = content_block.block_type.try_into()?;
+        let rendered_html = ContentRenderer::render_block(&block_type, content_block.body.clone())?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let lesson_content_blocks = lesson_repository
+            .get_lesson_content_blocks_ordered(ctx, content_block.lesson_id)
+            .await?;
+
+        let order = lesson_content_blocks.len() + 1;
+
+        let content_block_for_c = ContentBlockForCreateCommand {
+            lesson_id: content_block.lesson_id,
+            block_type,
+            body: content_block.body,
+            rendered_html,
+            order: order as i32,
+        };
+
+        lesson_repository.create_content_block(ctx, content_block_for_c).await
+    }
+
+    pub async fn delete_content_block(
+        &self, 
+        ctx: &Ctx,
+        content_block_id: i64
+    ) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let content_block = lesson_repository.get_content_block(ctx, content_block_id).await?;
+
+        self.permission_manager
+            .check_lesson_permission(ctx, content_block.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, content_block.lesson_id)
+            .await?;
+
+        lesson_repository.delete_content_block(ctx, content_block_id).await?;
+
+        let lesson_content_blocks = lesson_repository
+            .get_lesson_content_blocks_ordered(ctx, content_block.lesson_id)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(index, content_block)| ContentBlockForChangeOrder { id: content_block.id, order: index as i32 + 1 })
+            .collect();
+
+        lesson_repository.update_content_block_orders(ctx, lesson_content_blocks).await
+    }
+
+    /// The block type is fixed on creation, only the body can be replaced.
+    /// Edits of a published course wait in its revision.
+    pub async fn update_content_block(
+        &self,
+        ctx: &Ctx, 
+        content_block_for_u: ContentBlockForUpdate
+    ) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let content_block = lesson_repository.get_content_block(ctx, content_block_for_u.id).await?;
+
+        self.permission_manager
+            .check_lesson_permission(ctx, content_block.lesson_id, CoursePermission::EditContent)
+            .await?;
+
+        let rendered_html = ContentRenderer::render_block(&content_block.block_type, content_block_for_u.body.clone())?;
+
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        if let Some(revision) = revision_stager.get_lesson_revision(ctx, content_block.lesson_id).await? {
+            let edit = StagedEdit::ContentBlock(StagedContentBlockEdit {
+                content_block_id: content_block_for_u.id,
+                lesson_id: content_block.lesson_id,
+                body: content_block_for_u.body,
+                rendered_html,
+            });
+
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
+
+        let content_block_for_u = ContentBlockForUpdateCommand {
+            id: content_block_for_u.id,
+            body: content_block_for_u.body,
+            rendered_html,
+        };
+
+        lesson_repository.update_content_block(ctx, content_block_for_u).await
+    }
+
+    pub async fn change_order(
+        &self, 
+        ctx: &Ctx,
+        content_block_for_u_order: ContentBlockForChangeOrder
+    ) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let content_block = lesson_repository.get_content_block(ctx, content_block_for_u_order.id).await?;
+
+        self.permission_manager
+            .check_lesson_permission(ctx, content_block.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, content_block.lesson_id)
+            .await?;
+
+        let lesson_content_blocks = lesson_repository
+            .get_lesson_content_blocks_ordered(ctx, content_block.lesson_id)
+            .await?;
+
+        let lesson_content_blocks = self.compute_orders(&lesson_content_blocks, &content_block_for_u_order)?;
+        lesson_repository.update_content_block_orders(ctx, lesson_content_blocks).await?;
+
+        Ok(())
+    }
+
+    fn compute_orders(
+        &self,
+        content_blocks: &[ContentBlockForChangeOrder], 
+        content_block_for_u_order: &ContentBlockForChangeOrder
+    ) -> LessonResult<Vec<ContentBlockForChangeOrder>> {
+        let orders: Vec<(i64, i32)> = content_blocks.iter().map(|content_block| (content_block.id, content_block.order)).collect();
+
+        order_calculator::compute_orders(&orders, content_block_for_u_order.id, content_block_for_u_order.order)
+            .map(|orders| orders.into_iter().map(|(id, order)| ContentBlockForChangeOrder { id, order }).collect())
+            .ok_or_else(|| LessonError::IncorrectContentBlockOrder { 
+                content_block_id: content_block_for_u_order.id,
+                order: content_block_for_u_order.order 
+            }.into())
+    }
+}
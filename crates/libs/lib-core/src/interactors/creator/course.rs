@@ -1,12 +1,12 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use lib_utils::time::now_utc_sec;
 
 use crate::{
     ctx::Ctx,
-    interactors::{error::CourseError, img_file::{remove_file, upload_file}, permission_manager::PermissionManager}, 
-    interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult}, 
-    models::course::{CourseForCreate, CourseForUpdate, CourseForUpdateCommand, UserCourse}
+    interactors::{error::{CoreError, CourseError}, file_key::new_file_folder, image_processor::ImageProcessor, permission_manager::PermissionManager, progress_reconciler::ProgressReconciler, publish_readiness_checker::PublishReadinessChecker, upload_collector::UploadCollector}, 
+    interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult, file_storage::{IFileStorage, PUBLIC_PREFIX}}, 
+    models::{course::{Course, CourseForCreate, CourseForUpdate, CourseForUpdateCommand, CoursePermission, CourseSchedule, CourseScheduleReport, CourseStaffForUpdate, CourseState, UserCourse, UserCourseRole}, course_revision::{CourseRevision, StagedEdit}, course_image::{CourseImageFormat, CourseImageUrls, CourseImageVariant, CourseImageVariants}, file::UploadGcReport, lesson_progress::ProgressReconciliation, placement_test::PlacementTest, publish_readiness::PublishReadiness, unlock_policy::{LessonRelease, UnlockPolicy, UnlockPolicyKind}}
 };
 
 pub struct CreatorCourseInteractor {
@@ -43,7 +43,7 @@ impl CreatorCourseInteractor {
         course_for_u: CourseForUpdate,
         course_id: i64,
     ) -> CourseResult<()> {
-        self.permission_manager.check_course_creator_permission(ctx, course_id).await?;
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
 
         let command = CourseForUpdateCommand {
             title: course_for_u.title,
@@ -52,6 +52,7 @@ impl CreatorCourseInteractor {
             price: course_for_u.price,
             color: course_for_u.color,
             img_url: None,
+            img_variants: None,
             published_date: None,
             state: None,
         };
@@ -60,33 +61,94 @@ impl CreatorCourseInteractor {
         course_repository.update_course(ctx, command, course_id).await
     }
 
+    /// Stores the resized variants of the image, `img_url` points to the PNG hero variant.
+    /// The new files are written before the course is updated and the old ones are deleted after,
+    /// files left behind by a failure on either side are removed by the upload collector.
     pub async fn set_course_img(
         &self,
         ctx: &Ctx,
         course_id: i64,
         file_data: &[u8],
-    ) -> CourseResult<String> {
-        self.permission_manager.check_course_creator_permission(ctx, course_id).await?;
+    ) -> CourseResult<CourseImageVariants> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let file_data = file_data.to_vec();
+        let processed_images = tokio::task::spawn_blocking(move || ImageProcessor::process_course_image(&file_data))
+            .await
+            .map_err(|_| CourseError::FailSpawnBlockForImage)??;
 
         let course_repository = self.repository_manager.get_course_repository();
 
         let course = course_repository.get_course(ctx, course_id).await?;
 
-        let new_img_url = upload_file(file_data).await?;
+        let file_storage = self.repository_manager.get_file_storage();
+        let folder = new_file_folder(PUBLIC_PREFIX, "course_images");
+
+        let mut urls = HashMap::new();
+        let mut new_keys = Vec::new();
+        for processed_image in processed_images {
+            let key = format!("{}/{}.{}", folder, processed_image.variant.name(), processed_image.format.extension());
+
+            if let Err(err) = file_storage.put(&key, processed_image.data, processed_image.format.content_type()).await {
+                Self::delete_files_quietly(file_storage.as_ref(), &new_keys).await;
+                return Err(err);
+            }
+
+            urls.insert((processed_image.variant, processed_image.format), file_storage.public_url(&key));
+            new_keys.push(key);
+        }
+
+        let mut variant_urls = |variant| CourseImageUrls {
+            webp: urls.remove(&(variant, CourseImageFormat::Webp)).unwrap_or_default(),
+            png: urls.remove(&(variant, CourseImageFormat::Png)).unwrap_or_default(),
+        };
+
+        let img_variants = CourseImageVariants {
+            thumbnail: variant_urls(CourseImageVariant::Thumbnail),
+            card: variant_urls(CourseImageVariant::Card),
+            hero: variant_urls(CourseImageVariant::Hero),
+        };
 
         let course_for_u = CourseForUpdateCommand::builder()
-            .img_url(new_img_url.clone())
+            .img_url(img_variants.hero.png.clone())
+            .img_variants(img_variants.clone())
             .build();
 
-        course_repository.update_course(ctx, course_for_u, course_id).await?;
+        if let Err(err) = course_repository.update_course(ctx, course_for_u, course_id).await {
+            Self::delete_files_quietly(file_storage.as_ref(), &new_keys).await;
+            return Err(err);
+        }
 
-        let outdated_img_url = course.img_url;
+        let mut outdated_img_urls: Vec<String> = course.img_variants
+            .map(|img_variants| img_variants.urls().into_iter().cloned().collect())
+            .unwrap_or_default();
+        outdated_img_urls.extend(course.img_url);
+        outdated_img_urls.sort();
+        outdated_img_urls.dedup();
 
-        if let Some(img_ulr) = outdated_img_url {
-            remove_file(format!("public/{}", img_ulr)).await?;
-        }
+        // Images uploaded before the storage was introduced are left in place.
+        let outdated_keys: Vec<String> = outdated_img_urls
+            .iter()
+            .filter_map(|img_url| file_storage.key_from_public_url(img_url))
+            .collect();
+
+        // The course already shows the new image, so a failed delete is not the creator's problem.
+        Self::delete_files_quietly(file_storage.as_ref(), &outdated_keys).await;
+
+        Ok(img_variants)
+    }
 
-        Ok(new_img_url)
+    /// Blocking errors and warnings the creator should look at before publishing.
+    pub async fn check_publish_readiness(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<PublishReadiness> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::EditContent).await?;
+
+        PublishReadinessChecker::new(self.repository_manager.clone())
+            .check_course(ctx, course_id)
+            .await
     }
 
     pub async fn publish_course(
@@ -94,16 +156,36 @@ impl CreatorCourseInteractor {
         ctx: &Ctx,
         course_id: i64,
     ) -> CourseResult<()> {
-        self.permission_manager.check_course_creator_permission(ctx, course_id).await?;
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
 
-        let published_date = now_utc_sec();
-        let command = CourseForUpdateCommand::builder()
-            .state(crate::models::course::CourseState::Published)
-            .published_date(published_date)
-            .build();
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        self.publish(ctx, &course, now_utc_sec()).await
+    }
+
+    /// Back to Draft, only while nobody is enrolled.
+    pub async fn unpublish_course(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
 
         let course_repository = self.repository_manager.get_course_repository();
-        course_repository.update_course(ctx, command, course_id).await
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        if !course.state.can_change_to(&CourseState::Draft) {
+            return Err(Self::incorrect_transition(&course.state, &CourseState::Draft));
+        }
+
+        let students = course_repository.unpublish_course(ctx, course_id).await?;
+
+        if students > 0 {
+            return Err(CourseError::CourseHasStudents { course_id, students }.into());
+        }
+
+        Ok(())
     }
 
     pub async fn archive_course(
@@ -111,14 +193,427 @@ impl CreatorCourseInteractor {
         ctx: &Ctx,
         course_id: i64,
     ) -> CourseResult<()> {
-        self.permission_manager.check_course_creator_permission(ctx, course_id).await?;
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
 
-        let command = CourseForUpdateCommand::builder()
-            .state(crate::models::course::CourseState::Archived)
-            .build();
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        self.archive(ctx, &course).await
+    }
+
+    /// Replaces the schedule of the course. A publish date needs a draft, an archive date
+    /// needs a published course or a publish date before it.
+    pub async fn set_course_schedule(
+        &self,
+        ctx: &Ctx,
+        schedule: CourseSchedule,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, schedule.course_id, CoursePermission::ManageCourse).await?;
 
         let course_repository = self.repository_manager.get_course_repository();
-        course_repository.update_course(ctx, command, course_id).await
+        let course = course_repository.get_course(ctx, schedule.course_id).await?;
+
+        let now = now_utc_sec();
+        for date in schedule.publish_date.iter().chain(schedule.archive_date.iter()) {
+            if *date <= now {
+                return Err(CourseError::ScheduledDateInPast { date: *date }.into());
+            }
+        }
+
+        if schedule.publish_date.is_some() && course.state != CourseState::Draft {
+            return Err(Self::incorrect_transition(&course.state, &CourseState::Published));
+        }
+
+        match (schedule.publish_date, schedule.archive_date) {
+            (Some(publish_date), Some(archive_date)) if archive_date <= publish_date => {
+                return Err(CourseError::ArchiveDateBeforePublishDate { publish_date, archive_date }.into());
+            },
+            (None, Some(_)) if course.state != CourseState::Published => {
+                return Err(Self::incorrect_transition(&course.state, &CourseState::Archived));
+            },
+            _ => {},
+        }
+
+        course_repository.update_course_schedule(ctx, schedule).await
+    }
+
+    pub async fn clear_course_schedule(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let schedule = CourseSchedule {
+            course_id,
+            publish_date: None,
+            archive_date: None,
+        };
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.update_course_schedule(ctx, schedule).await
+    }
+
+    /// Performs the scheduled transitions that are due. A scheduled course is published with
+    /// the scheduled date, the schedule of one that is not ready is dropped, so it is never
+    /// published later with a stale date. Schedules a manual transition made impossible are
+    /// dropped too. A course that fails doesn't stop the others.
+    pub(crate) async fn run_due_schedules(
+        &self,
+        ctx: &Ctx,
+        now: i64,
+    ) -> CourseResult<CourseScheduleReport> {
+        let course_repository = self.repository_manager.get_course_repository();
+
+        let mut report = CourseScheduleReport {
+            published: 0,
+            archived: 0,
+            not_ready_course_ids: Vec::new(),
+            failed_course_ids: Vec::new(),
+        };
+
+        for schedule in course_repository.get_due_course_schedules(ctx, now).await? {
+            if self.run_due_schedule(ctx, &schedule, now, &mut report).await.is_err() {
+                report.failed_course_ids.push(schedule.course_id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn run_due_schedule(
+        &self,
+        ctx: &Ctx,
+        schedule: &CourseSchedule,
+        now: i64,
+        report: &mut CourseScheduleReport,
+    ) -> CourseResult<()> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let mut course = course_repository.get_course(ctx, schedule.course_id).await?;
+
+        if let Some(publish_date) = schedule.publish_date.filter(|date| *date <= now) {
+            if course.state != CourseState::Draft {
+                self.update_schedule(ctx, &course, None, course.scheduled_archive_date).await?;
+            } else {
+                match self.publish(ctx, &course, publish_date).await {
+                    Ok(()) => report.published += 1,
+                    Err(CoreError::CourseError(CourseError::CourseIsNotReadyToPublish { .. })) => {
+                        // A draft is never archived, the archive date goes with the publish date.
+                        self.update_schedule(ctx, &course, None, None).await?;
+                        report.not_ready_course_ids.push(course.id);
+
+                        return Ok(());
+                    },
+                    Err(err) => return Err(err),
+                }
+            }
+
+            course = course_repository.get_course(ctx, course.id).await?;
+        }
+
+        if schedule.archive_date.is_some_and(|date| date <= now) {
+            if course.state.can_change_to(&CourseState::Archived) {
+                self.archive(ctx, &course).await?;
+                report.archived += 1;
+            } else {
+                self.update_schedule(ctx, &course, course.scheduled_publish_date, None).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Staged edits students don't see yet, empty when there are none.
+    pub async fn get_course_revision(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<CourseRevision> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::EditContent).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let revision = course_repository
+            .get_course_revision(ctx, course_id)
+            .await?
+            .unwrap_or_else(|| CourseRevision::new(course_id));
+
+        Ok(revision)
+    }
+
+    /// Applies the staged edits at once. Edits of entities deleted in the meantime
+    /// are dropped, retaken exercises reopen the lessons.
+    pub async fn publish_course_revision(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        if course.state != CourseState::Published {
+            return Err(CourseError::CourseMustBePublishedError.into());
+        }
+
+        let Some(revision) = course_repository.publish_course_revision(ctx, course_id).await? else {
+            return Err(CourseError::CourseHasNoPendingRevision { course_id }.into());
+        };
+
+        let retaken_lesson_ids: Vec<i64> = revision.edits
+            .iter()
+            .filter_map(|edit| match edit {
+                StagedEdit::Exercise(edit) if edit.retake => Some(edit.lesson_id),
+                _ => None,
+            })
+            .collect();
+
+        if !retaken_lesson_ids.is_empty() {
+            ProgressReconciler::new(self.repository_manager.clone())
+                .reconcile_retake(ctx, course_id, &retaken_lesson_ids)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn discard_course_revision(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        if course_repository.get_course_revision(ctx, course_id).await?.is_none() {
+            return Err(CourseError::CourseHasNoPendingRevision { course_id }.into());
+        }
+
+        course_repository.delete_course_revision(ctx, course_id).await
+    }
+
+    /// Publishes an archived course again, keeping its original published date.
+    pub async fn restore_course(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        if course.state != CourseState::Archived {
+            return Err(Self::incorrect_transition(&course.state, &CourseState::Published));
+        }
+
+        // The course may have been edited into a broken state while it was archived.
+        self.check_ready_to_publish(ctx, course_id).await?;
+
+        self.change_state(ctx, course_id, CourseState::Published).await
+    }
+
+    /// Only a draft can be deleted, together with its lessons, exercises and files.
+    pub async fn delete_course(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let file_storage = self.repository_manager.get_file_storage();
+
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        if course.state != CourseState::Draft {
+            return Err(CourseError::CourseMustBeDraft { course_id }.into());
+        }
+
+        let attachments = lesson_repository.get_course_attachments(ctx, course_id).await?;
+
+        course_repository.delete_course(ctx, course_id).await?;
+
+        let mut keys: Vec<String> = attachments
+            .into_iter()
+            .map(|attachment| attachment.storage_key)
+            .collect();
+
+        if let Some(img_variants) = course.img_variants {
+            keys.extend(img_variants.urls().into_iter().filter_map(|img_url| file_storage.key_from_public_url(img_url)));
+        }
+        keys.extend(course.img_url.and_then(|img_url| file_storage.key_from_public_url(&img_url)));
+        keys.sort();
+        keys.dedup();
+
+        Self::delete_files_quietly(file_storage.as_ref(), &keys).await;
+
+        Ok(())
+    }
+
+    pub async fn update_placement_test(
+        &self,
+        ctx: &Ctx,
+        placement_test: PlacementTest,
+    ) -> CourseResult<()> {
+        let course_id = placement_test.course_id;
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        for exercise_id in &placement_test.exercise_ids {
+            let exercise = exercise_repository.get_exercise(ctx, *exercise_id).await?;
+            let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+
+            if lesson.course_id != course_id {
+                return Err(CourseError::PlacementExerciseNotInCourse { exercise_id: *exercise_id }.into());
+            }
+        }
+
+        let number_of_lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?.len() as i32;
+
+        for level in &placement_test.levels {
+            let is_score_correct = level.min_score > 0.0 && level.min_score <= 1.0;
+            let is_range_correct = level.lessons_to_skip > 0 && level.lessons_to_skip <= number_of_lessons;
+
+            if !is_score_correct || !is_range_correct {
+                return Err(CourseError::IncorrectPlacementLevel { min_score: level.min_score, lessons_to_skip: level.lessons_to_skip }.into());
+            }
+        }
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.save_placement_test(ctx, placement_test).await
+    }
+
+    pub async fn update_unlock_policy(
+        &self,
+        ctx: &Ctx,
+        mut unlock_policy: UnlockPolicy,
+    ) -> CourseResult<()> {
+        let course_id = unlock_policy.course_id;
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageCourse).await?;
+
+        if unlock_policy.kind.ne(&UnlockPolicyKind::Drip) {
+            unlock_policy.drip_releases.clear();
+        }
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lessons_ordered = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+
+        for drip_release in &unlock_policy.drip_releases {
+            let lesson_id = drip_release.lesson_id;
+
+            let is_course_lesson = lessons_ordered.iter().any(|lesson| lesson.id == lesson_id);
+            let is_release_correct = match drip_release.release {
+                LessonRelease::DaysAfterEnrollment { days } => days >= 0,
+                LessonRelease::Date { .. } => true,
+            };
+
+            if !is_course_lesson || !is_release_correct {
+                return Err(CourseError::IncorrectDripRelease { lesson_id }.into());
+            }
+        }
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.save_unlock_policy(ctx, unlock_policy).await
+    }
+
+    /// Recomputes the lesson progresses of every student, for the cases the automatic
+    /// reconciliation after content changes didn't cover.
+    pub async fn reconcile_progress(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<ProgressReconciliation> {
+        self.permission_manager.check_admin_permission(ctx)?;
+
+        ProgressReconciler::new(self.repository_manager.clone())
+            .reconcile_course(ctx, course_id)
+            .await
+    }
+
+    /// Admin only. With `dry_run` the report lists the orphaned files without deleting them.
+    pub async fn collect_orphaned_uploads(
+        &self,
+        ctx: &Ctx,
+        dry_run: bool,
+    ) -> CourseResult<UploadGcReport> {
+        self.permission_manager.check_admin_permission(ctx)?;
+
+        UploadCollector::new(self.repository_manager.clone())
+            .collect(ctx, dry_run)
+            .await
+    }
+
+    pub async fn get_course_staff(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<Vec<UserCourse>> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ViewStatistics).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.get_course_staff(ctx, course_id).await
+    }
+
+    /// Adds a co-author or an assistant, students have to leave the course first.
+    pub async fn invite_staff_member(
+        &self,
+        ctx: &Ctx,
+        staff_for_u: CourseStaffForUpdate,
+    ) -> CourseResult<()> {
+        let course_id = staff_for_u.course_id;
+        let user_id = staff_for_u.user_id;
+
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageStaff).await?;
+        Self::check_staff_role(&staff_for_u.user_role)?;
+
+        self.repository_manager.get_user_repository().get_user(ctx, user_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        if course_repository.get_user_course_optional(ctx, user_id, course_id).await?.is_some() {
+            return Err(CourseError::UserIsAlreadyInCourse { user_id, course_id }.into());
+        }
+
+        let user_course = UserCourse {
+            user_id,
+            course_id,
+            user_role: staff_for_u.user_role,
+            date_registered: now_utc_sec(),
+        };
+
+        course_repository.create_user_course(ctx, user_course).await
+    }
+
+    pub async fn change_staff_role(
+        &self,
+        ctx: &Ctx,
+        staff_for_u: CourseStaffForUpdate,
+    ) -> CourseResult<()> {
+        let course_id = staff_for_u.course_id;
+        let user_id = staff_for_u.user_id;
+
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageStaff).await?;
+        Self::check_staff_role(&staff_for_u.user_role)?;
+        self.get_staff_member(ctx, course_id, user_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.update_user_course_role(ctx, user_id, course_id, staff_for_u.user_role).await
+    }
+
+    pub async fn remove_staff_member(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+        user_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::ManageStaff).await?;
+        self.get_staff_member(ctx, course_id, user_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.delete_user_course(ctx, user_id, course_id).await
     }
 
     pub async fn register_for_course(
@@ -138,7 +633,7 @@ impl CreatorCourseInteractor {
             .await?;
 
         if let Some(user_course) = user_course {
-            if user_course.user_role.eq(&crate::models::course::UserCourseRole::Creator) {
+            if user_course.user_role.is_staff() {
                 return Err(CourseError::CreatorCannotSubscribeToTheCourse.into());
             }
             
@@ -167,4 +662,113 @@ impl CreatorCourseInteractor {
         let course_repository = self.repository_manager.get_course_repository();
         course_repository.delete_user_course(ctx, ctx.user_id(), course_id).await
     }
-}
+
+    fn check_staff_role(user_role: &UserCourseRole) -> CourseResult<()> {
+        if !matches!(user_role, UserCourseRole::CoAuthor | UserCourseRole::Assistant) {
+            return Err(CourseError::IncorrectStaffRole { role: user_role.to_string() }.into());
+        }
+
+        Ok(())
+    }
+
+    /// A co-author or an assistant of the course, the creator can't be changed or removed here.
+    async fn get_staff_member(&self, ctx: &Ctx, course_id: i64, user_id: i64) -> CourseResult<UserCourse> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let user_course = course_repository.get_user_course_optional(ctx, user_id, course_id).await?;
+
+        match user_course {
+            Some(user_course) if matches!(user_course.user_role, UserCourseRole::CoAuthor | UserCourseRole::Assistant) => Ok(user_course),
+            _ => Err(CourseError::UserIsNotCourseStaff { user_id, course_id }.into()),
+        }
+    }
+
+    async fn delete_files_quietly(file_storage: &(dyn IFileStorage + Send + Sync), keys: &[String]) {
+        for key in keys {
+            let _ = file_storage.delete(key).await;
+        }
+    }
+
+    /// Checks readiness, a scheduled publish date is done with once the course is live.
+    async fn publish(&self, ctx: &Ctx, course: &Course, published_date: i64) -> CourseResult<()> {
+        if course.state != CourseState::Draft {
+            return Err(Self::incorrect_transition(&course.state, &CourseState::Published));
+        }
+
+        self.check_ready_to_publish(ctx, course.id).await?;
+
+        let command = CourseForUpdateCommand::builder()
+            .state(CourseState::Published)
+            .published_date(published_date)
+            .build();
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.update_course(ctx, command, course.id).await?;
+
+        if course.scheduled_publish_date.is_some() {
+            self.update_schedule(ctx, course, None, course.scheduled_archive_date).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn check_ready_to_publish(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()> {
+        let readiness = PublishReadinessChecker::new(self.repository_manager.clone())
+            .check_course(ctx, course_id)
+            .await?;
+
+        if !readiness.is_ready() {
+            let issues = readiness.errors.iter().map(|issue| issue.kind.code().to_string()).collect();
+            return Err(CourseError::CourseIsNotReadyToPublish { course_id, issues }.into());
+        }
+
+        Ok(())
+    }
+
+    async fn archive(&self, ctx: &Ctx, course: &Course) -> CourseResult<()> {
+        self.change_state(ctx, course.id, CourseState::Archived).await?;
+
+        if course.scheduled_archive_date.is_some() {
+            self.update_schedule(ctx, course, course.scheduled_publish_date, None).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_schedule(&self, ctx: &Ctx, course: &Course, publish_date: Option<i64>, archive_date: Option<i64>) -> CourseResult<()> {
+        let schedule = CourseSchedule {
+            course_id: course.id,
+            publish_date,
+            archive_date,
+        };
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.update_course_schedule(ctx, schedule).await
+    }
+
+    /// A course leaving Published drops its pending revision, edits are written right away
+    /// from then on and publishing the stale revision later would overwrite them.
+    async fn change_state(&self, ctx: &Ctx, course_id: i64, state: CourseState) -> CourseResult<()> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        if !course.state.can_change_to(&state) {
+            return Err(Self::incorrect_transition(&course.state, &state));
+        }
+
+        let command = CourseForUpdateCommand::builder()
+            .state(state)
+            .build();
+
+        course_repository.update_course(ctx, command, course_id).await?;
+
+        if course.state == CourseState::Published {
+            course_repository.delete_course_revision(ctx, course_id).await?;
+        }
+
+        Ok(())
+    }
+
+    fn incorrect_transition(from: &CourseState, to: &CourseState) -> CoreError {
+        CourseError::IncorrectCourseStateTransition { from: from.to_string(), to: to.to_string() }.into()
+    }
+}
\ No newline at end of file
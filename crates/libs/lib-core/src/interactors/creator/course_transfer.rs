@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use lib_utils::time::now_utc_sec;
+
+use crate::{
+    ctx::Ctx,
+    interactors::{error::{CoreError, CourseError}, permission_manager::PermissionManager},
+    interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult},
+    models::{course::{CoursePermission, UserCourseRole}, course_transfer::{CourseTransfer, CourseTransferForCreate, CourseTransferState}}
+};
+
+pub struct CreatorCourseTransferInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CreatorCourseTransferInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl CreatorCourseTransferInteractor {
+    /// The course moves only once the recipient accepts, one transfer may be pending at a time.
+    pub async fn propose_course_transfer(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+        to_user_id: i64,
+    ) -> CourseResult<i64> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::TransferOwnership).await?;
+
+        let creator_id = self.get_creator_id(ctx, course_id).await?;
+        self.check_recipient(ctx, course_id, creator_id, to_user_id).await?;
+
+        if let Some(pending) = self.get_pending_transfer(ctx, course_id).await? {
+            return Err(CourseError::CourseTransferAlreadyPending { course_id, transfer_id: pending.id }.into());
+        }
+
+        let transfer_for_c = CourseTransferForCreate {
+            course_id,
+            from_user_id: creator_id,
+            to_user_id,
+            forced: false,
+        };
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.create_course_transfer(ctx, transfer_for_c).await
+    }
+
+    /// A recipient enrolled as a student or a staff member leaves that role for the creator one.
+    pub async fn accept_course_transfer(
+        &self,
+        ctx: &Ctx,
+        transfer_id: i64,
+    ) -> CourseResult<()> {
+        let transfer = self.get_recipient_transfer(ctx, transfer_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.complete_course_transfer(ctx, transfer, now_utc_sec()).await
+    }
+
+    pub async fn decline_course_transfer(
+        &self,
+        ctx: &Ctx,
+        transfer_id: i64,
+    ) -> CourseResult<()> {
+        self.get_recipient_transfer(ctx, transfer_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.resolve_course_transfer(ctx, transfer_id, CourseTransferState::Declined, now_utc_sec()).await
+    }
+
+    pub async fn cancel_course_transfer(
+        &self,
+        ctx: &Ctx,
+        transfer_id: i64,
+    ) -> CourseResult<()> {
+        let transfer = self.get_pending_transfer_by_id(ctx, transfer_id).await?;
+        self.permission_manager.check_course_permission(ctx, transfer.course_id, CoursePermission::TransferOwnership).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.resolve_course_transfer(ctx, transfer_id, CourseTransferState::Cancelled, now_utc_sec()).await
+    }
+
+    /// Admin only. The course moves right away, a pending transfer of it is cancelled.
+    pub async fn force_course_transfer(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+        to_user_id: i64,
+    ) -> CourseResult<()> {
+        self.permission_manager.check_admin_permission(ctx)?;
+
+        let creator_id = self.get_creator_id(ctx, course_id).await?;
+        self.check_recipient(ctx, course_id, creator_id, to_user_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let now = now_utc_sec();
+
+        if let Some(pending) = self.get_pending_transfer(ctx, course_id).await? {
+            course_repository.resolve_course_transfer(ctx, pending.id, CourseTransferState::Cancelled, now).await?;
+        }
+
+        let transfer_for_c = CourseTransferForCreate {
+            course_id,
+            from_user_id: creator_id,
+            to_user_id,
+            forced: true,
+        };
+
+        course_repository.force_course_transfer(ctx, transfer_for_c, now).await
+    }
+
+    /// The audit trail of the course ownership.
+    pub async fn get_course_transfers(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> CourseResult<Vec<CourseTransfer>> {
+        self.permission_manager.check_course_permission(ctx, course_id, CoursePermission::TransferOwnership).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.get_course_transfers(ctx, course_id).await
+    }
+
+    pub async fn get_incoming_course_transfers(
+        &self,
+        ctx: &Ctx,
+    ) -> CourseResult<Vec<CourseTransfer>> {
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.get_incoming_course_transfers(ctx, ctx.user_id()).await
+    }
+
+    async fn get_creator_id(&self, ctx: &Ctx, course_id: i64) -> CourseResult<i64> {
+        let course_repository = self.repository_manager.get_course_repository();
+
+        course_repository
+            .get_course_staff(ctx, course_id)
+            .await?
+            .into_iter()
+            .find(|user_course| user_course.user_role == UserCourseRole::Creator)
+            .map(|user_course| user_course.user_id)
+            .ok_or(CourseError::CourseHasNoCreator { course_id }.into())
+    }
+
+    async fn check_recipient(&self, ctx: &Ctx, course_id: i64, creator_id: i64, to_user_id: i64) -> CourseResult<()> {
+        if to_user_id == creator_id {
+            return Err(CourseError::UserIsAlreadyCourseCreator { user_id: to_user_id, course_id }.into());
+        }
+
+        self.repository_manager.get_user_repository().get_user(ctx, to_user_id).await?;
+
+        Ok(())
+    }
+
+    async fn get_pending_transfer(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseTransfer>> {
+        let course_repository = self.repository_manager.get_course_repository();
+
+        let pending = course_repository
+            .get_course_transfers(ctx, course_id)
+            .await?
+            .into_iter()
+            .find(|transfer| transfer.state == CourseTransferState::Pending);
+
+        Ok(pending)
+    }
+
+    async fn get_pending_transfer_by_id(&self, ctx: &Ctx, transfer_id: i64) -> CourseResult<CourseTransfer> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let transfer = course_repository.get_course_transfer(ctx, transfer_id).await?;
+
+        if transfer.state != CourseTransferState::Pending {
+            return Err(CourseError::CourseTransferIsNotPending { transfer_id }.into());
+        }
+
+        Ok(transfer)
+    }
+
+    /// Only the recipient answers a transfer.
+    async fn get_recipient_transfer(&self, ctx: &Ctx, transfer_id: i64) -> CourseResult<CourseTransfer> {
+        let transfer = self.get_pending_transfer_by_id(ctx, transfer_id).await?;
+
+        if transfer.to_user_id != ctx.user_id() {
+            return Err(CoreError::PermissionDenied);
+        }
+
+        Ok(transfer)
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use serde_json::Value;
 
-use crate::{ctx::Ctx, interactors::{error::ExerciseError, exercise_validator::ExerciseValidator, permission_manager::PermissionManager}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::exercise::{ExerciseForChangeOrder, ExerciseForCreate, ExerciseForCreateCommand, ExerciseForUpdate, ExerciseType}};
+use crate::{ctx::Ctx, interactors::{order_calculator, conspect_diff::ConspectDiffBuilder, conspect_layout::ConspectLayout, difficulty_calibrator::DifficultyCalibrator, error::ExerciseError, exercise_checker::ExerciseChecker, exercise_validator::ExerciseValidator, mistake_statistics::MistakeStatisticsBuilder, permission_manager::PermissionManager, progress_reconciler::ProgressReconciler, revision_stager::RevisionStager, student::exercise::StudentExerciseInteractor}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{course::CoursePermission, course_revision::{StagedEdit, StagedExerciseEdit}, difficulty_calibration::DifficultyCalibration, exercise::{ConspectDiff, ConspectLayoutAlgorithm, ExerciseForChangeOrder, ExerciseMistakeStatistics, ExerciseForCreate, ExerciseForCreateCommand, ExerciseForUpdate, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForReview, ExerciseCompletionForReviewCommand, ExerciseCompletionState, MAX_REVIEW_COMMENT_LENGTH}, similarity_flag::SimilarityComparison}};
 
 
 pub struct CreatorExerciseInteractor {
@@ -30,11 +30,15 @@ impl CreatorExerciseInteractor {
         exercise: ExerciseForCreate, 
     ) -> ExerciseResult<i64> {
         self.permission_manager
-            .check_lesson_creator_permission(ctx, exercise.lesson_id)
+            .check_lesson_permission(ctx, exercise.lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, exercise.lesson_id)
             .await?;
 
         ExerciseValidator::validate_exercise(&exercise.exercise_type, exercise.answer_body.clone())?;
         ExerciseValidator::validate_exercise(&exercise.exercise_type, exercise.exercise_body.clone())?;
+        ExerciseValidator::validate_peer_review(exercise.peer_review_count, exercise.peer_review_rubric.clone())?;
 
         let exercise_repository = self.repository_manager.get_exercise_repository();
         let lesson_exercises = exercise_repository
@@ -51,13 +55,26 @@ impl CreatorExerciseInteractor {
             exercise_order: order as i32,
             difficult: exercise.difficult,
             time_to_complete: exercise.time_to_complete,
+            manual_review: exercise.manual_review,
+            auto_difficulty: exercise.auto_difficulty,
+            peer_review_count: exercise.peer_review_count,
+            peer_review_rubric: exercise.peer_review_rubric,
             answer_body: exercise.answer_body,
             exercise_body: exercise.exercise_body,
         };
 
-        exercise_repository.create(ctx, exercise_for_c).await
+        let exercise_id = exercise_repository.create(ctx, exercise_for_c).await?;
+
+        // Students who finished the lesson have a new exercise to do. The exercise is created
+        // either way, a failed reconciliation is left to the admin endpoint.
+        let _ = ProgressReconciler::new(self.repository_manager.clone())
+            .reconcile_lesson_course(ctx, exercise.lesson_id)
+            .await;
+
+        Ok(exercise_id)
     }
 
+    /// Edits of a published course wait in its revision, a retake happens when the revision is published.
     pub async fn update_exercise(
         &self, 
         ctx: &Ctx,
@@ -65,33 +82,51 @@ impl CreatorExerciseInteractor {
         is_retake_exercise: bool,
     ) -> ExerciseResult<()> {
         self.permission_manager
-            .check_exercise_creator_permission(ctx, exercise_for_u.id)
+            .check_exercise_permission(ctx, exercise_for_u.id, CoursePermission::EditContent)
             .await?;
 
         self.validate_exercise(exercise_for_u.exercise_type.clone(), exercise_for_u.answer_body.clone())?;
         self.validate_exercise(exercise_for_u.exercise_type.clone(), exercise_for_u.exercise_body.clone())?;
 
         let exercise_repository = self.repository_manager.get_exercise_repository();
+        let mut exercise = exercise_repository.get_exercise(ctx, exercise_for_u.id).await?;
 
-        if is_retake_exercise {
-            self.change_lessons_state(ctx, exercise_for_u.id).await?;
-            exercise_repository.remove_exercise_completions(ctx, exercise_for_u.id).await?;
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        let revision = revision_stager.get_lesson_revision(ctx, exercise.lesson_id).await?;
+
+        if exercise_for_u.peer_review_count.is_some() || exercise_for_u.peer_review_rubric.is_some() {
+            // Checked against the exercise students will get, with the edits staged before.
+            if let Some(revision) = &revision {
+                revision.apply_to_exercise(exercise_for_u.id, &mut exercise);
+            }
+
+            ExerciseValidator::validate_peer_review(
+                exercise_for_u.peer_review_count.unwrap_or(exercise.peer_review_count),
+                exercise_for_u.peer_review_rubric.clone().unwrap_or(exercise.peer_review_rubric),
+            )?;
         }
 
-        exercise_repository.update(ctx, exercise_for_u).await?;
+        if let Some(revision) = revision {
+            let edit = StagedEdit::Exercise(StagedExerciseEdit::new(exercise_for_u, exercise.lesson_id, is_retake_exercise));
 
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
 
-        Ok(())
-    }
+        let exercise_id = exercise_for_u.id;
 
-    async fn change_lessons_state(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<()> {
-        let exercise_repository = self.repository_manager.get_exercise_repository();
-        let exercise = exercise_repository.get_exercise(ctx, exercise_id).await?;
+        if is_retake_exercise {
+            exercise_repository.remove_exercise_completions(ctx, exercise_id).await?;
+        }
 
-        let lesson_repository = self.repository_manager.get_lesson_repository();
-        let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+        exercise_repository.update(ctx, exercise_for_u).await?;
 
-        lesson_repository.change_lesson_progress_states_for_update_exercise(ctx, lesson.course_id, lesson.lesson_order).await?;
+        if is_retake_exercise {
+            let lesson = self.repository_manager.get_lesson_repository().get_lesson(ctx, exercise.lesson_id).await?;
+
+            ProgressReconciler::new(self.repository_manager.clone())
+                .reconcile_retake(ctx, lesson.course_id, &[lesson.id])
+                .await?;
+        }
 
         Ok(())
     }
@@ -114,18 +149,258 @@ impl CreatorExerciseInteractor {
         Ok(())
     }
 
+    pub async fn layout_exercise_body(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+        exercise_type: &ExerciseType,
+        body: Value,
+        algorithm: ConspectLayoutAlgorithm,
+    ) -> ExerciseResult<Value> {
+        self.permission_manager
+            .check_lesson_permission(ctx, lesson_id, CoursePermission::EditContent)
+            .await?;
+
+        match exercise_type {
+            ExerciseType::Conspect |
+            ExerciseType::InteractiveConspect => ConspectLayout::layout_conspect(body, algorithm),
+        }
+    }
+
+    pub async fn get_exercise_completion_diff(
+        &self,
+        ctx: &Ctx,
+        ex_comp_id: i64,
+    ) -> ExerciseResult<ConspectDiff> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comp = exercise_repository.get_exercise_completion(ctx, ex_comp_id).await?;
+
+        self.permission_manager
+            .check_exercise_permission(ctx, ex_comp.exercise_id, CoursePermission::Grade)
+            .await?;
+
+        let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
+
+        match exercise.exercise_type {
+            ExerciseType::Conspect |
+            ExerciseType::InteractiveConspect => ConspectDiffBuilder::diff_conspects(exercise.answer_body, ex_comp.body),
+        }
+    }
+
+    pub async fn get_review_queue(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        self.permission_manager
+            .check_course_permission(ctx, course_id, CoursePermission::Grade)
+            .await?;
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        exercise_repository.get_course_pending_reviews(ctx, course_id).await
+    }
+
+    /// Mistake statistics of every exercise of the course, in lesson and exercise order.
+    pub async fn get_mistake_statistics(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> ExerciseResult<Vec<ExerciseMistakeStatistics>> {
+        self.permission_manager
+            .check_course_permission(ctx, course_id, CoursePermission::ViewStatistics)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+
+        let mut result = Vec::new();
+
+        for lesson in lessons {
+            let exercises = exercise_repository.get_lesson_exercises_ordered(ctx, lesson.id).await?;
+
+            for exercise in exercises {
+                let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise.id).await?;
+                let exercise_data = exercise_repository.get_exercise(ctx, exercise.id).await?;
+
+                result.push(MistakeStatisticsBuilder::build(exercise.id, &exercise_data, &ex_comps)?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Suggested versus assigned difficulty of every exercise of the course, in lesson and exercise order.
+    pub async fn get_difficulty_calibration(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> ExerciseResult<Vec<DifficultyCalibration>> {
+        self.permission_manager
+            .check_course_permission(ctx, course_id, CoursePermission::ViewStatistics)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+
+        let mut result = Vec::new();
+
+        for lesson in lessons {
+            let exercises = exercise_repository.get_lesson_exercises_ordered(ctx, lesson.id).await?;
+
+            for exercise in exercises {
+                let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise.id).await?;
+                let exercise_data = exercise_repository.get_exercise(ctx, exercise.id).await?;
+
+                let observations = DifficultyCalibrator::observe(&ex_comps);
+
+                result.push(DifficultyCalibration {
+                    exercise_id: exercise.id,
+                    title: exercise_data.title.clone(),
+                    suggested_difficulty: DifficultyCalibrator::suggest(&exercise_data, &observations),
+                    assigned_difficulty: exercise_data.difficult,
+                    auto_difficulty: exercise_data.auto_difficulty,
+                    observations,
+                    history: exercise_repository.get_exercise_difficulty_changes(ctx, exercise.id).await?,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub async fn get_similarity_flags(
+        &self,
+        ctx: &Ctx,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<SimilarityComparison>> {
+        self.permission_manager
+            .check_exercise_permission(ctx, exercise_id, CoursePermission::Grade)
+            .await?;
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let flags = exercise_repository.get_exercise_similarity_flags(ctx, exercise_id).await?;
+
+        let mut result = Vec::new();
+
+        for flag in flags {
+            let first = exercise_repository.get_exercise_completion(ctx, flag.first_exercise_completion_id).await?;
+            let second = exercise_repository.get_exercise_completion(ctx, flag.second_exercise_completion_id).await?;
+
+            result.push(SimilarityComparison {
+                id: flag.id,
+                score: flag.score,
+                first,
+                second,
+            });
+        }
+
+        Ok(result)
+    }
+
+    pub async fn send_exercise_completion_to_review(
+        &self,
+        ctx: &Ctx,
+        ex_comp_id: i64,
+    ) -> ExerciseResult<()> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comp = exercise_repository.get_exercise_completion(ctx, ex_comp_id).await?;
+
+        self.permission_manager
+            .check_exercise_permission(ctx, ex_comp.exercise_id, CoursePermission::Grade)
+            .await?;
+
+        match ex_comp.state {
+            ExerciseCompletionState::Succeeded |
+            ExerciseCompletionState::Failed if !ex_comp.is_practice => (),
+            _ => return Err(ExerciseError::AttemptCannotBeSentToReview {}.into()),
+        }
+
+        exercise_repository
+            .update_exercise_completion_state(ctx, ex_comp_id, ExerciseCompletionState::PendingReview)
+            .await
+    }
+
+    pub async fn review_exercise_completion(
+        &self,
+        ctx: &Ctx,
+        review: ExerciseCompletionForReview,
+    ) -> ExerciseResult<()> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comp = exercise_repository.get_exercise_completion(ctx, review.id).await?;
+
+        self.permission_manager
+            .check_exercise_permission(ctx, ex_comp.exercise_id, CoursePermission::Grade)
+            .await?;
+
+        if ex_comp.state.ne(&ExerciseCompletionState::PendingReview) {
+            return Err(ExerciseError::AttemptIsNotPendingReview {}.into());
+        }
+
+        let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
+
+        let max_points = ex_comp.max_points.unwrap_or(ExerciseChecker::max_points(&exercise));
+        if review.points_scored < 0.0 || review.points_scored > max_points {
+            return Err(ExerciseError::ReviewPointsOutOfRange { points_scored: review.points_scored, max_points }.into());
+        }
+
+        if let Some(length) = review.comment.as_ref().map(|comment| comment.chars().count()) {
+            if length > MAX_REVIEW_COMMENT_LENGTH {
+                return Err(ExerciseError::ReviewCommentTooLong { length, max_length: MAX_REVIEW_COMMENT_LENGTH }.into());
+            }
+        }
+
+        let state = if review.approved {
+            ExerciseCompletionState::Succeeded
+        } else {
+            ExerciseCompletionState::Failed
+        };
+
+        let ex_comp_for_r = ExerciseCompletionForReviewCommand {
+            id: review.id,
+            points_scored: review.points_scored,
+            state,
+            review_comment: review.comment,
+        };
+
+        exercise_repository.review_exercise_completion(ctx, ex_comp_for_r).await?;
+
+        if review.approved {
+            let student_exercise_interactor = StudentExerciseInteractor::new(self.repository_manager.clone());
+
+            if student_exercise_interactor.is_lesson_state_complete(ctx, exercise.lesson_id, ex_comp.user_id).await? {
+                student_exercise_interactor.complete_lesson(ctx, exercise.lesson_id, ex_comp.user_id).await?;
+            }
+        } else {
+            // The attempt sent to review may be the one that completed the lesson.
+            ProgressReconciler::new(self.repository_manager.clone())
+                .reconcile_lesson_course(ctx, exercise.lesson_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn change_order(
         &self, 
         ctx: &Ctx,
         exercise_for_u_order: ExerciseForChangeOrder, 
     ) -> ExerciseResult<()> {
         self.permission_manager
-            .check_exercise_creator_permission(ctx, exercise_for_u_order.id)
+            .check_exercise_permission(ctx, exercise_for_u_order.id, CoursePermission::EditContent)
             .await?;
 
         let exercise_repository = self.repository_manager.get_exercise_repository();
         let exercise = exercise_repository.get_exercise(ctx, exercise_for_u_order.id).await?;
 
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, exercise.lesson_id)
+            .await?;
+
         let lesson_exercises = exercise_repository
             .get_lesson_exercises_ordered(ctx, exercise.lesson_id)
             .await?;
@@ -133,63 +408,25 @@ impl CreatorExerciseInteractor {
         let lesson_exercises = self.compute_orders(&lesson_exercises, &exercise_for_u_order)?;
         exercise_repository.update_exercise_orders(ctx, lesson_exercises).await?;
 
+        let _ = ProgressReconciler::new(self.repository_manager.clone())
+            .reconcile_lesson_course(ctx, exercise.lesson_id)
+            .await;
+
         Ok(())
     }
 
     fn compute_orders(
         &self,
-        exercises: &Vec<ExerciseForChangeOrder>, 
+        exercises: &[ExerciseForChangeOrder], 
         exercise_for_u_order: &ExerciseForChangeOrder
     ) -> ExerciseResult<Vec<ExerciseForChangeOrder>> {
-        let number_of_exercises = exercises.len() as i32;
-        if number_of_exercises < exercise_for_u_order.order {
-            return Err(
-                ExerciseError::IncorrectExerciseOreder { 
-                    exercise_id: exercise_for_u_order.id,
-                    order: exercise_for_u_order.order 
-                }.into()
-            );
-        }
-
-        let mut result = Vec::new();
-        let mut d_order = 0;
-
-        for exercise in exercises {
-            let order = if exercise.id == exercise_for_u_order.id {
-                d_order = if d_order != 0 {
-                    0
-                } else {
-                    -1
-                };
-
-                exercise_for_u_order.order
-            } else if exercise.order == exercise_for_u_order.order {
-                let order = if d_order == 0 {
-                    exercise.order + 1
-                } else {
-                    exercise.order - 1
-                };
-                d_order += 1;
-
-                order 
-            } else {
-                let order = exercise.order + d_order;
-
-                if exercise.order == exercise_for_u_order.order {
-                    d_order = 0;
-                }
-
-                order
-            };
-
-            let new_order = ExerciseForChangeOrder {
-                id: exercise.id,
-                order,
-            };
-
-            result.push(new_order);
-        }
-
-        Ok(result)
+        let orders: Vec<(i64, i32)> = exercises.iter().map(|exercise| (exercise.id, exercise.order)).collect();
+
+        order_calculator::compute_orders(&orders, exercise_for_u_order.id, exercise_for_u_order.order)
+            .map(|orders| orders.into_iter().map(|(id, order)| ExerciseForChangeOrder { id, order }).collect())
+            .ok_or_else(|| ExerciseError::IncorrectExerciseOreder { 
+                exercise_id: exercise_for_u_order.id,
+                order: exercise_for_u_order.order 
+            }.into())
     }
 }
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::{ctx::Ctx, interactors::{error::LessonError, permission_manager::PermissionManager}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::lesson::{LessonForChangeOreder, LessonForCreate, LessonForCreateCommand, LessonForUpdate}};
+use lib_utils::time::now_utc_sec;
+
+use crate::{ctx::Ctx, interactors::{order_calculator, error::LessonError, permission_manager::PermissionManager, progress_reconciler::ProgressReconciler, revision_stager::RevisionStager, student::lesson::StudentLessonInteractor}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::{course::CoursePermission, exam::ExamForUpdate, exercise_completion::ExerciseCompletionState, course_revision::{LessonPreview, StagedEdit, StagedExamEdit, StagedLessonEdit}, lesson::{LessonForChangeOreder, LessonForChangeSection, LessonForCreate, LessonForCreateCommand, LessonForUpdate}}};
 
 
 pub struct CreatorLessonInteractor {
@@ -28,19 +30,25 @@ impl CreatorLessonInteractor {
         lesson: LessonForCreate
     ) -> LessonResult<i64> {
         self.permission_manager
-            .check_course_creator_permission(ctx, lesson.course_id)
+            .check_course_permission(ctx, lesson.course_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_structure_editable(ctx, lesson.course_id)
             .await?;
 
+        self.check_section_course(ctx, lesson.section_id, lesson.course_id).await?;
+
         let lesson_repository = self.repository_manager.get_lesson_repository();
 
-        let course_lessons = lesson_repository
-            .get_course_lessons_ordered(ctx, lesson.course_id)
+        let section_lessons = lesson_repository
+            .get_section_lessons_ordered(ctx, lesson.course_id, lesson.section_id)
             .await?;
 
-        let order = course_lessons.len() + 1;
+        let order = section_lessons.len() + 1;
 
         let lesson_for_c = LessonForCreateCommand {
             course_id: lesson.course_id,
+            section_id: lesson.section_id,
             title: lesson.title,
             description: lesson.description,
             order: order as i32,
@@ -55,22 +63,46 @@ impl CreatorLessonInteractor {
         lesson_id: i64
     ) -> LessonResult<()> {
         self.permission_manager
-            .check_lesson_creator_permission(ctx, lesson_id)
+            .check_lesson_permission(ctx, lesson_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, lesson_id)
             .await?;
 
         let lesson_repository = self.repository_manager.get_lesson_repository();
-        lesson_repository.delete_lesson(ctx, lesson_id).await
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+        lesson_repository.delete_lesson(ctx, lesson_id).await?;
+
+        // Students may have finished every lesson that is left. The lesson is deleted
+        // either way, a failed reconciliation is left to the admin endpoint.
+        let _ = ProgressReconciler::new(self.repository_manager.clone())
+            .reconcile_course(ctx, lesson.course_id)
+            .await;
+
+        Ok(())
     }
 
+    /// Edits of a published course wait in its revision.
     pub async fn update_lesson(
         &self,
         ctx: &Ctx, 
         lesson_for_u: LessonForUpdate
     ) -> LessonResult<()> {
         self.permission_manager
-            .check_lesson_creator_permission(ctx, lesson_for_u.id)
+            .check_lesson_permission(ctx, lesson_for_u.id, CoursePermission::EditContent)
             .await?;
 
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        if let Some(revision) = revision_stager.get_lesson_revision(ctx, lesson_for_u.id).await? {
+            let edit = StagedEdit::Lesson(StagedLessonEdit {
+                lesson_id: lesson_for_u.id,
+                title: lesson_for_u.title,
+                description: lesson_for_u.description,
+            });
+
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
+
         let lesson_repository = self.repository_manager.get_lesson_repository();
         lesson_repository.update_lesson(ctx, lesson_for_u).await
     }
@@ -81,76 +113,260 @@ impl CreatorLessonInteractor {
         lesson_for_u_order: LessonForChangeOreder
     ) -> LessonResult<()> {
         self.permission_manager
-            .check_lesson_creator_permission(ctx, lesson_for_u_order.id)
+            .check_lesson_permission(ctx, lesson_for_u_order.id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, lesson_for_u_order.id)
             .await?;
 
         let lesson_repository = self.repository_manager.get_lesson_repository();
 
         let course_lesson = lesson_repository.get_lesson(ctx, lesson_for_u_order.id).await?;
-        let course_lessons = lesson_repository
-            .get_course_lessons_ordered(ctx, course_lesson.course_id)
+        let section_lessons = lesson_repository
+            .get_section_lessons_ordered(ctx, course_lesson.course_id, course_lesson.section_id)
             .await?;
 
-        let course_lessons = self.compute_orders(&course_lessons, &lesson_for_u_order)?;
-        lesson_repository.update_lesson_orders(ctx, course_lessons).await?;
+        let section_lessons = self.compute_orders(&section_lessons, &lesson_for_u_order)?;
+        lesson_repository.update_lesson_orders(ctx, section_lessons).await?;
+
+        let _ = ProgressReconciler::new(self.repository_manager.clone())
+            .reconcile_course(ctx, course_lesson.course_id)
+            .await;
 
         Ok(())
     }
 
-    fn compute_orders(
+    /// Appends the lesson to the target section and closes the gap it leaves behind.
+    pub async fn change_section(
         &self,
-        lessons: &Vec<LessonForChangeOreder>, 
-        lesson_for_u_order: &LessonForChangeOreder
-    ) -> LessonResult<Vec<LessonForChangeOreder>> {
-        let number_of_lessons = lessons.len() as i32;
-        if number_of_lessons < lesson_for_u_order.order {
-            return Err(
-                LessonError::IncorrectLessonOreder { 
-                    lesson_id: lesson_for_u_order.id,
-                    order: lesson_for_u_order.order 
-                }.into()
-            );
+        ctx: &Ctx,
+        lesson_for_c_section: LessonForChangeSection,
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_lesson_permission(ctx, lesson_for_c_section.id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_lesson_structure_editable(ctx, lesson_for_c_section.id)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_for_c_section.id).await?;
+
+        if lesson.section_id == lesson_for_c_section.section_id {
+            return Ok(());
+        }
+
+        self.check_section_course(ctx, lesson_for_c_section.section_id, lesson.course_id).await?;
+
+        let target_lessons = lesson_repository
+            .get_section_lessons_ordered(ctx, lesson.course_id, lesson_for_c_section.section_id)
+            .await?;
+
+        let order = target_lessons.len() as i32 + 1;
+
+        let source_lessons = lesson_repository
+            .get_section_lessons_ordered(ctx, lesson.course_id, lesson.section_id)
+            .await?
+            .into_iter()
+            .filter(|source_lesson| source_lesson.id != lesson.id)
+            .enumerate()
+            .map(|(index, lesson)| LessonForChangeOreder { id: lesson.id, order: index as i32 + 1 })
+            .collect();
+
+        lesson_repository
+            .update_lesson_section(ctx, lesson.id, lesson_for_c_section.section_id, order, source_lessons)
+            .await
+    }
+
+    async fn check_section_course(&self, ctx: &Ctx, section_id: Option<i64>, course_id: i64) -> LessonResult<()> {
+        let Some(section_id) = section_id else { return Ok(()) };
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let section = lesson_repository.get_section(ctx, section_id).await?;
+
+        if section.course_id != course_id {
+            return Err(LessonError::SectionOfAnotherCourse { section_id, course_id }.into());
         }
 
-        let mut result = Vec::new();
-        let mut d_order = 0;
-
-        for lesson in lessons {
-            let order = if lesson.id == lesson_for_u_order.id {
-                d_order = if d_order != 0 {
-                    0
-                } else {
-                    -1
-                };
-
-                lesson_for_u_order.order
-            } else if lesson.order == lesson_for_u_order.order {
-                let order = if d_order == 0 {
-                    lesson.order + 1
-                } else {
-                    lesson.order - 1
-                };
-                d_order += 1;
-
-                order 
-            } else {
-                let order = lesson.order + d_order;
-
-                if lesson.order == lesson_for_u_order.order {
-                    d_order = 0;
-                }
-
-                order
-            };
-
-            let new_order = LessonForChangeOreder {
-                id: lesson.id,
-                order,
-            };
-
-            result.push(new_order);
+        Ok(())
+    }
+
+    /// The lesson with the pending revision of its course applied, as students will see it after publishing.
+    pub async fn preview_lesson(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<LessonPreview> {
+        self.permission_manager
+            .check_lesson_permission(ctx, lesson_id, CoursePermission::EditContent)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let course_repository = self.repository_manager.get_course_repository();
+
+        let mut lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+        let revision = course_repository.get_course_revision(ctx, lesson.course_id).await?;
+
+        let mut content_blocks = Vec::new();
+        for content_block in lesson_repository.get_lesson_content_blocks_ordered(ctx, lesson_id).await? {
+            content_blocks.push(lesson_repository.get_content_block(ctx, content_block.id).await?);
+        }
+
+        let mut exercises = Vec::new();
+        for exercise in exercise_repository.get_lesson_exercises_ordered(ctx, lesson_id).await? {
+            exercises.push((exercise.id, exercise_repository.get_exercise(ctx, exercise.id).await?));
+        }
+
+        let mut description = None;
+        if let Some(revision) = revision {
+            description = revision.apply_to_lesson(&mut lesson);
+
+            for content_block in &mut content_blocks {
+                revision.apply_to_content_block(content_block);
+            }
+
+            for (exercise_id, exercise) in &mut exercises {
+                revision.apply_to_exercise(*exercise_id, exercise);
+            }
         }
 
-        Ok(result)
+        Ok(LessonPreview {
+            lesson,
+            description,
+            content_blocks,
+            exercises,
+        })
+    }
+
+    /// Turns the lesson into an exam or moves the window of an existing one,
+    /// in a published course the change waits in its revision.
+    pub async fn update_exam(
+        &self,
+        ctx: &Ctx,
+        exam_for_u: ExamForUpdate,
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_lesson_permission(ctx, exam_for_u.lesson_id, CoursePermission::EditContent)
+            .await?;
+
+        if exam_for_u.opens_at >= exam_for_u.closes_at {
+            return Err(LessonError::IncorrectExamWindow { opens_at: exam_for_u.opens_at, closes_at: exam_for_u.closes_at }.into());
+        }
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        if lesson_repository.get_exam(ctx, exam_for_u.lesson_id).await?.is_none() {
+            self.check_lesson_has_no_progress(ctx, exam_for_u.lesson_id).await?;
+        }
+
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        if let Some(revision) = revision_stager.get_lesson_revision(ctx, exam_for_u.lesson_id).await? {
+            let edit = StagedEdit::Exam(StagedExamEdit {
+                lesson_id: exam_for_u.lesson_id,
+                opens_at: exam_for_u.opens_at,
+                closes_at: exam_for_u.closes_at,
+                removed: false,
+            });
+
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
+
+        lesson_repository.save_exam(ctx, exam_for_u).await
+    }
+
+    /// Turns the exam back into a regular lesson, in a published course the change waits in its revision.
+    pub async fn remove_exam(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_lesson_permission(ctx, lesson_id, CoursePermission::EditContent)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let Some(exam) = lesson_repository.get_exam(ctx, lesson_id).await? else {
+            return Err(LessonError::LessonIsNotExam { lesson_id }.into());
+        };
+
+        self.check_lesson_has_no_progress(ctx, lesson_id).await?;
+
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        if let Some(revision) = revision_stager.get_lesson_revision(ctx, lesson_id).await? {
+            let edit = StagedEdit::Exam(StagedExamEdit {
+                lesson_id,
+                opens_at: exam.opens_at,
+                closes_at: exam.closes_at,
+                removed: true,
+            });
+
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
+
+        lesson_repository.delete_exam(ctx, lesson_id).await
+    }
+
+    /// Students who already started the lesson took it under the old rules.
+    async fn check_lesson_has_no_progress(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        let lesson_progresses = lesson_repository.get_course_lesson_progresses(ctx, lesson.course_id).await?;
+        if lesson_progresses.iter().any(|lesson_progress| lesson_progress.lesson_id == lesson_id) {
+            return Err(LessonError::LessonHasProgress { lesson_id }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Grades every submitted attempt of the closed exam, later submissions are graded right away.
+    pub async fn release_exam_results(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_lesson_permission(ctx, lesson_id, CoursePermission::Grade)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let Some(exam) = lesson_repository.get_exam(ctx, lesson_id).await? else {
+            return Err(LessonError::LessonIsNotExam { lesson_id }.into());
+        };
+
+        if now_utc_sec() < exam.closes_at {
+            return Err(LessonError::ExamIsNotClosed { lesson_id, closes_at: exam.closes_at }.into());
+        }
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercises = exercise_repository.get_lesson_exercises_ordered(ctx, lesson_id).await?;
+
+        let student_lesson_interactor = StudentLessonInteractor::new(self.repository_manager.clone());
+        let mut graded_attempts = Vec::new();
+
+        for exercise in &exercises {
+            let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise.id).await?;
+
+            for ex_comp in ex_comps.iter().filter(|ex_comp| ex_comp.state.eq(&ExerciseCompletionState::Submitted)) {
+                graded_attempts.push(student_lesson_interactor.exam_attempt_grade(ctx, ex_comp).await?);
+            }
+        }
+
+        lesson_repository.release_exam_results(ctx, lesson_id, graded_attempts).await
+    }
+
+    fn compute_orders(
+        &self,
+        lessons: &[LessonForChangeOreder], 
+        lesson_for_u_order: &LessonForChangeOreder
+    ) -> LessonResult<Vec<LessonForChangeOreder>> {
+        let orders: Vec<(i64, i32)> = lessons.iter().map(|lesson| (lesson.id, lesson.order)).collect();
+
+        order_calculator::compute_orders(&orders, lesson_for_u_order.id, lesson_for_u_order.order)
+            .map(|orders| orders.into_iter().map(|(id, order)| LessonForChangeOreder { id, order }).collect())
+            .ok_or_else(|| LessonError::IncorrectLessonOreder { 
+                lesson_id: lesson_for_u_order.id,
+                order: lesson_for_u_order.order 
+            }.into())
     }
 }
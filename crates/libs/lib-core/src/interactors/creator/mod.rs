@@ -1,3 +1,7 @@
 pub mod course;
+pub mod course_transfer;
 pub mod lesson;
-pub mod exercise;
\ No newline at end of file
+pub mod exercise;
+pub mod section;
+pub mod content_block;
+pub mod attachment;
\ No newline at end of file
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::{order_calculator, error::LessonError, permission_manager::PermissionManager, revision_stager::RevisionStager}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::{course::CoursePermission, course_revision::{StagedEdit, StagedSectionEdit}, section::{SectionForChangeOrder, SectionForCreate, SectionForCreateCommand, SectionForUpdate}}};
+
+
+pub struct CreatorSectionInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CreatorSectionInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl CreatorSectionInteractor {
+    pub async fn create_section(
+        &self, 
+        ctx: &Ctx,
+        section: SectionForCreate
+    ) -> LessonResult<i64> {
+        self.permission_manager
+            .check_course_permission(ctx, section.course_id, CoursePermission::EditContent)
+            .await?;
+        RevisionStager::new(self.repository_manager.clone())
+            .check_structure_editable(ctx, section.course_id)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let course_sections = lesson_repository
+            .get_course_sections_ordered(ctx, section.course_id)
+            .await?;
+
+        let order = course_sections.len() + 1;
+
+        let section_for_c = SectionForCreateCommand {
+            course_id: section.course_id,
+            title: section.title,
+            description: section.description,
+            order: order as i32,
+        };
+
+        lesson_repository.create_section(ctx, section_for_c).await
+    }
+
+    /// Only an empty section can be deleted, the sections after it move up.
+    pub async fn delete_section(
+        &self, 
+        ctx: &Ctx,
+        section_id: i64
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_section_permission(ctx, section_id, CoursePermission::EditContent)
+            .await?;
+
+        self.check_structure_editable(ctx, section_id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let section = lesson_repository.get_section(ctx, section_id).await?;
+
+        let section_lessons = lesson_repository
+            .get_section_lessons_ordered(ctx, section.course_id, Some(section_id))
+            .await?;
+
+        if !section_lessons.is_empty() {
+            return Err(LessonError::SectionIsNotEmpty { section_id }.into());
+        }
+
+        lesson_repository.delete_section(ctx, section_id).await?;
+
+        let course_sections = lesson_repository
+            .get_course_sections_ordered(ctx, section.course_id)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(index, section)| SectionForChangeOrder { id: section.id, order: index as i32 + 1 })
+            .collect();
+
+        lesson_repository.update_section_orders(ctx, course_sections).await
+    }
+
+    /// Renaming a section of a published course waits in its revision.
+    pub async fn update_section(
+        &self,
+        ctx: &Ctx, 
+        section_for_u: SectionForUpdate
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_section_permission(ctx, section_for_u.id, CoursePermission::EditContent)
+            .await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let section = lesson_repository.get_section(ctx, section_for_u.id).await?;
+
+        let revision_stager = RevisionStager::new(self.repository_manager.clone());
+        if let Some(revision) = revision_stager.get_course_revision(ctx, section.course_id).await? {
+            let edit = StagedEdit::Section(StagedSectionEdit {
+                section_id: section_for_u.id,
+                title: section_for_u.title,
+                description: section_for_u.description,
+            });
+
+            return revision_stager.stage(ctx, revision, edit).await;
+        }
+
+        lesson_repository.update_section(ctx, section_for_u).await
+    }
+
+    /// Moves the section with all of its lessons.
+    pub async fn change_order(
+        &self, 
+        ctx: &Ctx,
+        section_for_u_order: SectionForChangeOrder
+    ) -> LessonResult<()> {
+        self.permission_manager
+            .check_section_permission(ctx, section_for_u_order.id, CoursePermission::EditContent)
+            .await?;
+
+        self.check_structure_editable(ctx, section_for_u_order.id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let section = lesson_repository.get_section(ctx, section_for_u_order.id).await?;
+        let course_sections = lesson_repository
+            .get_course_sections_ordered(ctx, section.course_id)
+            .await?;
+
+        let course_sections = self.compute_orders(&course_sections, &section_for_u_order)?;
+        lesson_repository.update_section_orders(ctx, course_sections).await?;
+
+        Ok(())
+    }
+
+    async fn check_structure_editable(&self, ctx: &Ctx, section_id: i64) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let section = lesson_repository.get_section(ctx, section_id).await?;
+
+        RevisionStager::new(self.repository_manager.clone())
+            .check_structure_editable(ctx, section.course_id)
+            .await
+    }
+
+    fn compute_orders(
+        &self,
+        sections: &[SectionForChangeOrder], 
+        section_for_u_order: &SectionForChangeOrder
+    ) -> LessonResult<Vec<SectionForChangeOrder>> {
+        let orders: Vec<(i64, i32)> = sections.iter().map(|section| (section.id, section.order)).collect();
+
+        order_calculator::compute_orders(&orders, section_for_u_order.id, section_for_u_order.order)
+            .map(|orders| orders.into_iter().map(|(id, order)| SectionForChangeOrder { id, order }).collect())
+            .ok_or_else(|| LessonError::IncorrectSectionOrder { 
+                section_id: section_for_u_order.id,
+                order: section_for_u_order.order 
+            }.into())
+    }
+}
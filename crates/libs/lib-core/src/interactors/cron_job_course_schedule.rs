@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use lib_utils::time::now_utc_sec;
+
+use crate::{ctx::Ctx, interactors::creator::course::CreatorCourseInteractor, interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult}, models::course::CourseScheduleReport};
+
+pub struct CronJobCourseSchedule {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CronJobCourseSchedule {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl CronJobCourseSchedule {
+    pub async fn run_due_schedules(&self) -> CourseResult<CourseScheduleReport> {
+        let ctx = Ctx::root_ctx();
+
+        CreatorCourseInteractor::new(self.repository_manager.clone())
+            .run_due_schedules(&ctx, now_utc_sec())
+            .await
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::difficulty_calibrator::DifficultyCalibrator, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{difficulty_calibration::{DifficultyCalibrationReport, DifficultyChangeForCreate}, exercise::Exercise}};
+
+pub struct CronJobDifficulty {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CronJobDifficulty {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl CronJobDifficulty {
+    /// A failed exercise is reported and the others are calibrated anyway.
+    pub async fn calibrate_difficulties(&self) -> ExerciseResult<DifficultyCalibrationReport> {
+        let ctx = Ctx::root_ctx();
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercise_ids = exercise_repository.get_auto_difficulty_exercises(&ctx).await?;
+        let exercises = exercise_repository.get_exercises(&ctx, exercise_ids).await?;
+
+        let mut report = DifficultyCalibrationReport {
+            changed_exercises: 0,
+            failed_exercise_ids: Vec::new(),
+        };
+
+        for (exercise_id, exercise) in exercises {
+            match self.calibrate_difficulty(&ctx, exercise_id, exercise).await {
+                Ok(changed) => report.changed_exercises += changed,
+                Err(_) => report.failed_exercise_ids.push(exercise_id),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn calibrate_difficulty(&self, ctx: &Ctx, exercise_id: i64, exercise: Exercise) -> ExerciseResult<i32> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise_id).await?;
+
+        let observations = DifficultyCalibrator::observe(&ex_comps);
+
+        let new_difficulty = match DifficultyCalibrator::suggest(&exercise, &observations) {
+            Some(difficulty) if difficulty != exercise.difficult => difficulty,
+            _ => return Ok(0),
+        };
+
+        let difficulty_change_for_c = DifficultyChangeForCreate {
+            exercise_id,
+            old_difficulty: exercise.difficult,
+            new_difficulty,
+            pass_rate: observations.pass_rate,
+        };
+
+        exercise_repository.change_exercise_difficulty(ctx, difficulty_change_for_c).await?;
+
+        Ok(1)
+    }
+}
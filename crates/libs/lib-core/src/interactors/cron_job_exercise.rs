@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use lib_utils::time::now_utc_sec;
 
-use crate::{ctx::Ctx, interactors::exercise_checker::ExerciseChecker, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand}, lesson_progress::LessonProgressState}};
+use crate::{ctx::Ctx, interactors::{exercise_checker::ExerciseChecker, student::lesson::StudentLessonInteractor}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand}, lesson_progress::LessonProgressState}};
 
 pub struct CronJobExercise {
     repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
@@ -35,14 +35,53 @@ impl CronJobExercise {
         Ok(result)
     }
 
+    /// Submits exams whose window has closed, return number of submitted exams
+    pub async fn submit_overdue_exams(&self) -> ExerciseResult<i32> {
+        let ctx = Ctx::root_ctx();
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let overdue_progresses = lesson_repository.get_overdue_exam_progresses(&ctx, now_utc_sec()).await?;
+
+        let lesson_interactor = StudentLessonInteractor::new(self.repository_manager.clone());
+
+        let mut result = 0;
+
+        for lesson_progress in overdue_progresses {
+            let Some(exam) = lesson_repository.get_exam(&ctx, lesson_progress.lesson_id).await? else { continue };
+
+            lesson_interactor.submit_user_exam(&ctx, &exam, lesson_progress.user_id).await?;
+            result += 1;
+        }
+
+        Ok(result)
+    }
+
     async fn complete_exercise(&self, ctx: &Ctx, ex_comp: ExerciseCompletion) -> ExerciseResult<i32> {
         let exercise_repository = self.repository_manager.get_exercise_repository();
 
         let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
 
+        // Exam attempts are handed in together when the exam is submitted.
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        if lesson_repository.get_exam(ctx, exercise.lesson_id).await?.is_some() {
+            return Ok(0);
+        }
+
         let now = now_utc_sec();
         if let Some(time_to_complete) = exercise.time_to_complete {
-            let deadline = time_to_complete as i64 + ex_comp.date_started;
+            // The deadline stands still while the lesson is paused.
+            let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+            let is_paused = lesson_repository
+                .get_lessons_progresses(ctx, lesson.course_id, ex_comp.user_id)
+                .await?
+                .iter()
+                .any(|lesson_progress| lesson_progress.lesson_id == lesson.id && lesson_progress.state.is_paused());
+
+            if is_paused {
+                return Ok(0);
+            }
+
+            let deadline = ex_comp.deadline(time_to_complete as i64);
 
             if now < deadline {
                 return Ok(0);
@@ -61,6 +100,10 @@ impl CronJobExercise {
 
         exercise_repository.complete_exercise_completion(ctx, ex_comp_for_u).await?;
 
+        if ex_comp.is_practice {
+            return Ok(1);
+        }
+
         if self.is_lesson_state_complete(ctx, exercise.lesson_id, ex_comp.user_id).await? {
             self.complete_lesson(ctx, exercise.lesson_id, ex_comp.user_id).await?;
         }
@@ -82,8 +125,8 @@ impl CronJobExercise {
     }
 
     async fn complete_lesson(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<()> {
-        let lesson_repository = self.repository_manager.get_lesson_repository();
-
-        lesson_repository.update_lesson_progress_state(ctx, LessonProgressState::Done, lesson_id, user_id).await
+        StudentLessonInteractor::new(self.repository_manager.clone())
+            .change_lesson_progress_state(ctx, lesson_id, user_id, LessonProgressState::Done)
+            .await
     }
 }
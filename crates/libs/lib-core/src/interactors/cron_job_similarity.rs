@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::similarity_checker::SimilarityChecker, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::similarity_flag::{SimilarityFlagForCreate, SimilarityReport}};
+
+pub struct CronJobSimilarity {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CronJobSimilarity {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl CronJobSimilarity {
+    /// A broken exercise is reported and skipped, it doesn't stop the others.
+    pub async fn flag_similar_submissions(&self) -> ExerciseResult<SimilarityReport> {
+        let ctx = Ctx::root_ctx();
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercise_ids = exercise_repository.get_exercises_for_similarity_check(&ctx).await?;
+
+        let mut report = SimilarityReport {
+            flagged_pairs: 0,
+            failed_exercise_ids: Vec::new(),
+        };
+
+        for exercise_id in exercise_ids {
+            match self.flag_exercise_submissions(&ctx, exercise_id).await {
+                Ok(flagged_pairs) => report.flagged_pairs += flagged_pairs,
+                Err(_) => report.failed_exercise_ids.push(exercise_id),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn flag_exercise_submissions(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<i32> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let exercise = exercise_repository.get_exercise(ctx, exercise_id).await?;
+        let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise_id).await?;
+
+        let flags_for_c: Vec<SimilarityFlagForCreate> = SimilarityChecker::find_similar_pairs(exercise.exercise_body, &ex_comps)?
+            .into_iter()
+            .map(|(first_exercise_completion_id, second_exercise_completion_id, score)| SimilarityFlagForCreate {
+                exercise_id,
+                first_exercise_completion_id,
+                second_exercise_completion_id,
+                score,
+            })
+            .collect();
+
+        let result = flags_for_c.len() as i32;
+
+        exercise_repository.replace_similarity_flags(ctx, exercise_id, flags_for_c).await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::upload_collector::UploadCollector, interfaces::{command_repository_manager::ICommandRepositoryManager, file_storage::FileStorageResult}, models::file::UploadGcReport};
+
+pub struct CronJobUploadGc {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl CronJobUploadGc {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl CronJobUploadGc {
+    pub async fn delete_orphaned_uploads(&self) -> FileStorageResult<UploadGcReport> {
+        let ctx = Ctx::root_ctx();
+
+        UploadCollector::new(self.repository_manager.clone())
+            .collect(&ctx, false)
+            .await
+    }
+}
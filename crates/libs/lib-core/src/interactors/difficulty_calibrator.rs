@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use crate::models::{difficulty_calibration::DifficultyObservations, exercise::{Exercise, ExerciseDifficulty}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}};
+
+/// No difficulty is suggested from fewer students than this.
+pub const MIN_NUMBER_OF_STUDENTS: i64 = 5;
+
+/// Time an exercise without `time_to_complete` is expected to take at most.
+const REFERENCE_TIME_SEC: f32 = 30.0 * 60.0;
+
+const PASS_RATE_WEIGHT: f32 = 0.6;
+const ATTEMPTS_WEIGHT: f32 = 0.2;
+const TIME_WEIGHT: f32 = 0.2;
+
+pub struct DifficultyCalibrator;
+
+impl DifficultyCalibrator {
+    /// Only graded attempts count, practice attempts and attempts in progress or waiting for a review are skipped.
+    pub fn observe(ex_comps: &[ExerciseCompletion]) -> DifficultyObservations {
+        let mut students: HashMap<i64, bool> = HashMap::new();
+        let mut number_of_attempts = 0;
+        let mut time_spent = Vec::new();
+
+        for ex_comp in ex_comps {
+            if ex_comp.is_practice {
+                continue;
+            }
+
+            let succeeded = match ex_comp.state {
+                ExerciseCompletionState::Succeeded => true,
+                ExerciseCompletionState::Failed => false,
+                ExerciseCompletionState::InProgress |
+                ExerciseCompletionState::PendingReview |
+                ExerciseCompletionState::Submitted => continue,
+            };
+
+            *students.entry(ex_comp.user_id).or_insert(false) |= succeeded;
+            number_of_attempts += 1;
+
+            if let Some(date_last_changes) = ex_comp.date_last_changes {
+                time_spent.push((date_last_changes - ex_comp.date_started - ex_comp.paused_seconds).max(0) as f32);
+            }
+        }
+
+        let number_of_students = students.len() as i64;
+        if number_of_students == 0 {
+            return DifficultyObservations {
+                number_of_students,
+                pass_rate: 0.0,
+                average_attempts: 0.0,
+                average_time_spent: None,
+            };
+        }
+
+        let passed = students.values().filter(|succeeded| **succeeded).count();
+
+        DifficultyObservations {
+            number_of_students,
+            pass_rate: passed as f32 / number_of_students as f32,
+            average_attempts: number_of_attempts as f32 / number_of_students as f32,
+            average_time_spent: (!time_spent.is_empty())
+                .then(|| time_spent.iter().sum::<f32>() / time_spent.len() as f32),
+        }
+    }
+
+    /// Combines the failure rate, retries and time spent into a score in `[0, 1]`
+    /// and splits it evenly between Easy, Medium and Hard.
+    pub fn suggest(exercise: &Exercise, observations: &DifficultyObservations) -> Option<ExerciseDifficulty> {
+        if exercise.difficult.eq(&ExerciseDifficulty::Read) || observations.number_of_students < MIN_NUMBER_OF_STUDENTS {
+            return None;
+        }
+
+        let retries = ((observations.average_attempts - 1.0) / 2.0).clamp(0.0, 1.0);
+
+        let expected_time = exercise.time_to_complete
+            .map(|time_to_complete| time_to_complete as f32)
+            .filter(|time_to_complete| *time_to_complete > 0.0)
+            .unwrap_or(REFERENCE_TIME_SEC);
+        let time = observations.average_time_spent
+            .map(|time_spent| (time_spent / expected_time).clamp(0.0, 1.0))
+            .unwrap_or(0.0);
+
+        let score = PASS_RATE_WEIGHT * (1.0 - observations.pass_rate) + ATTEMPTS_WEIGHT * retries + TIME_WEIGHT * time;
+
+        let difficulty = if score < 1.0 / 3.0 {
+            ExerciseDifficulty::Easy
+        } else if score < 2.0 / 3.0 {
+            ExerciseDifficulty::Medium
+        } else {
+            ExerciseDifficulty::Hard
+        };
+
+        Some(difficulty)
+    }
+}
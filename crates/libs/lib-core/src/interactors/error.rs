@@ -30,6 +30,7 @@ pub enum CoreError {
 	// File error
 	#[from]
 	IOError(#[serde_as(as = "DisplayFromStr")] std::io::Error),
+	FileStorageError { key: String, description: String },
 
 	#[from]
 	SerdeJson(#[serde_as(as = "DisplayFromStr")] serde_json::Error),
@@ -56,6 +57,44 @@ pub enum CourseError {
 	CreatorCannotSubscribeToTheCourse,
 	CannotRegisterForCourseTwice,
 	CourseStateDoesNotExist { state: String },
+	IncorrectCourseStateTransition { from: String, to: String },
+	/// Students keep access to an archived course, archive it instead.
+	CourseHasStudents { course_id: i64, students: i64 },
+	CourseMustBeDraft { course_id: i64 },
+	/// Codes of the blocking readiness issues, the full checklist has its own endpoint.
+	CourseIsNotReadyToPublish { course_id: i64, issues: Vec<String> },
+	ScheduledDateInPast { date: i64 },
+	ArchiveDateBeforePublishDate { publish_date: i64, archive_date: i64 },
+	CourseHasNoPendingRevision { course_id: i64 },
+	/// Creating, deleting and reordering can't be staged, unpublish the course first.
+	PublishedCourseStructureIsLocked { course_id: i64 },
+	/// Restoring the course would publish the edits to students without a revision.
+	ArchivedCourseIsReadOnly { course_id: i64 },
+	/// Only co-authors and assistants are invited, the creator stays the creator.
+	IncorrectStaffRole { role: String },
+	UserIsAlreadyInCourse { user_id: i64, course_id: i64 },
+	UserIsNotCourseStaff { user_id: i64, course_id: i64 },
+	CourseTransferStateDoesNotExist { state: String },
+	CourseTransferAlreadyPending { course_id: i64, transfer_id: i64 },
+	CourseTransferIsNotPending { transfer_id: i64 },
+	UserIsAlreadyCourseCreator { user_id: i64, course_id: i64 },
+	CourseHasNoCreator { course_id: i64 },
+
+	PlacementTestNotFound { course_id: i64 },
+	PlacementExerciseNotInCourse { exercise_id: i64 },
+	IncorrectPlacementLevel { min_score: f32, lessons_to_skip: i32 },
+	PlacementTestMustBeTakenFirst { course_id: i64 },
+	PlacementAnswerNotInTest { exercise_id: i64 },
+	PlacementTestAlreadyTaken { course_id: i64 },
+
+	UnlockPolicyDoesNotExist { policy: String },
+	IncorrectDripRelease { lesson_id: i64 },
+
+	ImageFormatNotSupported,
+	ImageTooLarge { size: usize, max_size: usize },
+	ImageDimensionsTooLarge { width: u32, height: u32, max_dimension: u32 },
+	IncorrectImage { description: String },
+	FailSpawnBlockForImage,
 }
 
 #[derive(Debug, Serialize)]
@@ -64,11 +103,43 @@ pub enum LessonError {
     PreviousLessonNotCompleted { lesson_id: i64 },
 	PreviousLessonNotFound { lesson_id: i64 },
 	LessonNotFound { lesson_id: i64 },
+	LessonAlreadyStarted { lesson_id: i64 },
+	LessonIsLocked { lesson_id: i64, locked_until: i64 },
+
+	IncorrectExamWindow { opens_at: i64, closes_at: i64 },
+	LessonIsNotExam { lesson_id: i64 },
+	ExamIsNotOpen { lesson_id: i64 },
+	ExamCanBeTakenOnce { lesson_id: i64 },
+	ExamIsNotInProgress { lesson_id: i64 },
+	ExamInProgress { lesson_id: i64 },
+	ExamIsNotClosed { lesson_id: i64, closes_at: i64 },
+	/// Turning a lesson into an exam or back would change the rules for attempts already made.
+	LessonHasProgress { lesson_id: i64 },
+
+	IncorrectSectionOrder { section_id: i64, order: i32 },
+	SectionIsNotEmpty { section_id: i64 },
+	SectionOfAnotherCourse { section_id: i64, course_id: i64 },
+
+	ContentBlockTypeDoesNotExist { block_type: String },
+	IncorrectContentBlockBodyFormat { description: String },
+	IncorrectContentBlockUrl { url: String },
+	IncorrectContentBlockOrder { content_block_id: i64, order: i32 },
+
+	AttachmentTooLarge { size: usize, max_size: usize },
+	AttachmentTypeNotAllowed { content_type: String },
+	AttachmentContentDoesNotMatchType { content_type: String },
+	AttachmentIsEmpty,
+	InvalidAttachmentLink,
 }
 
 #[derive(Debug, Serialize)]
 pub enum LessonProgressError {
 	LessonProgressStateDoesNotExist { state: String },
+	LessonProgressNotFound { lesson_id: i64 },
+	IncorrectStateTransition { from: String, to: String },
+	ExamCannotBePaused { lesson_id: i64 },
+	/// Only the student's own pause can be resumed, a pause without a date was set by the system.
+	LessonNotPausedByStudent { lesson_id: i64 },
 }
 
 #[derive(Debug, Serialize)]
@@ -79,6 +150,8 @@ pub enum ExerciseError {
 	IncorrectProcessStagesFormat,
 	IncorrectExerciseType,
 	IncorrectExerciseDifficulty,
+	IncorrectLayoutAlgorithm { algorithm: String },
+	TooManyNodesForLayout { number_of_nodes: usize, max_number_of_nodes: usize },
 	NotEnoughNodesError { number_of_nodes: usize },
 	NotEnoughConnectionsError { number_of_connections: usize },
 	CannotUpdateExerciseBodyWithoutType,
@@ -95,6 +168,24 @@ pub enum ExerciseError {
 	ExerciseCompletionAccessDenied { user_id: i64, ex_comp_id: i64},
 	TimeToCompleteExerciseHasExpired,
 	AttemptHasAlreadyBeenCompleted,
+	AttemptHasNotBeenCompleted,
+	AttemptIsNotPendingReview,
+	AttemptCannotBeSentToReview,
+	ReviewPointsOutOfRange { points_scored: f32, max_points: f32 },
+	ReviewCommentTooLong { length: usize, max_length: usize },
+	PracticeRequiresSucceededAttempt { exercise_id: i64 },
+	PracticeIsNotAvailableInExam,
+	ExamAllowsSingleAttempt { exercise_id: i64 },
+	ExamIsGradedOnSubmit,
+	ExamResultsAreNotReleased,
+
+	PeerReviewIsNotEnabled,
+	IncorrectRubricFormat { description: String },
+	IncorrectRubricScores,
+	PeerReviewStateDoesNotExist { state: String },
+	PeerReviewAccessDenied { user_id: i64, peer_review_id: i64 },
+	PeerReviewHasAlreadyBeenSubmitted,
+	ExerciseMustBeCompletedForPeerReview,
 
 }
 
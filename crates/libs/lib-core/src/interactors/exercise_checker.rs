@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use crate::{interfaces::exercise::ExerciseResult, models::{exercise::{Connection, Conspect, Exercise, ExerciseEstimate, Node, ProcessStages}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}}};
+use crate::{interfaces::exercise::ExerciseResult, models::{exercise::{Connection, Conspect, Definition, Exercise, ExerciseEstimate, HeaderBody, Node, NodeType, ProcessStages, Stage}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}}};
 
 use super::error::ExerciseError;
 
@@ -9,6 +9,15 @@ pub struct Estimate {
     pub max_points: i64,
 }
 
+/// What a single submission got wrong, matched the same way as for grading.
+pub struct ConspectMistakes {
+    pub missing_connections: Vec<(String, String)>,
+    pub extra_connections: Vec<(String, String)>,
+    /// Process stages node id and the zero-based position of a misplaced stage.
+    pub wrong_stage_positions: Vec<(String, usize)>,
+    pub blank_nodes: Vec<String>,
+}
+
 pub struct ExerciseChecker;
 
 impl ExerciseChecker {
@@ -36,13 +45,14 @@ impl ExerciseChecker {
             crate::models::exercise::ExerciseType::InteractiveConspect => Self::evaluate_conspects_bodies(exercise.answer_body.clone(), ex_comp.body.clone().unwrap())?,
         };
 
-        let max_points: f32 = f32::from(exercise.difficult.clone()) * 100.0;
+        let max_points = Self::max_points(exercise);
         let points = max_points * conspect_estimate.points as f32 / conspect_estimate.max_points as f32;        
 
-        let state = if points / max_points < 0.6 {
-            ExerciseCompletionState::Failed
+        // Practice attempts never wait for a creator, they don't affect grades anyway.
+        let state = if exercise.manual_review && !ex_comp.is_practice {
+            ExerciseCompletionState::PendingReview
         } else {
-            ExerciseCompletionState::Succeeded
+            Self::graded_state(points, max_points)
         };
 
         Ok(ExerciseEstimate {
@@ -53,12 +63,125 @@ impl ExerciseChecker {
         })
     }
 
-    fn evaluate_conspects_bodies(answer_conspect: Value, solution_conspect: Value) -> ExerciseResult<Estimate> {
-        let answer_conspect: Conspect = serde_json::from_value(answer_conspect)
-            .map_err(|err| ExerciseError::IncorrectExerciseBodyFormat { description: err.to_string() })?;
+    /// Points a perfect attempt of the exercise scores at its current difficulty.
+    pub(crate) fn max_points(exercise: &Exercise) -> f32 {
+        f32::from(exercise.difficult.clone()) * 100.0
+    }
+
+    /// An attempt passes with at least 60% of the points.
+    pub(crate) fn graded_state(points: f32, max_points: f32) -> ExerciseCompletionState {
+        if points / max_points < 0.6 {
+            ExerciseCompletionState::Failed
+        } else {
+            ExerciseCompletionState::Succeeded
+        }
+    }
 
-        let solution_conspect: Conspect = serde_json::from_value(solution_conspect)
-            .map_err(|err| ExerciseError::IncorrectExerciseBodyFormat { description: err.to_string() })?;
+    /// Share of the points `body` scores, the way an attempt would be graded.
+    pub(crate) fn score_answer(exercise: &Exercise, body: Value) -> ExerciseResult<f32> {
+        let estimate = match exercise.exercise_type {
+            crate::models::exercise::ExerciseType::Conspect |
+            crate::models::exercise::ExerciseType::InteractiveConspect => Self::evaluate_conspects_bodies(exercise.answer_body.clone(), body)?,
+        };
+
+        if estimate.max_points == 0 {
+            return Ok(0.0);
+        }
+
+        Ok(estimate.points as f32 / estimate.max_points as f32)
+    }
+
+    pub(crate) fn parse_conspect(conspect: Value) -> ExerciseResult<Conspect> {
+        serde_json::from_value(conspect)
+            .map_err(|err| ExerciseError::IncorrectExerciseBodyFormat { description: err.to_string() }.into())
+    }
+
+    pub(crate) fn parse_process_stages(node: &Node) -> ExerciseResult<Vec<Stage>> {
+        let stages = serde_json::from_value::<ProcessStages>(node.body.clone())
+            .map_err(|_| ExerciseError::IncorrectProcessStagesFormat)?.stages;
+
+        Ok(stages)
+    }
+
+    pub(crate) fn find_connection<'a>(connection: &Connection, connections: &'a [Connection]) -> Option<&'a Connection> {
+        connections
+            .iter()
+            .find(|other| other.from == connection.from && other.to == connection.to)
+    }
+
+    pub(crate) fn find_node<'a>(node_id: &str, nodes: &'a [Node]) -> Option<&'a Node> {
+        nodes.iter().find(|node| node.id == node_id)
+    }
+
+    pub(crate) fn find_mistakes(answer_conspect: &Conspect, solution_conspect: &Conspect) -> ExerciseResult<ConspectMistakes> {
+        let missing_connections = answer_conspect.connections
+            .iter()
+            .filter(|connection| Self::find_connection(connection, &solution_conspect.connections).is_none())
+            .map(|connection| (connection.from.clone(), connection.to.clone()))
+            .collect();
+
+        let extra_connections = solution_conspect.connections
+            .iter()
+            .filter(|connection| Self::find_connection(connection, &answer_conspect.connections).is_none())
+            .map(|connection| (connection.from.clone(), connection.to.clone()))
+            .collect();
+
+        let mut wrong_stage_positions = Vec::new();
+        let mut blank_nodes = Vec::new();
+
+        for node in &answer_conspect.nodes {
+            let solution_node = match Self::find_node(&node.id, &solution_conspect.nodes) {
+                Some(solution_node) => solution_node,
+                None => {
+                    blank_nodes.push(node.id.clone());
+                    continue;
+                },
+            };
+
+            if Self::is_node_blank(solution_node) {
+                blank_nodes.push(node.id.clone());
+            }
+
+            if let NodeType::ProcessStages = node.node_type {
+                let stages = Self::parse_process_stages(node)?;
+                let solution_stages = Self::parse_process_stages(solution_node)?;
+
+                for (position, stage) in stages.iter().enumerate() {
+                    let is_correct = solution_stages
+                        .get(position)
+                        .is_some_and(|solution_stage| solution_stage.id == stage.id);
+
+                    if !is_correct {
+                        wrong_stage_positions.push((node.id.clone(), position));
+                    }
+                }
+            }
+        }
+
+        Ok(ConspectMistakes {
+            missing_connections,
+            extra_connections,
+            wrong_stage_positions,
+            blank_nodes,
+        })
+    }
+
+    /// A node is blank when the part the student has to fill in is empty,
+    /// a body that doesn't match its type counts as blank too.
+    fn is_node_blank(node: &Node) -> bool {
+        match node.node_type {
+            NodeType::Header => serde_json::from_value::<HeaderBody>(node.body.clone())
+                .map_or(true, |body| body.header.trim().is_empty()),
+            NodeType::Definition => serde_json::from_value::<Definition>(node.body.clone())
+                .map_or(true, |body| body.definition.trim().is_empty()),
+            NodeType::ProcessStages => serde_json::from_value::<ProcessStages>(node.body.clone())
+                .map_or(true, |body| body.stages.is_empty()),
+        }
+    }
+
+    fn evaluate_conspects_bodies(answer_conspect: Value, solution_conspect: Value) -> ExerciseResult<Estimate> {
+        let answer_conspect = Self::parse_conspect(answer_conspect)?;
+        let solution_conspect = Self::parse_conspect(solution_conspect)?;
 
         let connections_estimate = Self::evaluate_connections(&answer_conspect.connections, &solution_conspect.connections)?;
         let nodes_estimate = Self::evaluate_nodes(&answer_conspect.nodes, &solution_conspect.nodes)?;
@@ -76,10 +199,7 @@ impl ExerciseChecker {
         };
         
         for answer_connection in answer_connections {
-            let connection_exist = solution_connections
-                .iter()
-                .find(|connection| connection.from == answer_connection.from && connection.to == answer_connection.to)
-                .is_some();
+            let connection_exist = Self::find_connection(answer_connection, solution_connections).is_some();
 
             if connection_exist {
                 result.points += 1;
@@ -118,16 +238,13 @@ impl ExerciseChecker {
     }
 
     fn evaluate_node_process_stages(node: &Node, solution_nodes: &Vec<Node>) -> ExerciseResult<Estimate> {
-        let solution_node = match solution_nodes.iter().find(|solution_node| solution_node.id == node.id) {
+        let solution_node = match Self::find_node(&node.id, solution_nodes) {
             Some(solution_node) => solution_node,
             None => return Ok(Estimate { points: 0, max_points: 0 }),
         };
 
-        let stages = serde_json::from_value::<ProcessStages>(node.body.clone())
-            .map_err(|_| ExerciseError::IncorrectProcessStagesFormat)?.stages;
-
-        let solution_stages = serde_json::from_value::<ProcessStages>(solution_node.body.clone())
-            .map_err(|_| ExerciseError::IncorrectProcessStagesFormat)?.stages;
+        let stages = Self::parse_process_stages(node)?;
+        let solution_stages = Self::parse_process_stages(solution_node)?;
 
         let mut points = 0;
 
@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use crate::{interfaces::exercise::ExerciseResult, models::exercise::{Conspect, Definition, ExerciseType, HeaderBody, Node, ProcessStages}};
+use crate::{interfaces::exercise::ExerciseResult, models::{exercise::{Conspect, Definition, ExerciseType, HeaderBody, Node, ProcessStages}, peer_review::RubricCriterion}};
 
 use super::error::ExerciseError;
 
@@ -67,4 +67,32 @@ impl ExerciseValidator {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn validate_peer_review(peer_review_count: i32, rubric: Value) -> ExerciseResult<()> {
+        if peer_review_count < 0 {
+            return Err(ExerciseError::IncorrectRubricFormat { description: "peer_review_count must not be negative".to_string() }.into());
+        }
+
+        if peer_review_count == 0 {
+            return Ok(());
+        }
+
+        let rubric = Self::parse_rubric(rubric)?;
+        if rubric.is_empty() {
+            return Err(ExerciseError::IncorrectRubricFormat { description: "rubric must have at least one criterion".to_string() }.into());
+        }
+
+        if rubric.iter().any(|criterion| criterion.max_points <= 0.0) {
+            return Err(ExerciseError::IncorrectRubricFormat { description: "max_points must be positive".to_string() }.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn parse_rubric(rubric: Value) -> ExerciseResult<Vec<RubricCriterion>> {
+        let rubric = serde_json::from_value::<Vec<RubricCriterion>>(rubric)
+            .map_err(|err| ExerciseError::IncorrectRubricFormat { description: err.to_string() })?;
+
+        Ok(rubric)
+    }
+}
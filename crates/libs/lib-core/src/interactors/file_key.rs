@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+/// Random key, so uploads never overwrite each other, keeping the extension of the content type.
+pub fn new_file_key(prefix: &str, folder: &str, content_type: &str) -> String {
+    match extension(content_type) {
+        Some(extension) => format!("{}/{}/{}.{}", prefix, folder, Uuid::new_v4(), extension),
+        None => format!("{}/{}/{}", prefix, folder, Uuid::new_v4()),
+    }
+}
+
+/// Random folder for files uploaded together, e.g. the variants of one image.
+pub fn new_file_folder(prefix: &str, folder: &str) -> String {
+    format!("{}/{}/{}", prefix, folder, Uuid::new_v4())
+}
+
+fn extension(content_type: &str) -> Option<&'static str> {
+    let extension = match content_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/webp" => "webp",
+        "image/gif" => "gif",
+        "application/pdf" => "pdf",
+        "application/vnd.ms-powerpoint" => "ppt",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation" => "pptx",
+        "application/vnd.oasis.opendocument.presentation" => "odp",
+        "application/msword" => "doc",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => "docx",
+        _ => return None,
+    };
+
+    Some(extension)
+}
@@ -0,0 +1,100 @@
+use std::io::Cursor;
+
+use image::{imageops::FilterType, DynamicImage, GenericImageView, ImageDecoder, ImageFormat, ImageReader};
+
+use crate::{interfaces::course::CourseResult, models::course_image::{CourseImageFormat, CourseImageVariant, ProcessedCourseImage, MAX_COURSE_IMG_DIMENSION, MAX_COURSE_IMG_SIZE}};
+
+use super::error::CourseError;
+
+const SUPPORTED_FORMATS: [ImageFormat; 4] = [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::WebP, ImageFormat::Gif];
+
+pub struct ImageProcessor;
+
+impl ImageProcessor {
+    /// Decodes the upload and encodes every variant from the decoded pixels,
+    /// so EXIF and other metadata of the original never reach the storage.
+    pub fn process_course_image(data: &[u8]) -> CourseResult<Vec<ProcessedCourseImage>> {
+        if data.len() > MAX_COURSE_IMG_SIZE {
+            return Err(CourseError::ImageTooLarge { size: data.len(), max_size: MAX_COURSE_IMG_SIZE }.into());
+        }
+
+        let image = Self::decode(data)?;
+
+        let mut result = Vec::new();
+        for variant in CourseImageVariant::ALL {
+            let (width, height) = Self::fit(variant.size(), image.dimensions());
+            let resized = image.resize_to_fill(width, height, FilterType::Lanczos3);
+
+            for format in CourseImageFormat::ALL {
+                result.push(ProcessedCourseImage {
+                    variant,
+                    format,
+                    data: Self::encode(&resized, format)?,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The format is taken from the magic bytes, the declared content type is not trusted.
+    fn decode(data: &[u8]) -> CourseResult<DynamicImage> {
+        let format = image::guess_format(data).map_err(|_| CourseError::ImageFormatNotSupported)?;
+        if !SUPPORTED_FORMATS.contains(&format) {
+            return Err(CourseError::ImageFormatNotSupported.into());
+        }
+
+        // The dimensions come from the header, checked before any pixel is allocated.
+        let mut decoder = ImageReader::with_format(Cursor::new(data), format)
+            .into_decoder()
+            .map_err(Self::incorrect_image)?;
+
+        let (width, height) = decoder.dimensions();
+        if width > MAX_COURSE_IMG_DIMENSION || height > MAX_COURSE_IMG_DIMENSION {
+            return Err(
+                CourseError::ImageDimensionsTooLarge { 
+                    width, 
+                    height, 
+                    max_dimension: MAX_COURSE_IMG_DIMENSION 
+                }.into()
+            );
+        }
+
+        // Photos are often stored sideways with the rotation in EXIF, which is dropped below.
+        let orientation = decoder.orientation().map_err(Self::incorrect_image)?;
+        let mut image = DynamicImage::from_decoder(decoder).map_err(Self::incorrect_image)?;
+        image.apply_orientation(orientation);
+
+        Ok(image)
+    }
+
+    /// Scales the variant down to the source, small uploads are cropped but never upscaled.
+    fn fit((width, height): (u32, u32), (source_width, source_height): (u32, u32)) -> (u32, u32) {
+        let scale = (source_width as f64 / width as f64)
+            .min(source_height as f64 / height as f64)
+            .min(1.0);
+
+        let width = ((width as f64 * scale).round() as u32).max(1);
+        let height = ((height as f64 * scale).round() as u32).max(1);
+
+        (width, height)
+    }
+
+    fn encode(image: &DynamicImage, format: CourseImageFormat) -> CourseResult<Vec<u8>> {
+        let image_format = match format {
+            CourseImageFormat::Webp => ImageFormat::WebP,
+            CourseImageFormat::Png => ImageFormat::Png,
+        };
+
+        let mut data = Vec::new();
+        DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_to(&mut Cursor::new(&mut data), image_format)
+            .map_err(Self::incorrect_image)?;
+
+        Ok(data)
+    }
+
+    fn incorrect_image(error: image::ImageError) -> CourseError {
+        CourseError::IncorrectImage { description: error.to_string() }
+    }
+}
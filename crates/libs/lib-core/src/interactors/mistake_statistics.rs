@@ -0,0 +1,72 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{interfaces::exercise::ExerciseResult, models::{exercise::{ConnectionMistake, Exercise, ExerciseMistakeStatistics, NodeMistake, StageMistake}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}}};
+
+use super::exercise_checker::ExerciseChecker;
+
+pub struct MistakeStatisticsBuilder;
+
+impl MistakeStatisticsBuilder {
+    /// Counts the mistakes of every graded attempt with a body. Attempts that are still
+    /// in progress or waiting for a manual review are skipped, as are bodies that don't parse
+    /// so one broken attempt doesn't hide the statistics of the exercise.
+    pub fn build(exercise_id: i64, exercise: &Exercise, ex_comps: &[ExerciseCompletion]) -> ExerciseResult<ExerciseMistakeStatistics> {
+        let answer_conspect = ExerciseChecker::parse_conspect(exercise.answer_body.clone())?;
+
+        let mut number_of_completions = 0;
+        let mut missing_connections = HashMap::new();
+        let mut extra_connections = HashMap::new();
+        let mut wrong_stage_positions = HashMap::new();
+        let mut blank_nodes = HashMap::new();
+
+        for ex_comp in ex_comps {
+            let is_graded = !ex_comp.is_practice && matches!(ex_comp.state, ExerciseCompletionState::Succeeded | ExerciseCompletionState::Failed);
+            let Some(body) = ex_comp.body.clone().filter(|_| is_graded) else { continue };
+
+            let Ok(solution_conspect) = ExerciseChecker::parse_conspect(body) else { continue };
+            let Ok(mistakes) = ExerciseChecker::find_mistakes(&answer_conspect, &solution_conspect) else { continue };
+
+            number_of_completions += 1;
+            Self::count(&mut missing_connections, mistakes.missing_connections);
+            Self::count(&mut extra_connections, mistakes.extra_connections);
+            Self::count(&mut wrong_stage_positions, mistakes.wrong_stage_positions);
+            Self::count(&mut blank_nodes, mistakes.blank_nodes);
+        }
+
+        Ok(ExerciseMistakeStatistics {
+            exercise_id,
+            title: exercise.title.clone(),
+            number_of_completions,
+            missing_connections: Self::connection_mistakes(missing_connections),
+            extra_connections: Self::connection_mistakes(extra_connections),
+            wrong_stage_positions: Self::most_frequent(wrong_stage_positions)
+                .into_iter()
+                .map(|((node_id, position), count)| StageMistake { node_id, position, count })
+                .collect(),
+            blank_nodes: Self::most_frequent(blank_nodes)
+                .into_iter()
+                .map(|(node_id, count)| NodeMistake { node_id, count })
+                .collect(),
+        })
+    }
+
+    fn count<T: Eq + Hash>(counter: &mut HashMap<T, i64>, mistakes: Vec<T>) {
+        for mistake in mistakes {
+            *counter.entry(mistake).or_insert(0) += 1;
+        }
+    }
+
+    fn connection_mistakes(counter: HashMap<(String, String), i64>) -> Vec<ConnectionMistake> {
+        Self::most_frequent(counter)
+            .into_iter()
+            .map(|((from, to), count)| ConnectionMistake { from, to, count })
+            .collect()
+    }
+
+    fn most_frequent<T: Ord>(counter: HashMap<T, i64>) -> Vec<(T, i64)> {
+        let mut result: Vec<(T, i64)> = counter.into_iter().collect();
+        result.sort_by(|(a_key, a_count), (b_key, b_count)| b_count.cmp(a_count).then_with(|| a_key.cmp(b_key)));
+
+        result
+    }
+}
@@ -3,7 +3,24 @@ pub mod student;
 pub mod user;
 pub mod error;
 pub mod permission_manager;
-pub mod img_file;
 pub mod cron_job_exercise;
+pub mod cron_job_similarity;
+pub mod cron_job_difficulty;
+pub mod cron_job_upload_gc;
+pub mod cron_job_course_schedule;
 pub(crate) mod exercise_validator;
-pub(crate) mod exercise_checker;
\ No newline at end of file
+pub(crate) mod exercise_checker;
+pub(crate) mod conspect_layout;
+pub(crate) mod conspect_diff;
+pub(crate) mod peer_review_assigner;
+pub(crate) mod similarity_checker;
+pub(crate) mod mistake_statistics;
+pub(crate) mod difficulty_calibrator;
+pub(crate) mod progress_reconciler;
+pub(crate) mod content_renderer;
+pub(crate) mod file_key;
+pub(crate) mod image_processor;
+pub(crate) mod upload_collector;
+pub(crate) mod publish_readiness_checker;
+pub(crate) mod revision_stager;
+pub(crate) mod order_calculator;
\ No newline at end of file
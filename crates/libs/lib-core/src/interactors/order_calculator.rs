@@ -0,0 +1,38 @@
+/// New `(id, order)` pairs of ordered items after moving `moved_id` to `new_order`,
+/// the items in between shift by one. `None` when `new_order` is outside of the list.
+pub fn compute_orders(items: &[(i64, i32)], moved_id: i64, new_order: i32) -> Option<Vec<(i64, i32)>> {
+    let number_of_items = items.len() as i32;
+    if new_order < 1 || number_of_items < new_order {
+        return None;
+    }
+
+    let mut result = Vec::new();
+    let mut d_order = 0;
+
+    for &(id, order) in items {
+        let order = if id == moved_id {
+            d_order = if d_order != 0 {
+                0
+            } else {
+                -1
+            };
+
+            new_order
+        } else if order == new_order {
+            let order = if d_order == 0 {
+                order + 1
+            } else {
+                order - 1
+            };
+            d_order += 1;
+
+            order
+        } else {
+            order + d_order
+        };
+
+        result.push((id, order));
+    }
+
+    Some(result)
+}
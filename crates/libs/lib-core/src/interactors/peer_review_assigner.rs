@@ -0,0 +1,90 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, peer_review::PeerReview};
+
+/// Weight of the auto grade in the final grade, the rest comes from the median peer score.
+pub const AUTO_GRADE_WEIGHT: f32 = 0.5;
+
+pub struct PeerReviewAssigner;
+
+impl PeerReviewAssigner {
+    /// Picks the completions the reviewer still has to be assigned, up to `count` in total.
+    /// Only the latest finished attempt of every classmate is considered, the reviewer's own
+    /// work is never picked and the least reviewed submissions go first. Ties go to classmates
+    /// who have reviewed the least themselves, since they still have to be served later and
+    /// cannot take their own submission.
+    pub fn select_submissions(
+        ex_comps: &[ExerciseCompletion],
+        peer_reviews: &[PeerReview],
+        reviewer_id: i64,
+        count: usize,
+    ) -> Vec<i64> {
+        let assigned: HashSet<i64> = peer_reviews
+            .iter()
+            .filter(|peer_review| peer_review.reviewer_id == reviewer_id)
+            .map(|peer_review| peer_review.exercise_completion_id)
+            .collect();
+
+        if assigned.len() >= count {
+            return Vec::new();
+        }
+
+        let mut latest: HashMap<i64, i64> = HashMap::new();
+        for ex_comp in ex_comps {
+            if ex_comp.state.eq(&ExerciseCompletionState::InProgress) || ex_comp.is_practice || ex_comp.user_id == reviewer_id {
+                continue;
+            }
+
+            let ex_comp_id = latest.entry(ex_comp.user_id).or_insert(ex_comp.id);
+            if *ex_comp_id < ex_comp.id {
+                *ex_comp_id = ex_comp.id;
+            }
+        }
+
+        let mut load: HashMap<i64, usize> = HashMap::new();
+        let mut reviewer_load: HashMap<i64, usize> = HashMap::new();
+        for peer_review in peer_reviews {
+            *load.entry(peer_review.exercise_completion_id).or_insert(0) += 1;
+            *reviewer_load.entry(peer_review.reviewer_id).or_insert(0) += 1;
+        }
+
+        let mut candidates: Vec<(usize, usize, i64)> = latest
+            .into_iter()
+            .filter(|(_, ex_comp_id)| !assigned.contains(ex_comp_id))
+            .map(|(user_id, ex_comp_id)| (
+                load.get(&ex_comp_id).copied().unwrap_or(0),
+                reviewer_load.get(&user_id).copied().unwrap_or(0),
+                ex_comp_id,
+            ))
+            .collect();
+
+        candidates.sort();
+
+        candidates
+            .into_iter()
+            .take(count - assigned.len())
+            .map(|(_, _, ex_comp_id)| ex_comp_id)
+            .collect()
+    }
+
+    pub fn median(scores: &[f32]) -> Option<f32> {
+        if scores.is_empty() {
+            return None;
+        }
+
+        let mut scores = scores.to_vec();
+        scores.sort_by(|a, b| a.total_cmp(b));
+
+        let middle = scores.len() / 2;
+        if scores.len().is_multiple_of(2) {
+            Some((scores[middle - 1] + scores[middle]) / 2.0)
+        } else {
+            Some(scores[middle])
+        }
+    }
+
+    /// Final points from the auto grade and the median peer score, both as shares of `max_points`.
+    pub fn blend(auto_score: f32, peer_score: f32, max_points: f32) -> f32 {
+        max_points * (AUTO_GRADE_WEIGHT * auto_score + (1.0 - AUTO_GRADE_WEIGHT) * peer_score)
+    }
+}
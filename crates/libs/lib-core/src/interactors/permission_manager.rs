@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{ctx::Ctx, interfaces::command_repository_manager::ICommandRepositoryManager};
+use crate::{ctx::Ctx, interfaces::command_repository_manager::ICommandRepositoryManager, models::course::CoursePermission};
 
 use super::error::CoreError;
 
@@ -19,10 +19,20 @@ impl PermissionManager {
 }
 
 impl PermissionManager {
-    pub async fn check_course_creator_permission(
+    pub fn check_admin_permission(&self, ctx: &Ctx) -> PermissionResult<()> {
+        if ctx.user_id() != 1000 {
+            return Err(CoreError::PermissionDenied);
+        }
+
+        Ok(())
+    }
+
+    /// Admin passes every check, staff members pass the checks their role allows.
+    pub async fn check_course_permission(
         &self, 
         ctx: &Ctx,
         course_id: i64,
+        permission: CoursePermission,
     ) -> PermissionResult<()> {
         let user_id = ctx.user_id();
 
@@ -33,7 +43,7 @@ impl PermissionManager {
 
         let course_repository = self.repository_manager.get_course_repository();
         let user_course = course_repository.get_user_course(ctx, ctx.user_id(), course_id).await?;
-        if !user_course.user_role.eq(&crate::models::course::UserCourseRole::Creator) {
+        if !user_course.user_role.has_permission(permission) {
             return Err(CoreError::PermissionDenied);
         }
 
@@ -61,15 +71,28 @@ impl PermissionManager {
         Ok(())
     }
 
-    pub async fn check_lesson_creator_permission(
+    pub async fn check_lesson_permission(
         &self, 
         ctx: &Ctx,
         lesson_id: i64,
+        permission: CoursePermission,
     ) -> PermissionResult<()> {
         let lesson_repository = self.repository_manager.get_lesson_repository();
         let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
 
-        self.check_course_creator_permission(ctx, lesson.course_id).await
+        self.check_course_permission(ctx, lesson.course_id, permission).await
+    }
+
+    pub async fn check_section_permission(
+        &self, 
+        ctx: &Ctx,
+        section_id: i64,
+        permission: CoursePermission,
+    ) -> PermissionResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let section = lesson_repository.get_section(ctx, section_id).await?;
+
+        self.check_course_permission(ctx, section.course_id, permission).await
     }
 
     pub async fn check_lesson_student_permission(
@@ -83,10 +106,11 @@ impl PermissionManager {
         self.check_course_student_permission(ctx, lesson.course_id).await
     }
 
-    pub async fn check_exercise_creator_permission(
+    pub async fn check_exercise_permission(
         &self,
         ctx: &Ctx,
         exercise_id: i64,
+        permission: CoursePermission,
     ) -> PermissionResult<()> {
         let exercise_repository = self.repository_manager.get_exercise_repository();
         let exercise = exercise_repository.get_exercise(ctx, exercise_id).await?;
@@ -94,7 +118,7 @@ impl PermissionManager {
         let lesson_repository = self.repository_manager.get_lesson_repository();
         let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
 
-        self.check_lesson_creator_permission(ctx, lesson.id).await
+        self.check_lesson_permission(ctx, lesson.id, permission).await
     }
 
     pub async fn check_exercise_student_permission(
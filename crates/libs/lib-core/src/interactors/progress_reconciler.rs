@@ -0,0 +1,230 @@
+use std::{collections::{HashMap, HashSet}, sync::Arc};
+
+use crate::{ctx::Ctx, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::{lesson::LessonForChangeOreder, lesson_progress::{LessonProgress, LessonProgressState, LessonProgressStateChange, ProgressReconciliation}}};
+
+/// Brings lesson progresses back in line with the lesson contents after a creator
+/// adds exercises or asks students to retake one.
+pub struct ProgressReconciler {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+/// The lesson students have to go through again and the lessons after it.
+struct Retake {
+    lesson_id: i64,
+    later_lesson_ids: HashSet<i64>,
+}
+
+impl ProgressReconciler {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl ProgressReconciler {
+    /// A lesson is done once every exercise has a graded successful attempt. Lessons skipped
+    /// by the placement test and exams keep their state, exams are done on submission.
+    pub async fn reconcile_course(&self, ctx: &Ctx, course_id: i64) -> LessonResult<ProgressReconciliation> {
+        self.reconcile(ctx, course_id, &[]).await
+    }
+
+    pub async fn reconcile_lesson_course(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<ProgressReconciliation> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        self.reconcile_course(ctx, lesson.course_id).await
+    }
+
+    /// The earliest retaken lesson is reopened and every lesson after it is locked, students
+    /// go through the retaken exercises before they move on.
+    pub async fn reconcile_retake(&self, ctx: &Ctx, course_id: i64, retaken_lesson_ids: &[i64]) -> LessonResult<ProgressReconciliation> {
+        self.reconcile(ctx, course_id, retaken_lesson_ids).await
+    }
+
+    /// Lifts the retake lock of the student's lessons once the retaken lesson is done again.
+    pub async fn unlock_lessons(&self, ctx: &Ctx, course_id: i64, user_id: i64) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let locked_progresses: Vec<LessonProgress> = lesson_repository
+            .get_lessons_progresses(ctx, course_id, user_id)
+            .await?
+            .into_iter()
+            .filter(|lesson_progress| lesson_progress.state.eq(&LessonProgressState::Locked))
+            .collect();
+
+        if locked_progresses.is_empty() {
+            return Ok(());
+        }
+
+        let lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+        let number_of_exercises = self.get_number_of_exercises(ctx, course_id, &lessons).await?;
+        let completed = self.get_completed_exercises(ctx, course_id).await?;
+
+        let changes = locked_progresses
+            .into_iter()
+            .map(|lesson_progress| {
+                let state = match Self::is_complete(&lesson_progress, &number_of_exercises, &completed) {
+                    Some(true) | None => LessonProgressState::Done,
+                    Some(false) => LessonProgressState::InProgress,
+                };
+
+                LessonProgressStateChange {
+                    lesson_id: lesson_progress.lesson_id,
+                    user_id,
+                    previous_state: LessonProgressState::Locked,
+                    state,
+                }
+            })
+            .collect();
+
+        lesson_repository.update_lesson_progresses_states(ctx, changes).await
+    }
+
+    async fn reconcile(&self, ctx: &Ctx, course_id: i64, retaken_lesson_ids: &[i64]) -> LessonResult<ProgressReconciliation> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+
+        let retake = lessons
+            .iter()
+            .position(|lesson| retaken_lesson_ids.contains(&lesson.id))
+            .map(|position| Retake {
+                lesson_id: lessons[position].id,
+                later_lesson_ids: lessons[position + 1..].iter().map(|lesson| lesson.id).collect(),
+            });
+
+        let number_of_exercises = self.get_number_of_exercises(ctx, course_id, &lessons).await?;
+        let completed = self.get_completed_exercises(ctx, course_id).await?;
+
+        let lesson_progresses = lesson_repository.get_course_lesson_progresses(ctx, course_id).await?;
+
+        let mut result = ProgressReconciliation {
+            course_id,
+            lessons_completed: 0,
+            lessons_reopened: 0,
+            students_completed_course: 0,
+        };
+
+        let mut changes = Vec::new();
+        let mut done_lessons: HashMap<i64, usize> = HashMap::new();
+
+        for lesson_progress in lesson_progresses {
+            let state = match &retake {
+                Some(retake) if retake.lesson_id == lesson_progress.lesson_id => LessonProgressState::InProgress,
+                Some(retake) if retake.later_lesson_ids.contains(&lesson_progress.lesson_id) => LessonProgressState::Locked,
+                _ => Self::reconciled_state(&lesson_progress, &number_of_exercises, &completed),
+            };
+
+            if state != lesson_progress.state {
+                // Reopening and locking a finished lesson are not student transitions,
+                // so they bypass the state machine.
+                match (&lesson_progress.state, &state) {
+                    (LessonProgressState::Done, _) => result.lessons_reopened += 1,
+                    (_, LessonProgressState::Done) => result.lessons_completed += 1,
+                    _ => {},
+                }
+
+                changes.push(LessonProgressStateChange {
+                    lesson_id: lesson_progress.lesson_id,
+                    user_id: lesson_progress.user_id,
+                    previous_state: lesson_progress.state.clone(),
+                    state: state.clone(),
+                });
+            }
+
+            if state.eq(&LessonProgressState::Done) {
+                *done_lessons.entry(lesson_progress.user_id).or_insert(0) += 1;
+            }
+        }
+
+        // All or nothing, a half applied reconciliation would leave students between two versions
+        // of the course. A progress the student moved since it was read keeps its new state.
+        lesson_repository.update_lesson_progresses_states(ctx, changes).await?;
+
+        result.students_completed_course = done_lessons
+            .values()
+            .filter(|number_of_done| !lessons.is_empty() && **number_of_done == lessons.len())
+            .count() as i64;
+
+        Ok(result)
+    }
+
+    /// Exercises of every lesson but the exams.
+    async fn get_number_of_exercises(&self, ctx: &Ctx, course_id: i64, lessons: &[LessonForChangeOreder]) -> LessonResult<HashMap<i64, i64>> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let exam_lesson_ids: HashSet<i64> = lesson_repository
+            .get_course_exams(ctx, course_id)
+            .await?
+            .into_iter()
+            .map(|exam| exam.lesson_id)
+            .collect();
+
+        let mut number_of_exercises = HashMap::new();
+        for lesson in lessons {
+            if exam_lesson_ids.contains(&lesson.id) {
+                continue;
+            }
+
+            let exercises = exercise_repository.get_lesson_exercises_ordered(ctx, lesson.id).await?;
+            number_of_exercises.insert(lesson.id, exercises.len() as i64);
+        }
+
+        Ok(number_of_exercises)
+    }
+
+    async fn get_completed_exercises(&self, ctx: &Ctx, course_id: i64) -> LessonResult<HashMap<(i64, i64), i64>> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let completed = exercise_repository
+            .get_course_completed_exercises(ctx, course_id)
+            .await?
+            .into_iter()
+            .map(|completed| ((completed.lesson_id, completed.user_id), completed.completed))
+            .collect();
+
+        Ok(completed)
+    }
+
+    /// `None` for lessons skipped by the placement test and exams, they keep their state.
+    fn is_complete(
+        lesson_progress: &LessonProgress,
+        number_of_exercises: &HashMap<i64, i64>,
+        completed: &HashMap<(i64, i64), i64>,
+    ) -> Option<bool> {
+        let number_of_exercises = match (lesson_progress.skipped_by_placement, number_of_exercises.get(&lesson_progress.lesson_id)) {
+            (false, Some(number_of_exercises)) => *number_of_exercises,
+            _ => return None,
+        };
+
+        let completed = completed
+            .get(&(lesson_progress.lesson_id, lesson_progress.user_id))
+            .copied()
+            .unwrap_or(0);
+
+        Some(completed >= number_of_exercises)
+    }
+
+    /// A locked lesson waits for the retaken lesson before it, it is not reconciled.
+    fn reconciled_state(
+        lesson_progress: &LessonProgress,
+        number_of_exercises: &HashMap<i64, i64>,
+        completed: &HashMap<(i64, i64), i64>,
+    ) -> LessonProgressState {
+        let state = lesson_progress.state.clone();
+
+        let Some(is_complete) = Self::is_complete(lesson_progress, number_of_exercises, completed) else {
+            return state;
+        };
+
+        match (state, is_complete) {
+            (LessonProgressState::Done, false) => LessonProgressState::InProgress,
+            (LessonProgressState::InProgress | LessonProgressState::Pause, true) => LessonProgressState::Done,
+            (state, _) => state,
+        }
+    }
+}
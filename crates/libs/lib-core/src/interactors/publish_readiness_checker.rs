@@ -0,0 +1,107 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{ctx::Ctx, interactors::exercise_validator::ExerciseValidator, interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult}, models::publish_readiness::{PublishReadiness, ReadinessIssue, ReadinessIssueKind}};
+
+/// Builds the checklist a course has to pass before it goes live.
+pub struct PublishReadinessChecker {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl PublishReadinessChecker {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl PublishReadinessChecker {
+    pub async fn check_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<PublishReadiness> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let mut issues = Vec::new();
+
+        let course = course_repository.get_course(ctx, course_id).await?;
+        if course.description.trim().is_empty() {
+            issues.push(Self::issue(ReadinessIssueKind::MissingDescription, "The course has no description".to_string()));
+        }
+        if course.img_url.is_none() {
+            issues.push(Self::issue(ReadinessIssueKind::MissingImage, "The course has no image".to_string()));
+        }
+
+        let lessons = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+        if lessons.is_empty() {
+            issues.push(Self::issue(ReadinessIssueKind::NoLessons, "The course has no lessons".to_string()));
+        }
+
+        for section in lesson_repository.get_course_sections_ordered(ctx, course_id).await? {
+            let section_lessons = lesson_repository
+                .get_section_lessons_ordered(ctx, course_id, Some(section.id))
+                .await?;
+
+            if section_lessons.is_empty() {
+                issues.push(ReadinessIssue {
+                    section_id: Some(section.id),
+                    ..Self::issue(ReadinessIssueKind::EmptySection, "The section has no lessons".to_string())
+                });
+            }
+        }
+
+        let mut lesson_exercises: HashMap<i64, Vec<_>> = HashMap::new();
+        for (exercise_id, exercise) in exercise_repository.get_course_exercises(ctx, course_id).await? {
+            lesson_exercises.entry(exercise.lesson_id).or_default().push((exercise_id, exercise));
+        }
+
+        for lesson in &lessons {
+            let exercises = lesson_exercises.remove(&lesson.id).unwrap_or_default();
+
+            if exercises.is_empty() {
+                let content_blocks = lesson_repository.get_lesson_content_blocks_ordered(ctx, lesson.id).await?;
+
+                if content_blocks.is_empty() {
+                    issues.push(ReadinessIssue {
+                        lesson_id: Some(lesson.id),
+                        ..Self::issue(ReadinessIssueKind::EmptyLesson, "The lesson has neither content nor exercises".to_string())
+                    });
+                }
+                continue;
+            }
+
+            for (exercise_id, exercise) in exercises {
+                let validation = ExerciseValidator::validate_exercise(&exercise.exercise_type, exercise.exercise_body)
+                    .and_then(|_| ExerciseValidator::validate_exercise(&exercise.exercise_type, exercise.answer_body))
+                    .and_then(|_| ExerciseValidator::validate_peer_review(exercise.peer_review_count, exercise.peer_review_rubric));
+
+                if let Err(err) = validation {
+                    issues.push(ReadinessIssue {
+                        lesson_id: Some(lesson.id),
+                        exercise_id: Some(exercise_id),
+                        ..Self::issue(ReadinessIssueKind::InvalidExercise, err.to_string())
+                    });
+                }
+            }
+        }
+
+        let (errors, warnings) = issues.into_iter().partition(|issue| issue.kind.is_blocking());
+
+        Ok(PublishReadiness {
+            course_id,
+            errors,
+            warnings,
+        })
+    }
+
+    fn issue(kind: ReadinessIssueKind, message: String) -> ReadinessIssue {
+        ReadinessIssue {
+            kind,
+            section_id: None,
+            lesson_id: None,
+            exercise_id: None,
+            message,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::{ctx::Ctx, interactors::error::CourseError, interfaces::{command_repository_manager::ICommandRepositoryManager, course::CourseResult}, models::{course::CourseState, course_revision::{CourseRevision, StagedEdit}}};
+
+/// Keeps edits of published courses away from students until the creator publishes them.
+pub struct RevisionStager {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl RevisionStager {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl RevisionStager {
+    /// The revision edits of the lesson go to, `None` while its course isn't published
+    /// and the edits are written right away.
+    pub async fn get_lesson_revision(&self, ctx: &Ctx, lesson_id: i64) -> CourseResult<Option<CourseRevision>> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        self.get_course_revision(ctx, lesson.course_id).await
+    }
+
+    pub async fn get_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        match course.state {
+            CourseState::Published => (),
+            CourseState::Archived => return Err(CourseError::ArchivedCourseIsReadOnly { course_id }.into()),
+            CourseState::Draft => return Ok(None),
+        }
+
+        let revision = course_repository
+            .get_course_revision(ctx, course.id)
+            .await?
+            .unwrap_or_else(|| CourseRevision::new(course.id));
+
+        Ok(Some(revision))
+    }
+
+    /// Only changes of existing entities can wait in a revision, the structure of a published
+    /// course is locked until it is unpublished.
+    pub async fn check_structure_editable(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let course = course_repository.get_course(ctx, course_id).await?;
+
+        match course.state {
+            CourseState::Published => Err(CourseError::PublishedCourseStructureIsLocked { course_id }.into()),
+            CourseState::Archived => Err(CourseError::ArchivedCourseIsReadOnly { course_id }.into()),
+            CourseState::Draft => Ok(()),
+        }
+    }
+
+    pub async fn check_lesson_structure_editable(&self, ctx: &Ctx, lesson_id: i64) -> CourseResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        self.check_structure_editable(ctx, lesson.course_id).await
+    }
+
+    pub async fn stage(&self, ctx: &Ctx, revision: CourseRevision, edit: StagedEdit) -> CourseResult<()> {
+        let course_repository = self.repository_manager.get_course_repository();
+        course_repository.stage_course_edit(ctx, revision.course_id, edit).await
+    }
+}
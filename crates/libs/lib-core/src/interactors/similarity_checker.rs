@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::{interfaces::exercise::ExerciseResult, models::{exercise::{Conspect, NodeType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}}};
+
+use super::exercise_checker::ExerciseChecker;
+
+/// Pairs scoring at least this much are flagged for the creator.
+pub const SIMILARITY_THRESHOLD: f32 = 0.8;
+
+const NODE_TEXT_WEIGHT: f32 = 0.4;
+const CONNECTION_WEIGHT: f32 = 0.4;
+const STAGE_ORDER_WEIGHT: f32 = 0.2;
+
+struct ConspectFeatures {
+    node_texts: HashSet<String>,
+    connections: HashSet<(String, String)>,
+    /// Stage ids of every process stages node, in the submitted order.
+    stage_orders: HashMap<String, Vec<i64>>,
+}
+
+pub struct SimilarityChecker;
+
+impl SimilarityChecker {
+    /// Compares the latest finished attempt of every student with each other and returns
+    /// the pairs `(first_ex_comp_id, second_ex_comp_id, score)` above the threshold.
+    /// Content taken over unchanged from `exercise_body` is ignored.
+    pub fn find_similar_pairs(exercise_body: Value, ex_comps: &[ExerciseCompletion]) -> ExerciseResult<Vec<(i64, i64, f32)>> {
+        // Everything every student gets from the exercise body proves nothing.
+        let template = Self::features(exercise_body)?;
+
+        let mut latest: HashMap<i64, &ExerciseCompletion> = HashMap::new();
+        for ex_comp in ex_comps {
+            if ex_comp.state.eq(&ExerciseCompletionState::InProgress) || ex_comp.is_practice || ex_comp.body.is_none() {
+                continue;
+            }
+
+            let entry = latest.entry(ex_comp.user_id).or_insert(ex_comp);
+            if entry.id < ex_comp.id {
+                *entry = ex_comp;
+            }
+        }
+
+        let mut submissions = Vec::new();
+        for ex_comp in latest.into_values() {
+            let Some(body) = ex_comp.body.clone() else { continue };
+
+            // A broken body can't be compared, but it must not stop the whole exercise.
+            if let Ok(features) = Self::features(body) {
+                submissions.push((ex_comp.id, features));
+            }
+        }
+
+        submissions.sort_by_key(|(ex_comp_id, _)| *ex_comp_id);
+
+        let mut result = Vec::new();
+
+        for (index, (first_id, first)) in submissions.iter().enumerate() {
+            for (second_id, second) in &submissions[index + 1..] {
+                let score = Self::similarity(&template, first, second);
+
+                if score >= SIMILARITY_THRESHOLD {
+                    result.push((*first_id, *second_id, score));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn similarity(template: &ConspectFeatures, first: &ConspectFeatures, second: &ConspectFeatures) -> f32 {
+        let mut components = Vec::new();
+
+        let first_texts: HashSet<&String> = first.node_texts.difference(&template.node_texts).collect();
+        let second_texts: HashSet<&String> = second.node_texts.difference(&template.node_texts).collect();
+        if let Some(score) = Self::jaccard(&first_texts, &second_texts) {
+            components.push((NODE_TEXT_WEIGHT, score));
+        }
+
+        let first_connections: HashSet<&(String, String)> = first.connections.difference(&template.connections).collect();
+        let second_connections: HashSet<&(String, String)> = second.connections.difference(&template.connections).collect();
+        if let Some(score) = Self::jaccard(&first_connections, &second_connections) {
+            components.push((CONNECTION_WEIGHT, score));
+        }
+
+        if let Some(score) = Self::stage_order_similarity(template, first, second) {
+            components.push((STAGE_ORDER_WEIGHT, score));
+        }
+
+        let total_weight: f32 = components.iter().map(|(weight, _)| weight).sum();
+        if total_weight == 0.0 {
+            return 0.0;
+        }
+
+        components.iter().map(|(weight, score)| weight * score).sum::<f32>() / total_weight
+    }
+
+    /// Share of the reordered process stages nodes that both students ordered the same way.
+    fn stage_order_similarity(template: &ConspectFeatures, first: &ConspectFeatures, second: &ConspectFeatures) -> Option<f32> {
+        let mut compared = 0;
+        let mut identical = 0;
+
+        for (node_id, first_order) in &first.stage_orders {
+            let Some(second_order) = second.stage_orders.get(node_id) else { continue };
+
+            let template_order = template.stage_orders.get(node_id);
+            if template_order == Some(first_order) && template_order == Some(second_order) {
+                continue;
+            }
+
+            compared += 1;
+            if first_order == second_order {
+                identical += 1;
+            }
+        }
+
+        if compared == 0 {
+            return None;
+        }
+
+        Some(identical as f32 / compared as f32)
+    }
+
+    fn jaccard<T: Eq + std::hash::Hash>(first: &HashSet<T>, second: &HashSet<T>) -> Option<f32> {
+        let union = first.union(second).count();
+        if union == 0 {
+            return None;
+        }
+
+        Some(first.intersection(second).count() as f32 / union as f32)
+    }
+
+    fn features(body: Value) -> ExerciseResult<ConspectFeatures> {
+        let conspect: Conspect = ExerciseChecker::parse_conspect(body)?;
+
+        let mut node_texts = HashSet::new();
+        let mut stage_orders = HashMap::new();
+
+        for node in &conspect.nodes {
+            let text = Self::normalize_text(&node.body);
+            if !text.is_empty() {
+                node_texts.insert(text);
+            }
+
+            if let NodeType::ProcessStages = node.node_type {
+                let stages = ExerciseChecker::parse_process_stages(node)?;
+                stage_orders.insert(node.id.clone(), stages.iter().map(|stage| stage.id).collect());
+            }
+        }
+
+        let connections = conspect.connections
+            .into_iter()
+            .map(|connection| (connection.from, connection.to))
+            .collect();
+
+        Ok(ConspectFeatures {
+            node_texts,
+            connections,
+            stage_orders,
+        })
+    }
+
+    /// All strings of a node body, lowercased and with collapsed whitespace.
+    fn normalize_text(body: &Value) -> String {
+        fn collect<'a>(value: &'a Value, words: &mut Vec<&'a str>) {
+            match value {
+                Value::String(text) => words.extend(text.split_whitespace()),
+                Value::Array(values) => values.iter().for_each(|value| collect(value, words)),
+                Value::Object(map) => map.values().for_each(|value| collect(value, words)),
+                _ => {},
+            }
+        }
+
+        let mut words = Vec::new();
+        collect(body, &mut words);
+
+        words.join(" ").to_lowercase()
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use lib_auth::token::{generate_file_token, validate_file_token, Token};
+
+use crate::{ctx::Ctx, interactors::{error::{CoreError, LessonError}, permission_manager::PermissionManager}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::attachment::{AttachmentFile, AttachmentLink}};
+
+
+pub struct StudentAttachmentInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl StudentAttachmentInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl StudentAttachmentInteractor {
+    /// The link is signed with the token salt of the user it is issued to, so nobody else can use it.
+    pub async fn get_download_link(
+        &self,
+        ctx: &Ctx,
+        attachment_id: i64,
+    ) -> LessonResult<AttachmentLink> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let attachment = lesson_repository.get_attachment(ctx, attachment_id).await?;
+
+        self.permission_manager
+            .check_lesson_student_permission(ctx, attachment.lesson_id)
+            .await?;
+
+        let user_repository = self.repository_manager.get_user_repository();
+        let user = user_repository.get_user(ctx, ctx.user_id()).await?;
+
+        let ident = Self::token_ident(attachment_id, user.id);
+        let token = generate_file_token(&ident, user.token_salt)
+            .map_err(|_| LessonError::InvalidAttachmentLink)?;
+
+        Ok(AttachmentLink {
+            expires_at: token.exp.clone(),
+            token: token.to_string(),
+        })
+    }
+
+    /// Serves a file by a signed link, the access to the lesson is checked again 
+    /// for the user the link was issued to.
+    pub async fn download_attachment(
+        &self,
+        token: &str,
+    ) -> LessonResult<AttachmentFile> {
+        let token: Token = token.parse().map_err(|_| LessonError::InvalidAttachmentLink)?;
+        let (attachment_id, user_id) = Self::parse_token_ident(&token.ident)?;
+
+        let ctx = Ctx::new(user_id).map_err(|_| LessonError::InvalidAttachmentLink)?;
+
+        let user_repository = self.repository_manager.get_user_repository();
+        let user = user_repository.get_user(&ctx, user_id).await?;
+
+        validate_file_token(&token, user.token_salt)
+            .map_err(|_| LessonError::InvalidAttachmentLink)?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let attachment = lesson_repository.get_attachment(&ctx, attachment_id).await?;
+
+        self.permission_manager
+            .check_lesson_student_permission(&ctx, attachment.lesson_id)
+            .await?;
+
+        let file_storage = self.repository_manager.get_file_storage();
+        let stored_file = file_storage.get(&attachment.storage_key).await?;
+
+        Ok(AttachmentFile {
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            data: stored_file.data,
+        })
+    }
+
+    fn token_ident(attachment_id: i64, user_id: i64) -> String {
+        format!("attachment:{}:{}", attachment_id, user_id)
+    }
+
+    fn parse_token_ident(ident: &str) -> LessonResult<(i64, i64)> {
+        let invalid_link = || CoreError::from(LessonError::InvalidAttachmentLink);
+
+        let mut parts = ident.split(':');
+        if parts.next() != Some("attachment") {
+            return Err(invalid_link());
+        }
+
+        let attachment_id = parts.next().and_then(|id| id.parse().ok()).ok_or_else(invalid_link)?;
+        let user_id = parts.next().and_then(|id| id.parse().ok()).ok_or_else(invalid_link)?;
+
+        if parts.next().is_some() {
+            return Err(invalid_link());
+        }
+
+        Ok((attachment_id, user_id))
+    }
+}
@@ -48,7 +48,7 @@ impl StudentCourseInteractor {
             .await?;
 
         if let Some(user_course) = user_course {
-            if user_course.user_role.eq(&crate::models::course::UserCourseRole::Creator) {
+            if user_course.user_role.is_staff() {
                 return Err(CourseError::CreatorCannotSubscribeToTheCourse.into());
             }
             
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use lib_utils::time::now_utc_sec;
 use serde_json::Value;
 
-use crate::{ctx::Ctx, interactors::{error::ExerciseError, exercise_checker::ExerciseChecker, exercise_validator::ExerciseValidator, permission_manager::PermissionManager}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{exercise::ExerciseEstimate, exercise_completion::{ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForUpdate, ExerciseCompletionState}, lesson_progress::LessonProgressState}};
+use crate::{ctx::Ctx, interactors::{conspect_diff::ConspectDiffBuilder, error::{ExerciseError, LessonError}, exercise_checker::ExerciseChecker, exercise_validator::ExerciseValidator, permission_manager::PermissionManager, student::{lesson::StudentLessonInteractor, peer_review::StudentPeerReviewInteractor}}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{exercise::{ConspectDiff, ExerciseEstimate, ExerciseType}, exercise_completion::{ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForUpdate, ExerciseCompletionState}, lesson_progress::LessonProgressState, unlock_policy::UnlockPolicyKind}};
 
 pub struct StudentExerciseInteractor {
     permission_manager: PermissionManager,
@@ -24,19 +24,67 @@ impl StudentExerciseInteractor {
 }
 
 impl StudentExerciseInteractor {
+    /// A practice attempt is graded as usual, but it doesn't count towards grades,
+    /// lesson progress or the number of attempts. It is only available once the
+    /// exercise has been passed. Exams allow a single attempt per exercise in any order.
     pub async fn start_exercise(
         &self,
         ctx: &Ctx,
-        exercise_id: i64
+        exercise_id: i64,
+        is_practice: bool,
     ) -> ExerciseResult<i64> {
         self.permission_manager.check_exercise_student_permission(ctx, exercise_id).await?;
         
         self.check_lesson_state(ctx, exercise_id).await?;
-        self.check_exercise_order(ctx, exercise_id).await?;
 
         let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercise = exercise_repository.get_exercise(ctx, exercise_id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+
+        let lesson_interactor = StudentLessonInteractor::new(self.repository_manager.clone());
+        lesson_interactor.check_no_exam_in_progress(ctx, lesson.course_id, lesson.id).await?;
+
         let pre_exercise_completions = exercise_repository.get_exercise_user_completions(ctx, ctx.user_id(), exercise_id).await?;
-        let number_of_attempts = pre_exercise_completions.len();
+
+        if let Some(exam) = lesson_repository.get_exam(ctx, lesson.id).await? {
+            if is_practice {
+                return Err(ExerciseError::PracticeIsNotAvailableInExam {}.into());
+            }
+
+            if now_utc_sec() >= exam.closes_at {
+                return Err(LessonError::ExamIsNotOpen { lesson_id: lesson.id }.into());
+            }
+
+            let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, lesson.course_id, ctx.user_id()).await?;
+            let is_in_progress = lesson_progresses
+                .iter()
+                .any(|lesson_progress| lesson_progress.lesson_id == lesson.id && lesson_progress.state.eq(&LessonProgressState::InProgress));
+
+            if !is_in_progress {
+                return Err(LessonError::ExamIsNotInProgress { lesson_id: lesson.id }.into());
+            }
+
+            if !pre_exercise_completions.is_empty() {
+                return Err(ExerciseError::ExamAllowsSingleAttempt { exercise_id }.into());
+            }
+        } else if is_practice {
+            let has_succeeded = pre_exercise_completions
+                .iter()
+                .any(|ex_comp| !ex_comp.is_practice && ex_comp.state == ExerciseCompletionState::Succeeded);
+
+            if !has_succeeded {
+                return Err(ExerciseError::PracticeRequiresSucceededAttempt { exercise_id }.into());
+            }
+        } else {
+            self.check_exercise_order(ctx, exercise_id).await?;
+        }
+
+        let number_of_attempts = pre_exercise_completions
+            .iter()
+            .filter(|ex_comp| !ex_comp.is_practice)
+            .count();
         let date_started = now_utc_sec();
 
         let ex_comp_for_c = ExerciseCompletionForCreate {
@@ -44,6 +92,7 @@ impl StudentExerciseInteractor {
             user_id: ctx.user_id(),
             number_of_attempts,
             date_started,
+            is_practice,
         };
 
         exercise_repository.create_exercise_completion(ctx, ex_comp_for_c).await       
@@ -61,7 +110,7 @@ impl StudentExerciseInteractor {
 
         match lesson_progresses.iter().find(|lesson_progress| lesson_progress.lesson_id == lesson.id) {
             Some(lesson_progress) => {
-                if lesson_progress.state.eq(&LessonProgressState::Pause) {
+                if lesson_progress.state.is_paused() {
                     return Err(ExerciseError::LessonProgressMustBeInProgress {}.into());
                 }
             },
@@ -79,6 +128,17 @@ impl StudentExerciseInteractor {
             return Ok(());
         }
 
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+        let unlock_policy = StudentLessonInteractor::new(self.repository_manager.clone())
+            .get_unlock_policy(ctx, lesson.course_id)
+            .await?;
+
+        // Only the sequential policy orders the exercises of a lesson.
+        if unlock_policy.kind.ne(&UnlockPolicyKind::Sequential) {
+            return Ok(());
+        }
+
         let exercises_ordered = exercise_repository
             .get_lesson_exercises_ordered(ctx, exercise.lesson_id)
             .await?;
@@ -90,7 +150,7 @@ impl StudentExerciseInteractor {
 
         let pre_exercise_completions = exercise_repository.get_exercise_user_completions(ctx, ctx.user_id(), previus_ex_id).await?;
         
-        match pre_exercise_completions.iter().find(|ex_comp| ex_comp.state == ExerciseCompletionState::Succeeded) {
+        match pre_exercise_completions.iter().find(|ex_comp| !ex_comp.is_practice && ex_comp.state == ExerciseCompletionState::Succeeded) {
             Some(_) => Ok(()),
             None => Err(crate::interactors::error::ExerciseError::PreviousExerciseNotCompleted { exercise_id: previus_ex_id }.into()),
         }
@@ -112,16 +172,35 @@ impl StudentExerciseInteractor {
         }
 
         let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
+
+        // Nothing changes while the lesson is paused.
+        self.check_lesson_state(ctx, ex_comp.exercise_id).await?;
         
         let now = now_utc_sec();
         if let Some(time_to_complete) = exercise.time_to_complete {
-            let deadline = time_to_complete as i64 + ex_comp.date_started;
+            let deadline = ex_comp.deadline(time_to_complete as i64);
 
             if now > deadline {
                 return Err(ExerciseError::TimeToCompleteExerciseHasExpired {}.into());
             }
         }
 
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, exercise.lesson_id).await?;
+
+        let lesson_interactor = StudentLessonInteractor::new(self.repository_manager.clone());
+        lesson_interactor.check_no_exam_in_progress(ctx, lesson.course_id, lesson.id).await?;
+
+        if let Some(exam) = lesson_repository.get_exam(ctx, exercise.lesson_id).await? {
+            if ex_comp.state.ne(&ExerciseCompletionState::InProgress) {
+                return Err(ExerciseError::AttemptHasAlreadyBeenCompleted {}.into());
+            }
+
+            if now >= exam.closes_at {
+                return Err(ExerciseError::TimeToCompleteExerciseHasExpired {}.into());
+            }
+        }
+
         ExerciseValidator::validate_exercise(&exercise.exercise_type, exercise_body_for_save.clone())?;
 
         let ex_comp_for_u = ExerciseCompletionForUpdate {
@@ -149,8 +228,16 @@ impl StudentExerciseInteractor {
             return Err(ExerciseError::AttemptHasAlreadyBeenCompleted {}.into());
         }
 
+        // A paused lesson can't be handed in either.
+        self.check_lesson_state(ctx, ex_comp.exercise_id).await?;
+
         let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
 
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        if lesson_repository.get_exam(ctx, exercise.lesson_id).await?.is_some() {
+            return Err(ExerciseError::ExamIsGradedOnSubmit {}.into());
+        }
+
         let exercise_estimate = ExerciseChecker::evaluate_exercise(&exercise, &ex_comp)?;
 
         let ex_comp_for_u = ExerciseCompletionForCompleteCommand {
@@ -162,6 +249,15 @@ impl StudentExerciseInteractor {
 
         exercise_repository.complete_exercise_completion(ctx, ex_comp_for_u).await?;
 
+        if ex_comp.is_practice {
+            return Ok(exercise_estimate);
+        }
+
+        if exercise.peer_review_count > 0 {
+            let peer_review_interactor = StudentPeerReviewInteractor::new(self.repository_manager.clone());
+            peer_review_interactor.assign_peer_reviews(ctx, ex_comp.exercise_id, &exercise, user_id).await?;
+        }
+
         if self.is_lesson_state_complete(ctx, exercise.lesson_id, user_id).await? {
             self.complete_lesson(ctx, exercise.lesson_id, user_id).await?;
         }
@@ -169,7 +265,36 @@ impl StudentExerciseInteractor {
         Ok(exercise_estimate)
     }
 
-    async fn is_lesson_state_complete(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<bool> {
+    pub async fn get_exercise_completion_diff(&self, ctx: &Ctx, ex_comp_id: i64) -> ExerciseResult<ConspectDiff> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comp = exercise_repository.get_exercise_completion(ctx, ex_comp_id).await?;
+
+        let user_id = ctx.user_id();
+
+        if ex_comp.user_id != user_id {
+            return Err(ExerciseError::ExerciseCompletionAccessDenied { user_id, ex_comp_id }.into());
+        }
+
+        if ex_comp.state.eq(&ExerciseCompletionState::InProgress) {
+            return Err(ExerciseError::AttemptHasNotBeenCompleted {}.into());
+        }
+
+        let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        if let Some(exam) = lesson_repository.get_exam(ctx, exercise.lesson_id).await? {
+            if !exam.results_released {
+                return Err(ExerciseError::ExamResultsAreNotReleased {}.into());
+            }
+        }
+
+        match exercise.exercise_type {
+            ExerciseType::Conspect |
+            ExerciseType::InteractiveConspect => ConspectDiffBuilder::diff_conspects(exercise.answer_body, ex_comp.body),
+        }
+    }
+
+    pub(crate) async fn is_lesson_state_complete(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<bool> {
         let exercise_repository = self.repository_manager.get_exercise_repository();
 
         let number_of_completed_exercises = exercise_repository.get_number_of_lesson_completed_exercises(ctx, lesson_id, user_id).await?;
@@ -182,9 +307,9 @@ impl StudentExerciseInteractor {
         Ok(false)
     }
 
-    async fn complete_lesson(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<()> {
-        let lesson_repository = self.repository_manager.get_lesson_repository();
-
-        lesson_repository.update_lesson_progress_state(ctx, LessonProgressState::Done, lesson_id, user_id).await
+    pub(crate) async fn complete_lesson(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<()> {
+        StudentLessonInteractor::new(self.repository_manager.clone())
+            .change_lesson_progress_state(ctx, lesson_id, user_id, LessonProgressState::Done)
+            .await
     }
 }
\ No newline at end of file
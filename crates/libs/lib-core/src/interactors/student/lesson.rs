@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::{ctx::Ctx, interactors::{error::CoreError, permission_manager::PermissionManager}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}};
+use lib_utils::time::now_utc_sec;
+
+use crate::{ctx::Ctx, interactors::{error::{CoreError, CourseError, LessonError, LessonProgressError}, exercise_checker::ExerciseChecker, permission_manager::PermissionManager, progress_reconciler::ProgressReconciler}, interfaces::{command_repository_manager::ICommandRepositoryManager, lesson::LessonResult}, models::{exam::Exam, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}, placement_test::{PlacementAnswer, PlacementAttemptForCreate, PlacementResult}, unlock_policy::{LessonLock, UnlockPolicy, UnlockPolicyKind}}};
 
 
 pub struct StudentLessonInteractor {
@@ -31,20 +33,228 @@ impl StudentLessonInteractor {
         let lesson_repository = self.repository_manager.get_lesson_repository();
         let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
 
-        if lesson.lesson_order == 1 {
-            lesson_repository.create_lesson_progress(ctx, lesson_id, ctx.user_id()).await?;
+        self.check_no_exam_in_progress(ctx, lesson.course_id, lesson_id).await?;
+
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, lesson.course_id, ctx.user_id()).await?;
+        let is_started = lesson_progresses.iter().any(|lesson_progress| lesson_progress.lesson_id == lesson_id);
+
+        if let Some(exam) = lesson_repository.get_exam(ctx, lesson_id).await? {
+            let now = now_utc_sec();
+            if now < exam.opens_at || now >= exam.closes_at {
+                return Err(LessonError::ExamIsNotOpen { lesson_id }.into());
+            }
+
+            if is_started {
+                return Err(LessonError::ExamCanBeTakenOnce { lesson_id }.into());
+            }
+        }
+
+        if is_started {
+            return Err(LessonError::LessonAlreadyStarted { lesson_id }.into());
+        }
+
+        let unlock_policy = self.get_unlock_policy(ctx, lesson.course_id).await?;
+
+        match unlock_policy.kind {
+            UnlockPolicyKind::Sequential => self.check_previous_lesson_completed(ctx, &lesson).await?,
+            UnlockPolicyKind::Free => {},
+            UnlockPolicyKind::Drip => {
+                let date_registered = self.get_date_registered(ctx, lesson.course_id).await?;
+
+                if let Some(release_date) = unlock_policy.release_date(lesson_id, date_registered) {
+                    if now_utc_sec() < release_date {
+                        return Err(LessonError::LessonIsLocked { lesson_id, locked_until: release_date }.into());
+                    }
+                }
+            },
+        }
+
+        lesson_repository.create_lesson_progress(ctx, lesson_id, ctx.user_id()).await?;
+        
+        Ok(())
+    }
+
+    /// Freezes the lesson: no attempts can be started or saved and timed attempts
+    /// stop running until the lesson is resumed.
+    pub async fn pause_lesson(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        self.permission_manager.check_lesson_student_permission(ctx, lesson_id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        if lesson_repository.get_exam(ctx, lesson_id).await?.is_some() {
+            return Err(LessonProgressError::ExamCannotBePaused { lesson_id }.into());
+        }
+
+        let lesson_progress = self.get_lesson_progress(ctx, lesson_id, ctx.user_id()).await?;
+        Self::check_state_transition(&lesson_progress.state, &LessonProgressState::Pause)?;
+
+        lesson_repository.pause_lesson_progress(ctx, lesson_id, ctx.user_id(), now_utc_sec()).await
+    }
+
+    pub async fn resume_lesson(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        self.permission_manager.check_lesson_student_permission(ctx, lesson_id).await?;
 
+        let lesson_progress = self.get_lesson_progress(ctx, lesson_id, ctx.user_id()).await?;
+        Self::check_state_transition(&lesson_progress.state, &LessonProgressState::InProgress)?;
+
+        let Some(date_paused) = lesson_progress.date_paused else {
+            return Err(LessonProgressError::LessonNotPausedByStudent { lesson_id }.into());
+        };
+        let paused_seconds = (now_utc_sec() - date_paused).max(0);
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        lesson_repository.resume_lesson_progress(ctx, lesson_id, ctx.user_id(), paused_seconds).await
+    }
+
+    /// The only way lesson progresses change state once created. Setting the current
+    /// state again does nothing, a lesson without a progress is left alone. A lesson
+    /// done again after a retake unlocks the lessons after it.
+    pub(crate) async fn change_lesson_progress_state(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+        user_id: i64,
+        state: LessonProgressState,
+    ) -> LessonResult<()> {
+        let lesson_progress = match self.get_lesson_progress(ctx, lesson_id, user_id).await {
+            Ok(lesson_progress) => lesson_progress,
+            Err(CoreError::LessonProgressError(LessonProgressError::LessonProgressNotFound { .. })) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        if lesson_progress.state.eq(&state) {
             return Ok(());
         }
 
+        Self::check_state_transition(&lesson_progress.state, &state)?;
+
+        let is_done = state.eq(&LessonProgressState::Done);
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        lesson_repository.update_lesson_progress_state(ctx, state, lesson_id, user_id).await?;
+
+        if is_done {
+            let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+            ProgressReconciler::new(self.repository_manager.clone())
+                .unlock_lessons(ctx, lesson.course_id, user_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    fn check_state_transition(from: &LessonProgressState, to: &LessonProgressState) -> LessonResult<()> {
+        if !from.can_change_to(to) {
+            return Err(LessonProgressError::IncorrectStateTransition { from: from.to_string(), to: to.to_string() }.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> LessonResult<LessonProgress> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        lesson_repository
+            .get_lessons_progresses(ctx, lesson.course_id, user_id)
+            .await?
+            .into_iter()
+            .find(|lesson_progress| lesson_progress.lesson_id == lesson_id)
+            .ok_or(LessonProgressError::LessonProgressNotFound { lesson_id }.into())
+    }
+
+    /// Lessons the student can't start yet under the unlock policy of the course.
+    pub async fn get_lesson_locks(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> LessonResult<Vec<LessonLock>> {
+        self.permission_manager.check_course_student_permission(ctx, course_id).await?;
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lessons_ordered = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, course_id, ctx.user_id()).await?;
+
+        let unlock_policy = self.get_unlock_policy(ctx, course_id).await?;
+        let date_registered = self.get_date_registered(ctx, course_id).await?;
+        let now = now_utc_sec();
+
+        let mut result = Vec::new();
+
+        for (index, lesson) in lessons_ordered.iter().enumerate() {
+            if lesson_progresses.iter().any(|lesson_progress| lesson_progress.lesson_id == lesson.id) {
+                continue;
+            }
+
+            match unlock_policy.kind {
+                UnlockPolicyKind::Sequential => {
+                    let is_previous_done = index == 0 || lesson_progresses
+                        .iter()
+                        .any(|lesson_progress| {
+                            lesson_progress.lesson_id == lessons_ordered[index - 1].id && lesson_progress.state.eq(&LessonProgressState::Done)
+                        });
+
+                    if !is_previous_done {
+                        result.push(LessonLock { lesson_id: lesson.id, locked_until: None });
+                    }
+                },
+                UnlockPolicyKind::Free => {},
+                UnlockPolicyKind::Drip => {
+                    if let Some(release_date) = unlock_policy.release_date(lesson.id, date_registered) {
+                        if now < release_date {
+                            result.push(LessonLock { lesson_id: lesson.id, locked_until: Some(release_date) });
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) async fn get_unlock_policy(&self, ctx: &Ctx, course_id: i64) -> LessonResult<UnlockPolicy> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let unlock_policy = course_repository
+            .get_unlock_policy(ctx, course_id)
+            .await?
+            .unwrap_or(UnlockPolicy::sequential(course_id));
+
+        Ok(unlock_policy)
+    }
+
+    /// Drip releases count from here, the admin isn't registered and sees everything released.
+    async fn get_date_registered(&self, ctx: &Ctx, course_id: i64) -> LessonResult<i64> {
+        let course_repository = self.repository_manager.get_course_repository();
+        let user_course = course_repository
+            .get_user_course_optional(ctx, ctx.user_id(), course_id)
+            .await?;
+
+        Ok(user_course.map_or(0, |user_course| user_course.date_registered))
+    }
+
+    /// The previous lesson is taken in course order, so the first lesson of a section
+    /// waits for the last lesson of the section before it.
+    async fn check_previous_lesson_completed(&self, ctx: &Ctx, lesson: &Lesson) -> LessonResult<()> {
+        let lesson_id = lesson.id;
+        let lesson_repository = self.repository_manager.get_lesson_repository();
         let lessons_ordered = lesson_repository.get_course_lessons_ordered(ctx, lesson.course_id).await?;
-        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, lesson.course_id, ctx.user_id()).await?;
 
-        let previus_lesson_id = match lessons_ordered.get((lesson.lesson_order - 2) as usize) {
-            Some(prev_less) => prev_less.id,
+        let previus_lesson_id = match lessons_ordered.iter().position(|lesson_ordered| lesson_ordered.id == lesson_id) {
+            Some(0) => return Ok(()),
+            Some(index) => lessons_ordered[index - 1].id,
             None => return Err(CoreError::LessonError(crate::interactors::error::LessonError::PreviousLessonNotFound { lesson_id })),
         };
 
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, lesson.course_id, ctx.user_id()).await?;
+
         let prev_lesson_progress = match lesson_progresses.iter().find(|lesson_progress| lesson_progress.lesson_id == previus_lesson_id) {
             Some(prev_l_progress) => prev_l_progress,
             None => return Err(CoreError::LessonError(crate::interactors::error::LessonError::PreviousLessonNotFound { lesson_id })),
@@ -54,8 +264,170 @@ impl StudentLessonInteractor {
             return Err(CoreError::LessonError(crate::interactors::error::LessonError::PreviousLessonNotCompleted { lesson_id }));
         }
 
-        lesson_repository.create_lesson_progress(ctx, lesson_id, ctx.user_id()).await?;
-        
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Grades the placement test and marks the lessons it skips as `Done`. It can only be
+    /// taken before the first lesson is started, and never skips past an exam.
+    pub async fn take_placement_test(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+        answers: Vec<PlacementAnswer>,
+    ) -> LessonResult<PlacementResult> {
+        self.permission_manager.check_course_student_permission(ctx, course_id).await?;
+
+        let course_repository = self.repository_manager.get_course_repository();
+        let placement_test = match course_repository.get_placement_test(ctx, course_id).await? {
+            Some(placement_test) => placement_test,
+            None => return Err(CourseError::PlacementTestNotFound { course_id }.into()),
+        };
+
+        if course_repository.get_placement_attempt(ctx, course_id, ctx.user_id()).await?.is_some() {
+            return Err(CourseError::PlacementTestAlreadyTaken { course_id }.into());
+        }
+
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, course_id, ctx.user_id()).await?;
+        if !lesson_progresses.is_empty() {
+            return Err(CourseError::PlacementTestMustBeTakenFirst { course_id }.into());
+        }
+
+        if let Some(answer) = answers.iter().find(|answer| !placement_test.exercise_ids.contains(&answer.exercise_id)) {
+            return Err(CourseError::PlacementAnswerNotInTest { exercise_id: answer.exercise_id }.into());
+        }
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+
+        let mut points = 0.0;
+        let mut max_points = 0.0;
+
+        for exercise_id in &placement_test.exercise_ids {
+            let exercise = exercise_repository.get_exercise(ctx, *exercise_id).await?;
+            let exercise_max_points = ExerciseChecker::max_points(&exercise);
+
+            // An exercise without an answer scores nothing.
+            if let Some(answer) = answers.iter().find(|answer| answer.exercise_id == *exercise_id) {
+                points += exercise_max_points * ExerciseChecker::score_answer(&exercise, answer.body.clone())?;
+            }
+            max_points += exercise_max_points;
+        }
+
+        let score = if max_points > 0.0 { points / max_points } else { 0.0 };
+
+        let lessons_to_skip = placement_test.lessons_to_skip(score) as usize;
+        let lessons_ordered = lesson_repository.get_course_lessons_ordered(ctx, course_id).await?;
+        let exams = lesson_repository.get_course_exams(ctx, course_id).await?;
+
+        let skipped_lesson_ids: Vec<i64> = lessons_ordered
+            .iter()
+            .take(lessons_to_skip)
+            .take_while(|lesson| !exams.iter().any(|exam| exam.lesson_id == lesson.id))
+            .map(|lesson| lesson.id)
+            .collect();
+
+        // Recorded even when nothing is skipped, the test can't be retaken for a better score.
+        let attempt_for_c = PlacementAttemptForCreate {
+            course_id,
+            user_id: ctx.user_id(),
+            score,
+            skipped_lesson_ids: skipped_lesson_ids.clone(),
+        };
+        course_repository.create_placement_attempt(ctx, attempt_for_c).await?;
+
+        Ok(PlacementResult {
+            score,
+            skipped_lesson_ids,
+        })
+    }
+
+    /// Hands in every attempt of the exam at once, the exam can't be resumed afterwards.
+    pub async fn submit_exam(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        self.permission_manager.check_lesson_student_permission(ctx, lesson_id).await?;
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let lesson = lesson_repository.get_lesson(ctx, lesson_id).await?;
+
+        let exam = match lesson_repository.get_exam(ctx, lesson_id).await? {
+            Some(exam) => exam,
+            None => return Err(LessonError::LessonIsNotExam { lesson_id }.into()),
+        };
+
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, lesson.course_id, ctx.user_id()).await?;
+        let is_in_progress = lesson_progresses
+            .iter()
+            .any(|lesson_progress| lesson_progress.lesson_id == lesson_id && lesson_progress.state.eq(&LessonProgressState::InProgress));
+
+        if !is_in_progress {
+            return Err(LessonError::ExamIsNotInProgress { lesson_id }.into());
+        }
+
+        self.submit_user_exam(ctx, &exam, ctx.user_id()).await
+    }
+
+    pub(crate) async fn submit_user_exam(&self, ctx: &Ctx, exam: &Exam, user_id: i64) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercises = exercise_repository.get_lesson_exercises_ordered(ctx, exam.lesson_id).await?;
+
+        for exercise in &exercises {
+            let ex_comps = exercise_repository.get_exercise_user_completions(ctx, user_id, exercise.id).await?;
+
+            for ex_comp in ex_comps.into_iter().filter(|ex_comp| ex_comp.state.eq(&ExerciseCompletionState::InProgress)) {
+                if exam.results_released || !lesson_repository.submit_exam_attempt(ctx, exam.lesson_id, ex_comp.id).await? {
+                    self.grade_exam_attempt(ctx, ex_comp).await?;
+                }
+            }
+        }
+
+        self.change_lesson_progress_state(ctx, exam.lesson_id, user_id, LessonProgressState::Done).await
+    }
+
+    async fn grade_exam_attempt(&self, ctx: &Ctx, ex_comp: ExerciseCompletion) -> LessonResult<()> {
+        let ex_comp_for_u = self.exam_attempt_grade(ctx, &ex_comp).await?;
+
+        self.repository_manager
+            .get_exercise_repository()
+            .complete_exercise_completion(ctx, ex_comp_for_u)
+            .await
+    }
+
+    pub(crate) async fn exam_attempt_grade(&self, ctx: &Ctx, ex_comp: &ExerciseCompletion) -> LessonResult<ExerciseCompletionForCompleteCommand> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercise = exercise_repository.get_exercise(ctx, ex_comp.exercise_id).await?;
+
+        let exercise_estimate = ExerciseChecker::evaluate_exercise(&exercise, ex_comp)?;
+
+        Ok(ExerciseCompletionForCompleteCommand {
+            points_scored: exercise_estimate.points,
+            max_points: exercise_estimate.max_points,
+            state: exercise_estimate.state,
+            id: ex_comp.id,
+        })
+    }
+
+    /// While an exam is in progress every other lesson of the course is locked.
+    pub(crate) async fn check_no_exam_in_progress(&self, ctx: &Ctx, course_id: i64, lesson_id: i64) -> LessonResult<()> {
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let exams = lesson_repository.get_course_exams(ctx, course_id).await?;
+        if exams.is_empty() {
+            return Ok(());
+        }
+
+        let lesson_progresses = lesson_repository.get_lessons_progresses(ctx, course_id, ctx.user_id()).await?;
+
+        let exam_in_progress = lesson_progresses
+            .iter()
+            .filter(|lesson_progress| lesson_progress.state.eq(&LessonProgressState::InProgress))
+            .find(|lesson_progress| lesson_progress.lesson_id != lesson_id && exams.iter().any(|exam| exam.lesson_id == lesson_progress.lesson_id));
+
+        match exam_in_progress {
+            Some(lesson_progress) => Err(LessonError::ExamInProgress { lesson_id: lesson_progress.lesson_id }.into()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{ctx::Ctx, interactors::{error::ExerciseError, exercise_checker::ExerciseChecker, exercise_validator::ExerciseValidator, peer_review_assigner::PeerReviewAssigner, permission_manager::PermissionManager, progress_reconciler::ProgressReconciler, student::exercise::StudentExerciseInteractor}, interfaces::{command_repository_manager::ICommandRepositoryManager, exercise::ExerciseResult}, models::{exercise::Exercise, exercise_completion::ExerciseCompletionState, peer_review::{PeerReviewAssignment, PeerReviewForCreate, PeerReviewForSubmit, PeerReviewForSubmitCommand, PeerReviewState, MAX_PEER_REVIEW_COMMENT_LENGTH}}};
+
+pub struct StudentPeerReviewInteractor {
+    permission_manager: PermissionManager,
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl StudentPeerReviewInteractor {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        let permission_manager = PermissionManager::new(repository_manager.clone());
+
+        Self {
+            permission_manager,
+            repository_manager,
+        }
+    }
+}
+
+impl StudentPeerReviewInteractor {
+    pub async fn get_peer_review_assignments(
+        &self,
+        ctx: &Ctx,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<PeerReviewAssignment>> {
+        self.permission_manager.check_exercise_student_permission(ctx, exercise_id).await?;
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let exercise = exercise_repository.get_exercise(ctx, exercise_id).await?;
+
+        if exercise.peer_review_count <= 0 {
+            return Err(ExerciseError::PeerReviewIsNotEnabled {}.into());
+        }
+
+        let user_id = ctx.user_id();
+        let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise_id).await?;
+
+        let has_completed_attempt = ex_comps
+            .iter()
+            .any(|ex_comp| ex_comp.user_id == user_id && ex_comp.state.ne(&ExerciseCompletionState::InProgress));
+
+        if !has_completed_attempt {
+            return Err(ExerciseError::ExerciseMustBeCompletedForPeerReview {}.into());
+        }
+
+        // Classmates may have finished since the last call, so top the assignments up first.
+        self.assign_peer_reviews(ctx, exercise_id, &exercise, user_id).await?;
+
+        let peer_reviews = exercise_repository.get_exercise_peer_reviews(ctx, exercise_id).await?;
+
+        let result = peer_reviews
+            .into_iter()
+            .filter(|peer_review| peer_review.reviewer_id == user_id)
+            .map(|peer_review| PeerReviewAssignment {
+                peer_review_id: peer_review.id,
+                exercise_id,
+                body: ex_comps
+                    .iter()
+                    .find(|ex_comp| ex_comp.id == peer_review.exercise_completion_id)
+                    .and_then(|ex_comp| ex_comp.body.clone()),
+                rubric: exercise.peer_review_rubric.clone(),
+                state: peer_review.state,
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    pub(crate) async fn assign_peer_reviews(
+        &self,
+        ctx: &Ctx,
+        exercise_id: i64,
+        exercise: &Exercise,
+        reviewer_id: i64,
+    ) -> ExerciseResult<()> {
+        if exercise.peer_review_count <= 0 {
+            return Ok(());
+        }
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comps = exercise_repository.get_exercise_completions(ctx, exercise_id).await?;
+        let peer_reviews = exercise_repository.get_exercise_peer_reviews(ctx, exercise_id).await?;
+
+        let selected = PeerReviewAssigner::select_submissions(
+            &ex_comps,
+            &peer_reviews,
+            reviewer_id,
+            exercise.peer_review_count as usize,
+        );
+
+        if selected.is_empty() {
+            return Ok(());
+        }
+
+        let peer_reviews_for_c = selected
+            .into_iter()
+            .map(|exercise_completion_id| PeerReviewForCreate {
+                exercise_id,
+                exercise_completion_id,
+                reviewer_id,
+            })
+            .collect();
+
+        exercise_repository.create_peer_reviews(ctx, peer_reviews_for_c).await
+    }
+
+    pub async fn submit_peer_review(
+        &self,
+        ctx: &Ctx,
+        peer_review_for_s: PeerReviewForSubmit,
+    ) -> ExerciseResult<()> {
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let peer_review = exercise_repository.get_peer_review(ctx, peer_review_for_s.id).await?;
+
+        let user_id = ctx.user_id();
+
+        if peer_review.reviewer_id != user_id {
+            return Err(ExerciseError::PeerReviewAccessDenied { user_id, peer_review_id: peer_review.id }.into());
+        }
+
+        if peer_review.state.eq(&PeerReviewState::Submitted) {
+            return Err(ExerciseError::PeerReviewHasAlreadyBeenSubmitted {}.into());
+        }
+
+        if let Some(length) = peer_review_for_s.comment.as_ref().map(|comment| comment.chars().count()) {
+            if length > MAX_PEER_REVIEW_COMMENT_LENGTH {
+                return Err(ExerciseError::ReviewCommentTooLong { length, max_length: MAX_PEER_REVIEW_COMMENT_LENGTH }.into());
+            }
+        }
+
+        let exercise = exercise_repository.get_exercise(ctx, peer_review.exercise_id).await?;
+        let rubric = ExerciseValidator::parse_rubric(exercise.peer_review_rubric.clone())?;
+
+        if rubric.len() != peer_review_for_s.rubric_scores.len() {
+            return Err(ExerciseError::IncorrectRubricScores {}.into());
+        }
+
+        let mut points = 0.0;
+        let mut max_points = 0.0;
+
+        for (criterion, score) in rubric.iter().zip(&peer_review_for_s.rubric_scores) {
+            if *score < 0.0 || *score > criterion.max_points {
+                return Err(ExerciseError::IncorrectRubricScores {}.into());
+            }
+
+            points += score;
+            max_points += criterion.max_points;
+        }
+
+        let peer_review_for_s_c = PeerReviewForSubmitCommand {
+            id: peer_review.id,
+            score: points / max_points,
+            rubric_scores: json!(peer_review_for_s.rubric_scores),
+            comment: peer_review_for_s.comment,
+        };
+
+        exercise_repository.submit_peer_review(ctx, peer_review_for_s_c).await?;
+
+        self.update_peer_grade(ctx, &exercise, peer_review.exercise_completion_id).await
+    }
+
+    /// The attempt is graded against the points it was started with, the exercise difficulty may
+    /// have changed since. A creator's review has the last word, peers don't grade reviewed exercises.
+    async fn update_peer_grade(&self, ctx: &Ctx, exercise: &Exercise, ex_comp_id: i64) -> ExerciseResult<()> {
+        if exercise.manual_review {
+            return Ok(());
+        }
+
+        let exercise_repository = self.repository_manager.get_exercise_repository();
+        let ex_comp = exercise_repository.get_exercise_completion(ctx, ex_comp_id).await?;
+
+        if !matches!(ex_comp.state, ExerciseCompletionState::Succeeded | ExerciseCompletionState::Failed) {
+            return Ok(());
+        }
+
+        let scores: Vec<f32> = exercise_repository
+            .get_exercise_completion_peer_reviews(ctx, ex_comp_id)
+            .await?
+            .iter()
+            .filter(|peer_review| peer_review.state.eq(&PeerReviewState::Submitted))
+            .filter_map(|peer_review| peer_review.score)
+            .collect();
+
+        let peer_score = match PeerReviewAssigner::median(&scores) {
+            Some(peer_score) => peer_score,
+            None => return Ok(()),
+        };
+
+        let max_points = match ex_comp.max_points {
+            Some(max_points) if max_points > 0.0 => max_points,
+            _ => return Ok(()),
+        };
+
+        let auto_score = match ex_comp.body.clone() {
+            Some(body) => ExerciseChecker::score_answer(exercise, body)?.clamp(0.0, 1.0),
+            None => 0.0,
+        };
+        let points_scored = PeerReviewAssigner::blend(auto_score, peer_score, max_points);
+        let state = ExerciseChecker::graded_state(points_scored, max_points);
+
+        exercise_repository.update_exercise_completion_points(ctx, ex_comp_id, points_scored, state).await?;
+
+        if state.eq(&ExerciseCompletionState::Succeeded) && ex_comp.state.ne(&ExerciseCompletionState::Succeeded) {
+            let student_exercise_interactor = StudentExerciseInteractor::new(self.repository_manager.clone());
+
+            if student_exercise_interactor.is_lesson_state_complete(ctx, exercise.lesson_id, ex_comp.user_id).await? {
+                student_exercise_interactor.complete_lesson(ctx, exercise.lesson_id, ex_comp.user_id).await?;
+            }
+        } else if state.eq(&ExerciseCompletionState::Failed) && ex_comp.state.eq(&ExerciseCompletionState::Succeeded) {
+            // The lesson may have been done thanks to this attempt only.
+            ProgressReconciler::new(self.repository_manager.clone())
+                .reconcile_lesson_course(ctx, exercise.lesson_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashSet, sync::Arc};
+
+use lib_utils::time::now_utc_sec;
+
+use crate::{ctx::Ctx, interfaces::{command_repository_manager::ICommandRepositoryManager, file_storage::{FileStorageResult, IFileStorage, LEGACY_UPLOADS_PREFIX, PRIVATE_PREFIX, PUBLIC_PREFIX}}, models::file::{StoredFileInfo, UploadGcReport, UPLOAD_GC_GRACE_PERIOD_SEC}};
+
+/// Finds stored files no row points to: leftovers of failed database updates,
+/// deleted courses or old files the storage refused to delete.
+pub struct UploadCollector {
+    repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+}
+
+impl UploadCollector {
+    pub fn new(
+        repository_manager: Arc<dyn ICommandRepositoryManager + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository_manager,
+        }
+    }
+}
+
+impl UploadCollector {
+    pub async fn collect(&self, ctx: &Ctx, dry_run: bool) -> FileStorageResult<UploadGcReport> {
+        let file_storage = self.repository_manager.get_file_storage();
+        let legacy_upload_storage = self.repository_manager.get_legacy_upload_storage();
+
+        // Files are listed first, a file uploaded after the references were read
+        // would otherwise look orphaned (the grace period covers it as well).
+        let mut files = file_storage.list(PUBLIC_PREFIX).await?;
+        files.extend(file_storage.list(PRIVATE_PREFIX).await?);
+
+        let legacy_files = legacy_upload_storage.list(LEGACY_UPLOADS_PREFIX).await?;
+
+        let referenced_keys = self.get_referenced_keys(ctx).await?;
+
+        let mut report = UploadGcReport {
+            dry_run,
+            scanned: files.len() + legacy_files.len(),
+            referenced: 0,
+            recent: 0,
+            orphaned_keys: Vec::new(),
+            failed_keys: Vec::new(),
+        };
+
+        Self::sweep(file_storage.as_ref(), files, &referenced_keys, &mut report).await;
+        Self::sweep(legacy_upload_storage.as_ref(), legacy_files, &referenced_keys, &mut report).await;
+
+        report.orphaned_keys.sort();
+        report.failed_keys.sort();
+
+        Ok(report)
+    }
+
+    async fn sweep(
+        file_storage: &(dyn IFileStorage + Send + Sync),
+        files: Vec<StoredFileInfo>,
+        referenced_keys: &HashSet<String>,
+        report: &mut UploadGcReport,
+    ) {
+        let min_modified_at = now_utc_sec() - UPLOAD_GC_GRACE_PERIOD_SEC;
+
+        for file in files {
+            if referenced_keys.contains(&file.key) {
+                report.referenced += 1;
+                continue;
+            }
+
+            if file.modified_at > min_modified_at {
+                report.recent += 1;
+                continue;
+            }
+
+            if !report.dry_run && file_storage.delete(&file.key).await.is_err() {
+                report.failed_keys.push(file.key);
+                continue;
+            }
+
+            report.orphaned_keys.push(file.key);
+        }
+    }
+
+    async fn get_referenced_keys(&self, ctx: &Ctx) -> FileStorageResult<HashSet<String>> {
+        let file_storage = self.repository_manager.get_file_storage();
+        let course_repository = self.repository_manager.get_course_repository();
+        let lesson_repository = self.repository_manager.get_lesson_repository();
+
+        let mut public_urls = course_repository.get_course_img_urls(ctx).await?;
+        public_urls.extend(lesson_repository.get_content_block_urls(ctx).await?);
+
+        let mut result: HashSet<String> = public_urls
+            .iter()
+            .filter_map(|url| file_storage.key_from_public_url(url))
+            .collect();
+
+        let legacy_prefix = format!("{LEGACY_UPLOADS_PREFIX}/");
+        result.extend(public_urls.into_iter().filter(|url| url.starts_with(&legacy_prefix)));
+
+        result.extend(lesson_repository.get_attachment_storage_keys(ctx).await?);
+
+        Ok(result)
+    }
+}
@@ -1,4 +1,6 @@
-use super::{course::ICourseCommandRepository, exercise::IExerciseCommandRepository, lesson::ILessonCommandRepository, user::IUserCommandRepository};
+use std::sync::Arc;
+
+use super::{course::ICourseCommandRepository, exercise::IExerciseCommandRepository, file_storage::IFileStorage, lesson::ILessonCommandRepository, user::IUserCommandRepository};
 
 pub trait ICommandRepositoryManager {
     fn get_user_repository(&self) -> Box<dyn IUserCommandRepository + Send + Sync>;
@@ -8,4 +10,9 @@ pub trait ICommandRepositoryManager {
     fn get_lesson_repository(&self) -> Box<dyn ILessonCommandRepository + Send + Sync>;
 
     fn get_exercise_repository(&self) -> Box<dyn IExerciseCommandRepository + Send + Sync>;
+
+    fn get_file_storage(&self) -> Arc<dyn IFileStorage + Send + Sync>;
+
+    /// Directory of the uploads older than the file storage, only read and cleaned up.
+    fn get_legacy_upload_storage(&self) -> Arc<dyn IFileStorage + Send + Sync>;
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::{interactors::error::CoreError, ctx::Ctx, models::course::{Course, CourseForCreate, CourseForUpdateCommand, UserCourse}};
+use crate::{interactors::error::CoreError, ctx::Ctx, models::{course::{Course, CourseForCreate, CourseForUpdateCommand, CourseSchedule, UserCourse, UserCourseRole}, course_revision::{CourseRevision, StagedEdit}, course_transfer::{CourseTransfer, CourseTransferForCreate, CourseTransferState}, placement_test::{PlacementAttempt, PlacementAttemptForCreate, PlacementTest}, unlock_policy::UnlockPolicy}};
 
 pub type CourseResult<T> = core::result::Result<T, CoreError>;
 
@@ -16,7 +16,78 @@ pub trait ICourseCommandRepository {
 
     async fn update_course(&self, ctx: &Ctx, course_for_u: CourseForUpdateCommand, course_id: i64) -> CourseResult<()>;
 
+    /// Moves the course back to `Draft` and drops its revision unless it has students, the
+    /// students are counted and the state is changed in one transaction. Returns the number
+    /// of students, the course is left as it is when it is not zero.
+    async fn unpublish_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<i64>;
+
+    /// Removes the course with its sections, lessons, exercises and all progress in them,
+    /// a pending transfer of the course is cancelled.
+    async fn delete_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()>;
+
+    /// Overwrites both scheduled dates, so `None` clears a schedule.
+    async fn update_course_schedule(&self, ctx: &Ctx, schedule: CourseSchedule) -> CourseResult<()>;
+
+    /// Schedules of the courses with a publish or archive date at or before `now`.
+    async fn get_due_course_schedules(&self, ctx: &Ctx, now: i64) -> CourseResult<Vec<CourseSchedule>>;
+
+    async fn get_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>>;
+
+    /// Merges the edit into the revision of the course, creating it when there is none.
+    /// Concurrent edits are merged one after another.
+    async fn stage_course_edit(&self, ctx: &Ctx, course_id: i64, edit: StagedEdit) -> CourseResult<()>;
+
+    async fn delete_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()>;
+
+    /// Applies every staged edit and removes the revision in one transaction. Returns the applied
+    /// edits, without edits of deleted entities, `None` when the course has no revision.
+    async fn publish_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>>;
+
+    async fn get_course_students_count(&self, ctx: &Ctx, course_id: i64) -> CourseResult<i64>;
+
+    /// `img_url` and the variant URLs of every course.
+    async fn get_course_img_urls(&self, ctx: &Ctx) -> CourseResult<Vec<String>>;
+
     async fn create_user_course(&self, ctx: &Ctx, course_for_r: UserCourse) -> CourseResult<()>;
 
     async fn delete_user_course(&self, ctx: &Ctx, user_id: i64, course_id: i64) -> CourseResult<()>;
-}
+
+    /// Members of the course with any role but `Student`.
+    async fn get_course_staff(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Vec<UserCourse>>;
+
+    async fn update_user_course_role(&self, ctx: &Ctx, user_id: i64, course_id: i64, user_role: UserCourseRole) -> CourseResult<()>;
+
+    async fn get_course_transfer(&self, ctx: &Ctx, transfer_id: i64) -> CourseResult<CourseTransfer>;
+
+    /// Every transfer of the course, the oldest first.
+    async fn get_course_transfers(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Vec<CourseTransfer>>;
+
+    /// Pending transfers waiting for the answer of the user.
+    async fn get_incoming_course_transfers(&self, ctx: &Ctx, user_id: i64) -> CourseResult<Vec<CourseTransfer>>;
+
+    async fn create_course_transfer(&self, ctx: &Ctx, transfer_for_c: CourseTransferForCreate) -> CourseResult<i64>;
+
+    /// Closes a pending transfer without moving the course.
+    async fn resolve_course_transfer(&self, ctx: &Ctx, transfer_id: i64, state: CourseTransferState, date_resolved: i64) -> CourseResult<()>;
+
+    /// Accepts the transfer and moves the `Creator` row of the course to the recipient in one
+    /// transaction, the row the recipient already had in the course is replaced.
+    async fn complete_course_transfer(&self, ctx: &Ctx, transfer: CourseTransfer, date_resolved: i64) -> CourseResult<()>;
+
+    /// Records an accepted transfer and moves the course like `complete_course_transfer`.
+    async fn force_course_transfer(&self, ctx: &Ctx, transfer_for_c: CourseTransferForCreate, date_resolved: i64) -> CourseResult<()>;
+
+    async fn get_placement_test(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<PlacementTest>>;
+
+    async fn save_placement_test(&self, ctx: &Ctx, placement_test: PlacementTest) -> CourseResult<()>;
+
+    async fn get_placement_attempt(&self, ctx: &Ctx, course_id: i64, user_id: i64) -> CourseResult<Option<PlacementAttempt>>;
+
+    /// Records the attempt and creates `Done` progresses marked as skipped by the placement
+    /// test in one transaction.
+    async fn create_placement_attempt(&self, ctx: &Ctx, attempt_for_c: PlacementAttemptForCreate) -> CourseResult<()>;
+
+    async fn get_unlock_policy(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<UnlockPolicy>>;
+
+    async fn save_unlock_policy(&self, ctx: &Ctx, unlock_policy: UnlockPolicy) -> CourseResult<()>;
+}
\ No newline at end of file
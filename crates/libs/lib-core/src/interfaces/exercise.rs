@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::{ctx::Ctx, interactors::error::CoreError, models::{exercise::{Exercise, ExerciseForChangeOrder, ExerciseForCreateCommand, ExerciseForUpdate}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForUpdate}}};
+use crate::{ctx::Ctx, interactors::error::CoreError, models::{difficulty_calibration::{DifficultyChange, DifficultyChangeForCreate}, exercise::{Exercise, ExerciseForChangeOrder, ExerciseForCreateCommand, ExerciseForUpdate}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForReviewCommand, ExerciseCompletionForUpdate, ExerciseCompletionState}, lesson_progress::LessonCompletedExercises, peer_review::{PeerReview, PeerReviewForCreate, PeerReviewForSubmitCommand}, similarity_flag::{SimilarityFlag, SimilarityFlagForCreate}}};
 
 pub type ExerciseResult<T> = core::result::Result<T, CoreError>;
 
@@ -10,6 +10,12 @@ pub trait IExerciseCommandRepository {
 
     async fn get_exercise(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Exercise>;
 
+    /// Exercises with the given ids and the ids themselves, missing ids are skipped.
+    async fn get_exercises(&self, ctx: &Ctx, exercise_ids: Vec<i64>) -> ExerciseResult<Vec<(i64, Exercise)>>;
+
+    /// Exercises of every lesson of the course with their ids, in exercise order within a lesson.
+    async fn get_course_exercises(&self, ctx: &Ctx, course_id: i64) -> ExerciseResult<Vec<(i64, Exercise)>>;
+
     async fn create(&self, ctx: &Ctx, exercise_c: ExerciseForCreateCommand) -> ExerciseResult<i64>;
 
     async fn update(&self, ctx: &Ctx, exercise_u: ExerciseForUpdate) -> ExerciseResult<()>;
@@ -30,6 +36,45 @@ pub trait IExerciseCommandRepository {
 
     async fn get_number_of_lesson_completed_exercises(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<i64>;
 
+    /// The number of completed exercises of every student in every lesson of the course,
+    /// lessons a student has completed nothing in are left out.
+    async fn get_course_completed_exercises(&self, ctx: &Ctx, course_id: i64) -> ExerciseResult<Vec<LessonCompletedExercises>>;
+
     async fn remove_exercise_completions(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<()>;
 
+    async fn update_exercise_completion_state(&self, ctx: &Ctx, ex_comp_id: i64, state: ExerciseCompletionState) -> ExerciseResult<()>;
+
+    async fn review_exercise_completion(&self, ctx: &Ctx, ex_comp_for_r: ExerciseCompletionForReviewCommand) -> ExerciseResult<()>;
+
+    async fn get_course_pending_reviews(&self, ctx: &Ctx, course_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>>;
+
+    async fn get_exercise_completions(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>>;
+
+    async fn update_exercise_completion_points(&self, ctx: &Ctx, ex_comp_id: i64, points_scored: f32, state: ExerciseCompletionState) -> ExerciseResult<()>;
+
+    async fn get_exercise_peer_reviews(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<PeerReview>>;
+
+    async fn get_exercise_completion_peer_reviews(&self, ctx: &Ctx, ex_comp_id: i64) -> ExerciseResult<Vec<PeerReview>>;
+
+    async fn get_peer_review(&self, ctx: &Ctx, peer_review_id: i64) -> ExerciseResult<PeerReview>;
+
+    async fn create_peer_reviews(&self, ctx: &Ctx, peer_reviews_for_c: Vec<PeerReviewForCreate>) -> ExerciseResult<()>;
+
+    async fn submit_peer_review(&self, ctx: &Ctx, peer_review_for_s: PeerReviewForSubmitCommand) -> ExerciseResult<()>;
+
+    /// Exercises with at least two finished attempts from different students.
+    async fn get_exercises_for_similarity_check(&self, ctx: &Ctx) -> ExerciseResult<Vec<i64>>;
+
+    async fn get_exercise_similarity_flags(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<SimilarityFlag>>;
+
+    /// Drops the previously stored flags of the exercise and stores the new ones.
+    async fn replace_similarity_flags(&self, ctx: &Ctx, exercise_id: i64, flags_for_c: Vec<SimilarityFlagForCreate>) -> ExerciseResult<()>;
+
+    async fn get_auto_difficulty_exercises(&self, ctx: &Ctx) -> ExerciseResult<Vec<i64>>;
+
+    async fn get_exercise_difficulty_changes(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<DifficultyChange>>;
+
+    /// Sets the new difficulty of the exercise and records the change.
+    async fn change_exercise_difficulty(&self, ctx: &Ctx, difficulty_change_for_c: DifficultyChangeForCreate) -> ExerciseResult<()>;
+
 }
\ No newline at end of file
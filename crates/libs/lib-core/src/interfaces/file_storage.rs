@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::{interactors::error::CoreError, models::file::{StoredFile, StoredFileInfo}};
+
+pub type FileStorageResult<T> = core::result::Result<T, CoreError>;
+
+pub const PUBLIC_PREFIX: &str = "public";
+pub const PRIVATE_PREFIX: &str = "private";
+/// Course images saved before the file storage existed. Their URL is the key
+/// in the legacy upload storage.
+pub const LEGACY_UPLOADS_PREFIX: &str = "uploads";
+
+/// Keys under `public/` are readable by anyone through `public_url`, 
+/// everything else is only handed out by the application.
+#[async_trait]
+pub trait IFileStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> FileStorageResult<()>;
+
+    async fn get(&self, key: &str) -> FileStorageResult<StoredFile>;
+
+    async fn delete(&self, key: &str) -> FileStorageResult<()>;
+
+    /// Every file whose key starts with `{prefix}/`.
+    async fn list(&self, prefix: &str) -> FileStorageResult<Vec<StoredFileInfo>>;
+
+    fn public_url(&self, key: &str) -> String;
+
+    /// Reverse of `public_url`, `None` for URLs this storage did not produce.
+    fn key_from_public_url(&self, url: &str) -> Option<String>;
+}
@@ -3,9 +3,14 @@ use async_trait::async_trait;
 use crate::{
     ctx::Ctx, interactors::error::CoreError, 
     models::{
+        attachment::{Attachment, AttachmentForCreateCommand},
+        content_block::{ContentBlock, ContentBlockForChangeOrder, ContentBlockForCreateCommand, ContentBlockForUpdateCommand},
+        exam::{Exam, ExamForUpdate},
+        exercise_completion::ExerciseCompletionForCompleteCommand,
         lesson::{
             Lesson, LessonForChangeOreder, LessonForCreateCommand, LessonForUpdate}, 
-            lesson_progress::{LessonProgress, LessonProgressState}
+            lesson_progress::{LessonProgress, LessonProgressState, LessonProgressStateChange},
+            section::{Section, SectionForChangeOrder, SectionForCreateCommand, SectionForUpdate}
     }
 };
 
@@ -15,10 +20,26 @@ pub type LessonResult<T> = core::result::Result<T, CoreError>;
 pub trait ILessonCommandRepository {
     async fn get_lesson(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<Lesson>;
 
-    async fn change_lesson_progress_states_for_update_exercise(&self, ctx: &Ctx, course_id: i64, order: i32) -> LessonResult<()>;
+    /// Progresses of every student of the course.
+    async fn get_course_lesson_progresses(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<LessonProgress>>;
 
+    /// Lessons in course order: lessons outside sections first, then section by section.
+    /// `order` is the place of the lesson within its section.
     async fn get_course_lessons_ordered(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<LessonForChangeOreder>>;
 
+    async fn get_section_lessons_ordered(&self, ctx: &Ctx, course_id: i64, section_id: Option<i64>) -> LessonResult<Vec<LessonForChangeOreder>>;
+
+    /// Moves the lesson to the section and renumbers the lessons left in its old section
+    /// in one transaction.
+    async fn update_lesson_section(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+        section_id: Option<i64>,
+        order: i32,
+        source_lessons_for_u_order: Vec<LessonForChangeOreder>,
+    ) -> LessonResult<()>;
+
     async fn create_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64) -> LessonResult<()>;
     
     async fn get_lessons_progresses(&self, ctx: &Ctx, course_id: i64, user_id: i64) -> LessonResult<Vec<LessonProgress>>;
@@ -30,6 +51,73 @@ pub trait ILessonCommandRepository {
     async fn update_lesson(&self, ctx: &Ctx, lesson_for_u: LessonForUpdate) -> LessonResult<()>;
 
     async fn update_lesson_progress_state(&self, ctx: &Ctx, lesson_for_u: LessonProgressState, lesson_id: i64, user_id: i64) -> LessonResult<()>;
+
+    /// Applies every change in one transaction, a progress leaving Pause loses its pause date.
+    async fn update_lesson_progresses_states(&self, ctx: &Ctx, changes: Vec<LessonProgressStateChange>) -> LessonResult<()>;
     
     async fn update_lesson_orders(&self, ctx: &Ctx, lessons_for_u_order: Vec<LessonForChangeOreder>) -> LessonResult<()>;
+
+    async fn get_exam(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<Option<Exam>>;
+
+    async fn get_course_exams(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<Exam>>;
+
+    async fn save_exam(&self, ctx: &Ctx, exam_for_u: ExamForUpdate) -> LessonResult<()>;
+
+    /// Turns the exam back into a regular lesson.
+    async fn delete_exam(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<()>;
+
+    /// Grades the submitted attempts and releases the results in one transaction. The exam is
+    /// locked meanwhile, fails when an attempt missing from `graded_attempts` was submitted.
+    async fn release_exam_results(&self, ctx: &Ctx, lesson_id: i64, graded_attempts: Vec<ExerciseCompletionForCompleteCommand>) -> LessonResult<()>;
+
+    /// Marks the attempt submitted unless the results were released meanwhile,
+    /// returns `false` then and the attempt is to be graded right away.
+    async fn submit_exam_attempt(&self, ctx: &Ctx, lesson_id: i64, ex_comp_id: i64) -> LessonResult<bool>;
+
+    /// Progresses of exams still in progress although their window closed before `now`.
+    async fn get_overdue_exam_progresses(&self, ctx: &Ctx, now: i64) -> LessonResult<Vec<LessonProgress>>;
+
+    async fn pause_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64, date_paused: i64) -> LessonResult<()>;
+
+    /// Puts a paused lesson back in progress and clears the pause date. The running attempts
+    /// of the lesson get `paused_seconds` more time in the same transaction. Fails when the
+    /// progress isn't paused by the student anymore, a concurrent resume doesn't add the time twice.
+    async fn resume_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64, paused_seconds: i64) -> LessonResult<()>;
+
+    async fn get_section(&self, ctx: &Ctx, section_id: i64) -> LessonResult<Section>;
+
+    async fn get_course_sections_ordered(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<SectionForChangeOrder>>;
+
+    async fn create_section(&self, ctx: &Ctx, section_for_c: SectionForCreateCommand) -> LessonResult<i64>;
+
+    async fn update_section(&self, ctx: &Ctx, section_for_u: SectionForUpdate) -> LessonResult<()>;
+
+    async fn delete_section(&self, ctx: &Ctx, section_id: i64) -> LessonResult<()>;
+
+    async fn update_section_orders(&self, ctx: &Ctx, sections_for_u_order: Vec<SectionForChangeOrder>) -> LessonResult<()>;
+
+    async fn get_content_block(&self, ctx: &Ctx, content_block_id: i64) -> LessonResult<ContentBlock>;
+
+    async fn get_lesson_content_blocks_ordered(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<Vec<ContentBlockForChangeOrder>>;
+
+    async fn create_content_block(&self, ctx: &Ctx, content_block_for_c: ContentBlockForCreateCommand) -> LessonResult<i64>;
+
+    async fn update_content_block(&self, ctx: &Ctx, content_block_for_u: ContentBlockForUpdateCommand) -> LessonResult<()>;
+
+    async fn delete_content_block(&self, ctx: &Ctx, content_block_id: i64) -> LessonResult<()>;
+
+    async fn update_content_block_orders(&self, ctx: &Ctx, content_blocks_for_u_order: Vec<ContentBlockForChangeOrder>) -> LessonResult<()>;
+
+    /// URLs of the image, video and attachment blocks of every lesson.
+    async fn get_content_block_urls(&self, ctx: &Ctx) -> LessonResult<Vec<String>>;
+
+    async fn get_attachment(&self, ctx: &Ctx, attachment_id: i64) -> LessonResult<Attachment>;
+
+    async fn create_attachment(&self, ctx: &Ctx, attachment_for_c: AttachmentForCreateCommand) -> LessonResult<i64>;
+
+    async fn delete_attachment(&self, ctx: &Ctx, attachment_id: i64) -> LessonResult<()>;
+
+    async fn get_attachment_storage_keys(&self, ctx: &Ctx) -> LessonResult<Vec<String>>;
+
+    async fn get_course_attachments(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<Attachment>>;
 }
\ No newline at end of file
@@ -2,4 +2,5 @@ pub mod course;
 pub mod lesson;
 pub mod user;
 pub mod exercise;
-pub mod command_repository_manager;
\ No newline at end of file
+pub mod command_repository_manager;
+pub mod file_storage;
\ No newline at end of file
@@ -0,0 +1,56 @@
+/// Largest file a creator can attach to a lesson, 20 MB.
+pub const MAX_ATTACHMENT_SIZE: usize = 20 * 1024 * 1024;
+
+const PDF_MAGIC: &[u8] = b"%PDF-";
+/// Legacy Office documents are OLE compound files.
+const OLE_MAGIC: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+/// Office Open XML and OpenDocument files are ZIP archives.
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Documents and slides a lesson can be shipped with, with the magic bytes their files start with.
+pub const ALLOWED_ATTACHMENT_TYPES: [(&str, &[u8]); 6] = [
+    ("application/pdf", PDF_MAGIC),
+    ("application/vnd.ms-powerpoint", OLE_MAGIC),
+    ("application/vnd.openxmlformats-officedocument.presentationml.presentation", ZIP_MAGIC),
+    ("application/vnd.oasis.opendocument.presentation", ZIP_MAGIC),
+    ("application/msword", OLE_MAGIC),
+    ("application/vnd.openxmlformats-officedocument.wordprocessingml.document", ZIP_MAGIC),
+];
+
+#[derive(Clone)]
+pub struct Attachment {
+    pub id: i64,
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    /// Key in the private part of the file storage, never exposed to clients.
+    pub storage_key: String,
+}
+
+pub struct AttachmentForCreate {
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+}
+
+pub struct AttachmentForCreateCommand {
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+}
+
+/// Signed download link, valid until `expires_at` for the user it was issued to.
+pub struct AttachmentLink {
+    pub token: String,
+    /// Rfc3339
+    pub expires_at: String,
+}
+
+pub struct AttachmentFile {
+    pub file_name: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,106 @@
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::interactors::error::{CoreError, LessonError};
+
+#[derive(Clone)]
+pub struct ContentBlock {
+    pub id: i64,
+    pub lesson_id: i64,
+    pub block_type: ContentBlockType,
+    pub body: Value,
+    pub rendered_html: String,
+    pub block_order: i32,
+}
+
+#[derive(Clone, Display, Debug, PartialEq, Eq)]
+pub enum ContentBlockType {
+    Markdown,
+    Image,
+    Video,
+    Attachment,
+    Callout,
+}
+
+impl TryFrom<String> for ContentBlockType {
+    type Error = CoreError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Markdown" => Ok(Self::Markdown),
+            "Image" => Ok(Self::Image),
+            "Video" => Ok(Self::Video),
+            "Attachment" => Ok(Self::Attachment),
+            "Callout" => Ok(Self::Callout),
+            _ => Err(LessonError::ContentBlockTypeDoesNotExist { block_type: value }.into())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MarkdownBody {
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+pub struct ImageBody {
+    pub url: String,
+    pub alt: String,
+}
+
+#[derive(Deserialize)]
+pub struct VideoBody {
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+pub struct AttachmentBody {
+    pub url: String,
+    pub file_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CalloutBody {
+    pub style: CalloutStyle,
+    pub text: String,
+}
+
+#[derive(Deserialize, Display)]
+pub enum CalloutStyle {
+    Info,
+    Tip,
+    Warning,
+}
+
+pub struct ContentBlockForCreate {
+    pub lesson_id: i64,
+    pub block_type: String,
+    pub body: Value,
+}
+
+pub struct ContentBlockForCreateCommand {
+    pub lesson_id: i64,
+    pub block_type: ContentBlockType,
+    pub body: Value,
+    /// Sanitized HTML of the Markdown text, empty for blocks without text.
+    pub rendered_html: String,
+    pub order: i32,
+}
+
+pub struct ContentBlockForUpdate {
+    pub id: i64,
+    pub body: Value,
+}
+
+pub struct ContentBlockForUpdateCommand {
+    pub id: i64,
+    pub body: Value,
+    pub rendered_html: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ContentBlockForChangeOrder {
+    pub id: i64,
+    pub order: i32,
+}
@@ -2,6 +2,8 @@ use typed_builder::TypedBuilder;
 
 use crate::interactors::error::{CoreError, CourseError, UserError};
 
+use super::course_image::CourseImageVariants;
+
 #[derive(Clone)]
 pub struct Course {
     pub id: i64,
@@ -12,7 +14,10 @@ pub struct Course {
     pub color: String,
     pub published_date: Option<i64>,
     pub img_url: Option<String>,
+    pub img_variants: Option<CourseImageVariants>,
     pub state: CourseState,
+    pub scheduled_publish_date: Option<i64>,
+    pub scheduled_archive_date: Option<i64>,
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -22,6 +27,19 @@ pub enum CourseState {
 	Archived,
 }
 
+impl CourseState {
+	/// Draft and Published go back and forth, an archived course can only be restored to Published.
+	pub fn can_change_to(&self, state: &CourseState) -> bool {
+		matches!(
+			(self, state),
+			(CourseState::Draft, CourseState::Published)
+				| (CourseState::Published, CourseState::Draft)
+				| (CourseState::Published, CourseState::Archived)
+				| (CourseState::Archived, CourseState::Published)
+		)
+	}
+}
+
 impl TryFrom<String> for CourseState {
 	type Error = CoreError;
 
@@ -45,6 +63,23 @@ impl ToString for CourseState {
 	}
 }
 
+/// Future state changes of a course, `None` means nothing is scheduled.
+#[derive(Clone)]
+pub struct CourseSchedule {
+	pub course_id: i64,
+	pub publish_date: Option<i64>,
+	pub archive_date: Option<i64>,
+}
+
+pub struct CourseScheduleReport {
+	pub published: i32,
+	pub archived: i32,
+	/// Courses that were due but not ready to publish, their schedule is dropped.
+	pub not_ready_course_ids: Vec<i64>,
+	/// Courses whose transition failed, they are retried on the next run.
+	pub failed_course_ids: Vec<i64>,
+}
+
 pub struct CourseForCreate {
 	pub title: String,
 	pub description: String,
@@ -69,6 +104,8 @@ pub struct CourseForUpdateCommand {
 	#[builder(default, setter(strip_option))]
 	pub img_url: Option<String>,
 	#[builder(default, setter(strip_option))]
+	pub img_variants: Option<CourseImageVariants>,
+	#[builder(default, setter(strip_option))]
 	pub published_date: Option<i64>,
 	#[builder(default, setter(strip_option))]
 	pub state: Option<CourseState>,
@@ -83,6 +120,7 @@ pub struct CourseForUpdate {
 	pub img_url: Option<String>,
 }
 
+#[derive(Clone)]
 pub struct UserCourse {
     pub user_id: i64,
     pub course_id: i64,
@@ -90,10 +128,45 @@ pub struct UserCourse {
 	pub date_registered: i64,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum UserCourseRole {
     Student,
     Creator,
+	/// Edits the content next to the creator.
+	CoAuthor,
+	/// Follows the students and grades their attempts, can't edit the course.
+	Assistant,
+}
+
+/// What a member of the course staff may do, see `UserCourseRole::has_permission`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoursePermission {
+	/// Sections, lessons, exercises, content blocks, attachments and exams.
+	EditContent,
+	/// Settings, state, schedule, revisions, placement test and unlock policy.
+	ManageCourse,
+	ManageStaff,
+	/// Attendants and statistics.
+	ViewStatistics,
+	/// Reviewing attempts and releasing exam results.
+	Grade,
+	/// Handing the course over to another user.
+	TransferOwnership,
+}
+
+impl UserCourseRole {
+	pub fn has_permission(&self, permission: CoursePermission) -> bool {
+		match self {
+			UserCourseRole::Creator => true,
+			UserCourseRole::CoAuthor => matches!(permission, CoursePermission::EditContent | CoursePermission::ViewStatistics),
+			UserCourseRole::Assistant => matches!(permission, CoursePermission::ViewStatistics | CoursePermission::Grade),
+			UserCourseRole::Student => false,
+		}
+	}
+
+	pub fn is_staff(&self) -> bool {
+		!self.eq(&UserCourseRole::Student)
+	}
 }
 
 impl ToString for UserCourseRole {
@@ -101,6 +174,8 @@ impl ToString for UserCourseRole {
 		match self {
 			UserCourseRole::Student => "Student".to_string(),
 			UserCourseRole::Creator => "Creator".to_string(),
+			UserCourseRole::CoAuthor => "CoAuthor".to_string(),
+			UserCourseRole::Assistant => "Assistant".to_string(),
 		}
 	}
 }
@@ -112,7 +187,16 @@ impl TryFrom<String> for UserCourseRole {
 		match value.as_str() {
 			"Creator" => Ok(UserCourseRole::Creator),
 			"Student" => Ok(UserCourseRole::Student),
+			"CoAuthor" => Ok(UserCourseRole::CoAuthor),
+			"Assistant" => Ok(UserCourseRole::Assistant),
 			role => Err(UserError::RoleDoesNotExist { role: role.to_string() }.into())
 		}
 	}
+}
+
+/// Invites a user to the staff or changes the role of a staff member.
+pub struct CourseStaffForUpdate {
+	pub course_id: i64,
+	pub user_id: i64,
+	pub user_role: UserCourseRole,
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_COURSE_IMG_SIZE: usize = 10 * 1024 * 1024;
+pub const MAX_COURSE_IMG_DIMENSION: u32 = 8192;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CourseImageVariant {
+    Thumbnail,
+    Card,
+    Hero,
+}
+
+impl CourseImageVariant {
+    pub const ALL: [CourseImageVariant; 3] = [Self::Thumbnail, Self::Card, Self::Hero];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CourseImageVariant::Thumbnail => "thumbnail",
+            CourseImageVariant::Card => "card",
+            CourseImageVariant::Hero => "hero",
+        }
+    }
+
+    /// Width and height of the variant, all variants are 16:9.
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            CourseImageVariant::Thumbnail => (320, 180),
+            CourseImageVariant::Card => (640, 360),
+            CourseImageVariant::Hero => (1600, 900),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CourseImageFormat {
+    Webp,
+    Png,
+}
+
+impl CourseImageFormat {
+    pub const ALL: [CourseImageFormat; 2] = [Self::Webp, Self::Png];
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            CourseImageFormat::Webp => "image/webp",
+            CourseImageFormat::Png => "image/png",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CourseImageFormat::Webp => "webp",
+            CourseImageFormat::Png => "png",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct CourseImageUrls {
+    pub webp: String,
+    pub png: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct CourseImageVariants {
+    pub thumbnail: CourseImageUrls,
+    pub card: CourseImageUrls,
+    pub hero: CourseImageUrls,
+}
+
+impl CourseImageVariants {
+    pub fn urls(&self) -> [&String; 6] {
+        [
+            &self.thumbnail.webp, &self.thumbnail.png,
+            &self.card.webp, &self.card.png,
+            &self.hero.webp, &self.hero.png,
+        ]
+    }
+}
+
+/// One encoded variant, ready to be stored.
+pub struct ProcessedCourseImage {
+    pub variant: CourseImageVariant,
+    pub format: CourseImageFormat,
+    pub data: Vec<u8>,
+}
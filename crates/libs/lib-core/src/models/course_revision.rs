@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{content_block::{ContentBlock, ContentBlockForUpdateCommand}, exam::ExamForUpdate, exercise::{Exercise, ExerciseDifficulty, ExerciseForUpdate, ExerciseType}, lesson::{Lesson, LessonForUpdate}, section::SectionForUpdate};
+
+/// Edits of a published course students don't see yet. Only changes of existing
+/// sections, lessons, exams, exercises and content blocks are staged, at most one edit per entity.
+#[derive(Clone)]
+pub struct CourseRevision {
+    pub course_id: i64,
+    pub edits: Vec<StagedEdit>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum StagedEdit {
+    Lesson(StagedLessonEdit),
+    Exercise(StagedExerciseEdit),
+    ContentBlock(StagedContentBlockEdit),
+    Section(StagedSectionEdit),
+    Exam(StagedExamEdit),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StagedLessonEdit {
+    pub lesson_id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StagedExerciseEdit {
+    pub exercise_id: i64,
+    pub lesson_id: i64,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub exercise_type: Option<ExerciseType>,
+    pub answer_body: Option<Value>,
+    pub exercise_body: Option<Value>,
+    pub difficult: Option<ExerciseDifficulty>,
+    pub time_to_complete: Option<i64>,
+    pub manual_review: Option<bool>,
+    pub auto_difficulty: Option<bool>,
+    pub peer_review_count: Option<i32>,
+    pub peer_review_rubric: Option<Value>,
+    /// The attempts are removed when the revision is published.
+    pub retake: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StagedContentBlockEdit {
+    pub content_block_id: i64,
+    pub lesson_id: i64,
+    pub body: Value,
+    pub rendered_html: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StagedSectionEdit {
+    pub section_id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+/// Turns the lesson into an exam or moves the window of its exam.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StagedExamEdit {
+    pub lesson_id: i64,
+    pub opens_at: i64,
+    pub closes_at: i64,
+    /// The lesson becomes a regular one again, the window is ignored.
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// A lesson the way students will see it once the revision is published.
+pub struct LessonPreview {
+    pub lesson: Lesson,
+    /// Only set by a staged edit, the live description is not part of the lesson model.
+    pub description: Option<String>,
+    pub content_blocks: Vec<ContentBlock>,
+    pub exercises: Vec<(i64, Exercise)>,
+}
+
+impl CourseRevision {
+    pub fn new(course_id: i64) -> Self {
+        Self {
+            course_id,
+            edits: Vec::new(),
+        }
+    }
+
+    /// A later edit of the same entity replaces the earlier one, exercise edits are merged
+    /// field by field since an exercise update only carries the changed fields.
+    pub fn stage(&mut self, edit: StagedEdit) {
+        let existing = self.edits.iter_mut().find(|staged| staged.same_entity(&edit));
+
+        match (existing, edit) {
+            (Some(StagedEdit::Exercise(staged)), StagedEdit::Exercise(edit)) => staged.merge(edit),
+            (Some(staged), edit) => *staged = edit,
+            (None, edit) => self.edits.push(edit),
+        }
+    }
+
+    pub fn apply_to_lesson(&self, lesson: &mut Lesson) -> Option<String> {
+        self.edits.iter().find_map(|edit| match edit {
+            StagedEdit::Lesson(edit) if edit.lesson_id == lesson.id => {
+                lesson.title = edit.title.clone();
+                Some(edit.description.clone())
+            },
+            _ => None,
+        })
+    }
+
+    pub fn apply_to_exercise(&self, exercise_id: i64, exercise: &mut Exercise) {
+        for edit in &self.edits {
+            if let StagedEdit::Exercise(edit) = edit {
+                if edit.exercise_id == exercise_id {
+                    edit.apply_to(exercise);
+                }
+            }
+        }
+    }
+
+    pub fn apply_to_content_block(&self, content_block: &mut ContentBlock) {
+        for edit in &self.edits {
+            if let StagedEdit::ContentBlock(edit) = edit {
+                if edit.content_block_id == content_block.id {
+                    content_block.body = edit.body.clone();
+                    content_block.rendered_html = edit.rendered_html.clone();
+                }
+            }
+        }
+    }
+}
+
+impl StagedEdit {
+    fn same_entity(&self, other: &StagedEdit) -> bool {
+        match (self, other) {
+            (StagedEdit::Lesson(a), StagedEdit::Lesson(b)) => a.lesson_id == b.lesson_id,
+            (StagedEdit::Exercise(a), StagedEdit::Exercise(b)) => a.exercise_id == b.exercise_id,
+            (StagedEdit::ContentBlock(a), StagedEdit::ContentBlock(b)) => a.content_block_id == b.content_block_id,
+            (StagedEdit::Section(a), StagedEdit::Section(b)) => a.section_id == b.section_id,
+            (StagedEdit::Exam(a), StagedEdit::Exam(b)) => a.lesson_id == b.lesson_id,
+            _ => false,
+        }
+    }
+}
+
+impl StagedExerciseEdit {
+    pub fn new(exercise_for_u: ExerciseForUpdate, lesson_id: i64, retake: bool) -> Self {
+        Self {
+            exercise_id: exercise_for_u.id,
+            lesson_id,
+            title: exercise_for_u.title,
+            description: exercise_for_u.description,
+            exercise_type: exercise_for_u.exercise_type,
+            answer_body: exercise_for_u.answer_body,
+            exercise_body: exercise_for_u.exercise_body,
+            difficult: exercise_for_u.difficult,
+            time_to_complete: exercise_for_u.time_to_complete,
+            manual_review: exercise_for_u.manual_review,
+            auto_difficulty: exercise_for_u.auto_difficulty,
+            peer_review_count: exercise_for_u.peer_review_count,
+            peer_review_rubric: exercise_for_u.peer_review_rubric,
+            retake,
+        }
+    }
+
+    fn merge(&mut self, edit: StagedExerciseEdit) {
+        self.title = edit.title.or(self.title.take());
+        self.description = edit.description.or(self.description.take());
+        self.exercise_type = edit.exercise_type.or(self.exercise_type.take());
+        self.answer_body = edit.answer_body.or(self.answer_body.take());
+        self.exercise_body = edit.exercise_body.or(self.exercise_body.take());
+        self.difficult = edit.difficult.or(self.difficult.take());
+        self.time_to_complete = edit.time_to_complete.or(self.time_to_complete);
+        self.manual_review = edit.manual_review.or(self.manual_review);
+        self.auto_difficulty = edit.auto_difficulty.or(self.auto_difficulty);
+        self.peer_review_count = edit.peer_review_count.or(self.peer_review_count);
+        self.peer_review_rubric = edit.peer_review_rubric.or(self.peer_review_rubric.take());
+        self.retake |= edit.retake;
+    }
+
+    pub fn apply_to(&self, exercise: &mut Exercise) {
+        if let Some(title) = &self.title { exercise.title = title.clone(); }
+        if let Some(description) = &self.description { exercise.description = description.clone(); }
+        if let Some(exercise_type) = &self.exercise_type { exercise.exercise_type = exercise_type.clone(); }
+        if let Some(answer_body) = &self.answer_body { exercise.answer_body = answer_body.clone(); }
+        if let Some(exercise_body) = &self.exercise_body { exercise.exercise_body = exercise_body.clone(); }
+        if let Some(difficult) = &self.difficult { exercise.difficult = difficult.clone(); }
+        if let Some(time_to_complete) = self.time_to_complete { exercise.time_to_complete = Some(time_to_complete as i32); }
+        if let Some(manual_review) = self.manual_review { exercise.manual_review = manual_review; }
+        if let Some(auto_difficulty) = self.auto_difficulty { exercise.auto_difficulty = auto_difficulty; }
+        if let Some(peer_review_count) = self.peer_review_count { exercise.peer_review_count = peer_review_count; }
+        if let Some(peer_review_rubric) = &self.peer_review_rubric { exercise.peer_review_rubric = peer_review_rubric.clone(); }
+    }
+}
+
+impl From<StagedLessonEdit> for LessonForUpdate {
+    fn from(value: StagedLessonEdit) -> Self {
+        Self {
+            id: value.lesson_id,
+            title: value.title,
+            description: value.description,
+        }
+    }
+}
+
+impl From<StagedExerciseEdit> for ExerciseForUpdate {
+    fn from(value: StagedExerciseEdit) -> Self {
+        Self {
+            id: value.exercise_id,
+            title: value.title,
+            description: value.description,
+            exercise_type: value.exercise_type,
+            answer_body: value.answer_body,
+            exercise_body: value.exercise_body,
+            difficult: value.difficult,
+            time_to_complete: value.time_to_complete,
+            manual_review: value.manual_review,
+            auto_difficulty: value.auto_difficulty,
+            peer_review_count: value.peer_review_count,
+            peer_review_rubric: value.peer_review_rubric,
+        }
+    }
+}
+
+impl From<StagedContentBlockEdit> for ContentBlockForUpdateCommand {
+    fn from(value: StagedContentBlockEdit) -> Self {
+        Self {
+            id: value.content_block_id,
+            body: value.body,
+            rendered_html: value.rendered_html,
+        }
+    }
+}
+
+impl From<StagedSectionEdit> for SectionForUpdate {
+    fn from(value: StagedSectionEdit) -> Self {
+        Self {
+            id: value.section_id,
+            title: value.title,
+            description: value.description,
+        }
+    }
+}
+
+impl From<StagedExamEdit> for ExamForUpdate {
+    fn from(value: StagedExamEdit) -> Self {
+        Self {
+            lesson_id: value.lesson_id,
+            opens_at: value.opens_at,
+            closes_at: value.closes_at,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use derive_more::Display;
+
+use crate::interactors::error::CourseError;
+
+/// A hand-over of the course to another user. Transfers are never deleted, together
+/// they are the audit trail of the course ownership.
+#[derive(Clone)]
+pub struct CourseTransfer {
+    pub id: i64,
+    pub course_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub state: CourseTransferState,
+    /// Made by an admin without asking the recipient.
+    pub forced: bool,
+    pub date_proposed: i64,
+    pub date_resolved: Option<i64>,
+}
+
+#[derive(PartialEq, Display, Clone, Copy, Debug)]
+pub enum CourseTransferState {
+    Pending,
+    Accepted,
+    Declined,
+    Cancelled,
+}
+
+impl TryFrom<String> for CourseTransferState {
+    type Error = CourseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Pending" => Ok(Self::Pending),
+            "Accepted" => Ok(Self::Accepted),
+            "Declined" => Ok(Self::Declined),
+            "Cancelled" => Ok(Self::Cancelled),
+            state => Err(CourseError::CourseTransferStateDoesNotExist { state: state.to_string() }),
+        }
+    }
+}
+
+pub struct CourseTransferForCreate {
+    pub course_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub forced: bool,
+}
@@ -0,0 +1,45 @@
+use super::exercise::ExerciseDifficulty;
+
+/// What students actually went through on an exercise.
+pub struct DifficultyObservations {
+    pub number_of_students: i64,
+    /// Share of students with at least one succeeded attempt.
+    pub pass_rate: f32,
+    pub average_attempts: f32,
+    /// Average seconds from starting an attempt to its last change.
+    pub average_time_spent: Option<f32>,
+}
+
+pub struct DifficultyCalibration {
+    pub exercise_id: i64,
+    pub title: String,
+    pub assigned_difficulty: ExerciseDifficulty,
+    /// `None` for reading exercises and until enough students finished the exercise.
+    pub suggested_difficulty: Option<ExerciseDifficulty>,
+    pub auto_difficulty: bool,
+    pub observations: DifficultyObservations,
+    pub history: Vec<DifficultyChange>,
+}
+
+#[derive(Clone)]
+pub struct DifficultyChange {
+    pub id: i64,
+    pub exercise_id: i64,
+    pub old_difficulty: ExerciseDifficulty,
+    pub new_difficulty: ExerciseDifficulty,
+    pub pass_rate: f32,
+    pub date_changed: i64,
+}
+
+pub struct DifficultyCalibrationReport {
+    pub changed_exercises: i32,
+    /// Exercises that could not be calibrated, the others are calibrated anyway.
+    pub failed_exercise_ids: Vec<i64>,
+}
+
+pub struct DifficultyChangeForCreate {
+    pub exercise_id: i64,
+    pub old_difficulty: ExerciseDifficulty,
+    pub new_difficulty: ExerciseDifficulty,
+    pub pass_rate: f32,
+}
@@ -0,0 +1,15 @@
+/// Settings of a lesson taken as an exam. A lesson without them is a regular one.
+#[derive(Clone)]
+pub struct Exam {
+    pub lesson_id: i64,
+    pub opens_at: i64,
+    pub closes_at: i64,
+    /// Until the creator releases the results, submitted attempts stay ungraded.
+    pub results_released: bool,
+}
+
+pub struct ExamForUpdate {
+    pub lesson_id: i64,
+    pub opens_at: i64,
+    pub closes_at: i64,
+}
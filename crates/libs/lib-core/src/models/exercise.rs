@@ -1,5 +1,5 @@
 use derive_more::Display;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::interactors::error::{CoreError, ExerciseError};
@@ -17,9 +17,14 @@ pub struct Exercise {
     pub difficult: ExerciseDifficulty,
     pub time_to_complete: Option<i32>,  
     pub exercise_order: i32,
+    pub manual_review: bool,
+    /// Lets the calibration job replace `difficult` with the observed difficulty.
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
-#[derive(Clone, Display)]
+#[derive(Clone, Display, Serialize, Deserialize)]
 pub enum ExerciseType {
     Conspect,
     InteractiveConspect,
@@ -37,7 +42,7 @@ impl TryFrom<String> for ExerciseType {
     }
 }
 
-#[derive(Clone, Display, PartialEq, Eq)]
+#[derive(Clone, Display, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExerciseDifficulty {
     Read,
     Easy,
@@ -121,6 +126,97 @@ pub struct Stage {
     pub name: String,
 }
 
+pub struct ConspectDiff {
+    /// Connections present in the submission but missing from the answer.
+    pub added_connections: Vec<ConnectionDiff>,
+    /// Connections present in the answer but missing from the submission.
+    pub removed_connections: Vec<ConnectionDiff>,
+    pub nodes: Vec<NodeDiff>,
+}
+
+pub struct ConnectionDiff {
+    pub from: String,
+    pub to: String,
+}
+
+pub struct NodeDiff {
+    pub id: String,
+    pub x: i64,
+    pub y: i64,
+    pub state: NodeDiffState,
+    pub answer_body: Option<Value>,
+    pub solution_body: Option<Value>,
+    pub stages: Vec<StageDiff>,
+}
+
+#[derive(Clone, Copy, Display, PartialEq, Eq, Debug)]
+pub enum NodeDiffState {
+    Unchanged,
+    Changed,
+    Added,
+    Removed,
+}
+
+/// Stage position where the submitted stage differs from the answer stage.
+pub struct StageDiff {
+    pub position: usize,
+    pub answer_stage_id: Option<i64>,
+    pub solution_stage_id: Option<i64>,
+}
+
+/// Most frequent mistakes across the graded attempts of an exercise, most frequent first.
+pub struct ExerciseMistakeStatistics {
+    pub exercise_id: i64,
+    pub title: String,
+    pub number_of_completions: i64,
+    /// Answer connections the attempts did not contain.
+    pub missing_connections: Vec<ConnectionMistake>,
+    /// Connections the attempts added that are not in the answer.
+    pub extra_connections: Vec<ConnectionMistake>,
+    pub wrong_stage_positions: Vec<StageMistake>,
+    pub blank_nodes: Vec<NodeMistake>,
+}
+
+pub struct ConnectionMistake {
+    pub from: String,
+    pub to: String,
+    pub count: i64,
+}
+
+pub struct StageMistake {
+    pub node_id: String,
+    pub position: usize,
+    pub count: i64,
+}
+
+pub struct NodeMistake {
+    pub node_id: String,
+    pub count: i64,
+}
+
+/// Force directed layout is quadratic in the number of nodes on every iteration.
+pub const MAX_FORCE_DIRECTED_NODES: usize = 200;
+
+#[derive(Clone, Copy, Display, PartialEq, Eq)]
+pub enum ConspectLayoutAlgorithm {
+    Auto,
+    Layered,
+    ForceDirected,
+}
+
+impl TryFrom<String> for ConspectLayoutAlgorithm {
+    type Error = CoreError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Auto" => Ok(Self::Auto),
+            "Layered" => Ok(Self::Layered),
+            "ForceDirected" => Ok(Self::ForceDirected),
+            algorithm => Err(ExerciseError::IncorrectLayoutAlgorithm { algorithm: algorithm.to_string() }.into())
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ExerciseForChangeOrder {
     pub id: i64,
@@ -137,6 +233,11 @@ pub struct ExerciseForCreate {
     pub exercise_body: Value,
     pub difficult: ExerciseDifficulty,
     pub time_to_complete: Option<i32>,  
+    pub manual_review: bool,
+    /// Lets the calibration job replace `difficult` with the observed difficulty.
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
 pub struct ExerciseForCreateCommand {
@@ -149,6 +250,11 @@ pub struct ExerciseForCreateCommand {
     pub exercise_order: i32,
     pub difficult: ExerciseDifficulty,
     pub time_to_complete: Option<i32>,  
+    pub manual_review: bool,
+    /// Lets the calibration job replace `difficult` with the observed difficulty.
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
 #[derive(Clone)]
@@ -161,6 +267,10 @@ pub struct ExerciseForUpdate {
     pub exercise_body: Option<Value>,
     pub difficult: Option<ExerciseDifficulty>,
     pub time_to_complete: Option<i64>,  
+    pub manual_review: Option<bool>,
+    pub auto_difficulty: Option<bool>,
+    pub peer_review_count: Option<i32>,
+    pub peer_review_rubric: Option<Value>,
 }
 
 pub struct ExerciseEstimate {
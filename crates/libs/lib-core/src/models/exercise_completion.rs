@@ -3,6 +3,10 @@ use serde_json::Value;
 
 use crate::interactors::error::ExerciseError;
 
+/// Longest comment a reviewer can leave on an attempt, in characters.
+pub const MAX_REVIEW_COMMENT_LENGTH: usize = 1024;
+
+#[derive(Clone)]
 pub struct ExerciseCompletion {
     pub id: i64,
     pub exercise_id: i64,
@@ -14,6 +18,18 @@ pub struct ExerciseCompletion {
     pub date_last_changes: Option<i64>,
     pub state: ExerciseCompletionState,   
     pub body: Option<Value>,
+    pub review_comment: Option<String>,
+    /// Practice attempts are graded but never count towards grades or lesson progress.
+    pub is_practice: bool,
+    /// Time the lesson spent paused while the attempt was running.
+    pub paused_seconds: i64,
+}
+
+impl ExerciseCompletion {
+    /// End of the time to complete, paused time doesn't count.
+    pub fn deadline(&self, time_to_complete: i64) -> i64 {
+        self.date_started + time_to_complete + self.paused_seconds
+    }
 }
 
 #[derive(PartialEq, Display, Clone, Copy)]
@@ -21,6 +37,9 @@ pub enum ExerciseCompletionState {
     InProgress,
     Succeeded,
     Failed,      
+    PendingReview,
+    /// Exam attempt handed in, graded once the creator releases the results.
+    Submitted,
 }
 
 impl TryFrom<String> for ExerciseCompletionState {
@@ -31,6 +50,8 @@ impl TryFrom<String> for ExerciseCompletionState {
             "InProgress" => Ok(Self::InProgress),
             "Succeeded" => Ok(Self::Succeeded),
             "Failed" => Ok(Self::Failed),
+            "PendingReview" => Ok(Self::PendingReview),
+            "Submitted" => Ok(Self::Submitted),
             state => Err(ExerciseError::ExerciseCompletionStateDoesNotExist { state: state.to_string() }.into()),
         }
     }
@@ -41,6 +62,7 @@ pub struct ExerciseCompletionForCreate {
     pub user_id: i64,
     pub number_of_attempts: usize,
     pub date_started: i64,
+    pub is_practice: bool,
 }
 
 pub struct ExerciseCompletionForUpdate {
@@ -54,4 +76,18 @@ pub struct ExerciseCompletionForCompleteCommand {
     pub points_scored: f32,
     pub max_points: f32,
     pub state: ExerciseCompletionState,
-}
\ No newline at end of file
+}
+
+pub struct ExerciseCompletionForReview {
+    pub id: i64,
+    pub points_scored: f32,
+    pub comment: Option<String>,
+    pub approved: bool,
+}
+
+pub struct ExerciseCompletionForReviewCommand {
+    pub id: i64,
+    pub points_scored: f32,
+    pub state: ExerciseCompletionState,
+    pub review_comment: Option<String>,
+}
@@ -0,0 +1,26 @@
+pub struct StoredFile {
+    pub data: Vec<u8>,
+    pub content_type: String,
+}
+
+pub struct StoredFileInfo {
+    pub key: String,
+    /// Unix seconds of the last write.
+    pub modified_at: i64,
+}
+
+/// Files younger than this are never collected, they may belong to an upload
+/// whose database update has not finished yet.
+pub const UPLOAD_GC_GRACE_PERIOD_SEC: i64 = 24 * 60 * 60;
+
+pub struct UploadGcReport {
+    pub dry_run: bool,
+    pub scanned: usize,
+    pub referenced: usize,
+    /// Unreferenced, but still within the grace period.
+    pub recent: usize,
+    /// Deleted, or to be deleted on a dry run.
+    pub orphaned_keys: Vec<String>,
+    /// Orphans the storage refused to delete, they are retried on the next run.
+    pub failed_keys: Vec<String>,
+}
@@ -3,17 +3,22 @@ pub struct Lesson {
     pub id: i64,
     pub course_id: i64,
     pub title: String,
+    /// Lessons outside any section come before the sections.
+    pub section_id: Option<i64>,
+    /// Place of the lesson within its section.
     pub lesson_order: i32,
 }
 
 pub struct LessonForCreate {
     pub course_id: i64,
+    pub section_id: Option<i64>,
     pub title: String,
     pub description: String,
 }
 
 pub struct LessonForCreateCommand {
     pub course_id: i64,
+    pub section_id: Option<i64>,
     pub title: String,
     pub description: String,
     pub order: i32,
@@ -29,4 +34,10 @@ pub struct LessonForUpdate {
 pub struct LessonForChangeOreder {
     pub id: i64,
     pub order: i32,
+}
+
+/// Moves the lesson to the end of another section, `None` takes it out of sections.
+pub struct LessonForChangeSection {
+    pub id: i64,
+    pub section_id: Option<i64>,
 }
\ No newline at end of file
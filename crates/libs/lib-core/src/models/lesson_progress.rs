@@ -2,6 +2,7 @@ use derive_more::Display;
 
 use crate::interactors::error::{CoreError, LessonProgressError};
 
+#[derive(Clone)]
 pub struct LessonProgress {
   pub user_id: i64,
   pub lesson_id: i64,
@@ -10,13 +11,37 @@ pub struct LessonProgress {
   pub date_complete: Option<i64>,  
 
   pub state: LessonProgressState, 
+  pub skipped_by_placement: bool,
+  /// Start of the current pause, `None` unless the state is `Pause`.
+  pub date_paused: Option<i64>,
 }
 
-#[derive(Display, Debug, PartialEq, Eq)]
+#[derive(Display, Debug, Clone, PartialEq, Eq)]
 pub enum LessonProgressState {
 	Pause,
     InProgress,
     Done,
+    /// Set by a retake of an earlier lesson, lifted once the student finishes that lesson again.
+    Locked,
+}
+
+impl LessonProgressState {
+	/// InProgress <-> Pause, and either of them -> Done. Done and Locked are final for students,
+	/// only the progress reconciliation reopens or unlocks a lesson.
+	pub fn can_change_to(&self, state: &Self) -> bool {
+		matches!(
+			(self, state),
+			(Self::InProgress, Self::Pause) |
+			(Self::Pause, Self::InProgress) |
+			(Self::InProgress, Self::Done) |
+			(Self::Pause, Self::Done)
+		)
+	}
+
+	/// No attempts are started or saved and timed attempts stand still.
+	pub fn is_paused(&self) -> bool {
+		matches!(self, Self::Pause | Self::Locked)
+	}
 }
 
 impl TryFrom<String> for LessonProgressState {
@@ -24,9 +49,36 @@ impl TryFrom<String> for LessonProgressState {
 
 	fn try_from(value: String) -> Result<Self, Self::Error> {
 		match value.as_str() {
+			"Pause" => Ok(Self::Pause),
 			"InProgress" => Ok(Self::InProgress),
 			"Done" => Ok(Self::Done),
+			"Locked" => Ok(Self::Locked),
 			state => Err(LessonProgressError::LessonProgressStateDoesNotExist { state: state.to_string() }.into())
 		}
 	}
+}
+
+/// A state the progress reconciliation moves a lesson progress to.
+pub struct LessonProgressStateChange {
+  pub lesson_id: i64,
+  pub user_id: i64,
+  /// The state the change was computed from, a progress that left it meanwhile is not changed.
+  pub previous_state: LessonProgressState,
+  pub state: LessonProgressState,
+}
+
+/// Lesson exercises the student has a graded successful attempt of.
+pub struct LessonCompletedExercises {
+  pub lesson_id: i64,
+  pub user_id: i64,
+  pub completed: i64,
+}
+
+/// Outcome of recomputing the progresses of a course.
+pub struct ProgressReconciliation {
+  pub course_id: i64,
+  pub lessons_completed: i64,
+  pub lessons_reopened: i64,
+  /// Students with every lesson of the course done afterwards.
+  pub students_completed_course: i64,
 }
\ No newline at end of file
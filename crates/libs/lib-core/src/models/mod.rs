@@ -3,4 +3,18 @@ pub mod user;
 pub mod lesson;
 pub mod lesson_progress;
 pub mod exercise;
-pub mod exercise_completion;
\ No newline at end of file
+pub mod exercise_completion;
+pub mod peer_review;
+pub mod similarity_flag;
+pub mod difficulty_calibration;
+pub mod exam;
+pub mod placement_test;
+pub mod unlock_policy;
+pub mod section;
+pub mod content_block;
+pub mod attachment;
+pub mod file;
+pub mod course_image;
+pub mod publish_readiness;
+pub mod course_revision;
+pub mod course_transfer;
\ No newline at end of file
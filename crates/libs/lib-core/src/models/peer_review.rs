@@ -0,0 +1,73 @@
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::interactors::error::ExerciseError;
+
+/// Length of the `peer_review.comment` column, in characters.
+pub const MAX_PEER_REVIEW_COMMENT_LENGTH: usize = 1024;
+
+#[derive(Clone)]
+pub struct PeerReview {
+    pub id: i64,
+    pub exercise_id: i64,
+    pub exercise_completion_id: i64,
+    pub reviewer_id: i64,
+    pub score: Option<f32>,
+    pub rubric_scores: Option<Value>,
+    pub comment: Option<String>,
+    pub state: PeerReviewState,
+}
+
+#[derive(PartialEq, Display, Clone, Copy, Debug)]
+pub enum PeerReviewState {
+    Assigned,
+    Submitted,
+}
+
+impl TryFrom<String> for PeerReviewState {
+    type Error = ExerciseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Assigned" => Ok(Self::Assigned),
+            "Submitted" => Ok(Self::Submitted),
+            state => Err(ExerciseError::PeerReviewStateDoesNotExist { state: state.to_string() }),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RubricCriterion {
+    pub name: String,
+    pub max_points: f32,
+}
+
+/// Submission handed to a reviewer, without anything that identifies its author.
+pub struct PeerReviewAssignment {
+    pub peer_review_id: i64,
+    pub exercise_id: i64,
+    pub body: Option<Value>,
+    pub rubric: Value,
+    pub state: PeerReviewState,
+}
+
+pub struct PeerReviewForCreate {
+    pub exercise_id: i64,
+    pub exercise_completion_id: i64,
+    pub reviewer_id: i64,
+}
+
+pub struct PeerReviewForSubmit {
+    pub id: i64,
+    pub rubric_scores: Vec<f32>,
+    pub comment: Option<String>,
+}
+
+pub struct PeerReviewForSubmitCommand {
+    pub id: i64,
+    /// Share of the rubric maximum, in `[0, 1]`.
+    pub score: f32,
+    pub rubric_scores: Value,
+    pub comment: Option<String>,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Students scoring at least `min_score` (share of the points, `(0, 1]`)
+/// skip the first `lessons_to_skip` lessons of the course.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlacementLevel {
+    pub min_score: f32,
+    pub lessons_to_skip: i32,
+}
+
+#[derive(Clone)]
+pub struct PlacementTest {
+    pub course_id: i64,
+    /// Exercises of the course the test is made of.
+    pub exercise_ids: Vec<i64>,
+    pub levels: Vec<PlacementLevel>,
+}
+
+impl PlacementTest {
+    pub fn lessons_to_skip(&self, score: f32) -> i32 {
+        self.levels
+            .iter()
+            .filter(|level| level.min_score <= score)
+            .map(|level| level.lessons_to_skip)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub struct PlacementAnswer {
+    pub exercise_id: i64,
+    pub body: Value,
+}
+
+/// Every student takes the placement test of a course once.
+#[derive(Clone)]
+pub struct PlacementAttempt {
+    pub course_id: i64,
+    pub user_id: i64,
+    pub score: f32,
+}
+
+pub struct PlacementAttemptForCreate {
+    pub course_id: i64,
+    pub user_id: i64,
+    pub score: f32,
+    pub skipped_lesson_ids: Vec<i64>,
+}
+
+pub struct PlacementResult {
+    pub score: f32,
+    pub skipped_lesson_ids: Vec<i64>,
+}
@@ -0,0 +1,50 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReadinessIssueKind {
+    MissingDescription,
+    MissingImage,
+    NoLessons,
+    /// A lesson with neither content blocks nor exercises.
+    EmptyLesson,
+    /// An exercise the current validator rules reject, e.g. created before the rules changed.
+    InvalidExercise,
+    EmptySection,
+}
+
+impl ReadinessIssueKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ReadinessIssueKind::MissingDescription => "missing_description",
+            ReadinessIssueKind::MissingImage => "missing_image",
+            ReadinessIssueKind::NoLessons => "no_lessons",
+            ReadinessIssueKind::EmptyLesson => "empty_lesson",
+            ReadinessIssueKind::InvalidExercise => "invalid_exercise",
+            ReadinessIssueKind::EmptySection => "empty_section",
+        }
+    }
+
+    /// Blocking issues stop the course from being published, the rest are warnings.
+    pub fn is_blocking(&self) -> bool {
+        !matches!(self, ReadinessIssueKind::MissingImage | ReadinessIssueKind::EmptySection)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReadinessIssue {
+    pub kind: ReadinessIssueKind,
+    pub section_id: Option<i64>,
+    pub lesson_id: Option<i64>,
+    pub exercise_id: Option<i64>,
+    pub message: String,
+}
+
+pub struct PublishReadiness {
+    pub course_id: i64,
+    pub errors: Vec<ReadinessIssue>,
+    pub warnings: Vec<ReadinessIssue>,
+}
+
+impl PublishReadiness {
+    pub fn is_ready(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
@@ -0,0 +1,32 @@
+#[derive(Clone)]
+pub struct Section {
+    pub id: i64,
+    pub course_id: i64,
+    pub title: String,
+    pub section_order: i32,
+}
+
+pub struct SectionForCreate {
+    pub course_id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+pub struct SectionForCreateCommand {
+    pub course_id: i64,
+    pub title: String,
+    pub description: String,
+    pub order: i32,
+}
+
+pub struct SectionForUpdate {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SectionForChangeOrder {
+    pub id: i64,
+    pub order: i32,
+}
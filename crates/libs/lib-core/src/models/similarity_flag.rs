@@ -0,0 +1,33 @@
+use super::exercise_completion::ExerciseCompletion;
+
+/// Pair of attempts at the same exercise whose conspects are suspiciously alike.
+#[derive(Clone)]
+pub struct SimilarityFlag {
+    pub id: i64,
+    pub exercise_id: i64,
+    pub first_exercise_completion_id: i64,
+    pub second_exercise_completion_id: i64,
+    /// Similarity in `[0, 1]`.
+    pub score: f32,
+}
+
+pub struct SimilarityFlagForCreate {
+    pub exercise_id: i64,
+    pub first_exercise_completion_id: i64,
+    pub second_exercise_completion_id: i64,
+    pub score: f32,
+}
+
+/// Flagged pair with both attempts, for a side-by-side comparison.
+pub struct SimilarityComparison {
+    pub id: i64,
+    pub score: f32,
+    pub first: ExerciseCompletion,
+    pub second: ExerciseCompletion,
+}
+
+pub struct SimilarityReport {
+    pub flagged_pairs: i32,
+    /// Exercises that could not be checked, the others are checked anyway.
+    pub failed_exercise_ids: Vec<i64>,
+}
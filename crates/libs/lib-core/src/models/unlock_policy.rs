@@ -0,0 +1,84 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::interactors::error::{CoreError, CourseError};
+
+const SECONDS_IN_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Display, Debug, Clone, PartialEq, Eq)]
+pub enum UnlockPolicyKind {
+    /// A lesson or exercise opens once the previous one is completed.
+    Sequential,
+    /// Every lesson and exercise is open from the start.
+    Free,
+    /// Lessons open on their release date regardless of the progress.
+    Drip,
+}
+
+impl TryFrom<String> for UnlockPolicyKind {
+    type Error = CoreError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Sequential" => Ok(Self::Sequential),
+            "Free" => Ok(Self::Free),
+            "Drip" => Ok(Self::Drip),
+            policy => Err(CourseError::UnlockPolicyDoesNotExist { policy: policy.to_string() }.into())
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum LessonRelease {
+    DaysAfterEnrollment { days: i32 },
+    /// Unix timestamp
+    Date { date: i64 },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DripRelease {
+    pub lesson_id: i64,
+    pub release: LessonRelease,
+}
+
+#[derive(Clone)]
+pub struct UnlockPolicy {
+    pub course_id: i64,
+    pub kind: UnlockPolicyKind,
+    /// Lessons missing here are released right away. Only used by `Drip`.
+    pub drip_releases: Vec<DripRelease>,
+}
+
+impl UnlockPolicy {
+    /// Policy of a course that hasn't configured one.
+    pub fn sequential(course_id: i64) -> Self {
+        Self {
+            course_id,
+            kind: UnlockPolicyKind::Sequential,
+            drip_releases: Vec::new(),
+        }
+    }
+
+    /// When the lesson opens for a student registered at `date_registered`.
+    pub fn release_date(&self, lesson_id: i64, date_registered: i64) -> Option<i64> {
+        if self.kind.ne(&UnlockPolicyKind::Drip) {
+            return None;
+        }
+
+        let drip_release = self.drip_releases
+            .iter()
+            .find(|drip_release| drip_release.lesson_id == lesson_id)?;
+
+        match drip_release.release {
+            LessonRelease::DaysAfterEnrollment { days } => Some(date_registered + days as i64 * SECONDS_IN_DAY),
+            LessonRelease::Date { date } => Some(date),
+        }
+    }
+}
+
+/// A lesson the student can't start yet.
+pub struct LessonLock {
+    pub lesson_id: i64,
+    /// Release date of a drip lesson, `None` while it waits for the previous lesson.
+    pub locked_until: Option<i64>,
+}
@@ -0,0 +1,129 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use lib_core::{ctx::Ctx, interactors::{creator::attachment::CreatorAttachmentInteractor, error::{CoreError, LessonError}, student::attachment::StudentAttachmentInteractor}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{attachment::{Attachment, AttachmentForCreate, MAX_ATTACHMENT_SIZE}, course::CourseState, lesson::Lesson}};
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+const STUDENT_ID: i64 = 2;
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    Arc::new(
+        CommandRepositoryManagerMock::new(lessons)
+            .with_courses(vec![course(1, CourseState::Draft)])
+    )
+}
+
+fn attachment_for_create(file_name: &str, content_type: &str) -> AttachmentForCreate {
+    AttachmentForCreate { 
+        lesson_id: 1, 
+        file_name: file_name.to_string(), 
+        content_type: content_type.to_string(),
+    }
+}
+
+async fn get_attachment(repository_manager: &CommandRepositoryManagerMock, attachment_id: i64) -> Attachment {
+    repository_manager
+        .get_lesson_repository()
+        .get_attachment(&Ctx::root_ctx(), attachment_id)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_upload_limits() {
+    let interactor = CreatorAttachmentInteractor::new(repository_manager());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = interactor.upload_attachment(&ctx, attachment_for_create("setup.exe", "application/x-msdownload"), b"MZ").await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::AttachmentTypeNotAllowed { .. }))));
+
+    let result = interactor.upload_attachment(&ctx, attachment_for_create("slides.pdf", "application/pdf"), b"").await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::AttachmentIsEmpty))));
+
+    let too_large = vec![0; MAX_ATTACHMENT_SIZE + 1];
+    let result = interactor.upload_attachment(&ctx, attachment_for_create("slides.pdf", "application/pdf"), &too_large).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::AttachmentTooLarge { .. }))));
+
+    // An executable renamed to a PDF.
+    let result = interactor.upload_attachment(&ctx, attachment_for_create("slides.pdf", "application/pdf"), b"MZ").await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::AttachmentContentDoesNotMatchType { .. }))));
+
+    let docx = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+    interactor.upload_attachment(&ctx, attachment_for_create("notes.docx", docx), b"PK\x03\x04").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_upload_and_delete() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorAttachmentInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let first_id = interactor.upload_attachment(&ctx, attachment_for_create("../../slides.pdf", "application/pdf"), b"%PDF-1.").await.unwrap();
+    let second_id = interactor.upload_attachment(&ctx, attachment_for_create("slides.pdf", "application/pdf"), b"%PDF-2.").await.unwrap();
+
+    let first = get_attachment(&repository_manager, first_id).await;
+    let second = get_attachment(&repository_manager, second_id).await;
+
+    // Same name, same moment, still two files.
+    assert_eq!(first.file_name, "slides.pdf");
+    assert_ne!(first.storage_key, second.storage_key);
+    assert!(first.storage_key.starts_with("private/attachments/"));
+    assert!(first.storage_key.ends_with(".pdf"));
+    assert_eq!(first.size, 7);
+
+    interactor.delete_attachment(&ctx, first_id).await.unwrap();
+
+    {
+        let files = repository_manager.file_storage.files.lock().unwrap();
+        assert!(!files.contains_key(&first.storage_key));
+        assert_eq!(files.get(&second.storage_key), Some(&(b"%PDF-2.".to_vec(), "application/pdf".to_string())));
+    }
+
+    // The row is gone, the file left behind is for the upload collector.
+    repository_manager.file_storage.fail_deletes.store(true, Ordering::SeqCst);
+    interactor.delete_attachment(&ctx, second_id).await.unwrap();
+
+    let attachments = repository_manager.get_lesson_repository().get_course_attachments(&ctx, 1).await.unwrap();
+    assert!(attachments.is_empty());
+    assert!(repository_manager.file_storage.files.lock().unwrap().contains_key(&second.storage_key));
+}
+
+#[tokio::test]
+async fn test_signed_download_link() {
+    let repository_manager = repository_manager();
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let creator_interactor = CreatorAttachmentInteractor::new(repository_manager.clone());
+    for file_name in ["slides-1.pdf", "slides-2.pdf"] {
+        creator_interactor.upload_attachment(&ctx, attachment_for_create(file_name, "application/pdf"), b"%PDF-1.").await.unwrap();
+    }
+
+    let interactor = StudentAttachmentInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let link = interactor.get_download_link(&ctx, 1).await.unwrap();
+
+    let file = interactor.download_attachment(&link.token).await.unwrap();
+    assert_eq!(file.file_name, "slides-1.pdf");
+    assert_eq!(file.content_type, "application/pdf");
+    assert_eq!(file.data, b"%PDF-1.");
+
+    // The signature covers the attachment and the user the link was issued to.
+    let (_, rest) = link.token.split_once('.').unwrap();
+    for ident in ["attachment:2:2", "attachment:1:1"] {
+        let forged_token = format!("{}.{}", lib_utils::b64::b64u_encode(ident), rest);
+        let result = interactor.download_attachment(&forged_token).await;
+        assert!(matches!(result, Err(CoreError::LessonError(LessonError::InvalidAttachmentLink))));
+    }
+
+    let result = interactor.download_attachment("not-a-token").await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::InvalidAttachmentLink))));
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}};
+
+use async_trait::async_trait;
+use lib_utils::time::now_utc_sec;
+use lib_core::{interactors::error::CoreError, interfaces::file_storage::{FileStorageResult, IFileStorage}, models::file::{StoredFile, StoredFileInfo}};
+
+type Files = HashMap<String, (Vec<u8>, String)>;
+
+/// Files in memory, keyed the same way as the real backends.
+#[derive(Clone, Default)]
+pub struct FileStorageMock {
+    pub files: Arc<Mutex<Files>>,
+    pub modified_at: Arc<Mutex<HashMap<String, i64>>>,
+    pub fail_deletes: Arc<AtomicBool>,
+}
+
+impl FileStorageMock {
+    /// Moves every file out of the grace period of the upload collector.
+    pub fn age_files(&self) {
+        for modified_at in self.modified_at.lock().unwrap().values_mut() {
+            *modified_at = 0;
+        }
+    }
+}
+
+#[async_trait]
+impl IFileStorage for FileStorageMock {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> FileStorageResult<()> {
+        self.files.lock().unwrap().insert(key.to_string(), (data, content_type.to_string()));
+        self.modified_at.lock().unwrap().insert(key.to_string(), now_utc_sec());
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> FileStorageResult<StoredFile> {
+        let (data, content_type) = self.files
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or(CoreError::FileStorageError { key: key.to_string(), description: "not found".to_string() })?;
+
+        Ok(StoredFile { data, content_type })
+    }
+
+    async fn delete(&self, key: &str) -> FileStorageResult<()> {
+        if self.fail_deletes.load(Ordering::SeqCst) {
+            return Err(CoreError::FileStorageError { key: key.to_string(), description: "unavailable".to_string() });
+        }
+
+        self.files.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> FileStorageResult<Vec<StoredFileInfo>> {
+        let modified_at = self.modified_at.lock().unwrap();
+
+        let result = self.files
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|key| key.starts_with(&format!("{prefix}/")))
+            .map(|key| StoredFileInfo { key: key.clone(), modified_at: modified_at.get(key).copied().unwrap_or_default() })
+            .collect();
+
+        Ok(result)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("mock/{key}")
+    }
+
+    fn key_from_public_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix("mock/").map(ToString::to_string)
+    }
+}
@@ -0,0 +1,52 @@
+use lib_core::models::{course::{Course, CourseState}, exercise::{Exercise, ExerciseDifficulty, ExerciseType}};
+use serde_json::{json, Value};
+
+/// A course that passes the publish checklist, tests override the fields they care about
+/// with the struct update syntax.
+pub fn course(id: i64, state: CourseState) -> Course {
+    Course {
+        id,
+        title: format!("Course {id}"),
+        description: "Description".to_string(),
+        course_type: "Programming".to_string(),
+        price: 0.0,
+        color: "indigo".to_string(),
+        published_date: None,
+        img_url: None,
+        img_variants: None,
+        state,
+        scheduled_publish_date: None,
+        scheduled_archive_date: None,
+    }
+}
+
+/// A three header conspect, used both as an answer and as a student's solution.
+pub fn conspect() -> Value {
+    json!({
+        "nodes": [
+            { "id": "a", "x": 0, "y": 0, "node_type": "Header", "body": { "header": "a" } },
+            { "id": "b", "x": 0, "y": 100, "node_type": "Header", "body": { "header": "b" } },
+            { "id": "c", "x": 0, "y": 200, "node_type": "Header", "body": { "header": "c" } },
+        ],
+        "connections": [{ "from": "a", "to": "b" }, { "from": "b", "to": "c" }, { "from": "a", "to": "c" }],
+    })
+}
+
+/// A conspect exercise answered with [`conspect`] that starts from an empty body.
+pub fn exercise(lesson_id: i64) -> Exercise {
+    Exercise {
+        lesson_id,
+        title: "Exercise".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: conspect(),
+        exercise_body: json!({ "nodes": [], "connections": [] }),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    }
+}
@@ -1,2 +1,6 @@
+#![allow(dead_code)]
+
 pub mod repository;
-pub mod repository_manager;
\ No newline at end of file
+pub mod repository_manager;
+pub mod file_storage;
+pub mod fixtures;
\ No newline at end of file
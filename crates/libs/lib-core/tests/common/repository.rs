@@ -1,27 +1,432 @@
-use std::sync::{Arc, Mutex};
+use std::{collections::HashSet, sync::{Arc, Mutex}};
 
 use async_trait::async_trait;
-use lib_core::{ctx::Ctx, interactors::error::{CoreError, LessonError}, interfaces::{course::{CourseResult, ICourseCommandRepository}, lesson::{ILessonCommandRepository, LessonResult}, user::{IUserCommandRepository, UserResult}}, models::{course::{Course, CourseForCreate, CourseForUpdateCommand, UserCourse, UserCourseRole}, lesson::{Lesson, LessonForChangeOreder, LessonForCreateCommand, LessonForUpdate}, lesson_progress::LessonProgress, user::{User, UserForCreate, UserForUpdate}}};
+use uuid::Uuid;
+use lib_utils::time::now_utc_sec;
+use lib_core::{ctx::Ctx, interactors::error::{CoreError, CourseError, LessonError, LessonProgressError}, interfaces::{course::{CourseResult, ICourseCommandRepository}, exercise::{ExerciseResult, IExerciseCommandRepository}, lesson::{ILessonCommandRepository, LessonResult}, user::{IUserCommandRepository, UserResult}}, models::{course::{Course, CourseForCreate, CourseForUpdateCommand, CourseSchedule, CourseState, UserCourse, UserCourseRole}, course_revision::{CourseRevision, StagedEdit}, course_transfer::{CourseTransfer, CourseTransferForCreate, CourseTransferState}, exercise::{Exercise, ExerciseForChangeOrder, ExerciseForCreateCommand, ExerciseForUpdate}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForReviewCommand, ExerciseCompletionForUpdate, ExerciseCompletionState}, lesson::{Lesson, LessonForChangeOreder, LessonForCreateCommand, LessonForUpdate}, lesson_progress::{LessonCompletedExercises, LessonProgress, LessonProgressState, LessonProgressStateChange}, section::{Section, SectionForChangeOrder, SectionForCreateCommand, SectionForUpdate}, attachment::{Attachment, AttachmentForCreateCommand}, content_block::{ContentBlock, ContentBlockForChangeOrder, ContentBlockForCreateCommand, ContentBlockForUpdateCommand}, difficulty_calibration::{DifficultyChange, DifficultyChangeForCreate}, exam::{Exam, ExamForUpdate}, placement_test::{PlacementAttempt, PlacementAttemptForCreate, PlacementTest}, unlock_policy::UnlockPolicy, peer_review::{PeerReview, PeerReviewForCreate, PeerReviewForSubmitCommand, PeerReviewState}, similarity_flag::{SimilarityFlag, SimilarityFlagForCreate}, user::{User, UserForCreate, UserForUpdate}}};
 
 #[derive(Clone)]
-pub struct CourseCommandRepositoryMock;
+pub struct CourseCommandRepositoryMock {
+    pub courses: Arc<Mutex<Vec<Course>>>,
+    pub user_courses: Arc<Mutex<Vec<UserCourse>>>,
+    pub placement_tests: Arc<Mutex<Vec<PlacementTest>>>,
+    pub placement_attempts: Arc<Mutex<Vec<PlacementAttempt>>>,
+    pub unlock_policies: Arc<Mutex<Vec<UnlockPolicy>>>,
+    pub course_revisions: Arc<Mutex<Vec<CourseRevision>>>,
+    pub course_transfers: Arc<Mutex<Vec<CourseTransfer>>>,
+    /// Publishing a revision writes through the other repositories.
+    lesson_repository: LessonCommandRepositoryMock,
+    exercise_repository: ExerciseCommandRepositoryMock,
+}
+
+impl CourseCommandRepositoryMock {
+    pub fn new(lesson_repository: LessonCommandRepositoryMock, exercise_repository: ExerciseCommandRepositoryMock) -> Self {
+        Self {
+            courses: Arc::new(Mutex::new(Vec::new())),
+            user_courses: Arc::new(Mutex::new(Vec::new())),
+            placement_tests: Arc::new(Mutex::new(Vec::new())),
+            placement_attempts: Arc::new(Mutex::new(Vec::new())),
+            unlock_policies: Arc::new(Mutex::new(Vec::new())),
+            course_revisions: Arc::new(Mutex::new(Vec::new())),
+            course_transfers: Arc::new(Mutex::new(Vec::new())),
+            lesson_repository,
+            exercise_repository,
+        }
+    }
+}
 
 #[async_trait]
 impl ICourseCommandRepository for CourseCommandRepositoryMock {
-    async fn get_course(&self, _: &Ctx, _: i64) -> CourseResult<Course> { panic!() }
+    async fn get_course(&self, _: &Ctx, course_id: i64) -> CourseResult<Course> {
+        let result = self.courses
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|course| course.id == course_id)
+            .cloned()
+            .unwrap();
+
+        Ok(result)
+    }
     async fn create_draft(&self, _: &Ctx, _: CourseForCreate) -> CourseResult<i64> { panic!() }
-    async fn update_course(&self, _: &Ctx, _: CourseForUpdateCommand, _: i64) -> CourseResult<()> { panic!() }
-    async fn create_user_course(&self, _: &Ctx, _: UserCourse) -> CourseResult<()> { panic!() }
-    async fn get_user_course(&self, _: &Ctx, user_id: i64, course_id: i64) -> CourseResult<UserCourse> { 
-        let res = UserCourse { 
-            user_id, 
-            course_id, 
-            user_role: UserCourseRole::Creator
+    async fn get_course_img_urls(&self, _: &Ctx) -> CourseResult<Vec<String>> {
+        let mut result = Vec::new();
+
+        for course in self.courses.lock().unwrap().iter() {
+            result.extend(course.img_url.clone());
+            if let Some(img_variants) = &course.img_variants {
+                result.extend(img_variants.urls().into_iter().cloned());
+            }
+        }
+
+        Ok(result)
+    }
+    async fn update_course(&self, _: &Ctx, course_for_u: CourseForUpdateCommand, course_id: i64) -> CourseResult<()> {
+        let mut courses = self.courses.lock().unwrap();
+        let course = courses.iter_mut().find(|course| course.id == course_id).unwrap();
+
+        if let Some(title) = course_for_u.title { course.title = title; }
+        if let Some(description) = course_for_u.description { course.description = description; }
+        if let Some(course_type) = course_for_u.course_type { course.course_type = course_type; }
+        if let Some(price) = course_for_u.price { course.price = price; }
+        if let Some(color) = course_for_u.color { course.color = color; }
+        if let Some(img_url) = course_for_u.img_url { course.img_url = Some(img_url); }
+        if let Some(img_variants) = course_for_u.img_variants { course.img_variants = Some(img_variants); }
+        if let Some(published_date) = course_for_u.published_date { course.published_date = Some(published_date); }
+        if let Some(state) = course_for_u.state { course.state = state; }
+
+        Ok(())
+    }
+    async fn create_user_course(&self, _: &Ctx, user_course: UserCourse) -> CourseResult<()> {
+        self.user_courses.lock().unwrap().push(user_course);
+
+        Ok(())
+    }
+    async fn unpublish_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<i64> {
+        let students = self.get_course_students_count(ctx, course_id).await?;
+
+        if students == 0 {
+            let mut courses = self.courses.lock().unwrap();
+            let course = courses.iter_mut().find(|course| course.id == course_id).unwrap();
+            course.state = CourseState::Draft;
+
+            self.course_revisions.lock().unwrap().retain(|revision| revision.course_id != course_id);
+        }
+
+        Ok(students)
+    }
+    async fn delete_course(&self, _: &Ctx, course_id: i64) -> CourseResult<()> {
+        self.courses.lock().unwrap().retain(|course| course.id != course_id);
+        self.user_courses.lock().unwrap().retain(|user_course| user_course.course_id != course_id);
+
+        let mut course_transfers = self.course_transfers.lock().unwrap();
+        for transfer in course_transfers.iter_mut() {
+            if transfer.course_id == course_id && transfer.state == CourseTransferState::Pending {
+                transfer.state = CourseTransferState::Cancelled;
+                transfer.date_resolved = Some(now_utc_sec());
+            }
+        }
+
+        Ok(())
+    }
+    async fn update_course_schedule(&self, _: &Ctx, schedule: CourseSchedule) -> CourseResult<()> {
+        let mut courses = self.courses.lock().unwrap();
+        let course = courses.iter_mut().find(|course| course.id == schedule.course_id).unwrap();
+
+        course.scheduled_publish_date = schedule.publish_date;
+        course.scheduled_archive_date = schedule.archive_date;
+
+        Ok(())
+    }
+    async fn get_due_course_schedules(&self, _: &Ctx, now: i64) -> CourseResult<Vec<CourseSchedule>> {
+        let result = self.courses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|course| {
+                course.scheduled_publish_date.is_some_and(|date| date <= now)
+                    || course.scheduled_archive_date.is_some_and(|date| date <= now)
+            })
+            .map(|course| CourseSchedule {
+                course_id: course.id,
+                publish_date: course.scheduled_publish_date,
+                archive_date: course.scheduled_archive_date,
+            })
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_course_students_count(&self, _: &Ctx, course_id: i64) -> CourseResult<i64> {
+        let result = self.user_courses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|user_course| user_course.course_id == course_id && user_course.user_role == UserCourseRole::Student)
+            .count();
+
+        Ok(result as i64)
+    }
+    async fn get_user_course(&self, ctx: &Ctx, user_id: i64, course_id: i64) -> CourseResult<UserCourse> { 
+        self.get_user_course_optional(ctx, user_id, course_id)
+            .await?
+            .ok_or(CoreError::PermissionDenied)
+    }
+    /// Users missing from `user_courses` are creators, unless the course has its creator there.
+    async fn get_user_course_optional(&self, _: &Ctx, user_id: i64, course_id: i64) -> CourseResult<Option<UserCourse>> {
+        let user_courses = self.user_courses.lock().unwrap();
+
+        let user_course = user_courses
+            .iter()
+            .find(|user_course| user_course.user_id == user_id && user_course.course_id == course_id)
+            .cloned();
+
+        let has_creator = user_courses
+            .iter()
+            .any(|user_course| user_course.course_id == course_id && user_course.user_role == UserCourseRole::Creator);
+
+        let result = match user_course {
+            Some(user_course) => Some(user_course),
+            None if !has_creator => Some(UserCourse { 
+                user_id, 
+                course_id, 
+                user_role: UserCourseRole::Creator,
+                date_registered: 0,
+            }),
+            None => None,
+        };
+
+        Ok(result)
+    }
+    async fn delete_user_course(&self, _: &Ctx, user_id: i64, course_id: i64) -> CourseResult<()> {
+        self.user_courses
+            .lock()
+            .unwrap()
+            .retain(|user_course| user_course.user_id != user_id || user_course.course_id != course_id);
+
+        Ok(())
+    }
+    async fn get_course_staff(&self, _: &Ctx, course_id: i64) -> CourseResult<Vec<UserCourse>> {
+        let result = self.user_courses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|user_course| user_course.course_id == course_id && user_course.user_role.is_staff())
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn update_user_course_role(&self, _: &Ctx, user_id: i64, course_id: i64, user_role: UserCourseRole) -> CourseResult<()> {
+        let mut user_courses = self.user_courses.lock().unwrap();
+        let user_course = user_courses
+            .iter_mut()
+            .find(|user_course| user_course.user_id == user_id && user_course.course_id == course_id)
+            .unwrap();
+
+        user_course.user_role = user_role;
+
+        Ok(())
+    }
+    async fn get_course_transfer(&self, _: &Ctx, transfer_id: i64) -> CourseResult<CourseTransfer> {
+        let result = self.course_transfers
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|transfer| transfer.id == transfer_id)
+            .cloned()
+            .unwrap();
+
+        Ok(result)
+    }
+    async fn get_course_transfers(&self, _: &Ctx, course_id: i64) -> CourseResult<Vec<CourseTransfer>> {
+        let result = self.course_transfers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|transfer| transfer.course_id == course_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_incoming_course_transfers(&self, _: &Ctx, user_id: i64) -> CourseResult<Vec<CourseTransfer>> {
+        let result = self.course_transfers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|transfer| transfer.to_user_id == user_id && transfer.state == CourseTransferState::Pending)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn create_course_transfer(&self, _: &Ctx, transfer_for_c: CourseTransferForCreate) -> CourseResult<i64> {
+        let mut course_transfers = self.course_transfers.lock().unwrap();
+        let id = course_transfers.len() as i64 + 1;
+
+        course_transfers.push(CourseTransfer {
+            id,
+            course_id: transfer_for_c.course_id,
+            from_user_id: transfer_for_c.from_user_id,
+            to_user_id: transfer_for_c.to_user_id,
+            state: CourseTransferState::Pending,
+            forced: transfer_for_c.forced,
+            date_proposed: 0,
+            date_resolved: None,
+        });
+
+        Ok(id)
+    }
+    async fn resolve_course_transfer(&self, _: &Ctx, transfer_id: i64, state: CourseTransferState, date_resolved: i64) -> CourseResult<()> {
+        let mut course_transfers = self.course_transfers.lock().unwrap();
+        let transfer = course_transfers.iter_mut().find(|transfer| transfer.id == transfer_id).unwrap();
+
+        if transfer.state != CourseTransferState::Pending {
+            return Err(CourseError::CourseTransferIsNotPending { transfer_id }.into());
+        }
+
+        transfer.state = state;
+        transfer.date_resolved = Some(date_resolved);
+
+        Ok(())
+    }
+    async fn complete_course_transfer(&self, ctx: &Ctx, transfer: CourseTransfer, date_resolved: i64) -> CourseResult<()> {
+        self.resolve_course_transfer(ctx, transfer.id, CourseTransferState::Accepted, date_resolved).await?;
+
+        let mut user_courses = self.user_courses.lock().unwrap();
+        let was_enrolled = user_courses.iter().any(|user_course| user_course.user_id == transfer.to_user_id && user_course.course_id == transfer.course_id);
+        user_courses.retain(|user_course| user_course.user_id != transfer.to_user_id || user_course.course_id != transfer.course_id);
+
+        if was_enrolled {
+            let lesson_ids: Vec<i64> = self.lesson_repository.lessons
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|lesson| lesson.course_id == transfer.course_id)
+                .map(|lesson| lesson.id)
+                .collect();
+            let exercise_ids: Vec<i64> = self.exercise_repository.exercises
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, exercise)| lesson_ids.contains(&exercise.lesson_id))
+                .map(|(id, _)| *id)
+                .collect();
+
+            self.lesson_repository.lesson_progresses
+                .lock()
+                .unwrap()
+                .retain(|progress| progress.user_id != transfer.to_user_id || !lesson_ids.contains(&progress.lesson_id));
+            self.exercise_repository.exercise_completions
+                .lock()
+                .unwrap()
+                .retain(|ex_comp| ex_comp.user_id != transfer.to_user_id || !exercise_ids.contains(&ex_comp.exercise_id));
+        }
+
+        let creator = user_courses
+            .iter_mut()
+            .find(|user_course| user_course.user_id == transfer.from_user_id && user_course.course_id == transfer.course_id)
+            .unwrap();
+        creator.user_id = transfer.to_user_id;
+
+        Ok(())
+    }
+    async fn force_course_transfer(&self, ctx: &Ctx, transfer_for_c: CourseTransferForCreate, date_resolved: i64) -> CourseResult<()> {
+        let transfer_id = self.create_course_transfer(ctx, transfer_for_c).await?;
+        let transfer = self.get_course_transfer(ctx, transfer_id).await?;
+
+        self.complete_course_transfer(ctx, transfer, date_resolved).await
+    }
+    async fn get_placement_test(&self, _: &Ctx, course_id: i64) -> CourseResult<Option<PlacementTest>> {
+        let result = self.placement_tests
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|placement_test| placement_test.course_id == course_id)
+            .cloned();
+
+        Ok(result)
+    }
+    async fn save_placement_test(&self, _: &Ctx, _: PlacementTest) -> CourseResult<()> { panic!() }
+    async fn get_placement_attempt(&self, _: &Ctx, course_id: i64, user_id: i64) -> CourseResult<Option<PlacementAttempt>> {
+        let result = self.placement_attempts
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|attempt| attempt.course_id == course_id && attempt.user_id == user_id)
+            .cloned();
+
+        Ok(result)
+    }
+    async fn create_placement_attempt(&self, _: &Ctx, attempt_for_c: PlacementAttemptForCreate) -> CourseResult<()> {
+        self.placement_attempts.lock().unwrap().push(PlacementAttempt {
+            course_id: attempt_for_c.course_id,
+            user_id: attempt_for_c.user_id,
+            score: attempt_for_c.score,
+        });
+
+        let mut lesson_progresses = self.lesson_repository.lesson_progresses.lock().unwrap();
+
+        for lesson_id in attempt_for_c.skipped_lesson_ids {
+            lesson_progresses.push(LessonProgress {
+                user_id: attempt_for_c.user_id,
+                lesson_id,
+                date_started: 0,
+                date_complete: Some(0),
+                state: LessonProgressState::Done,
+                skipped_by_placement: true,
+                date_paused: None,
+            });
+        }
+
+        Ok(())
+    }
+    async fn get_unlock_policy(&self, _: &Ctx, course_id: i64) -> CourseResult<Option<UnlockPolicy>> {
+        let result = self.unlock_policies
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|unlock_policy| unlock_policy.course_id == course_id)
+            .cloned();
+
+        Ok(result)
+    }
+    async fn save_unlock_policy(&self, _: &Ctx, _: UnlockPolicy) -> CourseResult<()> { panic!() }
+    async fn get_course_revision(&self, _: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>> {
+        let result = self.course_revisions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|revision| revision.course_id == course_id)
+            .cloned();
+
+        Ok(result)
+    }
+    async fn stage_course_edit(&self, _: &Ctx, course_id: i64, edit: StagedEdit) -> CourseResult<()> {
+        let mut course_revisions = self.course_revisions.lock().unwrap();
+
+        match course_revisions.iter_mut().find(|revision| revision.course_id == course_id) {
+            Some(revision) => revision.stage(edit),
+            None => {
+                let mut revision = CourseRevision::new(course_id);
+                revision.stage(edit);
+                course_revisions.push(revision);
+            },
+        }
+
+        Ok(())
+    }
+    async fn delete_course_revision(&self, _: &Ctx, course_id: i64) -> CourseResult<()> {
+        self.course_revisions.lock().unwrap().retain(|revision| revision.course_id != course_id);
+
+        Ok(())
+    }
+    async fn publish_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>> {
+        let Some(revision) = self.get_course_revision(ctx, course_id).await? else {
+            return Ok(None);
         };
-        Ok(res)
+
+        for edit in revision.edits.clone() {
+            match edit {
+                StagedEdit::Lesson(edit) => self.lesson_repository.update_lesson(ctx, edit.into()).await?,
+                StagedEdit::Exercise(edit) => {
+                    if edit.retake {
+                        self.exercise_repository.remove_exercise_completions(ctx, edit.exercise_id).await?;
+                    }
+
+                    self.exercise_repository.update(ctx, edit.into()).await?
+                },
+                StagedEdit::ContentBlock(edit) => self.lesson_repository.update_content_block(ctx, edit.into()).await?,
+                StagedEdit::Section(edit) => self.lesson_repository.update_section(ctx, edit.into()).await?,
+                StagedEdit::Exam(edit) if edit.removed => self.lesson_repository.delete_exam(ctx, edit.lesson_id).await?,
+                StagedEdit::Exam(edit) => self.lesson_repository.save_exam(ctx, edit.into()).await?,
+            }
+        }
+
+        self.delete_course_revision(ctx, course_id).await?;
+
+        Ok(Some(revision))
     }
-    async fn get_user_course_optional(&self, _: &Ctx, _: i64, _: i64) -> CourseResult<Option<UserCourse>> { panic!() }
-    async fn delete_user_course(&self, _: &Ctx, _: i64, _: i64) -> CourseResult<()> { panic!() }
 }
 
 #[derive(Clone)]
@@ -29,7 +434,17 @@ pub struct UserCommandRepositoryMock;
 
 #[async_trait]
 impl IUserCommandRepository for UserCommandRepositoryMock {
-    async fn get_user(&self, _: &Ctx, _: i64) -> UserResult<User> { panic!() }
+    async fn get_user(&self, _: &Ctx, user_id: i64) -> UserResult<User> {
+        let user = User {
+            id: user_id,
+            username: format!("user{user_id}"),
+            pwd: String::new(),
+            pwd_salt: Uuid::nil(),
+            token_salt: Uuid::from_u128(user_id as u128),
+        };
+
+        Ok(user)
+    }
     async fn create_user(&self, _: &Ctx, _: UserForCreate) -> UserResult<i64> { panic!() }
     async fn update_user(&self, _: &Ctx, _: UserForUpdate) -> UserResult<()> { panic!() }
     async fn update_pwd(&self, _: &Ctx, _: i64, _: String) -> UserResult<()> { panic!() }
@@ -38,12 +453,25 @@ impl IUserCommandRepository for UserCommandRepositoryMock {
 #[derive(Clone)]
 pub struct LessonCommandRepositoryMock {
     pub lessons: Arc<Mutex<Vec<Lesson>>>,
+    pub lesson_progresses: Arc<Mutex<Vec<LessonProgress>>>,
+    pub exams: Arc<Mutex<Vec<Exam>>>,
+    pub sections: Arc<Mutex<Vec<Section>>>,
+    pub content_blocks: Arc<Mutex<Vec<ContentBlock>>>,
+    pub attachments: Arc<Mutex<Vec<Attachment>>>,
+    /// Releasing exam results and resuming a lesson write through to the attempts.
+    exercise_repository: ExerciseCommandRepositoryMock,
 }
 
 impl LessonCommandRepositoryMock {
-    pub fn new(lessons: Vec<Lesson>) -> Self {
+    pub fn new(lessons: Vec<Lesson>, exercise_repository: ExerciseCommandRepositoryMock) -> Self {
         Self {
             lessons: Arc::new(Mutex::new(lessons)),
+            lesson_progresses: Arc::new(Mutex::new(Vec::new())),
+            exams: Arc::new(Mutex::new(Vec::new())),
+            sections: Arc::new(Mutex::new(Vec::new())),
+            content_blocks: Arc::new(Mutex::new(Vec::new())),
+            attachments: Arc::new(Mutex::new(Vec::new())),
+            exercise_repository,
         }
     }
 }
@@ -72,8 +500,15 @@ impl ILessonCommandRepository for LessonCommandRepositoryMock {
         _: i64
     ) -> LessonResult<Vec<LessonForChangeOreder>> {
         let mut result = self.lessons.lock().unwrap().clone();
+        let sections = self.sections.lock().unwrap().clone();
 
-        result.sort_by(|a, b| a.lesson_order.cmp(&b.lesson_order));
+        result.sort_by_key(|a| {
+            let section_order = a.section_id.and_then(|section_id| {
+                sections.iter().find(|section| section.id == section_id).map(|section| section.section_order)
+            });
+
+            (section_order, a.lesson_order)
+        });
 
         let result = result.iter().map(|lesson| LessonForChangeOreder { 
             id: lesson.id, 
@@ -83,6 +518,39 @@ impl ILessonCommandRepository for LessonCommandRepositoryMock {
         Ok(result)
     }
 
+    async fn get_section_lessons_ordered(&self, _: &Ctx, _: i64, section_id: Option<i64>) -> LessonResult<Vec<LessonForChangeOreder>> {
+        let mut result: Vec<LessonForChangeOreder> = self.lessons
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|lesson| lesson.section_id == section_id)
+            .map(|lesson| LessonForChangeOreder { id: lesson.id, order: lesson.lesson_order })
+            .collect();
+
+        result.sort_by_key(|lesson| lesson.order);
+
+        Ok(result)
+    }
+
+    async fn update_lesson_section(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+        section_id: Option<i64>,
+        order: i32,
+        source_lessons_for_u_order: Vec<LessonForChangeOreder>,
+    ) -> LessonResult<()> {
+        {
+            let mut lessons = self.lessons.lock().unwrap();
+            let lesson = lessons.iter_mut().find(|lesson| lesson.id == lesson_id).unwrap();
+
+            lesson.section_id = section_id;
+            lesson.lesson_order = order;
+        }
+
+        self.update_lesson_orders(ctx, source_lessons_for_u_order).await
+    }
+
     async fn update_lesson_orders(
         &self, 
         _: &Ctx, 
@@ -109,19 +577,859 @@ impl ILessonCommandRepository for LessonCommandRepositoryMock {
         panic!()
     }
 
-    async fn delete_lesson(&self, _: &Ctx, _: i64) -> LessonResult<()> {
-        panic!()
+    async fn delete_lesson(&self, _: &Ctx, lesson_id: i64) -> LessonResult<()> {
+        self.lessons.lock().unwrap().retain(|lesson| lesson.id != lesson_id);
+        self.lesson_progresses.lock().unwrap().retain(|lesson_progress| lesson_progress.lesson_id != lesson_id);
+
+        Ok(())
     }
 
-    async fn update_lesson(&self, _: &Ctx, _: LessonForUpdate) -> LessonResult<()> {
-        panic!()
+    async fn update_lesson(&self, _: &Ctx, lesson_for_u: LessonForUpdate) -> LessonResult<()> {
+        let mut lessons = self.lessons.lock().unwrap();
+        let lesson = lessons
+            .iter_mut()
+            .find(|lesson| lesson.id == lesson_for_u.id)
+            .unwrap();
+
+        lesson.title = lesson_for_u.title;
+
+        Ok(())
     }
 
-    async fn create_lesson_progress(&self, _: &Ctx, _: i64, _: i64) -> LessonResult<()> {
-        panic!()
+    async fn create_lesson_progress(&self, _: &Ctx, lesson_id: i64, user_id: i64) -> LessonResult<()> {
+        self.lesson_progresses.lock().unwrap().push(LessonProgress {
+            user_id,
+            lesson_id,
+            date_started: 0,
+            date_complete: None,
+            state: LessonProgressState::InProgress,
+            skipped_by_placement: false,
+            date_paused: None,
+        });
+
+        Ok(())
     }
     
-    async fn get_lessons_progresses(&self, _: &Ctx, _: i64, _: i64) -> LessonResult<Vec<LessonProgress>> {
-        panic!()
+    async fn get_lessons_progresses(&self, _: &Ctx, _: i64, user_id: i64) -> LessonResult<Vec<LessonProgress>> {
+        let result = self.lesson_progresses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|lesson_progress| lesson_progress.user_id == user_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn get_course_lesson_progresses(&self, _: &Ctx, _: i64) -> LessonResult<Vec<LessonProgress>> {
+        let result = self.lesson_progresses
+            .lock()
+            .unwrap()
+            .clone();
+
+        Ok(result)
+    }
+
+    async fn update_lesson_progress_state(&self, _: &Ctx, state: LessonProgressState, lesson_id: i64, user_id: i64) -> LessonResult<()> {
+        let mut lesson_progresses = self.lesson_progresses.lock().unwrap();
+
+        let lesson_progress = lesson_progresses
+            .iter_mut()
+            .find(|lesson_progress| lesson_progress.lesson_id == lesson_id && lesson_progress.user_id == user_id)
+            .unwrap();
+
+        lesson_progress.state = state;
+
+        Ok(())
+    }
+
+    async fn update_lesson_progresses_states(&self, _: &Ctx, changes: Vec<LessonProgressStateChange>) -> LessonResult<()> {
+        let mut lesson_progresses = self.lesson_progresses.lock().unwrap();
+
+        for change in changes {
+            let lesson_progress = lesson_progresses
+                .iter_mut()
+                .find(|lesson_progress| lesson_progress.lesson_id == change.lesson_id && lesson_progress.user_id == change.user_id)
+                .unwrap();
+
+            if lesson_progress.state != change.previous_state {
+                continue;
+            }
+
+            if change.state != LessonProgressState::Pause {
+                lesson_progress.date_paused = None;
+            }
+
+            lesson_progress.state = change.state;
+        }
+
+        Ok(())
+    }
+
+    async fn pause_lesson_progress(&self, _: &Ctx, lesson_id: i64, user_id: i64, date_paused: i64) -> LessonResult<()> {
+        let mut lesson_progresses = self.lesson_progresses.lock().unwrap();
+
+        let lesson_progress = lesson_progresses
+            .iter_mut()
+            .find(|lesson_progress| lesson_progress.lesson_id == lesson_id && lesson_progress.user_id == user_id)
+            .unwrap();
+
+        lesson_progress.state = LessonProgressState::Pause;
+        lesson_progress.date_paused = Some(date_paused);
+
+        Ok(())
+    }
+
+    async fn resume_lesson_progress(&self, _: &Ctx, lesson_id: i64, user_id: i64, paused_seconds: i64) -> LessonResult<()> {
+        let mut lesson_progresses = self.lesson_progresses.lock().unwrap();
+
+        let lesson_progress = lesson_progresses
+            .iter_mut()
+            .find(|lesson_progress| lesson_progress.lesson_id == lesson_id && lesson_progress.user_id == user_id)
+            .unwrap();
+
+        if lesson_progress.state != LessonProgressState::Pause || lesson_progress.date_paused.is_none() {
+            return Err(LessonProgressError::IncorrectStateTransition {
+                from: lesson_progress.state.to_string(),
+                to: LessonProgressState::InProgress.to_string(),
+            }.into());
+        }
+
+        lesson_progress.state = LessonProgressState::InProgress;
+        lesson_progress.date_paused = None;
+
+        let exercise_ids: Vec<i64> = self.exercise_repository.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, exercise)| exercise.lesson_id == lesson_id)
+            .map(|(id, _)| *id)
+            .collect();
+
+        self.exercise_repository.exercise_completions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|ex_comp| ex_comp.user_id == user_id && ex_comp.state == ExerciseCompletionState::InProgress && exercise_ids.contains(&ex_comp.exercise_id))
+            .for_each(|ex_comp| ex_comp.paused_seconds += paused_seconds);
+
+        Ok(())
+    }
+
+    async fn get_section(&self, _: &Ctx, section_id: i64) -> LessonResult<Section> {
+        let section = self.sections
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|section| section.id == section_id)
+            .cloned()
+            .unwrap();
+
+        Ok(section)
+    }
+
+    async fn get_course_sections_ordered(&self, _: &Ctx, course_id: i64) -> LessonResult<Vec<SectionForChangeOrder>> {
+        let mut result: Vec<SectionForChangeOrder> = self.sections
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|section| section.course_id == course_id)
+            .map(|section| SectionForChangeOrder { id: section.id, order: section.section_order })
+            .collect();
+
+        result.sort_by_key(|section| section.order);
+
+        Ok(result)
+    }
+
+    async fn create_section(&self, _: &Ctx, section_for_c: SectionForCreateCommand) -> LessonResult<i64> {
+        let mut sections = self.sections.lock().unwrap();
+        let id = sections.iter().map(|section| section.id).max().unwrap_or(0) + 1;
+
+        sections.push(Section {
+            id,
+            course_id: section_for_c.course_id,
+            title: section_for_c.title,
+            section_order: section_for_c.order,
+        });
+
+        Ok(id)
+    }
+
+    async fn update_section(&self, _: &Ctx, section_for_u: SectionForUpdate) -> LessonResult<()> {
+        let mut sections = self.sections.lock().unwrap();
+        let section = sections.iter_mut().find(|section| section.id == section_for_u.id).unwrap();
+        section.title = section_for_u.title;
+
+        Ok(())
+    }
+
+    async fn delete_section(&self, _: &Ctx, section_id: i64) -> LessonResult<()> {
+        self.sections.lock().unwrap().retain(|section| section.id != section_id);
+
+        Ok(())
+    }
+
+    async fn update_section_orders(&self, _: &Ctx, sections_for_u_order: Vec<SectionForChangeOrder>) -> LessonResult<()> {
+        let mut sections = self.sections.lock().unwrap();
+
+        for section_order in &sections_for_u_order {
+            if let Some(section) = sections.iter_mut().find(|section| section.id == section_order.id) {
+                section.section_order = section_order.order;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_content_block(&self, _: &Ctx, content_block_id: i64) -> LessonResult<ContentBlock> {
+        let content_block = self.content_blocks
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|content_block| content_block.id == content_block_id)
+            .cloned()
+            .unwrap();
+
+        Ok(content_block)
+    }
+
+    async fn get_lesson_content_blocks_ordered(&self, _: &Ctx, lesson_id: i64) -> LessonResult<Vec<ContentBlockForChangeOrder>> {
+        let mut result: Vec<ContentBlockForChangeOrder> = self.content_blocks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|content_block| content_block.lesson_id == lesson_id)
+            .map(|content_block| ContentBlockForChangeOrder { id: content_block.id, order: content_block.block_order })
+            .collect();
+
+        result.sort_by_key(|content_block| content_block.order);
+
+        Ok(result)
+    }
+
+    async fn create_content_block(&self, _: &Ctx, content_block_for_c: ContentBlockForCreateCommand) -> LessonResult<i64> {
+        let mut content_blocks = self.content_blocks.lock().unwrap();
+        let id = content_blocks.iter().map(|content_block| content_block.id).max().unwrap_or(0) + 1;
+
+        content_blocks.push(ContentBlock {
+            id,
+            lesson_id: content_block_for_c.lesson_id,
+            block_type: content_block_for_c.block_type,
+            body: content_block_for_c.body,
+            rendered_html: content_block_for_c.rendered_html,
+            block_order: content_block_for_c.order,
+        });
+
+        Ok(id)
+    }
+
+    async fn update_content_block(&self, _: &Ctx, content_block_for_u: ContentBlockForUpdateCommand) -> LessonResult<()> {
+        let mut content_blocks = self.content_blocks.lock().unwrap();
+        let content_block = content_blocks
+            .iter_mut()
+            .find(|content_block| content_block.id == content_block_for_u.id)
+            .unwrap();
+
+        content_block.body = content_block_for_u.body;
+        content_block.rendered_html = content_block_for_u.rendered_html;
+
+        Ok(())
+    }
+
+    async fn get_attachment(&self, _: &Ctx, attachment_id: i64) -> LessonResult<Attachment> {
+        let attachment = self.attachments
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|attachment| attachment.id == attachment_id)
+            .cloned()
+            .unwrap();
+
+        Ok(attachment)
+    }
+
+    async fn create_attachment(&self, _: &Ctx, attachment_for_c: AttachmentForCreateCommand) -> LessonResult<i64> {
+        let mut attachments = self.attachments.lock().unwrap();
+        let id = attachments.iter().map(|attachment| attachment.id).max().unwrap_or(0) + 1;
+
+        attachments.push(Attachment {
+            id,
+            lesson_id: attachment_for_c.lesson_id,
+            file_name: attachment_for_c.file_name,
+            content_type: attachment_for_c.content_type,
+            size: attachment_for_c.size,
+            storage_key: attachment_for_c.storage_key,
+        });
+
+        Ok(id)
+    }
+
+    async fn delete_attachment(&self, _: &Ctx, attachment_id: i64) -> LessonResult<()> {
+        self.attachments.lock().unwrap().retain(|attachment| attachment.id != attachment_id);
+
+        Ok(())
+    }
+
+    async fn get_course_attachments(&self, _: &Ctx, course_id: i64) -> LessonResult<Vec<Attachment>> {
+        let lesson_ids: Vec<i64> = self.lessons
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|lesson| lesson.course_id == course_id)
+            .map(|lesson| lesson.id)
+            .collect();
+
+        let result = self.attachments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|attachment| lesson_ids.contains(&attachment.lesson_id))
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn get_attachment_storage_keys(&self, _: &Ctx) -> LessonResult<Vec<String>> {
+        let result = self.attachments
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|attachment| attachment.storage_key.clone())
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn get_content_block_urls(&self, _: &Ctx) -> LessonResult<Vec<String>> {
+        let result = self.content_blocks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|content_block| content_block.body.get("url")?.as_str().map(ToString::to_string))
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn delete_content_block(&self, _: &Ctx, content_block_id: i64) -> LessonResult<()> {
+        self.content_blocks.lock().unwrap().retain(|content_block| content_block.id != content_block_id);
+
+        Ok(())
+    }
+
+    async fn update_content_block_orders(&self, _: &Ctx, content_blocks_for_u_order: Vec<ContentBlockForChangeOrder>) -> LessonResult<()> {
+        let mut content_blocks = self.content_blocks.lock().unwrap();
+
+        for content_block_order in &content_blocks_for_u_order {
+            if let Some(content_block) = content_blocks.iter_mut().find(|content_block| content_block.id == content_block_order.id) {
+                content_block.block_order = content_block_order.order;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_exam(&self, _: &Ctx, lesson_id: i64) -> LessonResult<Option<Exam>> {
+        let exam = self.exams
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|exam| exam.lesson_id == lesson_id)
+            .cloned();
+
+        Ok(exam)
+    }
+
+    async fn get_course_exams(&self, _: &Ctx, _: i64) -> LessonResult<Vec<Exam>> {
+        Ok(self.exams.lock().unwrap().clone())
+    }
+
+    async fn save_exam(&self, _: &Ctx, exam_for_u: ExamForUpdate) -> LessonResult<()> {
+        let mut exams = self.exams.lock().unwrap();
+
+        match exams.iter_mut().find(|exam| exam.lesson_id == exam_for_u.lesson_id) {
+            Some(exam) => {
+                exam.opens_at = exam_for_u.opens_at;
+                exam.closes_at = exam_for_u.closes_at;
+            },
+            None => exams.push(Exam {
+                lesson_id: exam_for_u.lesson_id,
+                opens_at: exam_for_u.opens_at,
+                closes_at: exam_for_u.closes_at,
+                results_released: false,
+            }),
+        }
+
+        Ok(())
+    }
+
+    async fn delete_exam(&self, _: &Ctx, lesson_id: i64) -> LessonResult<()> {
+        self.exams.lock().unwrap().retain(|exam| exam.lesson_id != lesson_id);
+
+        Ok(())
+    }
+
+    async fn submit_exam_attempt(&self, ctx: &Ctx, lesson_id: i64, ex_comp_id: i64) -> LessonResult<bool> {
+        let results_released = self.exams
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|exam| exam.lesson_id == lesson_id && exam.results_released);
+
+        if !results_released {
+            self.exercise_repository.update_exercise_completion_state(ctx, ex_comp_id, ExerciseCompletionState::Submitted).await?;
+        }
+
+        Ok(!results_released)
+    }
+
+    async fn release_exam_results(&self, ctx: &Ctx, lesson_id: i64, graded_attempts: Vec<ExerciseCompletionForCompleteCommand>) -> LessonResult<()> {
+        for ex_comp_for_u in graded_attempts {
+            self.exercise_repository.complete_exercise_completion(ctx, ex_comp_for_u).await?;
+        }
+
+        let mut exams = self.exams.lock().unwrap();
+        let exam = exams.iter_mut().find(|exam| exam.lesson_id == lesson_id).unwrap();
+
+        exam.results_released = true;
+
+        Ok(())
+    }
+
+    async fn get_overdue_exam_progresses(&self, _: &Ctx, now: i64) -> LessonResult<Vec<LessonProgress>> {
+        let exams = self.exams.lock().unwrap().clone();
+
+        let result = self.lesson_progresses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|lesson_progress| lesson_progress.state == LessonProgressState::InProgress)
+            .filter(|lesson_progress| exams.iter().any(|exam| exam.lesson_id == lesson_progress.lesson_id && exam.closes_at <= now))
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+
+}
+
+#[derive(Clone)]
+pub struct ExerciseCommandRepositoryMock {
+    pub exercises: Arc<Mutex<Vec<(i64, Exercise)>>>,
+    pub exercise_completions: Arc<Mutex<Vec<ExerciseCompletion>>>,
+    pub peer_reviews: Arc<Mutex<Vec<PeerReview>>>,
+    pub similarity_flags: Arc<Mutex<Vec<SimilarityFlag>>>,
+    pub difficulty_changes: Arc<Mutex<Vec<DifficultyChange>>>,
+}
+
+impl ExerciseCommandRepositoryMock {
+    pub fn new(exercises: Vec<(i64, Exercise)>, exercise_completions: Vec<ExerciseCompletion>) -> Self {
+        Self {
+            exercises: Arc::new(Mutex::new(exercises)),
+            exercise_completions: Arc::new(Mutex::new(exercise_completions)),
+            peer_reviews: Arc::new(Mutex::new(Vec::new())),
+            similarity_flags: Arc::new(Mutex::new(Vec::new())),
+            difficulty_changes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl IExerciseCommandRepository for ExerciseCommandRepositoryMock {
+    async fn get_lesson_exercises_ordered(&self, _: &Ctx, lesson_id: i64) -> ExerciseResult<Vec<ExerciseForChangeOrder>> {
+        let mut result: Vec<ExerciseForChangeOrder> = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, exercise)| exercise.lesson_id == lesson_id)
+            .map(|(id, exercise)| ExerciseForChangeOrder { id: *id, order: exercise.exercise_order })
+            .collect();
+
+        result.sort_by_key(|exercise| exercise.order);
+
+        Ok(result)
+    }
+    async fn get_exercise(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<Exercise> {
+        let exercise = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| *id == exercise_id)
+            .map(|(_, exercise)| exercise.clone())
+            .unwrap();
+
+        Ok(exercise)
+    }
+    async fn get_exercises(&self, _: &Ctx, exercise_ids: Vec<i64>) -> ExerciseResult<Vec<(i64, Exercise)>> {
+        let result = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _)| exercise_ids.contains(id))
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_course_exercises(&self, _: &Ctx, _: i64) -> ExerciseResult<Vec<(i64, Exercise)>> {
+        let mut exercises = self.exercises.lock().unwrap().clone();
+        exercises.sort_by_key(|(_, exercise)| exercise.exercise_order);
+
+        Ok(exercises)
+    }
+    async fn create(&self, _: &Ctx, exercise_c: ExerciseForCreateCommand) -> ExerciseResult<i64> {
+        let mut exercises = self.exercises.lock().unwrap();
+        let id = exercises.iter().map(|(id, _)| *id).max().unwrap_or(0) + 1;
+
+        let exercise = Exercise {
+            lesson_id: exercise_c.lesson_id,
+            title: exercise_c.title,
+            description: exercise_c.description,
+            exercise_type: exercise_c.exercise_type,
+            answer_body: exercise_c.answer_body,
+            exercise_body: exercise_c.exercise_body,
+            difficult: exercise_c.difficult,
+            time_to_complete: exercise_c.time_to_complete,
+            exercise_order: exercise_c.exercise_order,
+            manual_review: exercise_c.manual_review,
+            auto_difficulty: exercise_c.auto_difficulty,
+            peer_review_count: exercise_c.peer_review_count,
+            peer_review_rubric: exercise_c.peer_review_rubric,
+        };
+        exercises.push((id, exercise));
+
+        Ok(id)
+    }
+    async fn update(&self, _: &Ctx, exercise_for_u: ExerciseForUpdate) -> ExerciseResult<()> {
+        let mut exercises = self.exercises.lock().unwrap();
+        let (_, exercise) = exercises
+            .iter_mut()
+            .find(|(id, _)| *id == exercise_for_u.id)
+            .unwrap();
+
+        if let Some(title) = exercise_for_u.title { exercise.title = title; }
+        if let Some(description) = exercise_for_u.description { exercise.description = description; }
+        if let Some(exercise_type) = exercise_for_u.exercise_type { exercise.exercise_type = exercise_type; }
+        if let Some(answer_body) = exercise_for_u.answer_body { exercise.answer_body = answer_body; }
+        if let Some(exercise_body) = exercise_for_u.exercise_body { exercise.exercise_body = exercise_body; }
+        if let Some(difficult) = exercise_for_u.difficult { exercise.difficult = difficult; }
+        if let Some(time_to_complete) = exercise_for_u.time_to_complete { exercise.time_to_complete = Some(time_to_complete as i32); }
+        if let Some(manual_review) = exercise_for_u.manual_review { exercise.manual_review = manual_review; }
+        if let Some(auto_difficulty) = exercise_for_u.auto_difficulty { exercise.auto_difficulty = auto_difficulty; }
+        if let Some(peer_review_count) = exercise_for_u.peer_review_count { exercise.peer_review_count = peer_review_count; }
+        if let Some(peer_review_rubric) = exercise_for_u.peer_review_rubric { exercise.peer_review_rubric = peer_review_rubric; }
+
+        Ok(())
+    }
+    async fn update_exercise_orders(&self, _: &Ctx, _: Vec<ExerciseForChangeOrder>) -> ExerciseResult<()> { panic!() }
+    async fn get_exercise_user_completions(&self, _: &Ctx, user_id: i64, exercise_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        let result = self.exercise_completions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|ex_comp| ex_comp.exercise_id == exercise_id && ex_comp.user_id == user_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn create_exercise_completion(&self, _: &Ctx, ex_comp_for_c: ExerciseCompletionForCreate) -> ExerciseResult<i64> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let id = exercise_completions.iter().map(|ex_comp| ex_comp.id).max().unwrap_or(0) + 1;
+
+        exercise_completions.push(ExerciseCompletion {
+            id,
+            exercise_id: ex_comp_for_c.exercise_id,
+            user_id: ex_comp_for_c.user_id,
+            points_scored: None,
+            max_points: None,
+            number_of_attempts: ex_comp_for_c.number_of_attempts as i32,
+            date_started: ex_comp_for_c.date_started,
+            date_last_changes: None,
+            state: ExerciseCompletionState::InProgress,
+            body: None,
+            review_comment: None,
+            is_practice: ex_comp_for_c.is_practice,
+            paused_seconds: 0,
+        });
+
+        Ok(id)
+    }
+    async fn get_exercise_completion(&self, _: &Ctx, ex_comp_id: i64) -> ExerciseResult<ExerciseCompletion> {
+        let ex_comp = self.exercise_completions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|ex_comp| ex_comp.id == ex_comp_id)
+            .cloned()
+            .unwrap();
+
+        Ok(ex_comp)
+    }
+    async fn update_exercise_completion(&self, _: &Ctx, ex_comp_for_u: ExerciseCompletionForUpdate) -> ExerciseResult<()> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let ex_comp = exercise_completions.iter_mut().find(|ex_comp| ex_comp.id == ex_comp_for_u.id).unwrap();
+
+        ex_comp.body = Some(ex_comp_for_u.body);
+        ex_comp.date_last_changes = Some(ex_comp_for_u.date_last_changes);
+
+        Ok(())
+    }
+    async fn complete_exercise_completion(&self, _: &Ctx, ex_comp_for_u: ExerciseCompletionForCompleteCommand) -> ExerciseResult<()> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let ex_comp = exercise_completions.iter_mut().find(|ex_comp| ex_comp.id == ex_comp_for_u.id).unwrap();
+
+        ex_comp.points_scored = Some(ex_comp_for_u.points_scored);
+        ex_comp.max_points = Some(ex_comp_for_u.max_points);
+        ex_comp.state = ex_comp_for_u.state;
+
+        Ok(())
+    }
+    async fn get_uncompleted_exercises(&self, _: &Ctx) -> ExerciseResult<Vec<ExerciseCompletion>> { panic!() }
+    async fn get_number_of_lesson_completed_exercises(&self, _: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<i64> {
+        let lesson_exercises: Vec<i64> = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, exercise)| exercise.lesson_id == lesson_id)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let number_of_completed_exercises = lesson_exercises
+            .iter()
+            .filter(|exercise_id| {
+                self.exercise_completions
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|ex_comp| ex_comp.exercise_id == **exercise_id && ex_comp.user_id == user_id && !ex_comp.is_practice && ex_comp.state == ExerciseCompletionState::Succeeded)
+            })
+            .count();
+
+        Ok(number_of_completed_exercises as i64)
+    }
+    async fn get_course_completed_exercises(&self, ctx: &Ctx, _: i64) -> ExerciseResult<Vec<LessonCompletedExercises>> {
+        let lesson_users: HashSet<(i64, i64)> = {
+            let exercises = self.exercises.lock().unwrap();
+
+            self.exercise_completions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter_map(|ex_comp| {
+                    exercises
+                        .iter()
+                        .find(|(id, _)| *id == ex_comp.exercise_id)
+                        .map(|(_, exercise)| (exercise.lesson_id, ex_comp.user_id))
+                })
+                .collect()
+        };
+
+        let mut result = Vec::new();
+        for (lesson_id, user_id) in lesson_users {
+            let completed = self.get_number_of_lesson_completed_exercises(ctx, lesson_id, user_id).await?;
+
+            result.push(LessonCompletedExercises { lesson_id, user_id, completed });
+        }
+
+        Ok(result)
+    }
+    async fn remove_exercise_completions(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<()> {
+        self.exercise_completions.lock().unwrap().retain(|ex_comp| ex_comp.exercise_id != exercise_id);
+
+        Ok(())
+    }
+    async fn update_exercise_completion_state(&self, _: &Ctx, ex_comp_id: i64, state: ExerciseCompletionState) -> ExerciseResult<()> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let ex_comp = exercise_completions.iter_mut().find(|ex_comp| ex_comp.id == ex_comp_id).unwrap();
+
+        ex_comp.state = state;
+
+        Ok(())
+    }
+    async fn review_exercise_completion(&self, _: &Ctx, ex_comp_for_r: ExerciseCompletionForReviewCommand) -> ExerciseResult<()> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let ex_comp = exercise_completions.iter_mut().find(|ex_comp| ex_comp.id == ex_comp_for_r.id).unwrap();
+
+        ex_comp.points_scored = Some(ex_comp_for_r.points_scored);
+        ex_comp.state = ex_comp_for_r.state;
+        ex_comp.review_comment = ex_comp_for_r.review_comment;
+
+        Ok(())
+    }
+    async fn get_course_pending_reviews(&self, _: &Ctx, _: i64) -> ExerciseResult<Vec<ExerciseCompletion>> { panic!() }
+    async fn get_exercise_completions(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        let result = self.exercise_completions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|ex_comp| ex_comp.exercise_id == exercise_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn update_exercise_completion_points(&self, _: &Ctx, ex_comp_id: i64, points_scored: f32, state: ExerciseCompletionState) -> ExerciseResult<()> {
+        let mut exercise_completions = self.exercise_completions.lock().unwrap();
+        let ex_comp = exercise_completions.iter_mut().find(|ex_comp| ex_comp.id == ex_comp_id).unwrap();
+
+        ex_comp.points_scored = Some(points_scored);
+        ex_comp.state = state;
+
+        Ok(())
+    }
+    async fn get_exercise_peer_reviews(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<PeerReview>> {
+        let result = self.peer_reviews
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|peer_review| peer_review.exercise_id == exercise_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_exercise_completion_peer_reviews(&self, _: &Ctx, ex_comp_id: i64) -> ExerciseResult<Vec<PeerReview>> {
+        let result = self.peer_reviews
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|peer_review| peer_review.exercise_completion_id == ex_comp_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_peer_review(&self, _: &Ctx, peer_review_id: i64) -> ExerciseResult<PeerReview> {
+        let peer_review = self.peer_reviews
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|peer_review| peer_review.id == peer_review_id)
+            .cloned()
+            .unwrap();
+
+        Ok(peer_review)
+    }
+    async fn create_peer_reviews(&self, _: &Ctx, peer_reviews_for_c: Vec<PeerReviewForCreate>) -> ExerciseResult<()> {
+        let mut peer_reviews = self.peer_reviews.lock().unwrap();
+
+        for peer_review_for_c in peer_reviews_for_c {
+            let exists = peer_reviews
+                .iter()
+                .any(|peer_review| peer_review.exercise_completion_id == peer_review_for_c.exercise_completion_id && peer_review.reviewer_id == peer_review_for_c.reviewer_id);
+            if exists {
+                continue;
+            }
+
+            let id = peer_reviews.len() as i64 + 1;
+
+            peer_reviews.push(PeerReview {
+                id,
+                exercise_id: peer_review_for_c.exercise_id,
+                exercise_completion_id: peer_review_for_c.exercise_completion_id,
+                reviewer_id: peer_review_for_c.reviewer_id,
+                score: None,
+                rubric_scores: None,
+                comment: None,
+                state: PeerReviewState::Assigned,
+            });
+        }
+
+        Ok(())
+    }
+    async fn submit_peer_review(&self, _: &Ctx, peer_review_for_s: PeerReviewForSubmitCommand) -> ExerciseResult<()> {
+        let mut peer_reviews = self.peer_reviews.lock().unwrap();
+        let peer_review = peer_reviews.iter_mut().find(|peer_review| peer_review.id == peer_review_for_s.id).unwrap();
+
+        peer_review.score = Some(peer_review_for_s.score);
+        peer_review.rubric_scores = Some(peer_review_for_s.rubric_scores);
+        peer_review.comment = peer_review_for_s.comment;
+        peer_review.state = PeerReviewState::Submitted;
+
+        Ok(())
+    }
+    async fn get_exercises_for_similarity_check(&self, _: &Ctx) -> ExerciseResult<Vec<i64>> {
+        let result = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(exercise_id, _)| *exercise_id)
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_exercise_similarity_flags(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<SimilarityFlag>> {
+        let result = self.similarity_flags
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|flag| flag.exercise_id == exercise_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn replace_similarity_flags(&self, _: &Ctx, exercise_id: i64, flags_for_c: Vec<SimilarityFlagForCreate>) -> ExerciseResult<()> {
+        let mut similarity_flags = self.similarity_flags.lock().unwrap();
+        similarity_flags.retain(|flag| flag.exercise_id != exercise_id);
+
+        for flag_for_c in flags_for_c {
+            let id = similarity_flags.len() as i64 + 1;
+
+            similarity_flags.push(SimilarityFlag {
+                id,
+                exercise_id: flag_for_c.exercise_id,
+                first_exercise_completion_id: flag_for_c.first_exercise_completion_id,
+                second_exercise_completion_id: flag_for_c.second_exercise_completion_id,
+                score: flag_for_c.score,
+            });
+        }
+
+        Ok(())
+    }
+    async fn get_auto_difficulty_exercises(&self, _: &Ctx) -> ExerciseResult<Vec<i64>> {
+        let result = self.exercises
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, exercise)| exercise.auto_difficulty)
+            .map(|(exercise_id, _)| *exercise_id)
+            .collect();
+
+        Ok(result)
+    }
+    async fn get_exercise_difficulty_changes(&self, _: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<DifficultyChange>> {
+        let result = self.difficulty_changes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|change| change.exercise_id == exercise_id)
+            .cloned()
+            .collect();
+
+        Ok(result)
+    }
+    async fn change_exercise_difficulty(&self, _: &Ctx, difficulty_change_for_c: DifficultyChangeForCreate) -> ExerciseResult<()> {
+        let mut exercises = self.exercises.lock().unwrap();
+        let (_, exercise) = exercises.iter_mut().find(|(id, _)| *id == difficulty_change_for_c.exercise_id).unwrap();
+        exercise.difficult = difficulty_change_for_c.new_difficulty.clone();
+
+        let mut difficulty_changes = self.difficulty_changes.lock().unwrap();
+        let id = difficulty_changes.len() as i64 + 1;
+
+        difficulty_changes.push(DifficultyChange {
+            id,
+            exercise_id: difficulty_change_for_c.exercise_id,
+            old_difficulty: difficulty_change_for_c.old_difficulty,
+            new_difficulty: difficulty_change_for_c.new_difficulty,
+            pass_rate: difficulty_change_for_c.pass_rate,
+            date_changed: 0,
+        });
+
+        Ok(())
     }
 }
\ No newline at end of file
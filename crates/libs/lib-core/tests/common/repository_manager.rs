@@ -1,27 +1,114 @@
-use lib_core::{interfaces::{command_repository_manager::ICommandRepositoryManager, course::ICourseCommandRepository, lesson::ILessonCommandRepository, user::IUserCommandRepository}, models::lesson::Lesson};
+use std::sync::Arc;
 
-use super::repository::{CourseCommandRepositoryMock, LessonCommandRepositoryMock, UserCommandRepositoryMock};
+use lib_core::{interfaces::{command_repository_manager::ICommandRepositoryManager, course::ICourseCommandRepository, exercise::IExerciseCommandRepository, file_storage::IFileStorage, lesson::ILessonCommandRepository, user::IUserCommandRepository}, models::{attachment::Attachment, content_block::ContentBlock, course::{Course, UserCourse}, exam::Exam, exercise::Exercise, exercise_completion::ExerciseCompletion, lesson::Lesson, lesson_progress::LessonProgress, peer_review::PeerReview, placement_test::PlacementTest, section::Section, unlock_policy::UnlockPolicy}};
+
+use super::{file_storage::FileStorageMock, repository::{CourseCommandRepositoryMock, ExerciseCommandRepositoryMock, LessonCommandRepositoryMock, UserCommandRepositoryMock}};
 
 pub struct CommandRepositoryManagerMock {
     user_repository: UserCommandRepositoryMock,
     course_repository: CourseCommandRepositoryMock,
     lesson_repository: LessonCommandRepositoryMock,
+    exercise_repository: ExerciseCommandRepositoryMock,
+    pub file_storage: FileStorageMock,
+    pub legacy_upload_storage: FileStorageMock,
 }
 
 impl CommandRepositoryManagerMock {
     pub fn new(lessons: Vec<Lesson>) -> Self {
-        let lesson_repository = LessonCommandRepositoryMock::new(lessons);
+        Self::new_with_exercises(lessons, Vec::new(), Vec::new())
+    }
+
+    pub fn new_with_exercises(
+        lessons: Vec<Lesson>,
+        exercises: Vec<(i64, Exercise)>,
+        exercise_completions: Vec<ExerciseCompletion>,
+    ) -> Self {
         let user_repository = UserCommandRepositoryMock;
-        let course_repository = CourseCommandRepositoryMock;
+        let exercise_repository = ExerciseCommandRepositoryMock::new(exercises, exercise_completions);
+        let lesson_repository = LessonCommandRepositoryMock::new(lessons, exercise_repository.clone());
+        let course_repository = CourseCommandRepositoryMock::new(lesson_repository.clone(), exercise_repository.clone());
 
         Self {
             user_repository,
             course_repository,
             lesson_repository,
+            exercise_repository,
+            file_storage: FileStorageMock::default(),
+            legacy_upload_storage: FileStorageMock::default(),
         }
     }
 }
 
+impl CommandRepositoryManagerMock {
+    pub fn with_lesson_progresses(self, lesson_progresses: Vec<LessonProgress>) -> Self {
+        self.lesson_repository.lesson_progresses.lock().unwrap().extend(lesson_progresses);
+
+        self
+    }
+
+    pub fn with_exams(self, exams: Vec<Exam>) -> Self {
+        self.lesson_repository.exams.lock().unwrap().extend(exams);
+
+        self
+    }
+
+    pub fn with_attachments(self, attachments: Vec<Attachment>) -> Self {
+        self.lesson_repository.attachments.lock().unwrap().extend(attachments);
+
+        self
+    }
+
+    pub fn with_content_blocks(self, content_blocks: Vec<ContentBlock>) -> Self {
+        self.lesson_repository.content_blocks.lock().unwrap().extend(content_blocks);
+
+        self
+    }
+
+    pub fn with_sections(self, sections: Vec<Section>) -> Self {
+        self.lesson_repository.sections.lock().unwrap().extend(sections);
+
+        self
+    }
+
+    pub fn with_courses(self, courses: Vec<Course>) -> Self {
+        self.course_repository.courses.lock().unwrap().extend(courses);
+
+        self
+    }
+
+    pub fn with_user_courses(self, user_courses: Vec<UserCourse>) -> Self {
+        self.course_repository.user_courses.lock().unwrap().extend(user_courses);
+
+        self
+    }
+
+    pub fn with_placement_test(self, placement_test: PlacementTest) -> Self {
+        self.course_repository.placement_tests.lock().unwrap().push(placement_test);
+
+        self
+    }
+
+    pub fn with_unlock_policy(self, unlock_policy: UnlockPolicy) -> Self {
+        self.course_repository.unlock_policies.lock().unwrap().push(unlock_policy);
+
+        self
+    }
+
+    pub fn get_peer_reviews(&self) -> Vec<PeerReview> {
+        self.exercise_repository.peer_reviews.lock().unwrap().clone()
+    }
+
+    pub fn get_exercise_completion(&self, ex_comp_id: i64) -> ExerciseCompletion {
+        self.exercise_repository.exercise_completions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|ex_comp| ex_comp.id == ex_comp_id)
+            .cloned()
+            .unwrap()
+    }
+}
+
 impl ICommandRepositoryManager for CommandRepositoryManagerMock {
     fn get_user_repository(&self) -> Box<dyn IUserCommandRepository + Send + Sync> {
         Box::new(self.user_repository.clone())
@@ -34,4 +121,16 @@ impl ICommandRepositoryManager for CommandRepositoryManagerMock {
     fn get_lesson_repository(&self) -> Box<dyn ILessonCommandRepository + Send + Sync> {
         Box::new(self.lesson_repository.clone())
     }
+
+    fn get_exercise_repository(&self) -> Box<dyn IExerciseCommandRepository + Send + Sync> {
+        Box::new(self.exercise_repository.clone())
+    }
+
+    fn get_file_storage(&self) -> Arc<dyn IFileStorage + Send + Sync> {
+        Arc::new(self.file_storage.clone())
+    }
+
+    fn get_legacy_upload_storage(&self) -> Arc<dyn IFileStorage + Send + Sync> {
+        Arc::new(self.legacy_upload_storage.clone())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::exercise::CreatorExerciseInteractor, error::{CoreError, ExerciseError}, student::exercise::StudentExerciseInteractor}, models::{exercise::{Exercise, ExerciseDifficulty, ExerciseType, NodeDiffState}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson}};
+use serde_json::{json, Value};
+
+use crate::common::repository_manager::CommandRepositoryManagerMock;
+
+mod common;
+
+
+fn stages_node(id: &str, x: i64, stages: &[i64]) -> Value {
+    let stages: Vec<Value> = stages
+        .iter()
+        .map(|stage_id| json!({ "id": stage_id, "name": format!("Stage {stage_id}") }))
+        .collect();
+
+    json!({
+        "id": id,
+        "x": x,
+        "y": 0,
+        "node_type": "ProcessStages",
+        "body": { "header": id, "stages": stages },
+    })
+}
+
+fn repository_manager(state: ExerciseCompletionState) -> Arc<CommandRepositoryManagerMock> {
+    let answer_body = json!({
+        "nodes": [stages_node("a", 0, &[1, 2, 3]), stages_node("b", 100, &[1])],
+        "connections": [{ "from": "a", "to": "b" }],
+    });
+
+    let solution_body = json!({
+        "nodes": [stages_node("a", 10, &[2, 1, 3]), stages_node("c", 200, &[1])],
+        "connections": [{ "from": "a", "to": "c" }],
+    });
+
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise 1".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body,
+        exercise_body: json!({ "nodes": [], "connections": [] }),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: 1,
+        user_id: 1,
+        points_scored: None,
+        max_points: None,
+        number_of_attempts: 0,
+        date_started: 0,
+        date_last_changes: None,
+        state,
+        body: Some(solution_body),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    Arc::new(CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], vec![ex_comp]))
+}
+
+#[tokio::test]
+async fn test_creator_attempt_diff() {
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager(ExerciseCompletionState::InProgress));
+    let ctx = Ctx::new(1).unwrap();
+
+    let diff = exercise_interactor.get_exercise_completion_diff(&ctx, 1).await.unwrap();
+
+    assert_eq!(diff.added_connections.len(), 1);
+    assert_eq!(diff.added_connections[0].to, "c");
+    assert_eq!(diff.removed_connections.len(), 1);
+    assert_eq!(diff.removed_connections[0].to, "b");
+
+    let states: Vec<(&str, NodeDiffState)> = diff.nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node.state))
+        .collect();
+    assert_eq!(states, vec![("a", NodeDiffState::Changed), ("b", NodeDiffState::Removed), ("c", NodeDiffState::Added)]);
+
+    let node_a = &diff.nodes[0];
+    assert_eq!(node_a.x, 10);
+    assert_eq!(node_a.stages.iter().map(|stage| stage.position).collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn test_student_attempt_diff_requires_completion() {
+    let ctx = Ctx::new(1).unwrap();
+
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager(ExerciseCompletionState::InProgress));
+    let result = exercise_interactor.get_exercise_completion_diff(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::AttemptHasNotBeenCompleted))));
+
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager(ExerciseCompletionState::Failed));
+    let diff = exercise_interactor.get_exercise_completion_diff(&ctx, 1).await.unwrap();
+    assert_eq!(diff.nodes.len(), 3);
+}
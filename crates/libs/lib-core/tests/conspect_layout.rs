@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::exercise::CreatorExerciseInteractor, error::{CoreError, ExerciseError}}, models::{exercise::{ConspectLayoutAlgorithm, ExerciseType, MAX_FORCE_DIRECTED_NODES}, lesson::Lesson}};
+use serde_json::{json, Value};
+
+use crate::common::repository_manager::CommandRepositoryManagerMock;
+
+mod common;
+
+
+fn node(id: &str) -> Value {
+    json!({
+        "id": id,
+        "node_type": "Header",
+        "body": { "header": id },
+    })
+}
+
+fn position(body: &Value, id: &str) -> (i64, i64) {
+    let node = body["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["id"] == id)
+        .unwrap();
+
+    (node["x"].as_i64().unwrap(), node["y"].as_i64().unwrap())
+}
+
+async fn layout(body: Value, algorithm: ConspectLayoutAlgorithm) -> Result<Value, CoreError> {
+    let lesson_id = 1;
+    let lessons = vec![Lesson { id: lesson_id, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    let repository_manager = Arc::new(CommandRepositoryManagerMock::new(lessons));
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager);
+
+    let ctx = Ctx::new(1).unwrap();
+
+    exercise_interactor
+        .layout_exercise_body(&ctx, lesson_id, &ExerciseType::Conspect, body, algorithm)
+        .await
+}
+
+#[tokio::test]
+async fn test_layered_layout() {
+    let body = json!({
+        "nodes": [node("a"), node("b"), node("c"), node("d")],
+        "connections": [
+            { "from": "a", "to": "b" },
+            { "from": "a", "to": "c" },
+            { "from": "b", "to": "d" },
+            { "from": "c", "to": "d" },
+        ],
+    });
+
+    let result = layout(body, ConspectLayoutAlgorithm::Layered).await.unwrap();
+
+    let a = position(&result, "a");
+    let b = position(&result, "b");
+    let c = position(&result, "c");
+    let d = position(&result, "d");
+
+    assert!(a.1 < b.1 && b.1 < d.1);
+    assert_eq!(b.1, c.1);
+    assert_ne!(b.0, c.0);
+    assert_eq!(result["nodes"][0]["body"]["header"], "a");
+}
+
+#[tokio::test]
+async fn test_auto_layout_with_cycle() {
+    let body = json!({
+        "nodes": [node("a"), node("b"), node("c")],
+        "connections": [
+            { "from": "a", "to": "b" },
+            { "from": "b", "to": "c" },
+            { "from": "c", "to": "a" },
+        ],
+    });
+
+    let result = layout(body, ConspectLayoutAlgorithm::Auto).await.unwrap();
+
+    let positions = ["a", "b", "c"].map(|id| position(&result, id));
+
+    assert_ne!(positions[0], positions[1]);
+    assert_ne!(positions[1], positions[2]);
+    assert_ne!(positions[0], positions[2]);
+    assert!(positions.iter().all(|(x, y)| *x >= 0 && *y >= 0));
+}
+
+#[tokio::test]
+async fn test_force_directed_node_limit() {
+    let number_of_nodes = MAX_FORCE_DIRECTED_NODES + 1;
+    let ids: Vec<String> = (0..number_of_nodes).map(|i| i.to_string()).collect();
+    let connections: Vec<Value> = (0..number_of_nodes)
+        .map(|i| json!({ "from": ids[i], "to": ids[(i + 1) % number_of_nodes] }))
+        .collect();
+    let body = json!({
+        "nodes": ids.iter().map(|id| node(id)).collect::<Vec<_>>(),
+        "connections": connections,
+    });
+
+    let result = layout(body.clone(), ConspectLayoutAlgorithm::ForceDirected).await;
+    assert!(matches!(
+        result,
+        Err(CoreError::ExerciseError(ExerciseError::TooManyNodesForLayout { max_number_of_nodes: MAX_FORCE_DIRECTED_NODES, .. }))
+    ));
+
+    // A large cycle is laid out in layers instead.
+    let result = layout(body, ConspectLayoutAlgorithm::Auto).await.unwrap();
+    assert!(position(&result, "0").1 < position(&result, "1").1);
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::content_block::CreatorContentBlockInteractor, error::{CoreError, LessonError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{content_block::{ContentBlockForChangeOrder, ContentBlockForCreate, ContentBlockForUpdate}, course::CourseState, lesson::Lesson}};
+use serde_json::json;
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    Arc::new(CommandRepositoryManagerMock::new(lessons).with_courses(vec![course(1, CourseState::Draft)]))
+}
+
+fn block(block_type: &str, body: serde_json::Value) -> ContentBlockForCreate {
+    ContentBlockForCreate { lesson_id: 1, block_type: block_type.to_string(), body }
+}
+
+async fn rendered_html(repository_manager: &CommandRepositoryManagerMock, content_block_id: i64) -> String {
+    repository_manager
+        .get_lesson_repository()
+        .get_content_block(&Ctx::root_ctx(), content_block_id)
+        .await
+        .unwrap()
+        .rendered_html
+}
+
+async fn lesson_order(repository_manager: &CommandRepositoryManagerMock) -> Vec<i64> {
+    repository_manager
+        .get_lesson_repository()
+        .get_lesson_content_blocks_ordered(&Ctx::root_ctx(), 1)
+        .await
+        .unwrap()
+        .iter()
+        .map(|content_block| content_block.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_markdown_is_rendered_sanitized() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorContentBlockInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let text = "# Title\n\n**bold** <script>alert(1)</script> [link](javascript:alert(1))";
+    let markdown_id = interactor.create_content_block(&ctx, block("Markdown", json!({ "text": text }))).await.unwrap();
+
+    let html = rendered_html(&repository_manager, markdown_id).await;
+    assert!(html.contains("<h1>Title</h1>"));
+    assert!(html.contains("<strong>bold</strong>"));
+    assert!(!html.contains("<script>"));
+    assert!(!html.contains("javascript:"));
+
+    let callout_id = interactor.create_content_block(&ctx, block("Callout", json!({ "style": "Warning", "text": "*Careful*" }))).await.unwrap();
+    assert_eq!(
+        rendered_html(&repository_manager, callout_id).await, 
+        "<aside class=\"callout callout-warning\"><p><em>Careful</em></p>\n</aside>"
+    );
+
+    interactor.update_content_block(&ctx, ContentBlockForUpdate { id: markdown_id, body: json!({ "text": "plain" }) }).await.unwrap();
+    assert_eq!(rendered_html(&repository_manager, markdown_id).await, "<p>plain</p>\n");
+}
+
+#[tokio::test]
+async fn test_content_block_body_is_validated() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorContentBlockInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = interactor.create_content_block(&ctx, block("Quiz", json!({}))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ContentBlockTypeDoesNotExist { .. }))));
+
+    let result = interactor.create_content_block(&ctx, block("Image", json!({ "url": "https://cdn.example.com/a.png" }))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockBodyFormat { .. }))));
+
+    let result = interactor.create_content_block(&ctx, block("Video", json!({ "url": "javascript:alert(1)" }))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockUrl { .. }))));
+
+    // The iframe only embeds known players.
+    let result = interactor.create_content_block(&ctx, block("Video", json!({ "url": "https://evil.example.com/embed/1" }))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockUrl { .. }))));
+
+    let result = interactor.create_content_block(&ctx, block("Video", json!({ "url": "https://www.youtube.com@evil.example.com/embed/1" }))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockUrl { .. }))));
+
+    interactor.create_content_block(&ctx, block("Video", json!({ "url": "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ" }))).await.unwrap();
+
+    let result = interactor.create_content_block(&ctx, block("Attachment", json!({ "url": "uploads/../secret", "file_name": "a.pdf" }))).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockUrl { .. }))));
+
+    let image_id = interactor.create_content_block(&ctx, block("Image", json!({ "url": "uploads/1.png", "alt": "Diagram" }))).await.unwrap();
+    assert_eq!(rendered_html(&repository_manager, image_id).await, "");
+}
+
+#[tokio::test]
+async fn test_content_blocks_order() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorContentBlockInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    for text in ["one", "two", "three"] {
+        interactor.create_content_block(&ctx, block("Markdown", json!({ "text": text }))).await.unwrap();
+    }
+    assert_eq!(lesson_order(&repository_manager).await, vec![1, 2, 3]);
+
+    interactor.change_order(&ctx, ContentBlockForChangeOrder { id: 3, order: 1 }).await.unwrap();
+    assert_eq!(lesson_order(&repository_manager).await, vec![3, 1, 2]);
+
+    let result = interactor.change_order(&ctx, ContentBlockForChangeOrder { id: 3, order: 4 }).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectContentBlockOrder { content_block_id: 3, order: 4 }))));
+
+    // The blocks after the deleted one move up.
+    interactor.delete_content_block(&ctx, 1).await.unwrap();
+    assert_eq!(lesson_order(&repository_manager).await, vec![3, 2]);
+
+    let orders: Vec<i32> = repository_manager
+        .get_lesson_repository()
+        .get_lesson_content_blocks_ordered(&Ctx::root_ctx(), 1)
+        .await
+        .unwrap()
+        .iter()
+        .map(|content_block| content_block.order)
+        .collect();
+    assert_eq!(orders, vec![1, 2]);
+}
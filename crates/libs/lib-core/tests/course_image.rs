@@ -0,0 +1,115 @@
+use std::{io::Cursor, sync::Arc};
+
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbImage};
+use lib_core::{ctx::Ctx, interactors::{creator::course::CreatorCourseInteractor, error::{CoreError, CourseError}}, models::{course::{Course, CourseState}, course_image::MAX_COURSE_IMG_SIZE}};
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const COURSE_ID: i64 = 1;
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let course = Course {
+        img_url: Some("uploads/legacy.png".to_string()),
+        ..course(COURSE_ID, CourseState::Draft)
+    };
+
+    Arc::new(CommandRepositoryManagerMock::new(Vec::new()).with_courses(vec![course]))
+}
+
+fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbImage::from_fn(width, height, |x, y| image::Rgb([(x % 256) as u8, (y % 256) as u8, 128]));
+
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(image).write_to(&mut Cursor::new(&mut data), format).unwrap();
+
+    data
+}
+
+/// JPEG with an APP1 segment saying the camera was rotated by 90 degrees.
+fn jpeg_with_exif(width: u32, height: u32) -> Vec<u8> {
+    let jpeg = encode(width, height, ImageFormat::Jpeg);
+
+    let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+    exif.extend([0, 1]);
+    exif.extend([0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0]);
+    exif.extend([0, 0, 0, 0]);
+
+    let mut data = jpeg[..2].to_vec();
+    data.extend([0xFF, 0xE1]);
+    data.extend(((exif.len() + 2) as u16).to_be_bytes());
+    data.extend(exif);
+    data.extend(&jpeg[2..]);
+
+    data
+}
+
+#[tokio::test]
+async fn test_course_image_is_validated() {
+    let interactor = CreatorCourseInteractor::new(repository_manager());
+    let ctx = Ctx::new(1).unwrap();
+
+    // The bytes decide, a renamed text file is not an image.
+    let result = interactor.set_course_img(&ctx, COURSE_ID, b"<svg onload=alert(1)>").await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ImageFormatNotSupported))));
+
+    let result = interactor.set_course_img(&ctx, COURSE_ID, b"BM\0\0\0\0\0\0\0\0\x36\0\0\0").await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ImageFormatNotSupported))));
+
+    let png = encode(64, 36, ImageFormat::Png);
+    let result = interactor.set_course_img(&ctx, COURSE_ID, &png[..png.len() / 2]).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::IncorrectImage { .. }))));
+
+    let result = interactor.set_course_img(&ctx, COURSE_ID, &encode(10000, 4, ImageFormat::Png)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ImageDimensionsTooLarge { width: 10000, height: 4, .. }))));
+
+    let too_large = vec![0; MAX_COURSE_IMG_SIZE + 1];
+    let result = interactor.set_course_img(&ctx, COURSE_ID, &too_large).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ImageTooLarge { .. }))));
+}
+
+#[tokio::test]
+async fn test_course_image_variants() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(1).unwrap();
+
+    let img_variants = interactor.set_course_img(&ctx, COURSE_ID, &jpeg_with_exif(400, 200)).await.unwrap();
+
+    {
+        let files = repository_manager.file_storage.files.lock().unwrap();
+        assert_eq!(files.len(), 6);
+
+        let file = |url: &str| files.get(url.strip_prefix("mock/").unwrap()).unwrap();
+
+        let (data, content_type) = file(&img_variants.thumbnail.webp);
+        assert_eq!(content_type, "image/webp");
+        assert_eq!(image::guess_format(data).unwrap(), ImageFormat::WebP);
+
+        // Rotated by the EXIF orientation, then cropped to 16:9 without upscaling.
+        let (data, content_type) = file(&img_variants.hero.png);
+        assert_eq!(content_type, "image/png");
+        assert!(!data.windows(4).any(|window| window == b"Exif"));
+        assert_eq!(image::load_from_memory(data).unwrap().dimensions(), (200, 113));
+
+        let (data, _) = file(&img_variants.thumbnail.png);
+        assert_eq!(image::load_from_memory(data).unwrap().dimensions(), (200, 113));
+
+        let (data, _) = file(&img_variants.card.webp);
+        assert_eq!(image::load_from_memory(data).unwrap().dimensions(), (200, 113));
+    }
+
+    let img_variants_2 = interactor.set_course_img(&ctx, COURSE_ID, &encode(1280, 720, ImageFormat::Png)).await.unwrap();
+
+    let files = repository_manager.file_storage.files.lock().unwrap();
+    assert_eq!(files.len(), 6);
+    for url in img_variants_2.urls() {
+        assert!(url.starts_with("mock/public/course_images/"));
+        assert!(files.contains_key(url.strip_prefix("mock/").unwrap()));
+    }
+
+    let (data, _) = files.get(img_variants_2.thumbnail.png.strip_prefix("mock/").unwrap()).unwrap();
+    assert_eq!(image::load_from_memory(data).unwrap().dimensions(), (320, 180));
+}
@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::{content_block::CreatorContentBlockInteractor, course::CreatorCourseInteractor, exercise::CreatorExerciseInteractor, lesson::CreatorLessonInteractor, section::CreatorSectionInteractor}, error::{CoreError, CourseError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{content_block::{ContentBlock, ContentBlockForUpdate, ContentBlockType}, course::CourseState, exam::ExamForUpdate, exercise::{Exercise, ExerciseDifficulty, ExerciseForUpdate, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::{Lesson, LessonForUpdate}, section::{Section, SectionForUpdate}}};
+use serde_json::json;
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+
+fn repository_manager(state: CourseState) -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: Some(1), lesson_order: 1 },
+    ];
+
+    let section = Section {
+        id: 1,
+        course_id: 1,
+        title: "Section 1".to_string(),
+        section_order: 1,
+    };
+
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: json!([]),
+        exercise_body: json!([]),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: 1,
+        user_id: 2,
+        points_scored: Some(10.0),
+        max_points: Some(10.0),
+        number_of_attempts: 1,
+        date_started: 0,
+        date_last_changes: Some(60),
+        state: ExerciseCompletionState::Succeeded,
+        body: None,
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let content_block = ContentBlock {
+        id: 1,
+        lesson_id: 1,
+        block_type: ContentBlockType::Markdown,
+        body: json!({ "text": "Hello" }),
+        rendered_html: "<p>Hello</p>\n".to_string(),
+        block_order: 1,
+    };
+
+    Arc::new(
+        CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], vec![ex_comp])
+            .with_courses(vec![course(1, state)])
+            .with_content_blocks(vec![content_block])
+            .with_sections(vec![section])
+    )
+}
+
+fn exercise_for_u(title: Option<&str>, description: Option<&str>) -> ExerciseForUpdate {
+    ExerciseForUpdate {
+        id: 1,
+        title: title.map(ToString::to_string),
+        description: description.map(ToString::to_string),
+        exercise_type: None,
+        answer_body: None,
+        exercise_body: None,
+        difficult: None,
+        time_to_complete: None,
+        manual_review: None,
+        auto_difficulty: None,
+        peer_review_count: None,
+        peer_review_rubric: None,
+    }
+}
+
+async fn stage_edits(repository_manager: &Arc<CommandRepositoryManagerMock>, ctx: &Ctx) {
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(ctx, LessonForUpdate { id: 1, title: "Lesson 1, revised".to_string(), description: "Revised".to_string() })
+        .await
+        .unwrap();
+
+    CreatorContentBlockInteractor::new(repository_manager.clone())
+        .update_content_block(ctx, ContentBlockForUpdate { id: 1, body: json!({ "text": "Hello again" }) })
+        .await
+        .unwrap();
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+    exercise_interactor.update_exercise(ctx, exercise_for_u(Some("Exercise, revised"), None), false).await.unwrap();
+    exercise_interactor.update_exercise(ctx, exercise_for_u(None, Some("Now with a description")), true).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_published_course_edits_are_staged() {
+    let repository_manager = repository_manager(CourseState::Published);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    stage_edits(&repository_manager, &ctx).await;
+
+    // Students keep the live course.
+    let lesson_repository = repository_manager.get_lesson_repository();
+    let exercise_repository = repository_manager.get_exercise_repository();
+    assert_eq!(lesson_repository.get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1");
+    assert_eq!(lesson_repository.get_content_block(&ctx, 1).await.unwrap().rendered_html, "<p>Hello</p>\n");
+    assert_eq!(exercise_repository.get_exercise(&ctx, 1).await.unwrap().title, "Exercise");
+    assert_eq!(exercise_repository.get_exercise_completions(&ctx, 1).await.unwrap().len(), 1);
+
+    // Both exercise updates end up in one edit.
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    assert_eq!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.len(), 3);
+
+    let preview = CreatorLessonInteractor::new(repository_manager.clone())
+        .preview_lesson(&ctx, 1)
+        .await
+        .unwrap();
+    assert_eq!(preview.lesson.title, "Lesson 1, revised");
+    assert_eq!(preview.description.as_deref(), Some("Revised"));
+    assert_eq!(preview.content_blocks[0].rendered_html, "<p>Hello again</p>\n");
+    assert_eq!(preview.exercises[0].1.title, "Exercise, revised");
+    assert_eq!(preview.exercises[0].1.description, "Now with a description");
+
+    course_interactor.publish_course_revision(&ctx, 1).await.unwrap();
+
+    assert_eq!(lesson_repository.get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1, revised");
+    assert_eq!(lesson_repository.get_content_block(&ctx, 1).await.unwrap().rendered_html, "<p>Hello again</p>\n");
+    let exercise = exercise_repository.get_exercise(&ctx, 1).await.unwrap();
+    assert_eq!(exercise.title, "Exercise, revised");
+    assert_eq!(exercise.description, "Now with a description");
+    // The retake was staged with the second update.
+    assert!(exercise_repository.get_exercise_completions(&ctx, 1).await.unwrap().is_empty());
+
+    assert!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.is_empty());
+    let result = course_interactor.publish_course_revision(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseHasNoPendingRevision { course_id: 1 }))));
+}
+
+#[tokio::test]
+async fn test_discard_course_revision() {
+    let repository_manager = repository_manager(CourseState::Published);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    stage_edits(&repository_manager, &ctx).await;
+
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    course_interactor.discard_course_revision(&ctx, 1).await.unwrap();
+
+    assert!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.is_empty());
+    assert_eq!(repository_manager.get_lesson_repository().get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1");
+    assert_eq!(repository_manager.get_exercise_repository().get_exercise_completions(&ctx, 1).await.unwrap().len(), 1);
+
+    let result = course_interactor.discard_course_revision(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseHasNoPendingRevision { course_id: 1 }))));
+}
+
+#[tokio::test]
+async fn test_draft_course_edits_are_written() {
+    let repository_manager = repository_manager(CourseState::Draft);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(&ctx, LessonForUpdate { id: 1, title: "Lesson 1, revised".to_string(), description: "Revised".to_string() })
+        .await
+        .unwrap();
+
+    assert_eq!(repository_manager.get_lesson_repository().get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1, revised");
+
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    assert!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.is_empty());
+}
+
+#[tokio::test]
+async fn test_revision_is_dropped_when_course_leaves_published() {
+    let repository_manager = repository_manager(CourseState::Published);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    stage_edits(&repository_manager, &ctx).await;
+
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    course_interactor.unpublish_course(&ctx, 1).await.unwrap();
+
+    assert!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.is_empty());
+
+    // Draft edits are written right away, no stale revision can overwrite them.
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(&ctx, LessonForUpdate { id: 1, title: "Lesson 1, draft".to_string(), description: "".to_string() })
+        .await
+        .unwrap();
+
+    let result = course_interactor.publish_course_revision(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseMustBePublishedError))));
+    assert_eq!(repository_manager.get_lesson_repository().get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1, draft");
+}
+
+#[tokio::test]
+async fn test_published_course_structure_is_locked() {
+    let repository_manager = repository_manager(CourseState::Published);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = CreatorLessonInteractor::new(repository_manager.clone())
+        .delete_lesson(&ctx, 1)
+        .await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::PublishedCourseStructureIsLocked { course_id: 1 }))));
+
+    let result = CreatorContentBlockInteractor::new(repository_manager.clone())
+        .delete_content_block(&ctx, 1)
+        .await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::PublishedCourseStructureIsLocked { course_id: 1 }))));
+
+    let lesson_repository = repository_manager.get_lesson_repository();
+    assert!(lesson_repository.get_lesson(&ctx, 1).await.is_ok());
+    assert!(lesson_repository.get_content_block(&ctx, 1).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_section_and_exam_edits_are_staged() {
+    let repository_manager = repository_manager(CourseState::Published);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    CreatorSectionInteractor::new(repository_manager.clone())
+        .update_section(&ctx, SectionForUpdate { id: 1, title: "Section 1, revised".to_string(), description: "".to_string() })
+        .await
+        .unwrap();
+
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .update_exam(&ctx, ExamForUpdate { lesson_id: 1, opens_at: 1_000, closes_at: 2_000 })
+        .await
+        .unwrap();
+
+    let lesson_repository = repository_manager.get_lesson_repository();
+    assert_eq!(lesson_repository.get_section(&ctx, 1).await.unwrap().title, "Section 1");
+    assert!(lesson_repository.get_exam(&ctx, 1).await.unwrap().is_none());
+
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    assert_eq!(course_interactor.get_course_revision(&ctx, 1).await.unwrap().edits.len(), 2);
+
+    course_interactor.publish_course_revision(&ctx, 1).await.unwrap();
+
+    assert_eq!(lesson_repository.get_section(&ctx, 1).await.unwrap().title, "Section 1, revised");
+    assert_eq!(lesson_repository.get_exam(&ctx, 1).await.unwrap().unwrap().closes_at, 2_000);
+}
+
+#[tokio::test]
+async fn test_archived_course_is_read_only() {
+    let repository_manager = repository_manager(CourseState::Archived);
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    // Restoring the course would hand the edits to students without a revision.
+    let result = CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(&ctx, LessonForUpdate { id: 1, title: "Lesson 1, archived".to_string(), description: "".to_string() })
+        .await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ArchivedCourseIsReadOnly { course_id: 1 }))));
+
+    let result = CreatorLessonInteractor::new(repository_manager.clone())
+        .delete_lesson(&ctx, 1)
+        .await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ArchivedCourseIsReadOnly { course_id: 1 }))));
+
+    let result = CreatorExerciseInteractor::new(repository_manager.clone())
+        .update_exercise(&ctx, exercise_for_u(Some("Exercise, archived"), None), true)
+        .await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ArchivedCourseIsReadOnly { course_id: 1 }))));
+
+    let lesson_repository = repository_manager.get_lesson_repository();
+    assert_eq!(lesson_repository.get_lesson(&ctx, 1).await.unwrap().title, "Lesson 1");
+    assert_eq!(repository_manager.get_exercise_repository().get_exercise_completions(&ctx, 1).await.unwrap().len(), 1);
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::course::CreatorCourseInteractor, cron_job_course_schedule::CronJobCourseSchedule, error::{CoreError, CourseError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{content_block::{ContentBlock, ContentBlockType}, course::{Course, CourseSchedule, CourseState}, lesson::Lesson}};
+use lib_utils::time::now_utc_sec;
+use serde_json::json;
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+const DAY: i64 = 24 * 60 * 60;
+
+fn lesson(id: i64, course_id: i64) -> Lesson {
+    Lesson { id, course_id, title: format!("Lesson {id}"), section_id: None, lesson_order: 1 }
+}
+
+fn content_block(lesson_id: i64) -> ContentBlock {
+    ContentBlock {
+        id: lesson_id,
+        lesson_id,
+        block_type: ContentBlockType::Markdown,
+        body: json!({ "text": "Hello" }),
+        rendered_html: "<p>Hello</p>".to_string(),
+        block_order: 1,
+    }
+}
+
+async fn get_course(repository_manager: &CommandRepositoryManagerMock, course_id: i64) -> Course {
+    repository_manager
+        .get_course_repository()
+        .get_course(&Ctx::root_ctx(), course_id)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_set_course_schedule() {
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(Vec::new())
+            .with_courses(vec![course(1, CourseState::Draft)])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let now = now_utc_sec();
+    let schedule = |publish_date, archive_date| CourseSchedule { course_id: 1, publish_date, archive_date };
+
+    let result = interactor.set_course_schedule(&ctx, schedule(Some(now - DAY), None)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ScheduledDateInPast { .. }))));
+
+    let result = interactor.set_course_schedule(&ctx, schedule(Some(now + 2 * DAY), Some(now + DAY))).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::ArchiveDateBeforePublishDate { .. }))));
+
+    // A draft is never archived unless it gets published first.
+    let result = interactor.set_course_schedule(&ctx, schedule(None, Some(now + DAY))).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::IncorrectCourseStateTransition { .. }))));
+
+    interactor.set_course_schedule(&ctx, schedule(Some(now + DAY), Some(now + 2 * DAY))).await.unwrap();
+    let course = get_course(&repository_manager, 1).await;
+    assert_eq!(course.scheduled_publish_date, Some(now + DAY));
+    assert_eq!(course.scheduled_archive_date, Some(now + 2 * DAY));
+
+    interactor.clear_course_schedule(&ctx, 1).await.unwrap();
+    let course = get_course(&repository_manager, 1).await;
+    assert_eq!(course.scheduled_publish_date, None);
+    assert_eq!(course.scheduled_archive_date, None);
+}
+
+#[tokio::test]
+async fn test_run_due_schedules() {
+    let now = now_utc_sec();
+
+    let courses = vec![
+        // Published and archived in one run.
+        Course { scheduled_publish_date: Some(now - 2 * DAY), scheduled_archive_date: Some(now - DAY), ..course(1, CourseState::Draft) },
+        // Has no description, the schedule is dropped rather than published late.
+        Course { description: String::new(), scheduled_publish_date: Some(now - DAY), ..course(2, CourseState::Draft) },
+        // Archived by hand, the schedule is stale.
+        Course { scheduled_archive_date: Some(now - DAY), ..course(3, CourseState::Archived) },
+        // Not due yet.
+        Course { scheduled_publish_date: Some(now + DAY), ..course(4, CourseState::Draft) },
+    ];
+
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(vec![lesson(1, 1), lesson(4, 4)])
+            .with_courses(courses)
+            .with_content_blocks(vec![content_block(1), content_block(4)])
+    );
+
+    let report = CronJobCourseSchedule::new(repository_manager.clone())
+        .run_due_schedules()
+        .await
+        .unwrap();
+
+    assert_eq!(report.published, 1);
+    assert_eq!(report.archived, 1);
+    assert_eq!(report.not_ready_course_ids, vec![2]);
+    assert!(report.failed_course_ids.is_empty());
+
+    let course = get_course(&repository_manager, 1).await;
+    assert!(course.state == CourseState::Archived);
+    assert_eq!(course.published_date, Some(now - 2 * DAY));
+    assert_eq!((course.scheduled_publish_date, course.scheduled_archive_date), (None, None));
+
+    let course = get_course(&repository_manager, 2).await;
+    assert!(course.state == CourseState::Draft);
+    assert_eq!(course.scheduled_publish_date, None);
+
+    let course = get_course(&repository_manager, 3).await;
+    assert!(course.state == CourseState::Archived);
+    assert_eq!(course.scheduled_archive_date, None);
+
+    let course = get_course(&repository_manager, 4).await;
+    assert!(course.state == CourseState::Draft);
+    assert_eq!(course.scheduled_publish_date, Some(now + DAY));
+}
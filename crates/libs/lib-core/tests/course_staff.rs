@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::{course::CreatorCourseInteractor, lesson::CreatorLessonInteractor}, error::{CoreError, CourseError}, permission_manager::PermissionManager}, models::{course::{CourseForUpdate, CoursePermission, CourseStaffForUpdate, CourseState, UserCourse, UserCourseRole}, lesson::{Lesson, LessonForUpdate}}};
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+const CO_AUTHOR_ID: i64 = 2;
+const ASSISTANT_ID: i64 = 3;
+const STUDENT_ID: i64 = 4;
+
+fn user_course(user_id: i64, user_role: UserCourseRole) -> UserCourse {
+    UserCourse {
+        user_id,
+        course_id: 1,
+        user_role,
+        date_registered: 0,
+    }
+}
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let user_courses = vec![
+        user_course(CREATOR_ID, UserCourseRole::Creator),
+        user_course(CO_AUTHOR_ID, UserCourseRole::CoAuthor),
+        user_course(ASSISTANT_ID, UserCourseRole::Assistant),
+        user_course(STUDENT_ID, UserCourseRole::Student),
+    ];
+
+    Arc::new(
+        CommandRepositoryManagerMock::new(lessons)
+            .with_courses(vec![course(1, CourseState::Draft)])
+            .with_user_courses(user_courses)
+    )
+}
+
+fn staff(user_id: i64, user_role: UserCourseRole) -> CourseStaffForUpdate {
+    CourseStaffForUpdate {
+        course_id: 1,
+        user_id,
+        user_role,
+    }
+}
+
+fn is_permission_denied<T>(result: Result<T, CoreError>) -> bool {
+    matches!(result, Err(CoreError::PermissionDenied))
+}
+
+#[tokio::test]
+async fn test_permission_matrix() {
+    let repository_manager = repository_manager();
+    let permission_manager = PermissionManager::new(repository_manager.clone());
+
+    let permissions = [
+        CoursePermission::EditContent,
+        CoursePermission::ManageCourse,
+        CoursePermission::ManageStaff,
+        CoursePermission::ViewStatistics,
+        CoursePermission::Grade,
+    ];
+
+    let expected = [
+        (CREATOR_ID, [true, true, true, true, true]),
+        (CO_AUTHOR_ID, [true, false, false, true, false]),
+        (ASSISTANT_ID, [false, false, false, true, true]),
+        (STUDENT_ID, [false, false, false, false, false]),
+        // Not in the course at all.
+        (5, [false, false, false, false, false]),
+    ];
+
+    for (user_id, allowed) in expected {
+        let ctx = Ctx::new(user_id).unwrap();
+
+        for (permission, allowed) in permissions.iter().zip(allowed) {
+            let result = permission_manager.check_course_permission(&ctx, 1, *permission).await;
+            assert_eq!(result.is_ok(), allowed, "user {user_id}, {permission:?}");
+        }
+    }
+
+    // The co-author edits content, but not the course itself.
+    let co_author_ctx = Ctx::new(CO_AUTHOR_ID).unwrap();
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(&co_author_ctx, LessonForUpdate { id: 1, title: "Lesson 1, revised".to_string(), description: "".to_string() })
+        .await
+        .unwrap();
+
+    let course_for_u = CourseForUpdate {
+        title: Some("Renamed".to_string()),
+        description: None,
+        course_type: None,
+        price: None,
+        color: None,
+        img_url: None,
+    };
+    let result = CreatorCourseInteractor::new(repository_manager.clone())
+        .update_course(&co_author_ctx, course_for_u, 1)
+        .await;
+    assert!(is_permission_denied(result));
+
+    let assistant_ctx = Ctx::new(ASSISTANT_ID).unwrap();
+    let result = CreatorLessonInteractor::new(repository_manager.clone())
+        .update_lesson(&assistant_ctx, LessonForUpdate { id: 1, title: "Lesson 1".to_string(), description: "".to_string() })
+        .await;
+    assert!(is_permission_denied(result));
+}
+
+#[tokio::test]
+async fn test_manage_staff() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    interactor.invite_staff_member(&ctx, staff(5, UserCourseRole::Assistant)).await.unwrap();
+
+    let result = interactor.invite_staff_member(&ctx, staff(5, UserCourseRole::CoAuthor)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::UserIsAlreadyInCourse { user_id: 5, course_id: 1 }))));
+
+    let result = interactor.invite_staff_member(&ctx, staff(STUDENT_ID, UserCourseRole::Assistant)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::UserIsAlreadyInCourse { .. }))));
+
+    let result = interactor.invite_staff_member(&ctx, staff(6, UserCourseRole::Creator)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::IncorrectStaffRole { .. }))));
+
+    // Only the creator manages the staff.
+    let co_author_ctx = Ctx::new(CO_AUTHOR_ID).unwrap();
+    assert!(is_permission_denied(interactor.invite_staff_member(&co_author_ctx, staff(6, UserCourseRole::Assistant)).await));
+
+    interactor.change_staff_role(&ctx, staff(5, UserCourseRole::CoAuthor)).await.unwrap();
+
+    let staff_members = interactor.get_course_staff(&ctx, 1).await.unwrap();
+    let roles: Vec<(i64, UserCourseRole)> = staff_members.into_iter().map(|member| (member.user_id, member.user_role)).collect();
+    assert_eq!(roles, vec![
+        (CREATOR_ID, UserCourseRole::Creator),
+        (CO_AUTHOR_ID, UserCourseRole::CoAuthor),
+        (ASSISTANT_ID, UserCourseRole::Assistant),
+        (5, UserCourseRole::CoAuthor),
+    ]);
+
+    let result = interactor.change_staff_role(&ctx, staff(CREATOR_ID, UserCourseRole::Assistant)).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::UserIsNotCourseStaff { user_id: CREATOR_ID, course_id: 1 }))));
+
+    let result = interactor.remove_staff_member(&ctx, 1, STUDENT_ID).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::UserIsNotCourseStaff { user_id: STUDENT_ID, course_id: 1 }))));
+
+    interactor.remove_staff_member(&ctx, 1, 5).await.unwrap();
+    assert_eq!(interactor.get_course_staff(&ctx, 1).await.unwrap().len(), 3);
+
+    let new_member_ctx = Ctx::new(5).unwrap();
+    assert!(is_permission_denied(interactor.get_course_staff(&new_member_ctx, 1).await));
+}
@@ -0,0 +1,136 @@
+use std::{io::Cursor, sync::Arc};
+
+use image::{DynamicImage, ImageFormat, RgbImage};
+use lib_core::{ctx::Ctx, interactors::{creator::{attachment::CreatorAttachmentInteractor, course::CreatorCourseInteractor}, error::{CoreError, CourseError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{attachment::AttachmentForCreate, content_block::{ContentBlock, ContentBlockType}, course::{CourseState, UserCourse, UserCourseRole}, lesson::Lesson}};
+use serde_json::json;
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+
+fn student(course_id: i64) -> UserCourse {
+    UserCourse {
+        user_id: 2,
+        course_id,
+        user_role: UserCourseRole::Student,
+        date_registered: 0,
+    }
+}
+
+async fn get_state(repository_manager: &CommandRepositoryManagerMock, course_id: i64) -> CourseState {
+    repository_manager
+        .get_course_repository()
+        .get_course(&Ctx::root_ctx(), course_id)
+        .await
+        .unwrap()
+        .state
+}
+
+fn is_incorrect_transition<T>(result: Result<T, CoreError>) -> bool {
+    matches!(result, Err(CoreError::CourseError(CourseError::IncorrectCourseStateTransition { .. })))
+}
+
+#[tokio::test]
+async fn test_state_transitions() {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let content_block = ContentBlock {
+        id: 1,
+        lesson_id: 1,
+        block_type: ContentBlockType::Markdown,
+        body: json!({ "text": "Hello" }),
+        rendered_html: "<p>Hello</p>".to_string(),
+        block_order: 1,
+    };
+
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(lessons)
+            .with_courses(vec![course(1, CourseState::Draft)])
+            .with_content_blocks(vec![content_block])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    assert!(is_incorrect_transition(interactor.archive_course(&ctx, 1).await));
+    assert!(is_incorrect_transition(interactor.restore_course(&ctx, 1).await));
+    assert!(is_incorrect_transition(interactor.unpublish_course(&ctx, 1).await));
+
+    interactor.publish_course(&ctx, 1).await.unwrap();
+    assert!(get_state(&repository_manager, 1).await == CourseState::Published);
+    assert!(is_incorrect_transition(interactor.publish_course(&ctx, 1).await));
+
+    interactor.unpublish_course(&ctx, 1).await.unwrap();
+    assert!(get_state(&repository_manager, 1).await == CourseState::Draft);
+
+    interactor.publish_course(&ctx, 1).await.unwrap();
+    interactor.archive_course(&ctx, 1).await.unwrap();
+    assert!(get_state(&repository_manager, 1).await == CourseState::Archived);
+    assert!(is_incorrect_transition(interactor.publish_course(&ctx, 1).await));
+    assert!(is_incorrect_transition(interactor.unpublish_course(&ctx, 1).await));
+
+    interactor.restore_course(&ctx, 1).await.unwrap();
+    assert!(get_state(&repository_manager, 1).await == CourseState::Published);
+}
+
+#[tokio::test]
+async fn test_unpublish_with_students() {
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(Vec::new())
+            .with_courses(vec![course(1, CourseState::Published)])
+            .with_user_courses(vec![student(1)])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = interactor.unpublish_course(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseHasStudents { course_id: 1, students: 1 }))));
+
+    // The enrolled students keep the course, it just leaves the catalog.
+    interactor.archive_course(&ctx, 1).await.unwrap();
+    assert!(get_state(&repository_manager, 1).await == CourseState::Archived);
+}
+
+#[tokio::test]
+async fn test_delete_draft() {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(lessons)
+            .with_courses(vec![course(1, CourseState::Draft), course(2, CourseState::Published)])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = interactor.delete_course(&ctx, 2).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseMustBeDraft { course_id: 2 }))));
+
+    let mut png = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::new(64, 36))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    interactor.set_course_img(&ctx, 1, &png).await.unwrap();
+
+    let attachment = AttachmentForCreate {
+        lesson_id: 1,
+        file_name: "slides.pdf".to_string(),
+        content_type: "application/pdf".to_string(),
+    };
+    CreatorAttachmentInteractor::new(repository_manager.clone())
+        .upload_attachment(&ctx, attachment, b"%PDF-1.")
+        .await
+        .unwrap();
+
+    assert_eq!(repository_manager.file_storage.files.lock().unwrap().len(), 7);
+
+    interactor.delete_course(&ctx, 1).await.unwrap();
+
+    assert!(repository_manager.file_storage.files.lock().unwrap().is_empty());
+    assert!(get_state(&repository_manager, 2).await == CourseState::Published);
+}
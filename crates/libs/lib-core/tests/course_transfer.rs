@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::{course::CreatorCourseInteractor, course_transfer::CreatorCourseTransferInteractor}, error::{CoreError, CourseError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{course::{CourseForUpdateCommand, CourseState, UserCourse, UserCourseRole}, course_transfer::CourseTransferState, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+
+use crate::common::{fixtures::{course, exercise}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const ADMIN_ID: i64 = 1000;
+const CREATOR_ID: i64 = 1;
+const CO_AUTHOR_ID: i64 = 2;
+const STUDENT_ID: i64 = 3;
+
+fn user_course(user_id: i64, user_role: UserCourseRole) -> UserCourse {
+    UserCourse {
+        user_id,
+        course_id: 1,
+        user_role,
+        date_registered: 0,
+    }
+}
+
+
+/// The student has started the only lesson and attempted its exercise.
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let ex_comps = vec![
+        ExerciseCompletion {
+            id: 1,
+            exercise_id: 1,
+            user_id: STUDENT_ID,
+            points_scored: None,
+            max_points: None,
+            number_of_attempts: 0,
+            date_started: 0,
+            date_last_changes: None,
+            state: ExerciseCompletionState::InProgress,
+            body: None,
+            review_comment: None,
+            is_practice: false,
+            paused_seconds: 0,
+        },
+    ];
+
+    let lesson_progresses = vec![
+        LessonProgress {
+            user_id: STUDENT_ID,
+            lesson_id: 1,
+            date_started: 0,
+            date_complete: None,
+            state: LessonProgressState::InProgress,
+            skipped_by_placement: false,
+            date_paused: None,
+        },
+    ];
+
+    let user_courses = vec![
+        user_course(CREATOR_ID, UserCourseRole::Creator),
+        user_course(CO_AUTHOR_ID, UserCourseRole::CoAuthor),
+        user_course(STUDENT_ID, UserCourseRole::Student),
+    ];
+
+    Arc::new(
+        CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise(1))], ex_comps)
+            .with_lesson_progresses(lesson_progresses)
+            .with_courses(vec![course(1, CourseState::Published)])
+            .with_user_courses(user_courses)
+    )
+}
+
+async fn get_role(repository_manager: &Arc<CommandRepositoryManagerMock>, user_id: i64) -> Option<UserCourseRole> {
+    let ctx = Ctx::new(user_id).unwrap();
+
+    repository_manager
+        .get_course_repository()
+        .get_user_course_optional(&ctx, user_id, 1)
+        .await
+        .unwrap()
+        .map(|user_course| user_course.user_role)
+}
+
+/// Lesson progresses and exercise attempts of the student.
+async fn count_student_progress(repository_manager: &Arc<CommandRepositoryManagerMock>) -> (usize, usize) {
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let lesson_progresses = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap();
+    let ex_comps = repository_manager
+        .get_exercise_repository()
+        .get_exercise_completions(&ctx, 1)
+        .await
+        .unwrap();
+
+    (lesson_progresses.len(), ex_comps.len())
+}
+
+#[tokio::test]
+async fn test_transfer_to_enrolled_student() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorCourseTransferInteractor::new(repository_manager.clone());
+    let creator_ctx = Ctx::new(CREATOR_ID).unwrap();
+    let student_ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    // Co-authors can't give the course away.
+    let co_author_ctx = Ctx::new(CO_AUTHOR_ID).unwrap();
+    let result = interactor.propose_course_transfer(&co_author_ctx, 1, CO_AUTHOR_ID).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    let transfer_id = interactor.propose_course_transfer(&creator_ctx, 1, STUDENT_ID).await.unwrap();
+
+    let result = interactor.propose_course_transfer(&creator_ctx, 1, CO_AUTHOR_ID).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseTransferAlreadyPending { course_id: 1, .. }))));
+
+    // Only the recipient answers.
+    let result = interactor.accept_course_transfer(&co_author_ctx, transfer_id).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    assert_eq!(interactor.get_incoming_course_transfers(&student_ctx).await.unwrap().len(), 1);
+
+    interactor.accept_course_transfer(&student_ctx, transfer_id).await.unwrap();
+
+    assert_eq!(get_role(&repository_manager, STUDENT_ID).await, Some(UserCourseRole::Creator));
+    assert_eq!(get_role(&repository_manager, CREATOR_ID).await, None);
+    assert_eq!(get_role(&repository_manager, CO_AUTHOR_ID).await, Some(UserCourseRole::CoAuthor));
+    assert!(interactor.get_incoming_course_transfers(&student_ctx).await.unwrap().is_empty());
+
+    // The new creator's progress as a student goes with the enrollment.
+    assert_eq!(count_student_progress(&repository_manager).await, (0, 0));
+
+    let result = interactor.accept_course_transfer(&student_ctx, transfer_id).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseTransferIsNotPending { .. }))));
+
+    // The former creator lost the course, the audit trail stays with it.
+    let result = interactor.get_course_transfers(&creator_ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    let transfers = interactor.get_course_transfers(&student_ctx, 1).await.unwrap();
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].from_user_id, CREATOR_ID);
+    assert_eq!(transfers[0].to_user_id, STUDENT_ID);
+    assert_eq!(transfers[0].state, CourseTransferState::Accepted);
+    assert!(!transfers[0].forced);
+    assert!(transfers[0].date_resolved.is_some());
+}
+
+#[tokio::test]
+async fn test_decline_cancel_and_force_transfer() {
+    let repository_manager = repository_manager();
+    let interactor = CreatorCourseTransferInteractor::new(repository_manager.clone());
+    let creator_ctx = Ctx::new(CREATOR_ID).unwrap();
+    let co_author_ctx = Ctx::new(CO_AUTHOR_ID).unwrap();
+
+    let result = interactor.propose_course_transfer(&creator_ctx, 1, CREATOR_ID).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::UserIsAlreadyCourseCreator { user_id: CREATOR_ID, course_id: 1 }))));
+
+    let transfer_id = interactor.propose_course_transfer(&creator_ctx, 1, CO_AUTHOR_ID).await.unwrap();
+    interactor.decline_course_transfer(&co_author_ctx, transfer_id).await.unwrap();
+
+    let transfer_id = interactor.propose_course_transfer(&creator_ctx, 1, CO_AUTHOR_ID).await.unwrap();
+    let result = interactor.cancel_course_transfer(&co_author_ctx, transfer_id).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+    interactor.cancel_course_transfer(&creator_ctx, transfer_id).await.unwrap();
+
+    interactor.propose_course_transfer(&creator_ctx, 1, STUDENT_ID).await.unwrap();
+
+    let result = interactor.force_course_transfer(&creator_ctx, 1, CO_AUTHOR_ID).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    let admin_ctx = Ctx::new(ADMIN_ID).unwrap();
+    interactor.force_course_transfer(&admin_ctx, 1, CO_AUTHOR_ID).await.unwrap();
+
+    assert_eq!(get_role(&repository_manager, CO_AUTHOR_ID).await, Some(UserCourseRole::Creator));
+    assert_eq!(get_role(&repository_manager, CREATOR_ID).await, None);
+    assert_eq!(get_role(&repository_manager, STUDENT_ID).await, Some(UserCourseRole::Student));
+    // Students keep their progress when someone else receives the course.
+    assert_eq!(count_student_progress(&repository_manager).await, (1, 1));
+
+    let transfers = interactor.get_course_transfers(&admin_ctx, 1).await.unwrap();
+    let states: Vec<(CourseTransferState, bool)> = transfers.iter().map(|transfer| (transfer.state, transfer.forced)).collect();
+    assert_eq!(states, vec![
+        (CourseTransferState::Declined, false),
+        (CourseTransferState::Cancelled, false),
+        // The pending proposal is cancelled by the forced transfer.
+        (CourseTransferState::Cancelled, false),
+        (CourseTransferState::Accepted, true),
+    ]);
+}
+
+#[tokio::test]
+async fn test_delete_course_cancels_pending_transfer() {
+    let repository_manager = repository_manager();
+    let course_repository = repository_manager.get_course_repository();
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let command = CourseForUpdateCommand::builder()
+        .state(CourseState::Draft)
+        .build();
+    course_repository.update_course(&ctx, command, 1).await.unwrap();
+
+    let transfer_interactor = CreatorCourseTransferInteractor::new(repository_manager.clone());
+    transfer_interactor.propose_course_transfer(&ctx, 1, CO_AUTHOR_ID).await.unwrap();
+
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    course_interactor.delete_course(&ctx, 1).await.unwrap();
+
+    let transfers = course_repository.get_course_transfers(&ctx, 1).await.unwrap();
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].state, CourseTransferState::Cancelled);
+    assert!(transfers[0].date_resolved.is_some());
+    assert!(course_repository.get_incoming_course_transfers(&ctx, CO_AUTHOR_ID).await.unwrap().is_empty());
+}
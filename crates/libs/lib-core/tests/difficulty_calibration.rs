@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::exercise::CreatorExerciseInteractor, cron_job_difficulty::CronJobDifficulty}, models::{exercise::{Exercise, ExerciseDifficulty}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson}};
+
+use crate::common::{fixtures, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+fn exercise(exercise_order: i32, auto_difficulty: bool) -> Exercise {
+    Exercise {
+        title: format!("Exercise {exercise_order}"),
+        exercise_order,
+        auto_difficulty,
+        ..fixtures::exercise(1)
+    }
+}
+
+/// Every student fails `attempts` times in a row.
+fn failed_attempts(exercise_id: i64, students: std::ops::Range<i64>, attempts: i64) -> Vec<ExerciseCompletion> {
+    students
+        .flat_map(|user_id| (0..attempts).map(move |attempt| (user_id, attempt)))
+        .map(|(user_id, attempt)| ExerciseCompletion {
+            id: exercise_id * 1000 + user_id * 10 + attempt,
+            exercise_id,
+            user_id,
+            points_scored: Some(0.0),
+            max_points: Some(35.0),
+            number_of_attempts: attempt as i32,
+            date_started: 0,
+            date_last_changes: Some(600),
+            state: ExerciseCompletionState::Failed,
+            body: None,
+            review_comment: None,
+            is_practice: false,
+            paused_seconds: 0,
+        })
+        .collect()
+}
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let exercises = vec![(1, exercise(1, true)), (2, exercise(2, true))];
+
+    let mut ex_comps = failed_attempts(1, 1..7, 3);
+    // Too few students to judge the second exercise.
+    ex_comps.extend(failed_attempts(2, 1..3, 3));
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    Arc::new(CommandRepositoryManagerMock::new_with_exercises(lessons, exercises, ex_comps))
+}
+
+#[tokio::test]
+async fn test_calibration_applies_observed_difficulty() {
+    let repository_manager = repository_manager();
+    let cron_difficulty = CronJobDifficulty::new(repository_manager.clone());
+
+    let report = cron_difficulty.calibrate_difficulties().await.unwrap();
+    assert_eq!(report.changed_exercises, 1);
+    assert!(report.failed_exercise_ids.is_empty());
+    // Nothing changes when the difficulty already matches the observations.
+    assert_eq!(cron_difficulty.calibrate_difficulties().await.unwrap().changed_exercises, 0);
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager);
+    let ctx = Ctx::new(1).unwrap();
+
+    let calibrations = exercise_interactor.get_difficulty_calibration(&ctx, 1).await.unwrap();
+    assert_eq!(calibrations.len(), 2);
+
+    let calibrated = &calibrations[0];
+    assert!(calibrated.assigned_difficulty == ExerciseDifficulty::Hard);
+    assert!(calibrated.suggested_difficulty == Some(ExerciseDifficulty::Hard));
+    assert_eq!(calibrated.observations.number_of_students, 6);
+    assert_eq!(calibrated.observations.pass_rate, 0.0);
+    assert_eq!(calibrated.observations.average_attempts, 3.0);
+    assert_eq!(calibrated.history.len(), 1);
+    assert!(calibrated.history[0].old_difficulty == ExerciseDifficulty::Easy);
+
+    let uncalibrated = &calibrations[1];
+    assert!(uncalibrated.assigned_difficulty == ExerciseDifficulty::Easy);
+    assert!(uncalibrated.suggested_difficulty.is_none());
+    assert!(uncalibrated.history.is_empty());
+}
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::lesson::CreatorLessonInteractor, cron_job_exercise::CronJobExercise, error::{CoreError, ExerciseError, LessonError}, student::{exercise::StudentExerciseInteractor, lesson::StudentLessonInteractor}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{course::CourseState, exam::{Exam, ExamForUpdate}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+use lib_utils::time::now_utc_sec;
+
+use crate::common::{fixtures::{conspect, course, exercise}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+const EXAM_LESSON_ID: i64 = 2;
+const EXAM_EXERCISE_ID: i64 = 1;
+const REGULAR_EXERCISE_ID: i64 = 2;
+
+
+
+fn lesson_progress(lesson_id: i64, state: LessonProgressState) -> LessonProgress {
+    LessonProgress {
+        user_id: STUDENT_ID,
+        lesson_id,
+        date_started: 0,
+        date_complete: None,
+        state,
+        skipped_by_placement: false,
+        date_paused: None,
+    }
+}
+
+/// Lesson 1 is a regular lesson the student has passed, lesson 2 is the exam.
+fn repository_manager(opens_at: i64, closes_at: i64, ex_comps: Vec<ExerciseCompletion>, lesson_progresses: Vec<LessonProgress>) -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+        Lesson { id: EXAM_LESSON_ID, course_id: 1, title: "Exam".to_string(), section_id: None, lesson_order: 2 },
+    ];
+
+    let exercises = vec![(EXAM_EXERCISE_ID, exercise(EXAM_LESSON_ID)), (REGULAR_EXERCISE_ID, exercise(1))];
+
+    let exam = Exam {
+        lesson_id: EXAM_LESSON_ID,
+        opens_at,
+        closes_at,
+        results_released: false,
+    };
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, exercises, ex_comps)
+        .with_courses(vec![course(1, CourseState::Draft)])
+        .with_lesson_progresses(lesson_progresses)
+        .with_exams(vec![exam]);
+
+    Arc::new(repository_manager)
+}
+
+async fn lesson_progress_state(repository_manager: &CommandRepositoryManagerMock, ctx: &Ctx, lesson_id: i64) -> LessonProgressState {
+    repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(ctx, 1, STUDENT_ID)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|lesson_progress| lesson_progress.lesson_id == lesson_id)
+        .unwrap()
+        .state
+}
+
+#[tokio::test]
+async fn test_exam_is_graded_when_results_are_released() {
+    let now = now_utc_sec();
+    let repository_manager = repository_manager(now - 60, now + 3600, Vec::new(), vec![lesson_progress(1, LessonProgressState::Done)]);
+
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    lesson_interactor.start_lesson(&ctx, EXAM_LESSON_ID).await.unwrap();
+
+    // Other lessons are locked while the exam is in progress.
+    let result = exercise_interactor.start_exercise(&ctx, REGULAR_EXERCISE_ID, false).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ExamInProgress { lesson_id: EXAM_LESSON_ID }))));
+
+    let ex_comp_id = exercise_interactor.start_exercise(&ctx, EXAM_EXERCISE_ID, false).await.unwrap();
+    let result = exercise_interactor.start_exercise(&ctx, EXAM_EXERCISE_ID, false).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ExamAllowsSingleAttempt { .. }))));
+
+    exercise_interactor.save_exercise_execution_changes(&ctx, ex_comp_id, conspect()).await.unwrap();
+
+    let result = exercise_interactor.complete_exercise(&ctx, ex_comp_id).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ExamIsGradedOnSubmit))));
+
+    lesson_interactor.submit_exam(&ctx, EXAM_LESSON_ID).await.unwrap();
+
+    let ex_comp = repository_manager.get_exercise_completion(ex_comp_id);
+    assert!(ex_comp.state == ExerciseCompletionState::Submitted);
+    assert_eq!(ex_comp.points_scored, None);
+    assert_eq!(lesson_progress_state(&repository_manager, &ctx, EXAM_LESSON_ID).await, LessonProgressState::Done);
+
+    let result = exercise_interactor.get_exercise_completion_diff(&ctx, ex_comp_id).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ExamResultsAreNotReleased))));
+
+    let result = lesson_interactor.start_lesson(&ctx, EXAM_LESSON_ID).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ExamCanBeTakenOnce { lesson_id: EXAM_LESSON_ID }))));
+
+    let creator_ctx = Ctx::new(1).unwrap();
+    let creator_lesson_interactor = CreatorLessonInteractor::new(repository_manager.clone());
+    let result = creator_lesson_interactor.release_exam_results(&creator_ctx, EXAM_LESSON_ID).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ExamIsNotClosed { lesson_id: EXAM_LESSON_ID, .. }))));
+
+    let exam_for_u = ExamForUpdate {
+        lesson_id: EXAM_LESSON_ID,
+        opens_at: now - 60,
+        closes_at: now - 1,
+    };
+    repository_manager.get_lesson_repository().save_exam(&creator_ctx, exam_for_u).await.unwrap();
+
+    creator_lesson_interactor.release_exam_results(&creator_ctx, EXAM_LESSON_ID).await.unwrap();
+
+    let ex_comp = repository_manager.get_exercise_completion(ex_comp_id);
+    assert!(ex_comp.state == ExerciseCompletionState::Succeeded);
+    assert!(ex_comp.points_scored.is_some() && ex_comp.points_scored == ex_comp.max_points);
+    exercise_interactor.get_exercise_completion_diff(&ctx, ex_comp_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_closed_exam_is_submitted_by_cron() {
+    let now = now_utc_sec();
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: EXAM_EXERCISE_ID,
+        user_id: STUDENT_ID,
+        points_scored: None,
+        max_points: None,
+        number_of_attempts: 0,
+        date_started: now - 600,
+        date_last_changes: None,
+        state: ExerciseCompletionState::InProgress,
+        body: Some(conspect()),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let lesson_progresses = vec![
+        lesson_progress(1, LessonProgressState::Done),
+        lesson_progress(EXAM_LESSON_ID, LessonProgressState::InProgress),
+    ];
+
+    let repository_manager = repository_manager(now - 3600, now - 10, vec![ex_comp], lesson_progresses);
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    let result = exercise_interactor.save_exercise_execution_changes(&ctx, 1, conspect()).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::TimeToCompleteExerciseHasExpired))));
+
+    let cron_exercise = CronJobExercise::new(repository_manager.clone());
+    assert_eq!(cron_exercise.submit_overdue_exams().await.unwrap(), 1);
+    assert_eq!(cron_exercise.submit_overdue_exams().await.unwrap(), 0);
+
+    assert!(repository_manager.get_exercise_completion(1).state == ExerciseCompletionState::Submitted);
+    assert_eq!(lesson_progress_state(&repository_manager, &ctx, EXAM_LESSON_ID).await, LessonProgressState::Done);
+
+    let other_ctx = Ctx::new(3).unwrap();
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager);
+    let result = lesson_interactor.start_lesson(&other_ctx, EXAM_LESSON_ID).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ExamIsNotOpen { lesson_id: EXAM_LESSON_ID }))));
+}
+
+#[tokio::test]
+async fn test_regular_attempt_is_locked_during_exam() {
+    let now = now_utc_sec();
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: REGULAR_EXERCISE_ID,
+        user_id: STUDENT_ID,
+        points_scored: None,
+        max_points: None,
+        number_of_attempts: 0,
+        date_started: now - 600,
+        date_last_changes: None,
+        state: ExerciseCompletionState::InProgress,
+        body: None,
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let repository_manager = repository_manager(now - 60, now + 3600, vec![ex_comp], vec![lesson_progress(1, LessonProgressState::Done)]);
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    exercise_interactor.save_exercise_execution_changes(&ctx, 1, conspect()).await.unwrap();
+
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager);
+    lesson_interactor.start_lesson(&ctx, EXAM_LESSON_ID).await.unwrap();
+
+    // An attempt started before the exam can't be worked on during it.
+    let result = exercise_interactor.save_exercise_execution_changes(&ctx, 1, conspect()).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::ExamInProgress { lesson_id: EXAM_LESSON_ID }))));
+}
+
+#[tokio::test]
+async fn test_exam_is_changed_only_without_progress() {
+    let now = now_utc_sec();
+    let repository_manager = repository_manager(now - 60, now + 3600, Vec::new(), vec![lesson_progress(1, LessonProgressState::Done)]);
+    let ctx = Ctx::new(1).unwrap();
+
+    let lesson_interactor = CreatorLessonInteractor::new(repository_manager.clone());
+
+    // Lesson 1 was passed as a regular lesson.
+    let exam_for_u = ExamForUpdate {
+        lesson_id: 1,
+        opens_at: now,
+        closes_at: now + 3600,
+    };
+    let result = lesson_interactor.update_exam(&ctx, exam_for_u).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::LessonHasProgress { lesson_id: 1 }))));
+
+    let result = lesson_interactor.remove_exam(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::LessonIsNotExam { lesson_id: 1 }))));
+
+    lesson_interactor.remove_exam(&ctx, EXAM_LESSON_ID).await.unwrap();
+
+    let lesson_repository = repository_manager.get_lesson_repository();
+    assert!(lesson_repository.get_exam(&ctx, EXAM_LESSON_ID).await.unwrap().is_none());
+
+    // Once a student started the lesson it stays the way they took it.
+    lesson_repository.create_lesson_progress(&ctx, EXAM_LESSON_ID, STUDENT_ID).await.unwrap();
+
+    let exam_for_u = ExamForUpdate {
+        lesson_id: EXAM_LESSON_ID,
+        opens_at: now,
+        closes_at: now + 3600,
+    };
+    let result = lesson_interactor.update_exam(&ctx, exam_for_u).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::LessonHasProgress { lesson_id: EXAM_LESSON_ID }))));
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::exercise::CreatorExerciseInteractor, error::{CoreError, ExerciseError}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{exercise::{Exercise, ExerciseDifficulty, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForReview, ExerciseCompletionState, MAX_REVIEW_COMMENT_LENGTH}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+use serde_json::json;
+
+use crate::common::repository_manager::CommandRepositoryManagerMock;
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+
+fn repository_manager(state: ExerciseCompletionState, lesson_state: LessonProgressState) -> Arc<CommandRepositoryManagerMock> {
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise 1".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: json!({ "nodes": [], "connections": [] }),
+        exercise_body: json!({ "nodes": [], "connections": [] }),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: true,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: 1,
+        user_id: STUDENT_ID,
+        points_scored: Some(10.0),
+        max_points: Some(35.0),
+        number_of_attempts: 0,
+        date_started: 0,
+        date_last_changes: None,
+        state,
+        body: None,
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let lesson_progress = LessonProgress {
+        user_id: STUDENT_ID,
+        lesson_id: 1,
+        date_started: 0,
+        date_complete: None,
+        state: lesson_state,
+        skipped_by_placement: false,
+        date_paused: None,
+    };
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], vec![ex_comp])
+        .with_lesson_progresses(vec![lesson_progress]);
+
+    Arc::new(repository_manager)
+}
+
+async fn lesson_progress_state(repository_manager: &CommandRepositoryManagerMock, ctx: &Ctx) -> LessonProgressState {
+    repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(ctx, 1, STUDENT_ID)
+        .await
+        .unwrap()
+        .remove(0)
+        .state
+}
+
+#[tokio::test]
+async fn test_approved_review_completes_lesson() {
+    let repository_manager = repository_manager(ExerciseCompletionState::PendingReview, LessonProgressState::InProgress);
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(1).unwrap();
+
+    let review = ExerciseCompletionForReview {
+        id: 1,
+        points_scored: 30.0,
+        comment: Some("Good structure".to_string()),
+        approved: true,
+    };
+
+    exercise_interactor.review_exercise_completion(&ctx, review).await.unwrap();
+
+    let ex_comp = repository_manager.get_exercise_completion(1);
+    assert!(ex_comp.state == ExerciseCompletionState::Succeeded);
+    assert_eq!(ex_comp.points_scored, Some(30.0));
+    assert_eq!(ex_comp.review_comment.as_deref(), Some("Good structure"));
+    assert_eq!(lesson_progress_state(&repository_manager, &ctx).await, LessonProgressState::Done);
+}
+
+#[tokio::test]
+async fn test_rejected_review_keeps_lesson_in_progress() {
+    let repository_manager = repository_manager(ExerciseCompletionState::PendingReview, LessonProgressState::InProgress);
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(1).unwrap();
+
+    let review = ExerciseCompletionForReview {
+        id: 1,
+        points_scored: 5.0,
+        comment: None,
+        approved: false,
+    };
+
+    exercise_interactor.review_exercise_completion(&ctx, review).await.unwrap();
+
+    assert!(repository_manager.get_exercise_completion(1).state == ExerciseCompletionState::Failed);
+    assert_eq!(lesson_progress_state(&repository_manager, &ctx).await, LessonProgressState::InProgress);
+}
+
+#[tokio::test]
+async fn test_rejected_review_reopens_done_lesson() {
+    let repository_manager = repository_manager(ExerciseCompletionState::Succeeded, LessonProgressState::Done);
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(1).unwrap();
+
+    exercise_interactor.send_exercise_completion_to_review(&ctx, 1).await.unwrap();
+
+    let review = ExerciseCompletionForReview {
+        id: 1,
+        points_scored: 5.0,
+        comment: None,
+        approved: false,
+    };
+
+    exercise_interactor.review_exercise_completion(&ctx, review).await.unwrap();
+
+    // The lesson was done thanks to the rejected attempt only.
+    assert_eq!(lesson_progress_state(&repository_manager, &ctx).await, LessonProgressState::InProgress);
+}
+
+#[tokio::test]
+async fn test_review_validation() {
+    let ctx = Ctx::new(1).unwrap();
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager(ExerciseCompletionState::PendingReview, LessonProgressState::InProgress));
+    let review = ExerciseCompletionForReview { id: 1, points_scored: 40.0, comment: None, approved: true };
+    let result = exercise_interactor.review_exercise_completion(&ctx, review).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ReviewPointsOutOfRange { .. }))));
+
+    let review = ExerciseCompletionForReview { id: 1, points_scored: 10.0, comment: Some("ы".repeat(MAX_REVIEW_COMMENT_LENGTH + 1)), approved: true };
+    let result = exercise_interactor.review_exercise_completion(&ctx, review).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ReviewCommentTooLong { length: 1025, max_length: 1024 }))));
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager(ExerciseCompletionState::Failed, LessonProgressState::InProgress));
+    let review = ExerciseCompletionForReview { id: 1, points_scored: 10.0, comment: None, approved: true };
+    let result = exercise_interactor.review_exercise_completion(&ctx, review).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::AttemptIsNotPendingReview))));
+
+    let repository_manager = repository_manager(ExerciseCompletionState::Failed, LessonProgressState::InProgress);
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+    exercise_interactor.send_exercise_completion_to_review(&ctx, 1).await.unwrap();
+    assert!(repository_manager.get_exercise_completion(1).state == ExerciseCompletionState::PendingReview);
+}
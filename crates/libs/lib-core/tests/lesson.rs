@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
-use lib_core::{interactors::creator::lesson::CreatorLessonInteractor, ctx::Ctx, interfaces::command_repository_manager::ICommandRepositoryManager, models::lesson::{Lesson, LessonForChangeOreder}};
+use lib_core::{interactors::creator::lesson::CreatorLessonInteractor, ctx::Ctx, interfaces::command_repository_manager::ICommandRepositoryManager, models::{course::CourseState, lesson::{Lesson, LessonForChangeOreder}}};
 
-use crate::common::repository_manager::CommandRepositoryManagerMock;
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
 
 mod common;
 
@@ -21,12 +21,12 @@ async fn test_change_order() {
     let data = vec![
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
-                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), lesson_order: 2 },
-                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), lesson_order: 3 },
-                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), lesson_order: 4 },
-                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), lesson_order: 5 },
-                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), lesson_order: 6 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
+                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), section_id: None, lesson_order: 2 },
+                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), section_id: None, lesson_order: 3 },
+                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), section_id: None, lesson_order: 4 },
+                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), section_id: None, lesson_order: 5 },
+                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), section_id: None, lesson_order: 6 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 3, order: 1 },
@@ -43,7 +43,7 @@ async fn test_change_order() {
         },
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 3, order: 1 },
@@ -55,12 +55,12 @@ async fn test_change_order() {
         },
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
-                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), lesson_order: 2 },
-                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), lesson_order: 3 },
-                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), lesson_order: 4 },
-                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), lesson_order: 5 },
-                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), lesson_order: 6 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
+                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), section_id: None, lesson_order: 2 },
+                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), section_id: None, lesson_order: 3 },
+                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), section_id: None, lesson_order: 4 },
+                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), section_id: None, lesson_order: 5 },
+                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), section_id: None, lesson_order: 6 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 5, order: 1 },
@@ -77,12 +77,12 @@ async fn test_change_order() {
         },
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
-                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), lesson_order: 2 },
-                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), lesson_order: 3 },
-                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), lesson_order: 4 },
-                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), lesson_order: 5 },
-                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), lesson_order: 6 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
+                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), section_id: None, lesson_order: 2 },
+                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), section_id: None, lesson_order: 3 },
+                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), section_id: None, lesson_order: 4 },
+                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), section_id: None, lesson_order: 5 },
+                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), section_id: None, lesson_order: 6 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 3, order: 1 },
@@ -99,12 +99,12 @@ async fn test_change_order() {
         },
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
-                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), lesson_order: 2 },
-                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), lesson_order: 3 },
-                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), lesson_order: 4 },
-                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), lesson_order: 5 },
-                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), lesson_order: 6 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
+                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), section_id: None, lesson_order: 2 },
+                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), section_id: None, lesson_order: 3 },
+                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), section_id: None, lesson_order: 4 },
+                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), section_id: None, lesson_order: 5 },
+                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), section_id: None, lesson_order: 6 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 5, order: 1 },
@@ -121,12 +121,12 @@ async fn test_change_order() {
         },
         TestData {
             lessons: vec![
-                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), lesson_order: 1 },
-                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), lesson_order: 2 },
-                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), lesson_order: 3 },
-                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), lesson_order: 4 },
-                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), lesson_order: 5 },
-                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), lesson_order: 6 },
+                Lesson { id: 3, course_id, title: "Lesson 3".to_string(), section_id: None, lesson_order: 1 },
+                Lesson { id: 5, course_id, title: "Lesson 5".to_string(), section_id: None, lesson_order: 2 },
+                Lesson { id: 1, course_id, title: "Lesson 1".to_string(), section_id: None, lesson_order: 3 },
+                Lesson { id: 10, course_id, title: "Lesson 10".to_string(), section_id: None, lesson_order: 4 },
+                Lesson { id: 16, course_id, title: "Lesson 16".to_string(), section_id: None, lesson_order: 5 },
+                Lesson { id: 9, course_id, title: "Lesson 9".to_string(), section_id: None, lesson_order: 6 },
             ], 
             result: vec![
                 LessonForChangeOreder { id: 9, order: 1 },
@@ -144,7 +144,10 @@ async fn test_change_order() {
     ];
 
     for test_data in data {
-        let repository_manager = Arc::new(CommandRepositoryManagerMock::new(test_data.lessons));
+        let repository_manager = Arc::new(
+            CommandRepositoryManagerMock::new(test_data.lessons)
+                .with_courses(vec![course(1, CourseState::Draft)])
+        );
 
         let lesson_interactor = CreatorLessonInteractor::new(repository_manager.clone());
 
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::creator::exercise::CreatorExerciseInteractor, models::{exercise::{Exercise, ExerciseDifficulty, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson}};
+use serde_json::{json, Value};
+
+use crate::common::repository_manager::CommandRepositoryManagerMock;
+
+mod common;
+
+
+fn conspect(definition: Option<&str>, stages: &[i64], connections: &[(&str, &str)]) -> Value {
+    let mut nodes = vec![
+        json!({ "id": "a", "x": 0, "y": 0, "node_type": "Header", "body": { "header": "a" } }),
+        json!({
+            "id": "s",
+            "x": 0,
+            "y": 100,
+            "node_type": "ProcessStages",
+            "body": { "header": "s", "stages": stages.iter().map(|id| json!({ "id": id, "name": "" })).collect::<Vec<_>>() },
+        }),
+    ];
+
+    if let Some(definition) = definition {
+        nodes.push(json!({ "id": "b", "x": 0, "y": 200, "node_type": "Definition", "body": { "header": "b", "definition": definition } }));
+    }
+
+    let connections: Vec<Value> = connections
+        .iter()
+        .map(|(from, to)| json!({ "from": from, "to": to }))
+        .collect();
+
+    json!({ "nodes": nodes, "connections": connections })
+}
+
+fn ex_comp(id: i64, state: ExerciseCompletionState, body: Value) -> ExerciseCompletion {
+    ExerciseCompletion {
+        id,
+        exercise_id: 1,
+        user_id: id,
+        points_scored: None,
+        max_points: None,
+        number_of_attempts: 0,
+        date_started: 0,
+        date_last_changes: None,
+        state,
+        body: Some(body),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_mistake_statistics() {
+    let answer_connections = [("a", "b"), ("a", "s")];
+
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise 1".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: conspect(Some("Definition"), &[1, 2], &answer_connections),
+        exercise_body: json!({ "nodes": [], "connections": [] }),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let ex_comps = vec![
+        ex_comp(1, ExerciseCompletionState::Succeeded, conspect(Some("Definition"), &[1, 2], &answer_connections)),
+        ex_comp(2, ExerciseCompletionState::Failed, conspect(Some(" "), &[2, 1], &[("a", "b"), ("b", "s")])),
+        ex_comp(3, ExerciseCompletionState::Failed, conspect(None, &[1, 2], &[("a", "b")])),
+        // Ungraded attempts are not counted.
+        ex_comp(4, ExerciseCompletionState::InProgress, conspect(None, &[], &[])),
+        ex_comp(5, ExerciseCompletionState::PendingReview, conspect(None, &[], &[])),
+        // Neither are bodies that don't parse.
+        ex_comp(6, ExerciseCompletionState::Failed, json!({ "nodes": "broken" })),
+    ];
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+    let repository_manager = Arc::new(CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], ex_comps));
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager);
+    let ctx = Ctx::new(1).unwrap();
+
+    let statistics = exercise_interactor.get_mistake_statistics(&ctx, 1).await.unwrap();
+    assert_eq!(statistics.len(), 1);
+
+    let statistics = &statistics[0];
+    assert_eq!(statistics.number_of_completions, 3);
+
+    let missing: Vec<(&str, &str, i64)> = statistics.missing_connections
+        .iter()
+        .map(|mistake| (mistake.from.as_str(), mistake.to.as_str(), mistake.count))
+        .collect();
+    assert_eq!(missing, vec![("a", "s", 2)]);
+
+    let extra: Vec<(&str, &str, i64)> = statistics.extra_connections
+        .iter()
+        .map(|mistake| (mistake.from.as_str(), mistake.to.as_str(), mistake.count))
+        .collect();
+    assert_eq!(extra, vec![("b", "s", 1)]);
+
+    let stages: Vec<(&str, usize, i64)> = statistics.wrong_stage_positions
+        .iter()
+        .map(|mistake| (mistake.node_id.as_str(), mistake.position, mistake.count))
+        .collect();
+    assert_eq!(stages, vec![("s", 0, 1), ("s", 1, 1)]);
+
+    let blank: Vec<(&str, i64)> = statistics.blank_nodes
+        .iter()
+        .map(|mistake| (mistake.node_id.as_str(), mistake.count))
+        .collect();
+    assert_eq!(blank, vec![("b", 2)]);
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{error::{CoreError, ExerciseError, LessonProgressError}, student::{exercise::StudentExerciseInteractor, lesson::StudentLessonInteractor}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{exam::Exam, exercise::Exercise, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+
+use crate::common::{fixtures::{self, conspect}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+const EXAM_LESSON_ID: i64 = 2;
+
+
+fn exercise(lesson_id: i64) -> Exercise {
+    Exercise {
+        time_to_complete: Some(60),
+        ..fixtures::exercise(lesson_id)
+    }
+}
+
+/// Two lessons with one exercise each, lesson 2 is an exam.
+fn repository_manager(lesson_progresses: Vec<LessonProgress>) -> Arc<CommandRepositoryManagerMock> {
+    let lessons = (1..=2)
+        .map(|id| Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id: None, lesson_order: id as i32 })
+        .collect();
+
+    let exam = Exam {
+        lesson_id: EXAM_LESSON_ID,
+        opens_at: 0,
+        closes_at: i64::MAX,
+        results_released: false,
+    };
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise(1)), (2, exercise(2))], Vec::new())
+        .with_exams(vec![exam])
+        .with_lesson_progresses(lesson_progresses);
+
+    Arc::new(repository_manager)
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_lesson() {
+    let repository_manager = repository_manager(Vec::new());
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = lesson_interactor.pause_lesson(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::LessonProgressError(LessonProgressError::LessonProgressNotFound { lesson_id: 1 }))));
+
+    lesson_interactor.start_lesson(&ctx, 1).await.unwrap();
+    let ex_comp_id = exercise_interactor.start_exercise(&ctx, 1, false).await.unwrap();
+
+    lesson_interactor.pause_lesson(&ctx, 1).await.unwrap();
+
+    let lesson_progresses = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap();
+    assert_eq!(lesson_progresses[0].state, LessonProgressState::Pause);
+    assert!(lesson_progresses[0].date_paused.is_some());
+
+    // Nothing can be saved while the lesson is paused.
+    let result = exercise_interactor.save_exercise_execution_changes(&ctx, ex_comp_id, conspect()).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::LessonProgressMustBeInProgress))));
+
+    // Nor handed in.
+    let result = exercise_interactor.complete_exercise(&ctx, ex_comp_id).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::LessonProgressMustBeInProgress))));
+
+    let result = lesson_interactor.pause_lesson(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::LessonProgressError(LessonProgressError::IncorrectStateTransition { .. }))));
+
+    lesson_interactor.resume_lesson(&ctx, 1).await.unwrap();
+
+    let lesson_progresses = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap();
+    assert_eq!(lesson_progresses[0].state, LessonProgressState::InProgress);
+    assert!(lesson_progresses[0].date_paused.is_none());
+
+    exercise_interactor.save_exercise_execution_changes(&ctx, ex_comp_id, conspect()).await.unwrap();
+    exercise_interactor.complete_exercise(&ctx, ex_comp_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_exam_cannot_be_paused() {
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager(Vec::new()));
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = lesson_interactor.pause_lesson(&ctx, EXAM_LESSON_ID).await;
+    assert!(matches!(result, Err(CoreError::LessonProgressError(LessonProgressError::ExamCannotBePaused { lesson_id: EXAM_LESSON_ID }))));
+}
+
+#[tokio::test]
+async fn test_pause_without_date_cannot_be_resumed() {
+    let lesson_progress = LessonProgress {
+        user_id: STUDENT_ID,
+        lesson_id: 1,
+        date_started: 0,
+        date_complete: None,
+        state: LessonProgressState::Pause,
+        skipped_by_placement: false,
+        date_paused: None,
+    };
+
+    let repository_manager = repository_manager(vec![lesson_progress]);
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = StudentLessonInteractor::new(repository_manager.clone()).resume_lesson(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::LessonProgressError(LessonProgressError::LessonNotPausedByStudent { lesson_id: 1 }))));
+}
+
+#[test]
+fn test_lesson_progress_state_transitions() {
+    use LessonProgressState::*;
+
+    assert!(InProgress.can_change_to(&Pause));
+    assert!(Pause.can_change_to(&InProgress));
+    assert!(Pause.can_change_to(&Done));
+    assert!(!Done.can_change_to(&Pause));
+    assert!(!Done.can_change_to(&InProgress));
+    assert!(!Locked.can_change_to(&InProgress));
+    assert!(!Locked.can_change_to(&Done));
+    assert_eq!(LessonProgressState::try_from("Pause".to_string()).unwrap(), Pause);
+}
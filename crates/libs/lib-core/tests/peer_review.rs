@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{error::{CoreError, ExerciseError}, student::peer_review::StudentPeerReviewInteractor}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{exercise::{Exercise, ExerciseDifficulty}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}, peer_review::{PeerReviewForSubmit, MAX_PEER_REVIEW_COMMENT_LENGTH}}};
+use serde_json::json;
+
+use crate::common::{fixtures::{self, conspect}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+
+fn exercise() -> Exercise {
+    Exercise {
+        exercise_body: conspect(),
+        peer_review_count: 2,
+        peer_review_rubric: json!([{ "name": "Structure", "max_points": 6.0 }, { "name": "Clarity", "max_points": 4.0 }]),
+        ..fixtures::exercise(1)
+    }
+}
+
+/// Every user `1..=number_of_students` has one finished attempt with id equal to the user id.
+fn repository_manager(number_of_students: i64) -> Arc<CommandRepositoryManagerMock> {
+    repository_manager_for(number_of_students, exercise())
+}
+
+fn repository_manager_for(number_of_students: i64, exercise: Exercise) -> Arc<CommandRepositoryManagerMock> {
+    let ex_comps = (1..=number_of_students)
+        .map(|user_id| ExerciseCompletion {
+            id: user_id,
+            exercise_id: 1,
+            user_id,
+            points_scored: Some(35.0),
+            max_points: Some(35.0),
+            number_of_attempts: 0,
+            date_started: 0,
+            date_last_changes: None,
+            state: ExerciseCompletionState::Succeeded,
+            body: Some(conspect()),
+            review_comment: None,
+            is_practice: false,
+            paused_seconds: 0,
+        })
+        .collect();
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    // The lesson of user 1 is done thanks to the attempt the peers grade.
+    let lesson_progress = LessonProgress {
+        user_id: 1,
+        lesson_id: 1,
+        date_started: 0,
+        date_complete: None,
+        state: LessonProgressState::Done,
+        skipped_by_placement: false,
+        date_paused: None,
+    };
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], ex_comps)
+        .with_lesson_progresses(vec![lesson_progress]);
+
+    Arc::new(repository_manager)
+}
+
+#[tokio::test]
+async fn test_assignments_are_balanced_without_self_review() {
+    let number_of_students = 5;
+    let repository_manager = repository_manager(number_of_students);
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager.clone());
+
+    for user_id in 1..=number_of_students {
+        let ctx = Ctx::new(user_id).unwrap();
+
+        let assignments = peer_review_interactor.get_peer_review_assignments(&ctx, 1).await.unwrap();
+        assert_eq!(assignments.len(), 2);
+
+        // Fetching again does not hand out more work.
+        let assignments = peer_review_interactor.get_peer_review_assignments(&ctx, 1).await.unwrap();
+        assert_eq!(assignments.len(), 2);
+    }
+
+    let peer_reviews = repository_manager.get_peer_reviews();
+    assert!(peer_reviews.iter().all(|peer_review| peer_review.reviewer_id != peer_review.exercise_completion_id));
+
+    for ex_comp_id in 1..=number_of_students {
+        let load = peer_reviews.iter().filter(|peer_review| peer_review.exercise_completion_id == ex_comp_id).count();
+        assert_eq!(load, 2);
+    }
+}
+
+#[tokio::test]
+async fn test_final_grade_blends_median_peer_score() {
+    let repository_manager = repository_manager(4);
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager.clone());
+
+    let mut scores = vec![vec![3.0, 2.0], vec![6.0, 1.0], vec![1.0, 1.0]].into_iter();
+
+    // Users 2..=4 review until each of them has scored the attempt of user 1 once.
+    for user_id in 2..=4 {
+        let ctx = Ctx::new(user_id).unwrap();
+
+        let assignments = peer_review_interactor.get_peer_review_assignments(&ctx, 1).await.unwrap();
+        let peer_reviews = repository_manager.get_peer_reviews();
+
+        let assignment = assignments
+            .iter()
+            .find(|assignment| {
+                peer_reviews
+                    .iter()
+                    .any(|peer_review| peer_review.id == assignment.peer_review_id && peer_review.exercise_completion_id == 1)
+            });
+
+        let Some(assignment) = assignment else { continue };
+
+        let peer_review_for_s = PeerReviewForSubmit {
+            id: assignment.peer_review_id,
+            rubric_scores: scores.next().unwrap(),
+            comment: None,
+        };
+
+        peer_review_interactor.submit_peer_review(&ctx, peer_review_for_s).await.unwrap();
+    }
+
+    let submitted: Vec<f32> = repository_manager
+        .get_peer_reviews()
+        .iter()
+        .filter(|peer_review| peer_review.exercise_completion_id == 1)
+        .filter_map(|peer_review| peer_review.score)
+        .collect();
+    assert!(!submitted.is_empty());
+
+    let mut sorted = submitted.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) { (sorted[middle - 1] + sorted[middle]) / 2.0 } else { sorted[middle] };
+
+    let points_scored = repository_manager.get_exercise_completion(1).points_scored.unwrap();
+    assert!((points_scored - 35.0 * (0.5 + 0.5 * median)).abs() < 1e-4);
+}
+
+#[tokio::test]
+async fn test_submit_validation() {
+    let repository_manager = repository_manager(3);
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager.clone());
+
+    let ctx = Ctx::new(1).unwrap();
+    let assignments = peer_review_interactor.get_peer_review_assignments(&ctx, 1).await.unwrap();
+    let peer_review_id = assignments[0].peer_review_id;
+
+    let result = peer_review_interactor
+        .submit_peer_review(&ctx, PeerReviewForSubmit { id: peer_review_id, rubric_scores: vec![7.0, 1.0], comment: None })
+        .await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::IncorrectRubricScores))));
+
+    let comment = Some("ы".repeat(MAX_PEER_REVIEW_COMMENT_LENGTH + 1));
+    let result = peer_review_interactor
+        .submit_peer_review(&ctx, PeerReviewForSubmit { id: peer_review_id, rubric_scores: vec![1.0, 1.0], comment })
+        .await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::ReviewCommentTooLong { length: 1025, max_length: 1024 }))));
+
+    let other_ctx = Ctx::new(2).unwrap();
+    let result = peer_review_interactor
+        .submit_peer_review(&other_ctx, PeerReviewForSubmit { id: peer_review_id, rubric_scores: vec![1.0, 1.0], comment: None })
+        .await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::PeerReviewAccessDenied { .. }))));
+
+    peer_review_interactor
+        .submit_peer_review(&ctx, PeerReviewForSubmit { id: peer_review_id, rubric_scores: vec![1.0, 1.0], comment: None })
+        .await
+        .unwrap();
+
+    let result = peer_review_interactor
+        .submit_peer_review(&ctx, PeerReviewForSubmit { id: peer_review_id, rubric_scores: vec![1.0, 1.0], comment: None })
+        .await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::PeerReviewHasAlreadyBeenSubmitted))));
+}
+
+/// Users 2 and 3 score the attempt of user 1 with the given rubric scores.
+async fn peer_grade(repository_manager: &Arc<CommandRepositoryManagerMock>, rubric_scores: Vec<f32>) {
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager.clone());
+
+    for user_id in 2..=3 {
+        let ctx = Ctx::new(user_id).unwrap();
+        peer_review_interactor.get_peer_review_assignments(&ctx, 1).await.unwrap();
+
+        let peer_review = repository_manager
+            .get_peer_reviews()
+            .into_iter()
+            .find(|peer_review| peer_review.reviewer_id == user_id && peer_review.exercise_completion_id == 1)
+            .unwrap();
+
+        let peer_review_for_s = PeerReviewForSubmit {
+            id: peer_review.id,
+            rubric_scores: rubric_scores.clone(),
+            comment: None,
+        };
+
+        peer_review_interactor.submit_peer_review(&ctx, peer_review_for_s).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_peer_grade_keeps_attempt_max_points_and_updates_state() {
+    // The exercise got harder after the attempts were made.
+    let repository_manager = repository_manager_for(3, Exercise { difficult: ExerciseDifficulty::Hard, ..exercise() });
+
+    peer_grade(&repository_manager, vec![0.0, 0.0]).await;
+
+    let ex_comp = repository_manager.get_exercise_completion(1);
+    assert!((ex_comp.points_scored.unwrap() - 35.0 * 0.5).abs() < 1e-4);
+    assert!(ex_comp.state == ExerciseCompletionState::Failed);
+
+    // The failed attempt no longer completes the lesson.
+    let lesson_progress = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&Ctx::root_ctx(), 1, 1)
+        .await
+        .unwrap()
+        .remove(0);
+    assert!(lesson_progress.state == LessonProgressState::InProgress);
+}
+
+#[tokio::test]
+async fn test_reviewed_exercise_keeps_creator_grade() {
+    let repository_manager = repository_manager_for(3, Exercise { manual_review: true, ..exercise() });
+
+    peer_grade(&repository_manager, vec![0.0, 0.0]).await;
+
+    let ex_comp = repository_manager.get_exercise_completion(1);
+    assert_eq!(ex_comp.points_scored, Some(35.0));
+    assert!(ex_comp.state == ExerciseCompletionState::Succeeded);
+}
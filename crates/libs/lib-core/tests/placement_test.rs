@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{error::{CoreError, CourseError}, student::lesson::StudentLessonInteractor}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{exam::Exam, lesson::Lesson, placement_test::{PlacementAnswer, PlacementLevel, PlacementTest}}};
+
+use crate::common::{fixtures::{conspect, exercise}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+const EXAM_LESSON_ID: i64 = 3;
+
+
+
+/// Four lessons with one exercise each, lesson 3 is an exam.
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = (1..=4)
+        .map(|id| Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id: None, lesson_order: id as i32 })
+        .collect();
+
+    let exercises = (1..=4).map(|id| (id, exercise(id))).collect();
+
+    let exam = Exam {
+        lesson_id: EXAM_LESSON_ID,
+        opens_at: 0,
+        closes_at: 0,
+        results_released: false,
+    };
+
+    let placement_test = PlacementTest {
+        course_id: 1,
+        exercise_ids: vec![1, 2],
+        levels: vec![
+            PlacementLevel { min_score: 0.5, lessons_to_skip: 1 },
+            PlacementLevel { min_score: 1.0, lessons_to_skip: 4 },
+        ],
+    };
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, exercises, Vec::new())
+        .with_exams(vec![exam])
+        .with_placement_test(placement_test);
+
+    Arc::new(repository_manager)
+}
+
+fn answer(exercise_id: i64) -> PlacementAnswer {
+    PlacementAnswer {
+        exercise_id,
+        body: conspect(),
+    }
+}
+
+#[tokio::test]
+async fn test_placement_skips_lessons_up_to_exam() {
+    let repository_manager = repository_manager();
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = lesson_interactor.take_placement_test(&ctx, 1, vec![answer(1), answer(2)]).await.unwrap();
+
+    assert!((result.score - 1.0).abs() < 1e-6);
+    // The top level skips every lesson, but the exam has to be taken anyway.
+    assert_eq!(result.skipped_lesson_ids, vec![1, 2]);
+
+    let lesson_progresses = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap();
+    assert_eq!(lesson_progresses.len(), 2);
+    assert!(lesson_progresses.iter().all(|lesson_progress| lesson_progress.skipped_by_placement));
+
+    let result = lesson_interactor.take_placement_test(&ctx, 1, vec![answer(1), answer(2)]).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::PlacementTestAlreadyTaken { course_id: 1 }))));
+}
+
+#[tokio::test]
+async fn test_placement_without_skips_can_not_be_retaken() {
+    let repository_manager = repository_manager();
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = lesson_interactor.take_placement_test(&ctx, 1, Vec::new()).await.unwrap();
+    assert!(result.skipped_lesson_ids.is_empty());
+
+    let attempt = repository_manager
+        .get_course_repository()
+        .get_placement_attempt(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(attempt.score, 0.0);
+
+    let result = lesson_interactor.take_placement_test(&ctx, 1, vec![answer(1), answer(2)]).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::PlacementTestAlreadyTaken { course_id: 1 }))));
+}
+
+#[tokio::test]
+async fn test_placement_score_picks_level() {
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    // Only half of the test is answered.
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager());
+    let result = lesson_interactor.take_placement_test(&ctx, 1, vec![answer(1)]).await.unwrap();
+    assert!((result.score - 0.5).abs() < 1e-6);
+    assert_eq!(result.skipped_lesson_ids, vec![1]);
+
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager());
+    let result = lesson_interactor.take_placement_test(&ctx, 1, Vec::new()).await.unwrap();
+    assert!(result.skipped_lesson_ids.is_empty());
+
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager());
+    let result = lesson_interactor.take_placement_test(&ctx, 1, vec![answer(3)]).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::PlacementAnswerNotInTest { exercise_id: 3 }))));
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{error::{CoreError, ExerciseError}, student::exercise::StudentExerciseInteractor}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{exercise::{Exercise, ExerciseDifficulty, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+use serde_json::json;
+
+use crate::common::{fixtures::conspect, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+
+
+fn repository_manager(state: ExerciseCompletionState, lesson_state: LessonProgressState) -> Arc<CommandRepositoryManagerMock> {
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise 1".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: conspect(),
+        exercise_body: json!({ "nodes": [], "connections": [] }),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: true,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let ex_comp = ExerciseCompletion {
+        id: 1,
+        exercise_id: 1,
+        user_id: STUDENT_ID,
+        points_scored: Some(35.0),
+        max_points: Some(35.0),
+        number_of_attempts: 0,
+        date_started: 0,
+        date_last_changes: None,
+        state,
+        body: Some(conspect()),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    };
+
+    let lesson_progress = LessonProgress {
+        user_id: STUDENT_ID,
+        lesson_id: 1,
+        date_started: 0,
+        date_complete: None,
+        state: lesson_state,
+        skipped_by_placement: false,
+        date_paused: None,
+    };
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise)], vec![ex_comp])
+        .with_lesson_progresses(vec![lesson_progress]);
+
+    Arc::new(repository_manager)
+}
+
+#[tokio::test]
+async fn test_practice_attempt_on_done_lesson() {
+    let repository_manager = repository_manager(ExerciseCompletionState::Succeeded, LessonProgressState::Done);
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let ex_comp_id = exercise_interactor.start_exercise(&ctx, 1, true).await.unwrap();
+    exercise_interactor.save_exercise_execution_changes(&ctx, ex_comp_id, conspect()).await.unwrap();
+
+    // Graded as usual, but the manual review of the exercise is skipped.
+    let estimate = exercise_interactor.complete_exercise(&ctx, ex_comp_id).await.unwrap();
+    assert!(estimate.state == ExerciseCompletionState::Succeeded);
+
+    let ex_comp = repository_manager.get_exercise_completion(ex_comp_id);
+    assert!(ex_comp.is_practice);
+    assert_eq!(ex_comp.number_of_attempts, 1);
+
+    let lesson_progress = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(lesson_progress.state, LessonProgressState::Done);
+
+    // The practice attempt is not counted as an attempt.
+    let ex_comp_id = exercise_interactor.start_exercise(&ctx, 1, false).await.unwrap();
+    assert_eq!(repository_manager.get_exercise_completion(ex_comp_id).number_of_attempts, 1);
+}
+
+#[tokio::test]
+async fn test_practice_requires_succeeded_attempt() {
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager(ExerciseCompletionState::Failed, LessonProgressState::InProgress));
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let result = exercise_interactor.start_exercise(&ctx, 1, true).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::PracticeRequiresSucceededAttempt { exercise_id: 1 }))));
+
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager(ExerciseCompletionState::Succeeded, LessonProgressState::Pause));
+    let result = exercise_interactor.start_exercise(&ctx, 1, true).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::LessonProgressMustBeInProgress))));
+}
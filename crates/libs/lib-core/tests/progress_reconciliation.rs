@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::{course::CreatorCourseInteractor, exercise::CreatorExerciseInteractor, lesson::CreatorLessonInteractor}, error::{CoreError, LessonProgressError}, student::{exercise::StudentExerciseInteractor, lesson::StudentLessonInteractor}}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{course::CourseState, exercise::{Exercise, ExerciseDifficulty, ExerciseForCreate, ExerciseForUpdate, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson, lesson_progress::{LessonProgress, LessonProgressState}}};
+use serde_json::json;
+
+use crate::common::{fixtures::{self, conspect, course}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const ADMIN_ID: i64 = 1000;
+const CREATOR_ID: i64 = 1;
+const STUDENT_ID: i64 = 2;
+
+
+fn exercise(lesson_id: i64) -> Exercise {
+    Exercise {
+        exercise_body: conspect(),
+        ..fixtures::exercise(lesson_id)
+    }
+}
+
+fn succeeded(id: i64, exercise_id: i64) -> ExerciseCompletion {
+    ExerciseCompletion {
+        id,
+        exercise_id,
+        user_id: STUDENT_ID,
+        points_scored: Some(1.0),
+        max_points: Some(1.0),
+        number_of_attempts: 1,
+        date_started: 0,
+        date_last_changes: None,
+        state: ExerciseCompletionState::Succeeded,
+        body: Some(conspect()),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    }
+}
+
+fn lesson_progress(lesson_id: i64, state: LessonProgressState) -> LessonProgress {
+    LessonProgress {
+        user_id: STUDENT_ID,
+        lesson_id,
+        date_started: 0,
+        date_complete: None,
+        state,
+        skipped_by_placement: false,
+        date_paused: None,
+    }
+}
+
+/// Two lessons with one solved exercise each, the second lesson got stuck in progress.
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = (1..=2)
+        .map(|id| Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id: None, lesson_order: id as i32 })
+        .collect();
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(
+        lessons,
+        vec![(1, exercise(1)), (2, exercise(2))],
+        vec![succeeded(1, 1), succeeded(2, 2)],
+    ).with_lesson_progresses(vec![
+        lesson_progress(1, LessonProgressState::Done),
+        lesson_progress(2, LessonProgressState::InProgress),
+    ])
+    .with_courses(vec![course(1, CourseState::Draft)]);
+
+    Arc::new(repository_manager)
+}
+
+async fn states(repository_manager: &CommandRepositoryManagerMock) -> Vec<(i64, LessonProgressState)> {
+    let ctx = Ctx::root_ctx();
+
+    let mut result: Vec<(i64, LessonProgressState)> = repository_manager
+        .get_lesson_repository()
+        .get_lessons_progresses(&ctx, 1, STUDENT_ID)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|lesson_progress| (lesson_progress.lesson_id, lesson_progress.state))
+        .collect();
+    result.sort_by_key(|(lesson_id, _)| *lesson_id);
+
+    result
+}
+
+#[tokio::test]
+async fn test_admin_reconciles_course() {
+    let repository_manager = repository_manager();
+    let course_interactor = CreatorCourseInteractor::new(repository_manager.clone());
+
+    let result = course_interactor.reconcile_progress(&Ctx::new(CREATOR_ID).unwrap(), 1).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    let result = course_interactor.reconcile_progress(&Ctx::new(ADMIN_ID).unwrap(), 1).await.unwrap();
+    assert_eq!((result.lessons_completed, result.lessons_reopened, result.students_completed_course), (1, 0, 1));
+    assert_eq!(states(&repository_manager).await, vec![(1, LessonProgressState::Done), (2, LessonProgressState::Done)]);
+
+    // Nothing is left to fix the second time.
+    let result = course_interactor.reconcile_progress(&Ctx::new(ADMIN_ID).unwrap(), 1).await.unwrap();
+    assert_eq!((result.lessons_completed, result.lessons_reopened, result.students_completed_course), (0, 0, 1));
+}
+
+#[tokio::test]
+async fn test_new_exercise_reopens_finished_lesson() {
+    let repository_manager = repository_manager();
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+
+    let exercise_for_c = ExerciseForCreate {
+        lesson_id: 1,
+        title: "New exercise".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: conspect(),
+        exercise_body: conspect(),
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    exercise_interactor.create_exercise(&Ctx::new(CREATOR_ID).unwrap(), exercise_for_c).await.unwrap();
+
+    // The stuck second lesson is completed by the same pass.
+    assert_eq!(states(&repository_manager).await, vec![(1, LessonProgressState::InProgress), (2, LessonProgressState::Done)]);
+}
+
+#[tokio::test]
+async fn test_retake_locks_later_lessons() {
+    let repository_manager = repository_manager();
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager.clone());
+
+    let exercise_for_u = ExerciseForUpdate {
+        id: 1,
+        title: Some("Harder exercise".to_string()),
+        description: None,
+        exercise_type: None,
+        answer_body: None,
+        exercise_body: None,
+        difficult: None,
+        time_to_complete: None,
+        manual_review: None,
+        auto_difficulty: None,
+        peer_review_count: None,
+        peer_review_rubric: None,
+    };
+
+    exercise_interactor.update_exercise(&Ctx::new(CREATOR_ID).unwrap(), exercise_for_u, true).await.unwrap();
+
+    // The second lesson is solved, still the student goes back to the first one.
+    assert_eq!(states(&repository_manager).await, vec![(1, LessonProgressState::InProgress), (2, LessonProgressState::Locked)]);
+
+    // Neither a later reconciliation nor the student lift the lock.
+    CreatorCourseInteractor::new(repository_manager.clone())
+        .reconcile_progress(&Ctx::new(ADMIN_ID).unwrap(), 1)
+        .await
+        .unwrap();
+    assert_eq!(states(&repository_manager).await, vec![(1, LessonProgressState::InProgress), (2, LessonProgressState::Locked)]);
+
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+    let result = StudentLessonInteractor::new(repository_manager.clone()).resume_lesson(&ctx, 2).await;
+    assert!(matches!(result, Err(CoreError::LessonProgressError(LessonProgressError::IncorrectStateTransition { .. }))));
+
+    // Retaking the first lesson unlocks the second one, which is still solved.
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager.clone());
+    let ex_comp_id = exercise_interactor.start_exercise(&ctx, 1, false).await.unwrap();
+    exercise_interactor.save_exercise_execution_changes(&ctx, ex_comp_id, conspect()).await.unwrap();
+    exercise_interactor.complete_exercise(&ctx, ex_comp_id).await.unwrap();
+
+    assert_eq!(states(&repository_manager).await, vec![(1, LessonProgressState::Done), (2, LessonProgressState::Done)]);
+}
+
+#[tokio::test]
+async fn test_deleted_lesson_reconciles_course() {
+    let repository_manager = repository_manager();
+
+    CreatorLessonInteractor::new(repository_manager.clone())
+        .delete_lesson(&Ctx::new(CREATOR_ID).unwrap(), 1)
+        .await
+        .unwrap();
+
+    // The stuck lesson is the only one left and it is solved.
+    assert_eq!(states(&repository_manager).await, vec![(2, LessonProgressState::Done)]);
+}
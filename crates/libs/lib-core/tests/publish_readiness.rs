@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::course::CreatorCourseInteractor, error::{CoreError, CourseError}}, models::{course::{Course, CourseState}, exercise::Exercise, lesson::Lesson, publish_readiness::ReadinessIssueKind, section::Section}};
+use serde_json::{json, Value};
+
+use crate::common::{fixtures::{self, conspect}, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+
+fn course(description: &str) -> Course {
+    Course {
+        description: description.to_string(),
+        ..fixtures::course(1, CourseState::Draft)
+    }
+}
+
+
+fn exercise(lesson_id: i64, body: Value) -> Exercise {
+    Exercise {
+        answer_body: body.clone(),
+        exercise_body: body,
+        ..fixtures::exercise(lesson_id)
+    }
+}
+
+#[tokio::test]
+async fn test_readiness_checklist() {
+    let lessons = (1..=3)
+        .map(|id| Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id: None, lesson_order: id as i32 })
+        .collect();
+
+    // Lesson 1 is empty, lesson 2 has an exercise saved before the validator got stricter.
+    let exercises = vec![
+        (1, exercise(2, json!({ "nodes": [], "connections": [] }))),
+        (2, exercise(3, conspect())),
+    ];
+
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new_with_exercises(lessons, exercises, Vec::new())
+            .with_courses(vec![course(" ")])
+            .with_sections(vec![Section { id: 1, course_id: 1, title: "Section".to_string(), section_order: 1 }])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let readiness = interactor.check_publish_readiness(&ctx, 1).await.unwrap();
+    assert!(!readiness.is_ready());
+
+    let errors: Vec<_> = readiness.errors.iter().map(|issue| (issue.kind, issue.lesson_id, issue.exercise_id)).collect();
+    assert_eq!(errors, vec![
+        (ReadinessIssueKind::MissingDescription, None, None),
+        (ReadinessIssueKind::EmptyLesson, Some(1), None),
+        (ReadinessIssueKind::InvalidExercise, Some(2), Some(1)),
+    ]);
+
+    let warnings: Vec<_> = readiness.warnings.iter().map(|issue| (issue.kind, issue.section_id)).collect();
+    assert_eq!(warnings, vec![
+        (ReadinessIssueKind::MissingImage, None),
+        (ReadinessIssueKind::EmptySection, Some(1)),
+    ]);
+
+    let result = interactor.publish_course(&ctx, 1).await;
+    let Err(CoreError::CourseError(CourseError::CourseIsNotReadyToPublish { course_id: 1, issues })) = result else {
+        panic!("the course must not be published");
+    };
+    assert_eq!(issues, vec!["missing_description", "empty_lesson", "invalid_exercise"]);
+}
+
+#[tokio::test]
+async fn test_warnings_do_not_block_publishing() {
+    let lessons = vec![
+        Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise(1, conspect()))], Vec::new())
+            .with_courses(vec![course("Description")])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let readiness = interactor.check_publish_readiness(&ctx, 1).await.unwrap();
+    assert!(readiness.is_ready());
+    assert_eq!(readiness.warnings.len(), 1);
+    assert_eq!(readiness.warnings[0].kind, ReadinessIssueKind::MissingImage);
+
+    interactor.publish_course(&ctx, 1).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_course_without_lessons() {
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(Vec::new())
+            .with_courses(vec![course("Description")])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let readiness = interactor.check_publish_readiness(&ctx, 1).await.unwrap();
+    let errors: Vec<_> = readiness.errors.iter().map(|issue| issue.kind).collect();
+    assert_eq!(errors, vec![ReadinessIssueKind::NoLessons]);
+}
+
+#[tokio::test]
+async fn test_restore_checks_readiness() {
+    let repository_manager = Arc::new(
+        CommandRepositoryManagerMock::new(Vec::new())
+            .with_courses(vec![Course { state: CourseState::Archived, ..course("Description") }])
+    );
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let result = interactor.restore_course(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::CourseError(CourseError::CourseIsNotReadyToPublish { .. }))));
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::{lesson::CreatorLessonInteractor, section::CreatorSectionInteractor}, error::{CoreError, LessonError}, student::lesson::StudentLessonInteractor}, interfaces::command_repository_manager::ICommandRepositoryManager, models::{course::CourseState, lesson::{Lesson, LessonForChangeOreder, LessonForChangeSection}, lesson_progress::LessonProgressState, section::{Section, SectionForChangeOrder, SectionForCreate}}};
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const CREATOR_ID: i64 = 1;
+const STUDENT_ID: i64 = 2;
+
+fn lesson(id: i64, section_id: Option<i64>, lesson_order: i32) -> Lesson {
+    Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id, lesson_order }
+}
+
+fn section(id: i64, section_order: i32) -> Section {
+    Section { id, course_id: 1, title: format!("Section {id}"), section_order }
+}
+
+/// Lesson 1 is outside sections, section 10 holds lessons 2 and 3, section 20 holds lesson 4.
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        lesson(4, Some(20), 1),
+        lesson(3, Some(10), 2),
+        lesson(2, Some(10), 1),
+        lesson(1, None, 1),
+    ];
+
+    let repository_manager = CommandRepositoryManagerMock::new(lessons)
+        .with_courses(vec![course(1, CourseState::Draft)])
+        .with_sections(vec![section(20, 2), section(10, 1)]);
+
+    Arc::new(repository_manager)
+}
+
+async fn course_order(repository_manager: &CommandRepositoryManagerMock) -> Vec<i64> {
+    repository_manager
+        .get_lesson_repository()
+        .get_course_lessons_ordered(&Ctx::root_ctx(), 1)
+        .await
+        .unwrap()
+        .iter()
+        .map(|lesson| lesson.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sections_order_lessons() {
+    let repository_manager = repository_manager();
+    let section_interactor = CreatorSectionInteractor::new(repository_manager.clone());
+    let lesson_interactor = CreatorLessonInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    assert_eq!(course_order(&repository_manager).await, vec![1, 2, 3, 4]);
+
+    // The section moves with its lessons.
+    section_interactor.change_order(&ctx, SectionForChangeOrder { id: 20, order: 1 }).await.unwrap();
+    assert_eq!(course_order(&repository_manager).await, vec![1, 4, 2, 3]);
+
+    // Lessons are ordered within their section only.
+    lesson_interactor.change_order(&ctx, LessonForChangeOreder { id: 3, order: 1 }).await.unwrap();
+    assert_eq!(course_order(&repository_manager).await, vec![1, 4, 3, 2]);
+
+    let result = lesson_interactor.change_order(&ctx, LessonForChangeOreder { id: 3, order: 3 }).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectLessonOreder { lesson_id: 3, order: 3 }))));
+
+    let result = section_interactor.change_order(&ctx, SectionForChangeOrder { id: 20, order: 3 }).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::IncorrectSectionOrder { section_id: 20, order: 3 }))));
+}
+
+#[tokio::test]
+async fn test_change_lesson_section() {
+    let repository_manager = repository_manager();
+    let section_interactor = CreatorSectionInteractor::new(repository_manager.clone());
+    let lesson_interactor = CreatorLessonInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(CREATOR_ID).unwrap();
+
+    let section_for_c = SectionForCreate {
+        course_id: 1,
+        title: "Section".to_string(),
+        description: "".to_string(),
+    };
+    let section_id = section_interactor.create_section(&ctx, section_for_c).await.unwrap();
+
+    lesson_interactor.change_section(&ctx, LessonForChangeSection { id: 2, section_id: Some(section_id) }).await.unwrap();
+    assert_eq!(course_order(&repository_manager).await, vec![1, 3, 4, 2]);
+
+    // The gap left in the first section is closed.
+    let lesson = repository_manager.get_lesson_repository().get_lesson(&ctx, 3).await.unwrap();
+    assert_eq!(lesson.lesson_order, 1);
+
+    let result = section_interactor.delete_section(&ctx, 10).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::SectionIsNotEmpty { section_id: 10 }))));
+
+    lesson_interactor.change_section(&ctx, LessonForChangeSection { id: 3, section_id: None }).await.unwrap();
+    section_interactor.delete_section(&ctx, 10).await.unwrap();
+
+    // Sections after the deleted one move up.
+    let sections = repository_manager.get_lesson_repository().get_course_sections_ordered(&ctx, 1).await.unwrap();
+    assert_eq!(sections, vec![SectionForChangeOrder { id: 20, order: 1 }, SectionForChangeOrder { id: section_id, order: 2 }]);
+    assert_eq!(course_order(&repository_manager).await, vec![1, 3, 4, 2]);
+}
+
+#[tokio::test]
+async fn test_sequential_unlock_crosses_sections() {
+    let repository_manager = repository_manager();
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let lesson_locks = lesson_interactor.get_lesson_locks(&ctx, 1).await.unwrap();
+    let locked: Vec<i64> = lesson_locks.iter().map(|lesson_lock| lesson_lock.lesson_id).collect();
+    assert_eq!(locked, vec![2, 3, 4]);
+
+    lesson_interactor.start_lesson(&ctx, 1).await.unwrap();
+    let result = lesson_interactor.start_lesson(&ctx, 2).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::PreviousLessonNotCompleted { lesson_id: 2 }))));
+
+    repository_manager
+        .get_lesson_repository()
+        .update_lesson_progress_state(&ctx, LessonProgressState::Done, 1, STUDENT_ID)
+        .await
+        .unwrap();
+
+    // The first lesson of a section waits for the lesson before the section.
+    lesson_interactor.start_lesson(&ctx, 2).await.unwrap();
+}
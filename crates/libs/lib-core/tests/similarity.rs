@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{creator::exercise::CreatorExerciseInteractor, cron_job_similarity::CronJobSimilarity}, models::{exercise::{Exercise, ExerciseDifficulty, ExerciseType}, exercise_completion::{ExerciseCompletion, ExerciseCompletionState}, lesson::Lesson}};
+use serde_json::{json, Value};
+
+use crate::common::repository_manager::CommandRepositoryManagerMock;
+
+mod common;
+
+
+fn header(id: &str) -> Value {
+    json!({ "id": id, "x": 0, "y": 0, "node_type": "Header", "body": { "header": id } })
+}
+
+fn definition(id: &str, definition: &str) -> Value {
+    json!({ "id": id, "x": 0, "y": 0, "node_type": "Definition", "body": { "header": id, "definition": definition } })
+}
+
+fn ex_comp(id: i64, user_id: i64, state: ExerciseCompletionState, body: Value) -> ExerciseCompletion {
+    ExerciseCompletion {
+        id,
+        exercise_id: 1,
+        user_id,
+        points_scored: None,
+        max_points: None,
+        number_of_attempts: 0,
+        date_started: 0,
+        date_last_changes: None,
+        state,
+        body: Some(body),
+        review_comment: None,
+        is_practice: false,
+        paused_seconds: 0,
+    }
+}
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let exercise_body = json!({
+        "nodes": [header("a"), header("b")],
+        "connections": [],
+    });
+
+    let exercise = Exercise {
+        lesson_id: 1,
+        title: "Exercise 1".to_string(),
+        description: "".to_string(),
+        exercise_type: ExerciseType::Conspect,
+        answer_body: exercise_body.clone(),
+        exercise_body,
+        difficult: ExerciseDifficulty::Easy,
+        time_to_complete: None,
+        exercise_order: 1,
+        manual_review: false,
+        auto_difficulty: false,
+        peer_review_count: 0,
+        peer_review_rubric: json!([]),
+    };
+
+    let copied = json!({
+        "nodes": [header("a"), header("b"), definition("c", "Energy  is conserved")],
+        "connections": [{ "from": "a", "to": "c" }, { "from": "c", "to": "b" }],
+    });
+
+    let copied_with_other_case = json!({
+        "nodes": [header("a"), header("b"), definition("c", "energy is conserved")],
+        "connections": [{ "from": "a", "to": "c" }, { "from": "c", "to": "b" }],
+    });
+
+    let original = json!({
+        "nodes": [header("a"), header("b"), definition("d", "Mass grows with speed")],
+        "connections": [{ "from": "b", "to": "d" }],
+    });
+
+    let ex_comps = vec![
+        ex_comp(1, 1, ExerciseCompletionState::Succeeded, copied.clone()),
+        ex_comp(2, 2, ExerciseCompletionState::Failed, copied_with_other_case),
+        ex_comp(3, 3, ExerciseCompletionState::Succeeded, original),
+        // Unfinished attempts are not compared.
+        ex_comp(4, 4, ExerciseCompletionState::InProgress, copied),
+    ];
+
+    // Checked first, its broken body must not stop the other exercises.
+    let broken = Exercise {
+        exercise_body: json!("broken"),
+        ..exercise.clone()
+    };
+
+    let lessons = vec![Lesson { id: 1, course_id: 1, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 }];
+
+    Arc::new(CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(2, broken), (1, exercise)], ex_comps))
+}
+
+#[tokio::test]
+async fn test_similar_submissions_are_flagged() {
+    let repository_manager = repository_manager();
+    let cron_similarity = CronJobSimilarity::new(repository_manager.clone());
+
+    let report = cron_similarity.flag_similar_submissions().await.unwrap();
+    assert_eq!(report.flagged_pairs, 1);
+    assert_eq!(report.failed_exercise_ids, vec![2]);
+
+    // Running the job again replaces the flags instead of duplicating them.
+    assert_eq!(cron_similarity.flag_similar_submissions().await.unwrap().flagged_pairs, 1);
+
+    let exercise_interactor = CreatorExerciseInteractor::new(repository_manager);
+    let ctx = Ctx::new(1).unwrap();
+
+    let comparisons = exercise_interactor.get_similarity_flags(&ctx, 1).await.unwrap();
+    assert_eq!(comparisons.len(), 1);
+
+    let comparison = &comparisons[0];
+    assert_eq!((comparison.first.id, comparison.second.id), (1, 2));
+    assert!((comparison.score - 1.0).abs() < 1e-6);
+    assert!(comparison.first.body.is_some() && comparison.second.body.is_some());
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use lib_core::{ctx::Ctx, interactors::{error::{CoreError, ExerciseError, LessonError}, student::{exercise::StudentExerciseInteractor, lesson::StudentLessonInteractor}}, models::{exercise::Exercise, lesson::Lesson, unlock_policy::{DripRelease, LessonRelease, UnlockPolicy, UnlockPolicyKind}}};
+use lib_utils::time::now_utc_sec;
+
+use crate::common::{fixtures, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const STUDENT_ID: i64 = 2;
+
+fn exercise(exercise_order: i32) -> Exercise {
+    Exercise {
+        exercise_order,
+        ..fixtures::exercise(1)
+    }
+}
+
+/// Three lessons, the first one has two exercises.
+fn repository_manager(unlock_policy: Option<UnlockPolicy>) -> Arc<CommandRepositoryManagerMock> {
+    let lessons = (1..=3)
+        .map(|id| Lesson { id, course_id: 1, title: format!("Lesson {id}"), section_id: None, lesson_order: id as i32 })
+        .collect();
+
+    let repository_manager = CommandRepositoryManagerMock::new_with_exercises(lessons, vec![(1, exercise(1)), (2, exercise(2))], Vec::new());
+
+    let repository_manager = match unlock_policy {
+        Some(unlock_policy) => repository_manager.with_unlock_policy(unlock_policy),
+        None => repository_manager,
+    };
+
+    Arc::new(repository_manager)
+}
+
+#[tokio::test]
+async fn test_sequential_by_default() {
+    let repository_manager = repository_manager(None);
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager);
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let lesson_locks = lesson_interactor.get_lesson_locks(&ctx, 1).await.unwrap();
+    let locked: Vec<(i64, Option<i64>)> = lesson_locks.iter().map(|lesson_lock| (lesson_lock.lesson_id, lesson_lock.locked_until)).collect();
+    assert_eq!(locked, vec![(2, None), (3, None)]);
+
+    assert!(lesson_interactor.start_lesson(&ctx, 2).await.is_err());
+
+    lesson_interactor.start_lesson(&ctx, 1).await.unwrap();
+    let result = lesson_interactor.start_lesson(&ctx, 1).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::LessonAlreadyStarted { lesson_id: 1 }))));
+
+    let result = exercise_interactor.start_exercise(&ctx, 2, false).await;
+    assert!(matches!(result, Err(CoreError::ExerciseError(ExerciseError::PreviousExerciseNotCompleted { .. }))));
+}
+
+#[tokio::test]
+async fn test_free_navigation() {
+    let unlock_policy = UnlockPolicy {
+        course_id: 1,
+        kind: UnlockPolicyKind::Free,
+        drip_releases: Vec::new(),
+    };
+
+    let repository_manager = repository_manager(Some(unlock_policy));
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager.clone());
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager);
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    assert!(lesson_interactor.get_lesson_locks(&ctx, 1).await.unwrap().is_empty());
+
+    lesson_interactor.start_lesson(&ctx, 3).await.unwrap();
+    lesson_interactor.start_lesson(&ctx, 1).await.unwrap();
+    exercise_interactor.start_exercise(&ctx, 2, false).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_drip_release() {
+    let release_date = now_utc_sec() + 24 * 60 * 60;
+
+    let unlock_policy = UnlockPolicy {
+        course_id: 1,
+        kind: UnlockPolicyKind::Drip,
+        drip_releases: vec![
+            DripRelease { lesson_id: 2, release: LessonRelease::Date { date: release_date } },
+            // The mock registers every student at the epoch, so this one is long released.
+            DripRelease { lesson_id: 3, release: LessonRelease::DaysAfterEnrollment { days: 7 } },
+        ],
+    };
+
+    let lesson_interactor = StudentLessonInteractor::new(repository_manager(Some(unlock_policy)));
+    let ctx = Ctx::new(STUDENT_ID).unwrap();
+
+    let lesson_locks = lesson_interactor.get_lesson_locks(&ctx, 1).await.unwrap();
+    let locked: Vec<(i64, Option<i64>)> = lesson_locks.iter().map(|lesson_lock| (lesson_lock.lesson_id, lesson_lock.locked_until)).collect();
+    assert_eq!(locked, vec![(2, Some(release_date))]);
+
+    let result = lesson_interactor.start_lesson(&ctx, 2).await;
+    assert!(matches!(result, Err(CoreError::LessonError(LessonError::LessonIsLocked { lesson_id: 2, .. }))));
+
+    lesson_interactor.start_lesson(&ctx, 3).await.unwrap();
+}
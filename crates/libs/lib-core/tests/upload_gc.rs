@@ -0,0 +1,162 @@
+use std::{io::Cursor, sync::{atomic::Ordering, Arc}};
+
+use image::{DynamicImage, ImageFormat, RgbImage};
+use lib_core::{ctx::Ctx, interactors::{creator::{attachment::CreatorAttachmentInteractor, course::CreatorCourseInteractor}, cron_job_upload_gc::CronJobUploadGc, error::CoreError}, interfaces::file_storage::IFileStorage, models::{attachment::AttachmentForCreate, content_block::{ContentBlock, ContentBlockType}, course::{Course, CourseState}, lesson::Lesson}};
+use serde_json::json;
+
+use crate::common::{fixtures::course, repository_manager::CommandRepositoryManagerMock};
+
+mod common;
+
+
+const ADMIN_ID: i64 = 1000;
+const COURSE_ID: i64 = 1;
+
+fn repository_manager() -> Arc<CommandRepositoryManagerMock> {
+    let lessons = vec![
+        Lesson { id: 1, course_id: COURSE_ID, title: "Lesson 1".to_string(), section_id: None, lesson_order: 1 },
+    ];
+
+    let content_block = ContentBlock {
+        id: 1,
+        lesson_id: 1,
+        block_type: ContentBlockType::Image,
+        body: json!({ "url": "mock/public/lesson_images/diagram.png", "alt": "Diagram" }),
+        rendered_html: String::new(),
+        block_order: 1,
+    };
+
+    let repository_manager = CommandRepositoryManagerMock::new(lessons)
+        .with_courses(vec![course(COURSE_ID, CourseState::Draft)])
+        .with_content_blocks(vec![content_block]);
+
+    Arc::new(repository_manager)
+}
+
+fn png() -> Vec<u8> {
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::new(64, 36))
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+        .unwrap();
+
+    data
+}
+
+#[tokio::test]
+async fn test_orphaned_uploads_are_collected() {
+    let repository_manager = repository_manager();
+    let file_storage = repository_manager.file_storage.clone();
+    let ctx = Ctx::new(1).unwrap();
+
+    CreatorCourseInteractor::new(repository_manager.clone())
+        .set_course_img(&ctx, COURSE_ID, &png())
+        .await
+        .unwrap();
+
+    let attachment = AttachmentForCreate {
+        lesson_id: 1,
+        file_name: "slides.pdf".to_string(),
+        content_type: "application/pdf".to_string(),
+    };
+    CreatorAttachmentInteractor::new(repository_manager.clone())
+        .upload_attachment(&ctx, attachment, b"%PDF-1.")
+        .await
+        .unwrap();
+
+    file_storage.put("public/lesson_images/diagram.png", b"png".to_vec(), "image/png").await.unwrap();
+    file_storage.put("public/course_images/lost/hero.png", b"png".to_vec(), "image/png").await.unwrap();
+    file_storage.put("private/attachments/lost.pdf", b"pdf".to_vec(), "application/pdf").await.unwrap();
+    file_storage.age_files();
+
+    // Could belong to an upload which has not reached the database yet.
+    file_storage.put("public/course_images/uploading/hero.png", b"png".to_vec(), "image/png").await.unwrap();
+
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+
+    let result = interactor.collect_orphaned_uploads(&ctx, true).await;
+    assert!(matches!(result, Err(CoreError::PermissionDenied)));
+
+    let report = interactor.collect_orphaned_uploads(&Ctx::new(ADMIN_ID).unwrap(), true).await.unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.scanned, 11);
+    assert_eq!(report.referenced, 8);
+    assert_eq!(report.recent, 1);
+    assert_eq!(report.orphaned_keys, vec!["private/attachments/lost.pdf", "public/course_images/lost/hero.png"]);
+    assert_eq!(file_storage.files.lock().unwrap().len(), 11);
+
+    let report = CronJobUploadGc::new(repository_manager.clone())
+        .delete_orphaned_uploads()
+        .await
+        .unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.orphaned_keys.len(), 2);
+    assert!(report.failed_keys.is_empty());
+
+    let files = file_storage.files.lock().unwrap();
+    assert_eq!(files.len(), 9);
+    assert!(!files.contains_key("private/attachments/lost.pdf"));
+    assert!(files.contains_key("public/course_images/uploading/hero.png"));
+}
+
+#[tokio::test]
+async fn test_legacy_uploads_are_collected() {
+    let legacy_course = Course {
+        img_url: Some("uploads/legacy.png".to_string()),
+        ..course(2, CourseState::Published)
+    };
+
+    let repository_manager = Arc::new(CommandRepositoryManagerMock::new(Vec::new()).with_courses(vec![legacy_course]));
+    let legacy_upload_storage = repository_manager.legacy_upload_storage.clone();
+
+    legacy_upload_storage.put("uploads/legacy.png", b"png".to_vec(), "image/png").await.unwrap();
+    legacy_upload_storage.put("uploads/lost.png", b"png".to_vec(), "image/png").await.unwrap();
+    legacy_upload_storage.age_files();
+    legacy_upload_storage.put("uploads/uploading.png", b"png".to_vec(), "image/png").await.unwrap();
+
+    let report = CronJobUploadGc::new(repository_manager.clone())
+        .delete_orphaned_uploads()
+        .await
+        .unwrap();
+    assert_eq!(report.scanned, 3);
+    assert_eq!(report.referenced, 1);
+    assert_eq!(report.recent, 1);
+    assert_eq!(report.orphaned_keys, vec!["uploads/lost.png"]);
+
+    let files = legacy_upload_storage.files.lock().unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(files.contains_key("uploads/legacy.png"));
+}
+
+#[tokio::test]
+async fn test_image_swap_survives_failed_delete() {
+    let repository_manager = repository_manager();
+    let file_storage = repository_manager.file_storage.clone();
+    let interactor = CreatorCourseInteractor::new(repository_manager.clone());
+    let ctx = Ctx::new(1).unwrap();
+
+    let old_img_variants = interactor.set_course_img(&ctx, COURSE_ID, &png()).await.unwrap();
+
+    file_storage.fail_deletes.store(true, Ordering::SeqCst);
+
+    // The course is updated even though the old variants could not be removed.
+    let img_variants = interactor.set_course_img(&ctx, COURSE_ID, &png()).await.unwrap();
+    assert_ne!(img_variants, old_img_variants);
+    assert_eq!(file_storage.files.lock().unwrap().len(), 12);
+
+    file_storage.fail_deletes.store(false, Ordering::SeqCst);
+    file_storage.age_files();
+
+    let report = CronJobUploadGc::new(repository_manager.clone())
+        .delete_orphaned_uploads()
+        .await
+        .unwrap();
+
+    let mut old_keys: Vec<String> = old_img_variants.urls()
+        .into_iter()
+        .map(|url| url.strip_prefix("mock/").unwrap().to_string())
+        .collect();
+    old_keys.sort();
+
+    assert_eq!(report.orphaned_keys, old_keys);
+    assert_eq!(file_storage.files.lock().unwrap().len(), 6);
+}
@@ -24,6 +24,8 @@ pub enum UserIden {
 #[derive(Iden)]
 pub enum CourseIden {
     Course, // Table name
+	ScheduledPublishDate,
+	ScheduledArchiveDate,
 }
 
 #[derive(Iden)]
@@ -38,9 +40,28 @@ pub enum UserCourseIden {
 pub enum LessonIden {
 	Lesson,
 	CourseId,
+	SectionId,
     LessonOrder,
 }
 
+#[derive(Iden)]
+pub enum SectionIden {
+	CourseId,
+	SectionOrder,
+}
+
+#[derive(Iden)]
+pub enum AttachmentIden {
+	LessonId,
+}
+
+#[derive(Iden)]
+pub enum ContentBlockIden {
+	LessonId,
+	BlockType,
+	BlockOrder,
+}
+
 #[derive(Iden)]
 pub enum LessonProgressIden {
 	LessonProgressId,
@@ -48,6 +69,7 @@ pub enum LessonProgressIden {
 	LessonId,
 	UserId,
 	State,
+	DatePaused,
 }
 
 #[derive(Iden)]
@@ -55,6 +77,7 @@ pub enum ExerciseIden {
 	Exercise,
 	LessonId,
     ExerciseOrder,
+	AutoDifficulty,
 }
 
 #[derive(Iden)]
@@ -64,4 +87,62 @@ pub enum ExerciseCompletionIden {
 	UserId,
 	ExerciseCompletionId,
 	State,
-}
\ No newline at end of file
+	IsPractice,
+	PausedSeconds,
+}
+
+#[derive(Iden)]
+pub enum PeerReviewIden {
+	PeerReview,
+	PeerReviewId,
+	ExerciseId,
+	ExerciseCompletionId,
+	ReviewerId,
+}
+
+#[derive(Iden)]
+pub enum ExamIden {
+	LessonId,
+	ClosesAt,
+}
+
+#[derive(Iden)]
+pub enum PlacementTestIden {
+	CourseId,
+}
+
+#[derive(Iden)]
+pub enum PlacementAttemptIden {
+	CourseId,
+	UserId,
+}
+
+#[derive(Iden)]
+pub enum UnlockPolicyIden {
+	CourseId,
+}
+
+#[derive(Iden)]
+pub enum CourseRevisionIden {
+	CourseId,
+}
+
+#[derive(Iden)]
+pub enum CourseTransferIden {
+	CourseTransferId,
+	CourseId,
+	ToUserId,
+	State,
+}
+
+#[derive(Iden)]
+pub enum SimilarityFlagIden {
+	ExerciseId,
+	Score,
+}
+
+#[derive(Iden)]
+pub enum DifficultyChangeIden {
+	DifficultyChangeId,
+	ExerciseId,
+}
@@ -27,4 +27,28 @@ pub fn get_exercise_table_ref() -> TableRef {
 
 pub fn get_exercise_completion_table_ref() -> TableRef {
 	TableRef::Table(SIden("exercise_completion").into_iden())
+}
+
+pub fn get_exam_table_ref() -> TableRef {
+	TableRef::Table(SIden("exam").into_iden())
+}
+
+pub fn get_placement_test_table_ref() -> TableRef {
+	TableRef::Table(SIden("placement_test").into_iden())
+}
+
+pub fn get_unlock_policy_table_ref() -> TableRef {
+	TableRef::Table(SIden("unlock_policy").into_iden())
+}
+
+pub fn get_section_table_ref() -> TableRef {
+	TableRef::Table(SIden("section").into_iden())
+}
+
+pub fn get_content_block_table_ref() -> TableRef {
+	TableRef::Table(SIden("content_block").into_iden())
+}
+
+pub fn get_attachment_table_ref() -> TableRef {
+	TableRef::Table(SIden("attachment").into_iden())
 }
\ No newline at end of file
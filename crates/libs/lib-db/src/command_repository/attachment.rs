@@ -0,0 +1,147 @@
+use lib_core::{ctx::Ctx, interfaces::lesson::LessonResult, models::attachment::{Attachment, AttachmentForCreateCommand}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{self, idens::{AttachmentIden, CommonIden, LessonIden}, table_ref::get_lesson_table_ref, DbRepository}, store::{db_manager::DbManager, error::DbError}};
+
+#[derive(Clone, Fields, FromRow, Debug)]
+struct AttachmentData {
+    pub id: i64,
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+}
+
+#[derive(Fields, FromRow)]
+struct AttachmentStorageKeyData {
+    pub storage_key: String,
+}
+
+#[derive(Fields)]
+struct AttachmentForInsert {
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+}
+
+pub struct AttachmentCommandRepository;
+
+impl DbRepository for AttachmentCommandRepository {
+    const TABLE: &'static str = "attachment";
+}
+
+impl AttachmentCommandRepository {
+    pub async fn get_attachment(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        attachment_id: i64,
+    ) -> LessonResult<Attachment> {
+        let attachment = base::get::<Self, AttachmentData>(ctx, dbm, attachment_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(Attachment {
+            id: attachment.id,
+            lesson_id: attachment.lesson_id,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size: attachment.size,
+            storage_key: attachment.storage_key,
+        })
+    }
+
+    pub async fn create_attachment(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        attachment_for_c: AttachmentForCreateCommand,
+    ) -> LessonResult<i64> {
+        let attachment_fi = AttachmentForInsert {
+            lesson_id: attachment_for_c.lesson_id,
+            file_name: attachment_for_c.file_name,
+            content_type: attachment_for_c.content_type,
+            size: attachment_for_c.size,
+            storage_key: attachment_for_c.storage_key,
+        };
+
+        let attachment_id = base::create::<Self, _>(ctx, dbm, attachment_fi)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(attachment_id)
+    }
+
+    pub async fn delete_attachment(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        attachment_id: i64,
+    ) -> LessonResult<()> {
+        base::delete::<Self>(ctx, dbm, attachment_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn get_attachment_storage_keys(
+        _: &Ctx,
+        dbm: &DbManager,
+    ) -> LessonResult<Vec<String>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(AttachmentStorageKeyData::field_column_refs());
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, AttachmentStorageKeyData, _>(&sql, values);
+        let attachments =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        Ok(attachments.into_iter().map(|attachment| attachment.storage_key).collect())
+    }
+
+    pub async fn get_course_attachments(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> LessonResult<Vec<Attachment>> {
+        let mut lesson_ids = Query::select();
+        lesson_ids
+            .from(get_lesson_table_ref())
+            .column(CommonIden::Id)
+            .and_where(Expr::col(LessonIden::CourseId).eq(course_id));
+
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(AttachmentData::field_column_refs())
+            .and_where(Expr::col(AttachmentIden::LessonId).in_subquery(lesson_ids));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, AttachmentData, _>(&sql, values);
+        let attachments =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let result = attachments.into_iter().map(|attachment| Attachment {
+            id: attachment.id,
+            lesson_id: attachment.lesson_id,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size: attachment.size,
+            storage_key: attachment.storage_key,
+        }).collect();
+
+        Ok(result)
+    }
+}
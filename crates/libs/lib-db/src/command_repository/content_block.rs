@@ -0,0 +1,193 @@
+use lib_core::{ctx::Ctx, interfaces::lesson::LessonResult, models::content_block::{ContentBlock, ContentBlockType, ContentBlockForChangeOrder, ContentBlockForCreateCommand, ContentBlockForUpdateCommand}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query, Value};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{self, idens::ContentBlockIden, DbRepository}, store::{db_manager::DbManager, error::DbError}};
+
+#[derive(Clone, Fields, FromRow, Debug)]
+struct ContentBlockData {
+    pub id: i64,
+    pub lesson_id: i64,
+    pub block_type: String,
+    pub body: serde_json::Value,
+    pub rendered_html: String,
+    pub block_order: i32,
+}
+
+#[derive(Fields)]
+struct ContentBlockForInsert {
+    pub lesson_id: i64,
+    pub block_type: String,
+    pub body: Value,
+    pub rendered_html: String,
+    pub block_order: i32,
+}
+
+#[derive(Fields)]
+struct ContentBlockForUpdateOrder {
+    pub block_order: i32,
+}
+
+#[derive(Fields)]
+struct ContentBlockForUpdateData {
+    pub body: Value,
+    pub rendered_html: String,
+}
+
+pub struct ContentBlockCommandRepository;
+
+impl DbRepository for ContentBlockCommandRepository {
+    const TABLE: &'static str = "content_block";
+}
+
+impl ContentBlockCommandRepository {
+    pub async fn get_content_block(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        content_block_id: i64,
+    ) -> LessonResult<ContentBlock> {
+        let content_block = base::get::<Self, ContentBlockData>(ctx, dbm, content_block_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(ContentBlock {
+            id: content_block.id,
+            lesson_id: content_block.lesson_id,
+            block_type: content_block.block_type.try_into()?,
+            body: content_block.body,
+            rendered_html: content_block.rendered_html,
+            block_order: content_block.block_order,
+        })
+    }
+
+    pub async fn get_lesson_content_blocks_ordered(
+        _: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+    ) -> LessonResult<Vec<ContentBlockForChangeOrder>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ContentBlockData::field_column_refs())
+            .and_where(Expr::col(ContentBlockIden::LessonId).eq(lesson_id))
+            .order_by(ContentBlockIden::BlockOrder, sea_query::Order::Asc);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ContentBlockData, _>(&sql, values);
+        let content_blocks =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let result = content_blocks.iter().map(|content_block| ContentBlockForChangeOrder {
+            id: content_block.id,
+            order: content_block.block_order,
+        }).collect();
+
+        Ok(result)
+    }
+
+    pub async fn create_content_block(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        content_block_for_c: ContentBlockForCreateCommand,
+    ) -> LessonResult<i64> {
+        let content_block_fi = ContentBlockForInsert {
+            lesson_id: content_block_for_c.lesson_id,
+            block_type: content_block_for_c.block_type.to_string(),
+            body: Value::Json(Some(Box::new(content_block_for_c.body))),
+            rendered_html: content_block_for_c.rendered_html,
+            block_order: content_block_for_c.order,
+        };
+
+        let content_block_id = base::create::<Self, _>(ctx, dbm, content_block_fi)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(content_block_id)
+    }
+
+    pub async fn update_content_block(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        content_block_for_u: ContentBlockForUpdateCommand,
+    ) -> LessonResult<()> {
+        let content_block_for_u_data = ContentBlockForUpdateData {
+            body: Value::Json(Some(Box::new(content_block_for_u.body))),
+            rendered_html: content_block_for_u.rendered_html,
+        };
+
+        base::update::<Self, _>(ctx, dbm, content_block_for_u.id, content_block_for_u_data)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn delete_content_block(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        content_block_id: i64,
+    ) -> LessonResult<()> {
+        base::delete::<Self>(ctx, dbm, content_block_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn update_content_block_orders(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        content_blocks_for_u_order: Vec<ContentBlockForChangeOrder>,
+    ) -> LessonResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        for content_block in &content_blocks_for_u_order {
+            let content_block_for_u_order = ContentBlockForUpdateOrder {
+                block_order: content_block.order,
+            };
+
+            base::update::<Self, ContentBlockForUpdateOrder>(ctx, &dbm, content_block.id, content_block_for_u_order)
+                .await
+                .map_err(Into::<DbError>::into)?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn get_content_block_urls(
+        _: &Ctx,
+        dbm: &DbManager,
+    ) -> LessonResult<Vec<String>> {
+        let block_types = [ContentBlockType::Image, ContentBlockType::Video, ContentBlockType::Attachment]
+            .map(|block_type| block_type.to_string());
+
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ContentBlockData::field_column_refs())
+            .and_where(Expr::col(ContentBlockIden::BlockType).is_in(block_types));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ContentBlockData, _>(&sql, values);
+        let content_blocks =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let result = content_blocks
+            .into_iter()
+            .filter_map(|content_block| content_block.body.get("url")?.as_str().map(ToString::to_string))
+            .collect();
+
+        Ok(result)
+    }
+}
@@ -2,8 +2,15 @@ use async_trait::async_trait;
 use lib_core::interactors::error::CoreError;
 use lib_core::ctx::Ctx;
 use lib_core::interfaces::course::{ICourseCommandRepository, CourseResult};
-use lib_core::models::course::{Course, CourseForCreate, CourseForUpdateCommand, UserCourse, UserCourseRole};
+use lib_core::models::course::{Course, CourseForCreate, CourseForUpdateCommand, CourseSchedule, CourseState, UserCourse, UserCourseRole};
+use lib_core::models::course_image::CourseImageVariants;
+use lib_core::models::course_revision::{CourseRevision, StagedEdit};
+use lib_core::models::course_transfer::{CourseTransfer, CourseTransferForCreate, CourseTransferState};
+use lib_core::models::placement_test::{PlacementAttempt, PlacementAttemptForCreate, PlacementTest};
+use lib_core::models::unlock_policy::UnlockPolicy;
 use modql::field::{Fields, HasFields};
+use sea_query::{Condition, Expr, Keyword, LockType, PostgresQueryBuilder, Query, SimpleExpr};
+use sea_query_binder::SqlxBinder;
 use serde::Serialize;
 use serde_with::serde_as;
 use sqlx::postgres::PgRow;
@@ -11,11 +18,18 @@ use sqlx::FromRow;
 use time::OffsetDateTime;
 use typed_builder::TypedBuilder;
 use crate::base::{self, DbRepository};
+use crate::base::idens::{CommonIden, CourseIden, ExerciseCompletionIden, ExerciseIden, LessonIden, LessonProgressIden};
+use crate::base::table_ref::{get_exercise_completion_table_ref, get_exercise_table_ref, get_lesson_progress_table_ref, get_lesson_table_ref};
 use crate::store::db_manager::DbManager;
 use crate::store::dbx::error::DbxError;
 use crate::store::error::DbError;
 use lib_utils::time::{from_unix_timestamp, Rfc3339};
 
+use super::course_revision::CourseRevisionCommandRepository;
+use super::course_transfer::CourseTransferCommandRepository;
+use super::placement_test::PlacementTestCommandRepository;
+use super::placement_attempt::PlacementAttemptCommandRepository;
+use super::unlock_policy::UnlockPolicyCommandRepository;
 use super::users_courses::{UsersCoursesForDelete, UsersCoursesCommandRepository, UsersCoursesRequest};
 
 #[serde_as]
@@ -39,7 +53,15 @@ pub struct CourseRequest {
 	#[builder(default, setter(strip_option))]
 	pub img_url: Option<String>,
 	#[builder(default, setter(strip_option))]
+	pub img_variants: Option<serde_json::Value>,
+	#[builder(default, setter(strip_option))]
 	pub state: Option<String>,
+	#[builder(default, setter(strip_option))]
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub scheduled_publish_date: Option<OffsetDateTime>,
+	#[builder(default, setter(strip_option))]
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub scheduled_archive_date: Option<OffsetDateTime>,
 }
 
 impl TryFrom<CourseRequest> for Course {
@@ -55,6 +77,7 @@ impl TryFrom<CourseRequest> for Course {
 		let color = DbError::handle_option_field(value.color, &entity, "color".to_string())?;
 		let state = DbError::handle_option_field(value.state, &entity, "state".to_string())?;
 		let published_date = value.published_date.and_then(|date| Some(date.unix_timestamp()));
+		let img_variants = value.img_variants.map(serde_json::from_value).transpose()?;
 
 		Ok(Course {
     		id,
@@ -65,11 +88,27 @@ impl TryFrom<CourseRequest> for Course {
     		color,
     		published_date,
     		img_url: value.img_url,
+    		img_variants,
     		state: state.try_into()?,
+    		scheduled_publish_date: value.scheduled_publish_date.map(|date| date.unix_timestamp()),
+    		scheduled_archive_date: value.scheduled_archive_date.map(|date| date.unix_timestamp()),
 		})
 	}
 }
 
+#[derive(Fields, FromRow)]
+struct CourseScheduleData {
+	pub id: i64,
+	pub scheduled_publish_date: Option<OffsetDateTime>,
+	pub scheduled_archive_date: Option<OffsetDateTime>,
+}
+
+#[derive(Fields, FromRow)]
+struct CourseImgData {
+	pub img_url: Option<String>,
+	pub img_variants: Option<serde_json::Value>,
+}
+
 /// Marker trait
 pub trait CourseBy: HasFields + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
 
@@ -90,6 +129,15 @@ impl CourseCommandRepository {
     		dbm,
 		}
 	}
+
+	fn date_or_null(date: Option<i64>) -> CourseResult<SimpleExpr> {
+		let result = match date {
+			Some(seconds) => SimpleExpr::from(from_unix_timestamp(seconds).map_err(DbError::DateError)?),
+			None => SimpleExpr::Keyword(Keyword::Null),
+		};
+
+		Ok(result)
+	}
 }
 
 #[async_trait]
@@ -162,6 +210,8 @@ impl ICourseCommandRepository for CourseCommandRepository {
 			None
 		};
 
+		let img_variants = course_for_u.img_variants.map(serde_json::to_value).transpose()?;
+
 		let course_req_u = CourseRequest { 
 			id: None,
 			title: course_for_u.title, 
@@ -170,8 +220,11 @@ impl ICourseCommandRepository for CourseCommandRepository {
 			price: course_for_u.price, 
 			color: course_for_u.color, 
 			img_url: course_for_u.img_url, 
+			img_variants,
 			published_date,
 			state: course_for_u.state.and_then(|state| Some(state.to_string())),
+			scheduled_publish_date: None,
+			scheduled_archive_date: None,
 		};
 
 		base::update::<Self, CourseRequest>(&ctx, &self.dbm, course_id, course_req_u)
@@ -181,6 +234,180 @@ impl ICourseCommandRepository for CourseCommandRepository {
 		Ok(())
 	}
 
+	async fn delete_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()> {
+		let dbm = self.dbm.new_with_txn()?;
+		dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+		let mut lesson_ids = Query::select();
+		lesson_ids
+			.from(get_lesson_table_ref())
+			.column(CommonIden::Id)
+			.and_where(Expr::col(LessonIden::CourseId).eq(course_id));
+
+		let mut exercise_ids = Query::select();
+		exercise_ids
+			.from(get_exercise_table_ref())
+			.column(CommonIden::Id)
+			.and_where(Expr::col(ExerciseIden::LessonId).in_subquery(lesson_ids.clone()));
+
+		// Attempts and progresses block the deletion of a single exercise or lesson,
+		// with the whole course they go as well. The rest cascades in the schema.
+		let queries = [
+			Query::delete()
+				.from_table(get_exercise_completion_table_ref())
+				.and_where(Expr::col(ExerciseCompletionIden::ExerciseId).in_subquery(exercise_ids))
+				.to_owned(),
+			Query::delete()
+				.from_table(get_lesson_progress_table_ref())
+				.and_where(Expr::col(LessonProgressIden::LessonId).in_subquery(lesson_ids))
+				.to_owned(),
+			Query::delete()
+				.from_table(get_lesson_table_ref())
+				.and_where(Expr::col(LessonIden::CourseId).eq(course_id))
+				.to_owned(),
+		];
+
+		for query in queries {
+			let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+			let sqlx_query = sqlx::query_with(&sql, values);
+			dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+		}
+
+		base::delete::<Self>(ctx, &dbm, course_id)
+			.await
+			.map_err(Into::<DbError>::into)?;
+
+		// Transfers outlive the course as its audit trail, a pending one can never be accepted now.
+		CourseTransferCommandRepository::cancel_pending_course_transfer(ctx, &dbm, course_id).await?;
+
+		dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+		Ok(())
+	}
+
+	async fn unpublish_course(&self, ctx: &Ctx, course_id: i64) -> CourseResult<i64> {
+		let dbm = self.dbm.new_with_txn()?;
+		dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+		// An enrollment holds a key share lock on the course through the foreign key, the update
+		// lock waits for running enrollments and keeps new ones out until the state is changed.
+		let mut query = Query::select();
+		query
+			.from(Self::table_ref())
+			.column(CommonIden::Id)
+			.and_where(Expr::col(CommonIden::Id).eq(course_id))
+			.lock(LockType::Update);
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, (i64,), _>(&sql, values);
+		dbm.dbx()
+			.fetch_optional(sqlx_query)
+			.await
+			.map_err(Into::<DbError>::into)?
+			.ok_or(DbError::EntityNotFound { entity: Self::TABLE.to_string(), id: course_id })?;
+
+		let students = UsersCoursesCommandRepository::count_students(&dbm, course_id).await?;
+
+		if students == 0 {
+			let course_req_u = CourseRequest::builder()
+				.state(CourseState::Draft.to_string())
+				.build();
+
+			base::update::<Self, CourseRequest>(ctx, &dbm, course_id, course_req_u)
+				.await
+				.map_err(Into::<DbError>::into)?;
+			CourseRevisionCommandRepository::delete_course_revision(ctx, &dbm, course_id).await?;
+		}
+
+		dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+		Ok(students)
+	}
+
+	async fn update_course_schedule(&self, _: &Ctx, schedule: CourseSchedule) -> CourseResult<()> {
+		let publish_date = Self::date_or_null(schedule.publish_date)?;
+		let archive_date = Self::date_or_null(schedule.archive_date)?;
+
+		let mut query = Query::update();
+		query
+			.table(Self::table_ref())
+			.value(CourseIden::ScheduledPublishDate, publish_date)
+			.value(CourseIden::ScheduledArchiveDate, archive_date)
+			.and_where(Expr::col(CommonIden::Id).eq(schedule.course_id));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_with(&sql, values);
+		self.dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+		Ok(())
+	}
+
+	async fn get_due_course_schedules(&self, _: &Ctx, now: i64) -> CourseResult<Vec<CourseSchedule>> {
+		let now = from_unix_timestamp(now).map_err(DbError::DateError)?;
+
+		let mut query = Query::select();
+		query
+			.from(Self::table_ref())
+			.columns(CourseScheduleData::field_column_refs())
+			.cond_where(
+				Condition::any()
+					.add(Expr::col(CourseIden::ScheduledPublishDate).lte(now))
+					.add(Expr::col(CourseIden::ScheduledArchiveDate).lte(now))
+			);
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, CourseScheduleData, _>(&sql, values);
+		let courses = self.dbm
+			.dbx()
+			.fetch_all(sqlx_query)
+			.await
+			.map_err(Into::<DbError>::into)?;
+
+		let result = courses
+			.into_iter()
+			.map(|course| CourseSchedule {
+				course_id: course.id,
+				publish_date: course.scheduled_publish_date.map(|date| date.unix_timestamp()),
+				archive_date: course.scheduled_archive_date.map(|date| date.unix_timestamp()),
+			})
+			.collect();
+
+		Ok(result)
+	}
+
+	async fn get_course_students_count(&self, _: &Ctx, course_id: i64) -> CourseResult<i64> {
+		let count = UsersCoursesCommandRepository::count_students(&self.dbm, course_id).await?;
+
+		Ok(count)
+	}
+
+	async fn get_course_img_urls(&self, _: &Ctx) -> CourseResult<Vec<String>> {
+		let mut query = Query::select();
+		query
+			.from(Self::table_ref())
+			.columns(CourseImgData::field_column_refs());
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, CourseImgData, _>(&sql, values);
+		let courses = self.dbm
+			.dbx()
+			.fetch_all(sqlx_query)
+			.await
+			.map_err(Into::<DbError>::into)?;
+
+		let mut result = Vec::new();
+		for course in courses {
+			result.extend(course.img_url);
+
+			if let Some(img_variants) = course.img_variants {
+				let img_variants: CourseImageVariants = serde_json::from_value(img_variants)?;
+				result.extend(img_variants.urls().into_iter().cloned());
+			}
+		}
+
+		Ok(result)
+	}
+
 	async fn get_user_course(&self, _: &Ctx, user_id: i64, course_id: i64) -> CourseResult<UserCourse> {
 		let user_course_req = UsersCoursesCommandRepository::get(&self.dbm, user_id, course_id).await?;
 
@@ -234,4 +461,89 @@ impl ICourseCommandRepository for CourseCommandRepository {
 
 		Ok(())
 	}
-}
+
+	async fn get_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>> {
+		CourseRevisionCommandRepository::get_course_revision(ctx, &self.dbm, course_id).await
+	}
+
+	async fn stage_course_edit(&self, ctx: &Ctx, course_id: i64, edit: StagedEdit) -> CourseResult<()> {
+		CourseRevisionCommandRepository::stage_course_edit(ctx, &self.dbm, course_id, edit).await
+	}
+
+	async fn delete_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<()> {
+		CourseRevisionCommandRepository::delete_course_revision(ctx, &self.dbm, course_id).await
+	}
+
+	async fn publish_course_revision(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<CourseRevision>> {
+		CourseRevisionCommandRepository::publish_course_revision(ctx, &self.dbm, course_id).await
+	}
+
+	async fn get_course_staff(&self, _: &Ctx, course_id: i64) -> CourseResult<Vec<UserCourse>> {
+		let staff = UsersCoursesCommandRepository::get_course_staff(&self.dbm, course_id).await?;
+
+		let mut result = Vec::new();
+		for user_course_req in staff {
+			result.push(user_course_req.try_into()?);
+		}
+
+		Ok(result)
+	}
+
+	async fn update_user_course_role(&self, ctx: &Ctx, user_id: i64, course_id: i64, user_role: UserCourseRole) -> CourseResult<()> {
+		UsersCoursesCommandRepository::update_role(ctx, &self.dbm, user_id, course_id, user_role).await?;
+
+		Ok(())
+	}
+
+	async fn get_course_transfer(&self, ctx: &Ctx, transfer_id: i64) -> CourseResult<CourseTransfer> {
+		CourseTransferCommandRepository::get_course_transfer(ctx, &self.dbm, transfer_id).await
+	}
+
+	async fn get_course_transfers(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Vec<CourseTransfer>> {
+		CourseTransferCommandRepository::get_course_transfers(ctx, &self.dbm, course_id).await
+	}
+
+	async fn get_incoming_course_transfers(&self, ctx: &Ctx, user_id: i64) -> CourseResult<Vec<CourseTransfer>> {
+		CourseTransferCommandRepository::get_incoming_course_transfers(ctx, &self.dbm, user_id).await
+	}
+
+	async fn create_course_transfer(&self, ctx: &Ctx, transfer_for_c: CourseTransferForCreate) -> CourseResult<i64> {
+		CourseTransferCommandRepository::create_course_transfer(ctx, &self.dbm, transfer_for_c).await
+	}
+
+	async fn resolve_course_transfer(&self, ctx: &Ctx, transfer_id: i64, state: CourseTransferState, date_resolved: i64) -> CourseResult<()> {
+		CourseTransferCommandRepository::resolve_course_transfer(ctx, &self.dbm, transfer_id, state, date_resolved).await
+	}
+
+	async fn complete_course_transfer(&self, ctx: &Ctx, transfer: CourseTransfer, date_resolved: i64) -> CourseResult<()> {
+		CourseTransferCommandRepository::complete_course_transfer(ctx, &self.dbm, transfer, date_resolved).await
+	}
+
+	async fn force_course_transfer(&self, ctx: &Ctx, transfer_for_c: CourseTransferForCreate, date_resolved: i64) -> CourseResult<()> {
+		CourseTransferCommandRepository::force_course_transfer(ctx, &self.dbm, transfer_for_c, date_resolved).await
+	}
+
+	async fn get_placement_test(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<PlacementTest>> {
+		PlacementTestCommandRepository::get_placement_test(ctx, &self.dbm, course_id).await
+	}
+
+	async fn save_placement_test(&self, ctx: &Ctx, placement_test: PlacementTest) -> CourseResult<()> {
+		PlacementTestCommandRepository::save_placement_test(ctx, &self.dbm, placement_test).await
+	}
+
+	async fn get_placement_attempt(&self, ctx: &Ctx, course_id: i64, user_id: i64) -> CourseResult<Option<PlacementAttempt>> {
+		PlacementAttemptCommandRepository::get_placement_attempt(ctx, &self.dbm, course_id, user_id).await
+	}
+
+	async fn create_placement_attempt(&self, ctx: &Ctx, attempt_for_c: PlacementAttemptForCreate) -> CourseResult<()> {
+		PlacementAttemptCommandRepository::create_placement_attempt(ctx, &self.dbm, attempt_for_c).await
+	}
+
+	async fn get_unlock_policy(&self, ctx: &Ctx, course_id: i64) -> CourseResult<Option<UnlockPolicy>> {
+		UnlockPolicyCommandRepository::get_unlock_policy(ctx, &self.dbm, course_id).await
+	}
+
+	async fn save_unlock_policy(&self, ctx: &Ctx, unlock_policy: UnlockPolicy) -> CourseResult<()> {
+		UnlockPolicyCommandRepository::save_unlock_policy(ctx, &self.dbm, unlock_policy).await
+	}
+}
\ No newline at end of file
@@ -0,0 +1,272 @@
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::{course::CourseResult, exercise::IExerciseCommandRepository, lesson::ILessonCommandRepository}, models::course_revision::{CourseRevision, StagedEdit}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, LockType, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{idens::{CommonIden, CourseRevisionIden}, prep_fields_for_create, prep_fields_for_update, table_ref::{get_content_block_table_ref, get_exercise_table_ref, get_lesson_table_ref, get_section_table_ref}, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+use super::{content_block::ContentBlockCommandRepository, exam::ExamCommandRepository, exercise::ExerciseCommandRepository, exercise_completion::ExerciseCompletionCommandRepository, lesson::LessonCommandRepository, section::SectionCommandRepository};
+
+#[derive(Fields)]
+struct CourseRevisionData {
+    pub course_id: i64,
+    pub edits: sea_query::Value,
+}
+
+#[derive(Fields)]
+struct CourseRevisionForUpdate {
+    pub edits: sea_query::Value,
+}
+
+#[derive(Fields, FromRow)]
+struct CourseRevisionQuery {
+    pub course_id: i64,
+    pub edits: serde_json::Value,
+}
+
+impl TryFrom<CourseRevisionQuery> for CourseRevision {
+    type Error = CoreError;
+
+    fn try_from(value: CourseRevisionQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            course_id: value.course_id,
+            edits: serde_json::from_value(value.edits)?,
+        })
+    }
+}
+
+pub struct CourseRevisionCommandRepository;
+
+impl DbRepository for CourseRevisionCommandRepository {
+    const TABLE: &'static str = "course_revision";
+}
+
+impl CourseRevisionCommandRepository {
+    pub async fn get_course_revision(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Option<CourseRevision>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(CourseRevisionQuery::field_column_refs())
+            .and_where(Expr::col(CourseRevisionIden::CourseId).eq(course_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, CourseRevisionQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        match entity {
+            Some(entity) => Ok(Some(entity.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Merges the edit into the revision of the course, creating it first. The revision row
+    /// stays locked while the edits are merged, so concurrent edits are all kept.
+    pub async fn stage_course_edit(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+        edit: StagedEdit,
+    ) -> CourseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        // A revision published meanwhile is gone once the lock is granted, the edit starts a new one.
+        let mut revision = loop {
+            Self::create_empty_course_revision(ctx, &dbm, course_id).await?;
+
+            if let Some(revision) = Self::get_course_revision_for_update(&dbm, course_id).await? {
+                break revision;
+            }
+        };
+
+        revision.stage(edit);
+
+        let revision_for_u = CourseRevisionForUpdate {
+            edits: sea_query::Value::Json(Some(Box::new(serde_json::to_value(&revision.edits)?))),
+        };
+
+        let mut fields = revision_for_u.not_none_fields();
+        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+        let fields = fields.for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(CourseRevisionIden::CourseId).eq(course_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn create_empty_course_revision(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        let revision_data = CourseRevisionData {
+            course_id,
+            edits: sea_query::Value::Json(Some(Box::new(serde_json::json!([])))),
+        };
+
+        let mut fields = revision_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?
+            .on_conflict(OnConflict::column(CourseRevisionIden::CourseId).do_nothing().to_owned());
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn get_course_revision_for_update(
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Option<CourseRevision>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(CourseRevisionQuery::field_column_refs())
+            .and_where(Expr::col(CourseRevisionIden::CourseId).eq(course_id))
+            .lock(LockType::Update);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, CourseRevisionQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        match entity {
+            Some(entity) => Ok(Some(entity.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn delete_course_revision(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        let mut query = Query::delete();
+        query
+            .from_table(Self::table_ref())
+            .and_where(Expr::col(CourseRevisionIden::CourseId).eq(course_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    /// Every edit and the removal of the revision share one transaction, so students
+    /// never see half of a revision. The revision is read under a lock, an edit staged
+    /// meanwhile either waits for the next revision or is published with this one.
+    /// Returns the published edits, edits of entities deleted since staging are dropped.
+    pub async fn publish_course_revision(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Option<CourseRevision>> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let Some(revision) = Self::get_course_revision_for_update(&dbm, course_id).await? else {
+            dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+            return Ok(None);
+        };
+
+        let lesson_repository = LessonCommandRepository::new(dbm.clone());
+        let exercise_repository = ExerciseCommandRepository::new(dbm.clone());
+
+        let mut published = CourseRevision::new(course_id);
+        for edit in revision.edits {
+            if !Self::edit_target_exists(&dbm, &edit).await? {
+                continue;
+            }
+
+            published.edits.push(edit.clone());
+
+            match edit {
+                StagedEdit::Lesson(edit) => {
+                    lesson_repository.update_lesson(ctx, edit.into()).await?;
+                },
+                StagedEdit::Exercise(edit) => {
+                    if edit.retake {
+                        ExerciseCompletionCommandRepository::remove_exercise_completions(&dbm, ctx, edit.exercise_id).await?;
+                    }
+
+                    exercise_repository.update(ctx, edit.into()).await?;
+                },
+                StagedEdit::ContentBlock(edit) => {
+                    ContentBlockCommandRepository::update_content_block(ctx, &dbm, edit.into()).await?;
+                },
+                StagedEdit::Section(edit) => {
+                    SectionCommandRepository::update_section(ctx, &dbm, edit.into()).await?;
+                },
+                StagedEdit::Exam(edit) if edit.removed => {
+                    ExamCommandRepository::delete_exam(ctx, &dbm, edit.lesson_id).await?;
+                },
+                StagedEdit::Exam(edit) => {
+                    ExamCommandRepository::save_exam(ctx, &dbm, edit.into()).await?;
+                },
+            }
+        }
+
+        Self::delete_course_revision(ctx, &dbm, course_id).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(Some(published))
+    }
+
+    async fn edit_target_exists(
+        dbm: &DbManager,
+        edit: &StagedEdit,
+    ) -> CourseResult<bool> {
+        let (table_ref, id) = match edit {
+            StagedEdit::Lesson(edit) => (get_lesson_table_ref(), edit.lesson_id),
+            StagedEdit::Exercise(edit) => (get_exercise_table_ref(), edit.exercise_id),
+            StagedEdit::ContentBlock(edit) => (get_content_block_table_ref(), edit.content_block_id),
+            StagedEdit::Section(edit) => (get_section_table_ref(), edit.section_id),
+            StagedEdit::Exam(edit) => (get_lesson_table_ref(), edit.lesson_id),
+        };
+
+        let mut query = Query::select();
+        query
+            .from(table_ref)
+            .expr(Expr::col(CommonIden::Id).count())
+            .and_where(Expr::col(CommonIden::Id).eq(id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_scalar_with::<_, i64, _>(&sql, values);
+        let count = dbm.dbx().fetch_one_scalar(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(count > 0)
+    }
+}
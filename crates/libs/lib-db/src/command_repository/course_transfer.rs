@@ -0,0 +1,275 @@
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::course::CourseResult, models::course_transfer::{CourseTransfer, CourseTransferForCreate, CourseTransferState}};
+use lib_utils::time::{from_unix_timestamp, now_utc};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+use time::OffsetDateTime;
+
+use crate::{base::{idens::CourseTransferIden, prep_fields_for_create, prep_fields_for_update, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+use super::users_courses::UsersCoursesCommandRepository;
+
+#[derive(Fields)]
+struct CourseTransferData {
+    pub course_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub state: String,
+    pub forced: bool,
+}
+
+#[derive(Fields)]
+struct CourseTransferForResolve {
+    pub state: String,
+    pub date_resolved: OffsetDateTime,
+}
+
+#[derive(Fields, FromRow)]
+struct CourseTransferQuery {
+    pub course_transfer_id: i64,
+    pub course_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub state: String,
+    pub forced: bool,
+    pub ctime: OffsetDateTime,
+    pub date_resolved: Option<OffsetDateTime>,
+}
+
+impl TryFrom<CourseTransferQuery> for CourseTransfer {
+    type Error = CoreError;
+
+    fn try_from(value: CourseTransferQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.course_transfer_id,
+            course_id: value.course_id,
+            from_user_id: value.from_user_id,
+            to_user_id: value.to_user_id,
+            state: value.state.try_into()?,
+            forced: value.forced,
+            date_proposed: value.ctime.unix_timestamp(),
+            date_resolved: value.date_resolved.map(|date| date.unix_timestamp()),
+        })
+    }
+}
+
+pub struct CourseTransferCommandRepository;
+
+impl DbRepository for CourseTransferCommandRepository {
+    const TABLE: &'static str = "course_transfer";
+}
+
+impl CourseTransferCommandRepository {
+    pub async fn get_course_transfer(
+        _: &Ctx,
+        dbm: &DbManager,
+        transfer_id: i64,
+    ) -> CourseResult<CourseTransfer> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(CourseTransferQuery::field_column_refs())
+            .and_where(Expr::col(CourseTransferIden::CourseTransferId).eq(transfer_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, CourseTransferQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?
+                .ok_or(DbError::EntityNotFound {
+                    entity: Self::TABLE.to_string(),
+                    id: transfer_id,
+                })?;
+
+        entity.try_into()
+    }
+
+    pub async fn get_course_transfers(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Vec<CourseTransfer>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(CourseTransferQuery::field_column_refs())
+            .and_where(Expr::col(CourseTransferIden::CourseId).eq(course_id))
+            .order_by(CourseTransferIden::CourseTransferId, sea_query::Order::Asc);
+
+        Self::fetch_transfers(dbm, query).await
+    }
+
+    pub async fn get_incoming_course_transfers(
+        _: &Ctx,
+        dbm: &DbManager,
+        user_id: i64,
+    ) -> CourseResult<Vec<CourseTransfer>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(CourseTransferQuery::field_column_refs())
+            .and_where(Expr::col(CourseTransferIden::ToUserId).eq(user_id))
+            .and_where(Expr::col(CourseTransferIden::State).eq(CourseTransferState::Pending.to_string()))
+            .order_by(CourseTransferIden::CourseTransferId, sea_query::Order::Asc);
+
+        Self::fetch_transfers(dbm, query).await
+    }
+
+    pub async fn create_course_transfer(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        transfer_for_c: CourseTransferForCreate,
+    ) -> CourseResult<i64> {
+        let transfer_data = CourseTransferData {
+            course_id: transfer_for_c.course_id,
+            from_user_id: transfer_for_c.from_user_id,
+            to_user_id: transfer_for_c.to_user_id,
+            state: CourseTransferState::Pending.to_string(),
+            forced: transfer_for_c.forced,
+        };
+
+        let mut fields = transfer_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?
+            .returning(Query::returning().columns([CourseTransferIden::CourseTransferId]));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, (i64,), _>(&sql, values);
+        let (transfer_id,) = dbm.dbx().fetch_one(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(transfer_id)
+    }
+
+    /// Only a pending transfer is resolved, a concurrent answer finds it resolved already.
+    pub async fn resolve_course_transfer(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        transfer_id: i64,
+        state: CourseTransferState,
+        date_resolved: i64,
+    ) -> CourseResult<()> {
+        let transfer_for_r = CourseTransferForResolve {
+            state: state.to_string(),
+            date_resolved: from_unix_timestamp(date_resolved).map_err(DbError::DateError)?,
+        };
+
+        let mut fields = transfer_for_r.not_none_fields();
+        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+        let fields = fields.for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(CourseTransferIden::CourseTransferId).eq(transfer_id))
+            .and_where(Expr::col(CourseTransferIden::State).eq(CourseTransferState::Pending.to_string()));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        let count = dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        if count == 0 {
+            return Err(DbError::CourseTransferMustBePending { transfer_id }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Cancels the pending transfer of the course, when there is one.
+    pub async fn cancel_pending_course_transfer(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<()> {
+        let transfer_for_r = CourseTransferForResolve {
+            state: CourseTransferState::Cancelled.to_string(),
+            date_resolved: now_utc(),
+        };
+
+        let mut fields = transfer_for_r.not_none_fields();
+        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+        let fields = fields.for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(CourseTransferIden::CourseId).eq(course_id))
+            .and_where(Expr::col(CourseTransferIden::State).eq(CourseTransferState::Pending.to_string()));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn complete_course_transfer(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        transfer: CourseTransfer,
+        date_resolved: i64,
+    ) -> CourseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        Self::resolve_course_transfer(ctx, &dbm, transfer.id, CourseTransferState::Accepted, date_resolved).await?;
+        UsersCoursesCommandRepository::move_creator(ctx, &dbm, transfer.course_id, transfer.from_user_id, transfer.to_user_id).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn force_course_transfer(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        transfer_for_c: CourseTransferForCreate,
+        date_resolved: i64,
+    ) -> CourseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let course_id = transfer_for_c.course_id;
+        let from_user_id = transfer_for_c.from_user_id;
+        let to_user_id = transfer_for_c.to_user_id;
+
+        let transfer_id = Self::create_course_transfer(ctx, &dbm, transfer_for_c).await?;
+        Self::resolve_course_transfer(ctx, &dbm, transfer_id, CourseTransferState::Accepted, date_resolved).await?;
+        UsersCoursesCommandRepository::move_creator(ctx, &dbm, course_id, from_user_id, to_user_id).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn fetch_transfers(
+        dbm: &DbManager,
+        query: sea_query::SelectStatement,
+    ) -> CourseResult<Vec<CourseTransfer>> {
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, CourseTransferQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        let mut result = Vec::new();
+
+        for transfer in entities {
+            result.push(transfer.try_into()?);
+        }
+
+        Ok(result)
+    }
+}
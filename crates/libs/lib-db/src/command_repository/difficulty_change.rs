@@ -0,0 +1,148 @@
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::exercise::ExerciseResult, models::difficulty_calibration::{DifficultyChange, DifficultyChangeForCreate}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+use time::OffsetDateTime;
+
+use crate::{base::{self, idens::{CommonIden, DifficultyChangeIden, ExerciseIden}, prep_fields_for_create, table_ref::get_exercise_table_ref, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+use super::exercise::ExerciseCommandRepository;
+
+#[derive(Fields)]
+struct DifficultyChangeData {
+    pub exercise_id: i64,
+    pub old_difficulty: String,
+    pub new_difficulty: String,
+    pub pass_rate: f32,
+}
+
+#[derive(Fields)]
+struct ExerciseForUpdateDifficulty {
+    pub difficult: String,
+}
+
+#[derive(Fields, FromRow)]
+struct DifficultyChangeQuery {
+    pub difficulty_change_id: i64,
+    pub exercise_id: i64,
+    pub old_difficulty: String,
+    pub new_difficulty: String,
+    pub pass_rate: f32,
+    pub ctime: OffsetDateTime,
+}
+
+impl TryFrom<DifficultyChangeQuery> for DifficultyChange {
+    type Error = CoreError;
+
+    fn try_from(value: DifficultyChangeQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.difficulty_change_id,
+            exercise_id: value.exercise_id,
+            old_difficulty: value.old_difficulty.try_into()?,
+            new_difficulty: value.new_difficulty.try_into()?,
+            pass_rate: value.pass_rate,
+            date_changed: value.ctime.unix_timestamp(),
+        })
+    }
+}
+
+pub struct DifficultyChangeCommandRepository;
+
+impl DbRepository for DifficultyChangeCommandRepository {
+    const TABLE: &'static str = "difficulty_change";
+}
+
+impl DifficultyChangeCommandRepository {
+    pub async fn get_auto_difficulty_exercises(
+        _: &Ctx,
+        dbm: &DbManager,
+    ) -> ExerciseResult<Vec<i64>> {
+        let mut query = Query::select();
+        query
+            .from(get_exercise_table_ref())
+            .column(CommonIden::Id)
+            .and_where(Expr::col(ExerciseIden::AutoDifficulty).eq(true));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, (i64,), _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(|(exercise_id,)| exercise_id).collect())
+    }
+
+    pub async fn get_exercise_difficulty_changes(
+        _: &Ctx,
+        dbm: &DbManager,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<DifficultyChange>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(DifficultyChangeQuery::field_column_refs())
+            .and_where(Expr::col(DifficultyChangeIden::ExerciseId).eq(exercise_id))
+            .order_by(DifficultyChangeIden::DifficultyChangeId, sea_query::Order::Asc);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, DifficultyChangeQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        let mut result = Vec::new();
+
+        for difficulty_change in entities {
+            result.push(difficulty_change.try_into()?);
+        }
+
+        Ok(result)
+    }
+
+    pub async fn change_exercise_difficulty(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        difficulty_change_for_c: DifficultyChangeForCreate,
+    ) -> ExerciseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let exercise_for_u_d = ExerciseForUpdateDifficulty {
+            difficult: difficulty_change_for_c.new_difficulty.to_string(),
+        };
+
+        base::update::<ExerciseCommandRepository, ExerciseForUpdateDifficulty>(ctx, &dbm, difficulty_change_for_c.exercise_id, exercise_for_u_d)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        let difficulty_change_data = DifficultyChangeData {
+            exercise_id: difficulty_change_for_c.exercise_id,
+            old_difficulty: difficulty_change_for_c.old_difficulty.to_string(),
+            new_difficulty: difficulty_change_for_c.new_difficulty.to_string(),
+            pass_rate: difficulty_change_for_c.pass_rate,
+        };
+
+        let mut fields = difficulty_change_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?;
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use lib_core::{ctx::Ctx, interfaces::lesson::LessonResult, models::{exam::{Exam, ExamForUpdate}, lesson_progress::{LessonProgress, LessonProgressState}}};
+use lib_utils::time::from_unix_timestamp;
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, LockType, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+use time::OffsetDateTime;
+
+use crate::{base::{idens::{CommonIden, ExamIden, LessonIden, LessonProgressIden}, prep_fields_for_create, prep_fields_for_update, table_ref::{get_lesson_progress_table_ref, get_lesson_table_ref}, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+use super::lesson_progress::LessonProgressData;
+
+#[derive(Fields)]
+struct ExamData {
+    pub lesson_id: i64,
+    pub opens_at: OffsetDateTime,
+    pub closes_at: OffsetDateTime,
+}
+
+#[derive(Fields)]
+struct ExamForUpdateWindow {
+    pub opens_at: OffsetDateTime,
+    pub closes_at: OffsetDateTime,
+}
+
+#[derive(Fields)]
+struct ExamForRelease {
+    pub results_released: bool,
+}
+
+#[derive(Fields, FromRow)]
+struct ExamQuery {
+    pub lesson_id: i64,
+    pub opens_at: OffsetDateTime,
+    pub closes_at: OffsetDateTime,
+    pub results_released: bool,
+}
+
+impl From<ExamQuery> for Exam {
+    fn from(value: ExamQuery) -> Self {
+        Self {
+            lesson_id: value.lesson_id,
+            opens_at: value.opens_at.unix_timestamp(),
+            closes_at: value.closes_at.unix_timestamp(),
+            results_released: value.results_released,
+        }
+    }
+}
+
+pub struct ExamCommandRepository;
+
+impl DbRepository for ExamCommandRepository {
+    const TABLE: &'static str = "exam";
+}
+
+impl ExamCommandRepository {
+    pub async fn get_exam(
+        _: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+    ) -> LessonResult<Option<Exam>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExamQuery::field_column_refs())
+            .and_where(Expr::col(ExamIden::LessonId).eq(lesson_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExamQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entity.map(Into::into))
+    }
+
+    /// Reads the exam under a row lock until the end of the transaction, the release takes
+    /// it for update and a submission shares it, so neither sees the other half done.
+    pub async fn get_exam_locked(
+        dbm: &DbManager,
+        lesson_id: i64,
+        lock: LockType,
+    ) -> LessonResult<Option<Exam>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExamQuery::field_column_refs())
+            .and_where(Expr::col(ExamIden::LessonId).eq(lesson_id))
+            .lock(lock);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExamQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entity.map(Into::into))
+    }
+
+    pub async fn get_course_exams(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> LessonResult<Vec<Exam>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExamQuery::field_column_refs())
+            .and_where(
+                Expr::col(ExamIden::LessonId)
+                    .in_subquery(Query::select()
+                        .column(CommonIden::Id)
+                        .from(get_lesson_table_ref())
+                        .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
+                        .to_owned())
+            );
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExamQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn save_exam(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        exam_for_u: ExamForUpdate,
+    ) -> LessonResult<()> {
+        let opens_at = from_unix_timestamp(exam_for_u.opens_at)?;
+        let closes_at = from_unix_timestamp(exam_for_u.closes_at)?;
+
+        if Self::get_exam(ctx, dbm, exam_for_u.lesson_id).await?.is_some() {
+            let exam_for_u_w = ExamForUpdateWindow {
+                opens_at,
+                closes_at,
+            };
+
+            let mut fields = exam_for_u_w.not_none_fields();
+            prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+            let fields = fields.for_sea_update();
+            let mut query = Query::update();
+            query
+                .table(Self::table_ref())
+                .values(fields)
+                .and_where(Expr::col(ExamIden::LessonId).eq(exam_for_u.lesson_id));
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+            return Ok(());
+        }
+
+        let exam_data = ExamData {
+            lesson_id: exam_for_u.lesson_id,
+            opens_at,
+            closes_at,
+        };
+
+        let mut fields = exam_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?;
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn delete_exam(
+        _: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        let mut query = Query::delete();
+        query
+            .from_table(Self::table_ref())
+            .and_where(Expr::col(ExamIden::LessonId).eq(lesson_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn release_exam_results(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+    ) -> LessonResult<()> {
+        let exam_for_r = ExamForRelease {
+            results_released: true,
+        };
+
+        let mut fields = exam_for_r.not_none_fields();
+        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+        let fields = fields.for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(ExamIden::LessonId).eq(lesson_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn get_overdue_exam_progresses(
+        _: &Ctx,
+        dbm: &DbManager,
+        now: i64,
+    ) -> LessonResult<Vec<LessonProgress>> {
+        let now = from_unix_timestamp(now)?;
+
+        let mut query = Query::select();
+        query
+            .from(get_lesson_progress_table_ref())
+            .columns(LessonProgressData::field_column_refs())
+            .and_where(Expr::col(LessonProgressIden::State).eq(LessonProgressState::InProgress.to_string()))
+            .and_where(
+                Expr::col(LessonProgressIden::LessonId)
+                    .in_subquery(Query::select()
+                        .column(ExamIden::LessonId)
+                        .from(Self::table_ref())
+                        .and_where(Expr::col(ExamIden::ClosesAt).lte(now))
+                        .to_owned())
+            );
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, LessonProgressData, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        let mut result = Vec::new();
+        for lesson_progress_data in &entities {
+            result.push(lesson_progress_data.try_into()?);
+        }
+
+        Ok(result)
+    }
+}
@@ -1,13 +1,30 @@
 use async_trait::async_trait;
-use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::exercise::{ExerciseResult, IExerciseCommandRepository}, models::{exercise::{ExerciseForChangeOrder, ExerciseForCreateCommand}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForUpdate}}};
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::exercise::{ExerciseResult, IExerciseCommandRepository}, models::{difficulty_calibration::{DifficultyChange, DifficultyChangeForCreate}, exercise::{ExerciseForChangeOrder, ExerciseForCreateCommand}, exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForReviewCommand, ExerciseCompletionForUpdate, ExerciseCompletionState}, lesson_progress::LessonCompletedExercises, peer_review::{PeerReview, PeerReviewForCreate, PeerReviewForSubmitCommand}, similarity_flag::{SimilarityFlag, SimilarityFlagForCreate}}};
 use modql::field::{Fields, HasFields};
-use sea_query::{Alias, Expr, PostgresQueryBuilder, Query, Value};
+use sea_query::{Alias, Expr, Func, PostgresQueryBuilder, Query, Value};
 use sea_query_binder::SqlxBinder;
 use sqlx::{postgres::PgRow, prelude::FromRow};
 
-use crate::{base::{self, idens::{CommonIden, ExerciseCompletionIden, ExerciseIden}, table_ref::get_exercise_completion_table_ref, DbRepository}, store::{db_manager::DbManager, error::DbError}};
+use crate::{base::{self, idens::{CommonIden, ExerciseCompletionIden, ExerciseIden, LessonIden}, table_ref::{get_exercise_completion_table_ref, get_lesson_table_ref}, DbRepository}, store::{db_manager::DbManager, error::DbError}};
 
-use super::exercise_completion::ExerciseCompletionCommandRepository;
+use super::{difficulty_change::DifficultyChangeCommandRepository, exercise_completion::ExerciseCompletionCommandRepository, peer_review::PeerReviewCommandRepository, similarity_flag::SimilarityFlagCommandRepository};
+
+#[derive(FromRow)]
+struct LessonCompletedExercisesData {
+    pub lesson_id: i64,
+    pub user_id: i64,
+    pub completed: i64,
+}
+
+impl From<LessonCompletedExercisesData> for LessonCompletedExercises {
+    fn from(value: LessonCompletedExercisesData) -> Self {
+        Self {
+            lesson_id: value.lesson_id,
+            user_id: value.user_id,
+            completed: value.completed,
+        }
+    }
+}
 
 #[derive(Fields)]
 struct Exercise {
@@ -20,6 +37,10 @@ struct Exercise {
     pub answer_body: Value,
     pub difficult: String,
     pub time_to_complete: Option<i32>,
+    pub manual_review: bool,
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
 #[derive(Fields, FromRow)]
@@ -34,6 +55,10 @@ struct ExerciseData {
     pub answer_body: serde_json::Value,
     pub difficult: String,
     pub time_to_complete: Option<i32>,
+    pub manual_review: bool,
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: serde_json::Value,
 }
 
 impl TryFrom<ExerciseData> for lib_core::models::exercise::Exercise {
@@ -50,6 +75,10 @@ impl TryFrom<ExerciseData> for lib_core::models::exercise::Exercise {
             exercise_order: value.exercise_order,
             answer_body: value.answer_body,
             exercise_body: value.exercise_body,
+            manual_review: value.manual_review,
+            auto_difficulty: value.auto_difficulty,
+            peer_review_count: value.peer_review_count,
+            peer_review_rubric: value.peer_review_rubric,
         })
     }
 }
@@ -61,6 +90,14 @@ struct ExerciseForUpdate {
     pub exercise_type: Option<String>,
     pub difficult: Option<String>,
     pub time_to_complete: Option<i64>,  
+    pub manual_review: Option<bool>,
+    pub auto_difficulty: Option<bool>,
+    pub peer_review_count: Option<i32>,
+}
+
+#[derive(Fields)]
+struct ExerciseForUpdatePeerReviewRubric {
+    pub peer_review_rubric: Value,
 }
 
 #[derive(Fields)]
@@ -98,6 +135,23 @@ impl ExerciseCommandRepository {
 		}
 	}
 
+    async fn fetch_exercises(&self, query: sea_query::SelectStatement) -> ExerciseResult<Vec<(i64, lib_core::models::exercise::Exercise)>> {
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExerciseData, _>(&sql, values);
+        let entities =
+            self.dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let mut result = Vec::new();
+        for exercise_data in entities {
+            result.push((exercise_data.id, exercise_data.try_into()?));
+        }
+
+        Ok(result)
+    }
+
     async fn update_exercise_body(&self, ctx: &Ctx, body: Option<serde_json::Value>, exercise_id: i64) -> ExerciseResult<()> {
         if let Some(body) = body {
             let exercise_for_u_b = ExerciseForUpdateExerciseBody { 
@@ -112,6 +166,20 @@ impl ExerciseCommandRepository {
         Ok(())
     }
 
+    async fn update_peer_review_rubric(&self, ctx: &Ctx, rubric: Option<serde_json::Value>, exercise_id: i64) -> ExerciseResult<()> {
+        if let Some(rubric) = rubric {
+            let exercise_for_u_r = ExerciseForUpdatePeerReviewRubric {
+                peer_review_rubric: Value::Json(Some(Box::new(rubric))),
+            };
+
+		    base::update::<Self, ExerciseForUpdatePeerReviewRubric>(ctx, &self.dbm, exercise_id, exercise_for_u_r)
+			    .await
+			    .map_err(Into::<DbError>::into)?;
+        }
+
+        Ok(())
+    }
+
     async fn update_answer_body(&self, ctx: &Ctx, body: Option<serde_json::Value>, exercise_id: i64) -> ExerciseResult<()> {
         if let Some(body) = body {
             let exercise_for_u_b = ExerciseForUpdateAnswerBody { 
@@ -138,6 +206,34 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
         Ok(res)
     }
 
+    async fn get_exercises(&self, _: &Ctx, exercise_ids: Vec<i64>) -> ExerciseResult<Vec<(i64, lib_core::models::exercise::Exercise)>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExerciseData::field_column_refs())
+            .and_where(Expr::col(CommonIden::Id).is_in(exercise_ids));
+
+        self.fetch_exercises(query).await
+    }
+
+    async fn get_course_exercises(&self, _: &Ctx, course_id: i64) -> ExerciseResult<Vec<(i64, lib_core::models::exercise::Exercise)>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExerciseData::field_column_refs())
+            .and_where(
+                Expr::col(ExerciseIden::LessonId)
+                    .in_subquery(Query::select()
+                        .column(CommonIden::Id)
+                        .from(get_lesson_table_ref())
+                        .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
+                        .to_owned())
+            )
+            .order_by(ExerciseIden::ExerciseOrder, sea_query::Order::Asc);
+
+        self.fetch_exercises(query).await
+    }
+
     async fn get_lesson_exercises_ordered(
         &self,
         _: &Ctx,
@@ -177,6 +273,10 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
             exercise_body: Value::Json(Some(Box::new(exercise_c.exercise_body))), 
             difficult: exercise_c.difficult.to_string(), 
             time_to_complete: exercise_c.time_to_complete, 
+            manual_review: exercise_c.manual_review,
+            auto_difficulty: exercise_c.auto_difficulty,
+            peer_review_count: exercise_c.peer_review_count,
+            peer_review_rubric: Value::Json(Some(Box::new(exercise_c.peer_review_rubric))),
         };
 
         let exercise_id = base::create::<Self, Exercise>(ctx, &self.dbm, exercise_fi)
@@ -200,6 +300,9 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
             exercise_type: exercise_for_u.exercise_type.and_then(|t| Some(t.to_string())), 
             difficult: exercise_for_u.difficult.clone().and_then(|d| Some(d.to_string())), 
             time_to_complete: exercise_for_u.time_to_complete,
+            manual_review: exercise_for_u.manual_review,
+            auto_difficulty: exercise_for_u.auto_difficulty,
+            peer_review_count: exercise_for_u.peer_review_count,
         };
 
 		base::update::<Self, ExerciseForUpdate>(&ctx, &self.dbm, exercise_for_u.id, data)
@@ -208,6 +311,7 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
 
         self.update_answer_body(ctx, exercise_for_u.answer_body, exercise_for_u.id).await?;
         self.update_exercise_body(ctx, exercise_for_u.exercise_body, exercise_for_u.id).await?;
+        self.update_peer_review_rubric(ctx, exercise_for_u.peer_review_rubric, exercise_for_u.id).await?;
 
 		dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
 
@@ -282,6 +386,70 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
         ExerciseCompletionCommandRepository::remove_exercise_completions(&self.dbm, ctx, exercise_id).await
     }
 
+    async fn update_exercise_completion_state(&self, ctx: &Ctx, ex_comp_id: i64, state: ExerciseCompletionState) -> ExerciseResult<()> {
+        ExerciseCompletionCommandRepository::update_state(&self.dbm, ctx, ex_comp_id, state).await
+    }
+
+    async fn review_exercise_completion(&self, ctx: &Ctx, ex_comp_for_r: ExerciseCompletionForReviewCommand) -> ExerciseResult<()> {
+        ExerciseCompletionCommandRepository::review_exercise(&self.dbm, ctx, ex_comp_for_r).await
+    }
+
+    async fn get_course_pending_reviews(&self, ctx: &Ctx, course_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        ExerciseCompletionCommandRepository::get_course_pending_reviews(&self.dbm, ctx, course_id).await
+    }
+
+    async fn get_exercise_completions(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        ExerciseCompletionCommandRepository::get_exercise_completions(ctx, &self.dbm, exercise_id).await
+    }
+
+    async fn update_exercise_completion_points(&self, ctx: &Ctx, ex_comp_id: i64, points_scored: f32, state: ExerciseCompletionState) -> ExerciseResult<()> {
+        ExerciseCompletionCommandRepository::update_points(&self.dbm, ctx, ex_comp_id, points_scored, state).await
+    }
+
+    async fn get_exercise_peer_reviews(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<PeerReview>> {
+        PeerReviewCommandRepository::get_exercise_peer_reviews(ctx, &self.dbm, exercise_id).await
+    }
+
+    async fn get_exercise_completion_peer_reviews(&self, ctx: &Ctx, ex_comp_id: i64) -> ExerciseResult<Vec<PeerReview>> {
+        PeerReviewCommandRepository::get_exercise_completion_peer_reviews(ctx, &self.dbm, ex_comp_id).await
+    }
+
+    async fn get_peer_review(&self, ctx: &Ctx, peer_review_id: i64) -> ExerciseResult<PeerReview> {
+        PeerReviewCommandRepository::get(ctx, &self.dbm, peer_review_id).await
+    }
+
+    async fn create_peer_reviews(&self, ctx: &Ctx, peer_reviews_for_c: Vec<PeerReviewForCreate>) -> ExerciseResult<()> {
+        PeerReviewCommandRepository::create_peer_reviews(ctx, &self.dbm, peer_reviews_for_c).await
+    }
+
+    async fn submit_peer_review(&self, ctx: &Ctx, peer_review_for_s: PeerReviewForSubmitCommand) -> ExerciseResult<()> {
+        PeerReviewCommandRepository::submit_peer_review(ctx, &self.dbm, peer_review_for_s).await
+    }
+
+    async fn get_exercises_for_similarity_check(&self, ctx: &Ctx) -> ExerciseResult<Vec<i64>> {
+        SimilarityFlagCommandRepository::get_exercises_for_similarity_check(ctx, &self.dbm).await
+    }
+
+    async fn get_exercise_similarity_flags(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<SimilarityFlag>> {
+        SimilarityFlagCommandRepository::get_exercise_similarity_flags(ctx, &self.dbm, exercise_id).await
+    }
+
+    async fn replace_similarity_flags(&self, ctx: &Ctx, exercise_id: i64, flags_for_c: Vec<SimilarityFlagForCreate>) -> ExerciseResult<()> {
+        SimilarityFlagCommandRepository::replace_similarity_flags(ctx, &self.dbm, exercise_id, flags_for_c).await
+    }
+
+    async fn get_auto_difficulty_exercises(&self, ctx: &Ctx) -> ExerciseResult<Vec<i64>> {
+        DifficultyChangeCommandRepository::get_auto_difficulty_exercises(ctx, &self.dbm).await
+    }
+
+    async fn get_exercise_difficulty_changes(&self, ctx: &Ctx, exercise_id: i64) -> ExerciseResult<Vec<DifficultyChange>> {
+        DifficultyChangeCommandRepository::get_exercise_difficulty_changes(ctx, &self.dbm, exercise_id).await
+    }
+
+    async fn change_exercise_difficulty(&self, ctx: &Ctx, difficulty_change_for_c: DifficultyChangeForCreate) -> ExerciseResult<()> {
+        DifficultyChangeCommandRepository::change_exercise_difficulty(ctx, &self.dbm, difficulty_change_for_c).await
+    }
+
     async fn get_number_of_lesson_completed_exercises(&self, _: &Ctx, lesson_id: i64, user_id: i64) -> ExerciseResult<i64> {
     	let mut subquery = Query::select();
     	subquery.from(Self::table_ref())
@@ -294,7 +462,8 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
         	)
         	.and_where(Expr::col((ExerciseIden::Exercise, ExerciseIden::LessonId)).eq(lesson_id))
         	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::UserId)).eq(user_id))
-        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"));
+        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"))
+        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::IsPractice)).eq(false));
 
     	let mut query = Query::select();
     	query.expr(Expr::col(Alias::new("subquery")).count())
@@ -308,4 +477,35 @@ impl IExerciseCommandRepository for ExerciseCommandRepository {
 
     	Ok(entities)
     }
+
+    async fn get_course_completed_exercises(&self, _: &Ctx, course_id: i64) -> ExerciseResult<Vec<LessonCompletedExercises>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .column((ExerciseIden::Exercise, ExerciseIden::LessonId))
+            .column((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::UserId))
+            .expr_as(Func::count_distinct(Expr::col((ExerciseIden::Exercise, CommonIden::Id))), Alias::new("completed"))
+            .inner_join(
+                get_exercise_completion_table_ref(),
+                Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::ExerciseId))
+                    .equals((ExerciseIden::Exercise, CommonIden::Id))
+            )
+            .inner_join(
+                get_lesson_table_ref(),
+                Expr::col((LessonIden::Lesson, CommonIden::Id))
+                    .equals((ExerciseIden::Exercise, ExerciseIden::LessonId))
+            )
+            .and_where(Expr::col((LessonIden::Lesson, LessonIden::CourseId)).eq(course_id))
+            .and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"))
+            .and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::IsPractice)).eq(false))
+            .group_by_col((ExerciseIden::Exercise, ExerciseIden::LessonId))
+            .group_by_col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::UserId));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, LessonCompletedExercisesData, _>(&sql, values);
+        let entities = self.dbm.dbx().fetch_all(sqlx_query).await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(Into::into).collect())
+    }
 }
\ No newline at end of file
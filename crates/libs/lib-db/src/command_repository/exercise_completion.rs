@@ -1,4 +1,4 @@
-use lib_core::{ctx::Ctx, interactors::error::ExerciseError, interfaces::exercise::ExerciseResult, models::exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForUpdate}};
+use lib_core::{ctx::Ctx, interactors::error::ExerciseError, interfaces::exercise::ExerciseResult, models::exercise_completion::{ExerciseCompletion, ExerciseCompletionForCompleteCommand, ExerciseCompletionForCreate, ExerciseCompletionForReviewCommand, ExerciseCompletionForUpdate, ExerciseCompletionState}};
 use lib_utils::time::from_unix_timestamp;
 use modql::field::{Fields, HasFields};
 use sea_query::{Expr, PostgresQueryBuilder, Query, Value};
@@ -6,7 +6,7 @@ use sea_query_binder::SqlxBinder;
 use sqlx::{postgres::PgRow, prelude::FromRow};
 use time::OffsetDateTime;
 
-use crate::{base::{idens::ExerciseCompletionIden, prep_fields_for_create, prep_fields_for_update, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+use crate::{base::{idens::{CommonIden, ExerciseCompletionIden, ExerciseIden, LessonIden}, prep_fields_for_create, prep_fields_for_update, table_ref::{get_exercise_table_ref, get_lesson_table_ref}, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
 
 #[derive(Fields)]
 struct ExerciseCompletionData {
@@ -14,6 +14,7 @@ struct ExerciseCompletionData {
     pub user_id: i64,
     pub number_of_attempts: i32,
     pub date_started: OffsetDateTime,
+    pub is_practice: bool,
 }
 
 #[derive(Fields)]
@@ -29,6 +30,24 @@ struct ExerciseCompletionForComplete {
     pub state: String,
 }
 
+#[derive(Fields)]
+struct ExerciseCompletionForUpdateState {
+    pub state: String,
+}
+
+#[derive(Fields)]
+struct ExerciseCompletionForUpdatePoints {
+    pub points_scored: f32,
+    pub state: String,
+}
+
+#[derive(Fields)]
+struct ExerciseCompletionForReview {
+    pub points_scored: f32,
+    pub state: String,
+    pub review_comment: Option<String>,
+}
+
 #[derive(Fields, FromRow)]
 struct ExerciseCompletionQuery {
     pub exercise_completion_id: i64,
@@ -41,6 +60,9 @@ struct ExerciseCompletionQuery {
     pub date_last_changes: Option<OffsetDateTime>,
     pub state: String,   
     pub body: Option<serde_json::Value>,
+    pub review_comment: Option<String>,
+    pub is_practice: bool,
+    pub paused_seconds: i64,
 }
 
 impl TryFrom<ExerciseCompletionQuery> for ExerciseCompletion {
@@ -58,6 +80,9 @@ impl TryFrom<ExerciseCompletionQuery> for ExerciseCompletion {
             state: value.state.try_into()?,
             body: value.body.clone(),
             max_points: value.max_points,
+            review_comment: value.review_comment,
+            is_practice: value.is_practice,
+            paused_seconds: value.paused_seconds,
         })
     }
 }
@@ -137,6 +162,7 @@ impl ExerciseCompletionCommandRepository {
             user_id: ex_comp_for_c.user_id,
             number_of_attempts: ex_comp_for_c.number_of_attempts as i32,
             date_started: from_unix_timestamp(ex_comp_for_c.date_started)?,
+            is_practice: ex_comp_for_c.is_practice,
         };
         let user_id = ctx.user_id();
 
@@ -229,6 +255,234 @@ impl ExerciseCompletionCommandRepository {
 	    }        
     }
 
+    pub async fn update_state(
+        dbm: &DbManager,
+        ctx: &Ctx,
+        ex_comp_id: i64,
+        state: ExerciseCompletionState,
+    ) -> ExerciseResult<()> {
+        let ex_comp_for_u_req = ExerciseCompletionForUpdateState {
+            state: state.to_string(),
+        };
+
+	    let mut fields = ex_comp_for_u_req.not_none_fields();
+	    prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+	    let fields = fields.for_sea_update();
+	    let mut query = Query::update();
+	    query
+		    .table(Self::table_ref())
+		    .values(fields)
+		    .and_where(Expr::col(ExerciseCompletionIden::ExerciseCompletionId).eq(ex_comp_id));
+
+	    let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+	    let sqlx_query = sqlx::query_with(&sql, values);
+	    let count = dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+	    if count == 0 {
+		    Err(DbError::EntityNotFound {
+			    entity: Self::TABLE.to_string(),
+			    id: ex_comp_id,
+		    }.into())
+	    } else {
+		    Ok(())
+	    }
+    }
+
+    pub async fn add_paused_time(
+        dbm: &DbManager,
+        _: &Ctx,
+        lesson_id: i64,
+        user_id: i64,
+        paused_seconds: i64,
+    ) -> ExerciseResult<()> {
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .value(ExerciseCompletionIden::PausedSeconds, Expr::col(ExerciseCompletionIden::PausedSeconds).add(paused_seconds))
+            .and_where(Expr::col(ExerciseCompletionIden::UserId).eq(user_id))
+            .and_where(Expr::col(ExerciseCompletionIden::State).eq(ExerciseCompletionState::InProgress.to_string()))
+            .and_where(
+                Expr::col(ExerciseCompletionIden::ExerciseId)
+                    .in_subquery(Query::select()
+                        .column(CommonIden::Id)
+                        .from(get_exercise_table_ref())
+                        .and_where(Expr::col(ExerciseIden::LessonId).eq(lesson_id))
+                        .to_owned())
+            );
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    /// Ids of the attempts of the lesson waiting for their grade.
+    pub async fn get_lesson_submitted_ids(
+        dbm: &DbManager,
+        _: &Ctx,
+        lesson_id: i64,
+    ) -> ExerciseResult<Vec<i64>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .column(ExerciseCompletionIden::ExerciseCompletionId)
+            .and_where(Expr::col(ExerciseCompletionIden::State).eq(ExerciseCompletionState::Submitted.to_string()))
+            .and_where(
+                Expr::col(ExerciseCompletionIden::ExerciseId)
+                    .in_subquery(Query::select()
+                        .column(CommonIden::Id)
+                        .from(get_exercise_table_ref())
+                        .and_where(Expr::col(ExerciseIden::LessonId).eq(lesson_id))
+                        .to_owned())
+            );
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, (i64,), _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(|(id,)| id).collect())
+    }
+
+    pub async fn review_exercise(
+        dbm: &DbManager,
+        ctx: &Ctx,
+        ex_comp_for_r: ExerciseCompletionForReviewCommand,
+    ) -> ExerciseResult<()> {
+        let ex_comp_for_r_req = ExerciseCompletionForReview {
+            points_scored: ex_comp_for_r.points_scored,
+            state: ex_comp_for_r.state.to_string(),
+            review_comment: ex_comp_for_r.review_comment,
+        };
+
+	    let mut fields = ex_comp_for_r_req.not_none_fields();
+	    prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+	    let fields = fields.for_sea_update();
+	    let mut query = Query::update();
+	    query
+		    .table(Self::table_ref())
+		    .values(fields)
+		    .and_where(Expr::col(ExerciseCompletionIden::ExerciseCompletionId).eq(ex_comp_for_r.id));
+
+	    let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+	    let sqlx_query = sqlx::query_with(&sql, values);
+	    let count = dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+	    if count == 0 {
+		    Err(DbError::EntityNotFound {
+			    entity: Self::TABLE.to_string(),
+			    id: ex_comp_for_r.id,
+		    }.into())
+	    } else {
+		    Ok(())
+	    }
+    }
+
+    pub async fn update_points(
+        dbm: &DbManager,
+        ctx: &Ctx,
+        ex_comp_id: i64,
+        points_scored: f32,
+        state: ExerciseCompletionState,
+    ) -> ExerciseResult<()> {
+        let ex_comp_for_u_req = ExerciseCompletionForUpdatePoints {
+            points_scored,
+            state: state.to_string(),
+        };
+
+	    let mut fields = ex_comp_for_u_req.not_none_fields();
+	    prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+	    let fields = fields.for_sea_update();
+	    let mut query = Query::update();
+	    query
+		    .table(Self::table_ref())
+		    .values(fields)
+		    .and_where(Expr::col(ExerciseCompletionIden::ExerciseCompletionId).eq(ex_comp_id));
+
+	    let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+	    let sqlx_query = sqlx::query_with(&sql, values);
+	    let count = dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+	    if count == 0 {
+		    Err(DbError::EntityNotFound {
+			    entity: Self::TABLE.to_string(),
+			    id: ex_comp_id,
+		    }.into())
+	    } else {
+		    Ok(())
+	    }
+    }
+
+    pub async fn get_exercise_completions(
+        _: &Ctx,
+        dbm: &DbManager,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExerciseCompletionQuery::field_column_refs())
+            .and_where(Expr::col(ExerciseCompletionIden::ExerciseId).eq(exercise_id));
+    
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExerciseCompletionQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+        
+        let mut result = Vec::new();
+
+        for ex_comp in entities {
+            result.push(ex_comp.try_into()?);
+        }
+
+        Ok(result)
+    }
+
+    pub async fn get_course_pending_reviews(
+        dbm: &DbManager,
+        _: &Ctx,
+        course_id: i64,
+    ) -> ExerciseResult<Vec<ExerciseCompletion>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(ExerciseCompletionQuery::field_column_refs())
+            .inner_join(get_exercise_table_ref(),
+                Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::ExerciseId))
+                .equals((ExerciseIden::Exercise, CommonIden::Id))
+            )
+            .inner_join(get_lesson_table_ref(),
+                Expr::col((ExerciseIden::Exercise, ExerciseIden::LessonId))
+                .equals((LessonIden::Lesson, CommonIden::Id))
+            )
+            .and_where(Expr::col((LessonIden::Lesson, LessonIden::CourseId)).eq(course_id))
+            .and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("PendingReview"))
+            .order_by((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::ExerciseCompletionId), sea_query::Order::Asc);
+    
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, ExerciseCompletionQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+        
+        let mut result = Vec::new();
+
+        for ex_comp in entities {
+            result.push(ex_comp.try_into()?);
+        }
+
+        Ok(result)
+    }
+
     pub async fn get_uncompleted_exercises(
         dbm: &DbManager,
         _: &Ctx,
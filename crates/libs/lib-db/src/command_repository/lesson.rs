@@ -1,24 +1,26 @@
 use async_trait::async_trait;
 use lib_core::{
     ctx::Ctx, 
+    interactors::error::LessonError,
     interfaces::lesson::{ILessonCommandRepository, LessonResult}, 
-    models::{lesson::{
+    models::{attachment::{Attachment, AttachmentForCreateCommand}, content_block::{ContentBlock, ContentBlockForChangeOrder, ContentBlockForCreateCommand, ContentBlockForUpdateCommand}, exam::{Exam, ExamForUpdate}, exercise_completion::{ExerciseCompletionForCompleteCommand, ExerciseCompletionState}, lesson::{
         Lesson, LessonForChangeOreder, LessonForCreateCommand, LessonForUpdate
-    }, lesson_progress::{LessonProgress, LessonProgressState}}
+    }, lesson_progress::{LessonProgress, LessonProgressState, LessonProgressStateChange}, section::{Section, SectionForChangeOrder, SectionForCreateCommand, SectionForUpdate}}
 };
 use modql::field::{Fields, HasFields};
-use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query::{Expr, Keyword, LockType, PostgresQueryBuilder, Query, SimpleExpr};
 use sea_query_binder::SqlxBinder;
 use sqlx::{postgres::PgRow, FromRow};
 
-use crate::{base::{self, idens::{CommonIden, LessonIden, LessonProgressIden}, prep_fields_for_update, table_ref::get_lesson_progress_table_ref, DbRepository}, store::{db_manager::DbManager, error::DbError}};
+use crate::{base::{self, idens::{CommonIden, LessonIden, LessonProgressIden}, table_ref::get_lesson_progress_table_ref, DbRepository}, store::{db_manager::DbManager, error::DbError}};
 
-use super::lesson_progress::LessonProgressCommandRepository;
+use super::{attachment::AttachmentCommandRepository, content_block::ContentBlockCommandRepository, exam::ExamCommandRepository, exercise_completion::ExerciseCompletionCommandRepository, lesson_progress::LessonProgressCommandRepository, section::SectionCommandRepository};
 
 #[derive(Clone, Fields, FromRow, Debug)]
 struct LessonData {
 	pub id: i64,
 	pub course_id: i64,
+    pub section_id: Option<i64>,
     pub title: String,
     pub lesson_order: i32,
 }
@@ -26,6 +28,7 @@ struct LessonData {
 #[derive(Fields)]
 struct LessonForInsert {
 	pub course_id: i64,
+    pub section_id: Option<i64>,
     pub title: String,
     pub description: String,
     pub lesson_order: i32,
@@ -79,6 +82,7 @@ impl ILessonCommandRepository for LessonCommandRepository {
             id: lesson.id, 
             course_id: lesson.course_id, 
             title: lesson.title, 
+            section_id: lesson.section_id,
             lesson_order: lesson.lesson_order, 
         };
 
@@ -87,7 +91,7 @@ impl ILessonCommandRepository for LessonCommandRepository {
 
     async fn get_course_lessons_ordered(
         &self, 
-        _: &Ctx, 
+        ctx: &Ctx, 
         course_id: i64
     ) -> LessonResult<Vec<LessonForChangeOreder>> {
         let mut query = Query::select();
@@ -97,6 +101,48 @@ impl ILessonCommandRepository for LessonCommandRepository {
             .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
             .order_by(LessonIden::LessonOrder, sea_query::Order::Asc);
     
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, LessonData, _>(&sql, values);
+        let mut lessons =
+            self.dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let sections = SectionCommandRepository::get_course_sections_ordered(ctx, &self.dbm, course_id).await?;
+
+        // Lessons outside sections go first, the sort is stable so the lesson order is kept.
+        lessons.sort_by_key(|lesson| {
+            lesson.section_id.and_then(|section_id| sections.iter().position(|section| section.id == section_id))
+        });
+
+        let result = lessons.iter().map(|lesson| LessonForChangeOreder { 
+            id: lesson.id, 
+            order: lesson.lesson_order, 
+        }).collect();
+
+        Ok(result)
+    }
+
+    async fn get_section_lessons_ordered(
+        &self, 
+        _: &Ctx, 
+        course_id: i64,
+        section_id: Option<i64>,
+    ) -> LessonResult<Vec<LessonForChangeOreder>> {
+        let section_condition = match section_id {
+            Some(section_id) => Expr::col(LessonIden::SectionId).eq(section_id),
+            None => Expr::col(LessonIden::SectionId).is_null(),
+        };
+
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(LessonData::field_column_refs())
+            .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
+            .and_where(section_condition)
+            .order_by(LessonIden::LessonOrder, sea_query::Order::Asc);
+    
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
         let sqlx_query = sqlx::query_as_with::<_, LessonData, _>(&sql, values);
         let lessons =
@@ -113,6 +159,48 @@ impl ILessonCommandRepository for LessonCommandRepository {
         Ok(result)
     }
 
+    async fn update_lesson_section(
+        &self,
+        ctx: &Ctx,
+        lesson_id: i64,
+        section_id: Option<i64>,
+        order: i32,
+        source_lessons_for_u_order: Vec<LessonForChangeOreder>,
+    ) -> LessonResult<()> {
+        let dbm = self.dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let section_id = match section_id {
+            Some(section_id) => SimpleExpr::from(section_id),
+            None => SimpleExpr::Keyword(Keyword::Null),
+        };
+
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .value(LessonIden::SectionId, section_id)
+            .value(LessonIden::LessonOrder, order)
+            .and_where(Expr::col(CommonIden::Id).eq(lesson_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        for lesson in &source_lessons_for_u_order {
+            let lesson_for_u_order = LessonForUpdateOrder {
+                lesson_order: lesson.order,
+            };
+
+            base::update::<Self, LessonForUpdateOrder>(ctx, &dbm, lesson.id, lesson_for_u_order)
+                .await
+                .map_err(Into::<DbError>::into)?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
     async fn create_lesson_progress(
         &self,
         ctx: &Ctx, 
@@ -147,6 +235,7 @@ impl ILessonCommandRepository for LessonCommandRepository {
     ) -> LessonResult<i64> {
         let lesson_fi = LessonForInsert {
             course_id: lesson_for_c.course_id, 
+            section_id: lesson_for_c.section_id,
             title: lesson_for_c.title, 
             description: lesson_for_c.description,
             lesson_order:  lesson_for_c.order,
@@ -204,70 +293,20 @@ impl ILessonCommandRepository for LessonCommandRepository {
         Ok(())
     }
 
-    async fn change_lesson_progress_states_for_update_exercise(
-        &self, 
-        ctx: &Ctx, 
-        course_id: i64, 
-        order: i32
-    ) -> LessonResult<()> {
-		let dbm = self.dbm.new_with_txn()?;
-		dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
-
-        let pause_state = LessonForUpdateState {
-            state: LessonProgressState::Pause.to_string(),
-        };
-        let mut fields = pause_state.not_none_fields();
-        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
-    
-        let lesson_progress_table_ref = get_lesson_progress_table_ref();
-        let fields = fields.for_sea_update();
-        let mut query = Query::update();
-        query
-        .table(lesson_progress_table_ref.clone()) 
-        .values(fields)
-        .and_where(
-            Expr::col(LessonProgressIden::LessonId)
-                .in_subquery(Query::select()
-                    .column(CommonIden::Id)
-                    .from(LessonIden::Lesson)
-                    .and_where(Expr::col(LessonIden::LessonOrder).gt(order))
-                    .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
-                    .to_owned())
-        );
-
-    
-        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
-        let sqlx_query = sqlx::query_with(&sql, values);
-        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
-
-        let in_progress_state = LessonForUpdateState {
-            state: LessonProgressState::InProgress.to_string(),
-        };
-        let mut fields = in_progress_state.not_none_fields();
-        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
-    
-        let fields = fields.for_sea_update();
-        let mut query = Query::update();
-        query
-        .table(lesson_progress_table_ref) 
-        .values(fields)
-        .and_where(
-            Expr::col(LessonProgressIden::LessonId)
-                .in_subquery(Query::select()
-                    .column(CommonIden::Id)
-                    .from(LessonIden::Lesson)
-                    .and_where(Expr::col(LessonIden::LessonOrder).eq(order))
-                    .and_where(Expr::col(LessonIden::CourseId).eq(course_id))
-                    .to_owned())
-        );
-    
-        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
-        let sqlx_query = sqlx::query_with(&sql, values);
-        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+    async fn get_course_lesson_progresses(
+        &self,
+        ctx: &Ctx,
+        course_id: i64,
+    ) -> LessonResult<Vec<LessonProgress>> {
+        let lesson_progresses_data = LessonProgressCommandRepository::get_course_lessons_progresses(&self.dbm, ctx, course_id)
+            .await?;
 
-		dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+        let mut result = Vec::new();
+        for lesson_progress_data in &lesson_progresses_data {
+            result.push(lesson_progress_data.try_into()?)
+        }
 
-        Ok(())
+        Ok(result)
     }
 
     async fn update_lesson_progress_state(
@@ -299,4 +338,169 @@ impl ILessonCommandRepository for LessonCommandRepository {
     
         Ok(())
     }
+
+    async fn get_exam(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<Option<Exam>> {
+        ExamCommandRepository::get_exam(ctx, &self.dbm, lesson_id).await
+    }
+
+    async fn get_course_exams(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<Exam>> {
+        ExamCommandRepository::get_course_exams(ctx, &self.dbm, course_id).await
+    }
+
+    async fn save_exam(&self, ctx: &Ctx, exam_for_u: ExamForUpdate) -> LessonResult<()> {
+        ExamCommandRepository::save_exam(ctx, &self.dbm, exam_for_u).await
+    }
+
+    async fn delete_exam(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<()> {
+        ExamCommandRepository::delete_exam(ctx, &self.dbm, lesson_id).await
+    }
+
+    async fn release_exam_results(&self, ctx: &Ctx, lesson_id: i64, graded_attempts: Vec<ExerciseCompletionForCompleteCommand>) -> LessonResult<()> {
+        let dbm = self.dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        // Submissions wait for the lock, the attempts submitted before it must all be graded.
+        if ExamCommandRepository::get_exam_locked(&dbm, lesson_id, LockType::Update).await?.is_none() {
+            return Err(LessonError::LessonIsNotExam { lesson_id }.into());
+        }
+
+        let submitted_ids = ExerciseCompletionCommandRepository::get_lesson_submitted_ids(&dbm, ctx, lesson_id).await?;
+        if submitted_ids.iter().any(|id| !graded_attempts.iter().any(|ex_comp_for_u| ex_comp_for_u.id == *id)) {
+            return Err(DbError::ExamAttemptSubmittedMeanwhile { lesson_id }.into());
+        }
+
+        for ex_comp_for_u in graded_attempts {
+            ExerciseCompletionCommandRepository::complete_exercise(&dbm, ctx, ex_comp_for_u).await?;
+        }
+
+        ExamCommandRepository::release_exam_results(ctx, &dbm, lesson_id).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn submit_exam_attempt(&self, ctx: &Ctx, lesson_id: i64, ex_comp_id: i64) -> LessonResult<bool> {
+        let dbm = self.dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let results_released = ExamCommandRepository::get_exam_locked(&dbm, lesson_id, LockType::Share)
+            .await?
+            .is_some_and(|exam| exam.results_released);
+
+        if !results_released {
+            ExerciseCompletionCommandRepository::update_state(&dbm, ctx, ex_comp_id, ExerciseCompletionState::Submitted).await?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(!results_released)
+    }
+
+    async fn get_overdue_exam_progresses(&self, ctx: &Ctx, now: i64) -> LessonResult<Vec<LessonProgress>> {
+        ExamCommandRepository::get_overdue_exam_progresses(ctx, &self.dbm, now).await
+    }
+
+    async fn update_lesson_progresses_states(&self, ctx: &Ctx, changes: Vec<LessonProgressStateChange>) -> LessonResult<()> {
+        let dbm = self.dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        for change in changes {
+            LessonProgressCommandRepository::update_state(ctx, &dbm, change).await?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn pause_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64, date_paused: i64) -> LessonResult<()> {
+        LessonProgressCommandRepository::pause(ctx, &self.dbm, lesson_id, user_id, date_paused).await?;
+        Ok(())
+    }
+
+    async fn resume_lesson_progress(&self, ctx: &Ctx, lesson_id: i64, user_id: i64, paused_seconds: i64) -> LessonResult<()> {
+        let dbm = self.dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        // The resume fails first when another one got ahead, the paused time is added once.
+        LessonProgressCommandRepository::resume(ctx, &dbm, lesson_id, user_id).await?;
+        ExerciseCompletionCommandRepository::add_paused_time(&dbm, ctx, lesson_id, user_id, paused_seconds).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    async fn get_section(&self, ctx: &Ctx, section_id: i64) -> LessonResult<Section> {
+        SectionCommandRepository::get_section(ctx, &self.dbm, section_id).await
+    }
+
+    async fn get_course_sections_ordered(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<SectionForChangeOrder>> {
+        SectionCommandRepository::get_course_sections_ordered(ctx, &self.dbm, course_id).await
+    }
+
+    async fn create_section(&self, ctx: &Ctx, section_for_c: SectionForCreateCommand) -> LessonResult<i64> {
+        SectionCommandRepository::create_section(ctx, &self.dbm, section_for_c).await
+    }
+
+    async fn update_section(&self, ctx: &Ctx, section_for_u: SectionForUpdate) -> LessonResult<()> {
+        SectionCommandRepository::update_section(ctx, &self.dbm, section_for_u).await
+    }
+
+    async fn delete_section(&self, ctx: &Ctx, section_id: i64) -> LessonResult<()> {
+        SectionCommandRepository::delete_section(ctx, &self.dbm, section_id).await
+    }
+
+    async fn update_section_orders(&self, ctx: &Ctx, sections_for_u_order: Vec<SectionForChangeOrder>) -> LessonResult<()> {
+        SectionCommandRepository::update_section_orders(ctx, &self.dbm, sections_for_u_order).await
+    }
+
+    async fn get_content_block(&self, ctx: &Ctx, content_block_id: i64) -> LessonResult<ContentBlock> {
+        ContentBlockCommandRepository::get_content_block(ctx, &self.dbm, content_block_id).await
+    }
+
+    async fn get_lesson_content_blocks_ordered(&self, ctx: &Ctx, lesson_id: i64) -> LessonResult<Vec<ContentBlockForChangeOrder>> {
+        ContentBlockCommandRepository::get_lesson_content_blocks_ordered(ctx, &self.dbm, lesson_id).await
+    }
+
+    async fn create_content_block(&self, ctx: &Ctx, content_block_for_c: ContentBlockForCreateCommand) -> LessonResult<i64> {
+        ContentBlockCommandRepository::create_content_block(ctx, &self.dbm, content_block_for_c).await
+    }
+
+    async fn update_content_block(&self, ctx: &Ctx, content_block_for_u: ContentBlockForUpdateCommand) -> LessonResult<()> {
+        ContentBlockCommandRepository::update_content_block(ctx, &self.dbm, content_block_for_u).await
+    }
+
+    async fn delete_content_block(&self, ctx: &Ctx, content_block_id: i64) -> LessonResult<()> {
+        ContentBlockCommandRepository::delete_content_block(ctx, &self.dbm, content_block_id).await
+    }
+
+    async fn update_content_block_orders(&self, ctx: &Ctx, content_blocks_for_u_order: Vec<ContentBlockForChangeOrder>) -> LessonResult<()> {
+        ContentBlockCommandRepository::update_content_block_orders(ctx, &self.dbm, content_blocks_for_u_order).await
+    }
+
+    async fn get_content_block_urls(&self, ctx: &Ctx) -> LessonResult<Vec<String>> {
+        ContentBlockCommandRepository::get_content_block_urls(ctx, &self.dbm).await
+    }
+
+    async fn get_attachment(&self, ctx: &Ctx, attachment_id: i64) -> LessonResult<Attachment> {
+        AttachmentCommandRepository::get_attachment(ctx, &self.dbm, attachment_id).await
+    }
+
+    async fn create_attachment(&self, ctx: &Ctx, attachment_for_c: AttachmentForCreateCommand) -> LessonResult<i64> {
+        AttachmentCommandRepository::create_attachment(ctx, &self.dbm, attachment_for_c).await
+    }
+
+    async fn delete_attachment(&self, ctx: &Ctx, attachment_id: i64) -> LessonResult<()> {
+        AttachmentCommandRepository::delete_attachment(ctx, &self.dbm, attachment_id).await
+    }
+
+    async fn get_attachment_storage_keys(&self, ctx: &Ctx) -> LessonResult<Vec<String>> {
+        AttachmentCommandRepository::get_attachment_storage_keys(ctx, &self.dbm).await
+    }
+
+    async fn get_course_attachments(&self, ctx: &Ctx, course_id: i64) -> LessonResult<Vec<Attachment>> {
+        AttachmentCommandRepository::get_course_attachments(ctx, &self.dbm, course_id).await
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
-use lib_core::{ctx::Ctx, interactors::error::CoreError, models::lesson_progress::LessonProgress};
-use lib_utils::time::now_utc;
+use lib_core::{ctx::Ctx, interactors::error::CoreError, models::lesson_progress::{LessonProgress, LessonProgressState, LessonProgressStateChange}};
+use lib_utils::time::{from_unix_timestamp, now_utc};
 use modql::field::{Fields, HasFields};
-use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query::{Expr, Keyword, PostgresQueryBuilder, Query, SimpleExpr};
 use sea_query_binder::SqlxBinder;
 use sqlx::FromRow;
 use time::OffsetDateTime;
@@ -17,6 +17,8 @@ pub struct LessonProgressData {
     pub date_complete: Option<OffsetDateTime>,  
 
     pub state: String,
+    pub skipped_by_placement: bool,
+    pub date_paused: Option<OffsetDateTime>,
 }
 
 impl TryFrom<&LessonProgressData> for LessonProgress {
@@ -29,6 +31,8 @@ impl TryFrom<&LessonProgressData> for LessonProgress {
             date_started: value.date_started.unix_timestamp(),
             date_complete: value.date_complete.and_then(|date| Some(date.unix_timestamp())),
             state: value.state.clone().try_into()?,
+            skipped_by_placement: value.skipped_by_placement,
+            date_paused: value.date_paused.map(|date| date.unix_timestamp()),
         })
     }
 }
@@ -40,6 +44,22 @@ struct LessonProgreesForInsert {
     date_started: OffsetDateTime,
 }
 
+#[derive(Fields)]
+struct SkippedLessonProgressForInsert {
+    user_id: i64,
+    lesson_id: i64,
+    date_started: OffsetDateTime,
+    date_complete: OffsetDateTime,
+    state: String,
+    skipped_by_placement: bool,
+}
+
+#[derive(Fields)]
+struct LessonProgressForPause {
+    state: String,
+    date_paused: OffsetDateTime,
+}
+
 #[derive(Clone)]
 pub struct LessonProgressCommandRepository;
 
@@ -82,6 +102,125 @@ impl LessonProgressCommandRepository {
         Ok(())
     }
 
+    /// Runs in the transaction of the placement attempt.
+    pub async fn create_skipped(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        user_id: i64,
+        lesson_ids: Vec<i64>,
+    ) -> DbResult<()> {
+        let now = now_utc();
+
+        for lesson_id in lesson_ids {
+            let lesson_progress_f_i = SkippedLessonProgressForInsert {
+                user_id,
+                lesson_id,
+                date_started: now,
+                date_complete: now,
+                state: LessonProgressState::Done.to_string(),
+                skipped_by_placement: true,
+            };
+
+            let mut fields = lesson_progress_f_i.not_none_fields();
+            prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+            let (columns, sea_values) = fields.for_sea_insert();
+            let mut query = Query::insert();
+            query
+                .into_table(Self::table_ref())
+                .columns(columns)
+                .values(sea_values)
+                .map_err(DbxError::SeaQuery)
+                .map_err(Into::<DbError>::into)?;
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn pause(
+        _: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+        user_id: i64,
+        date_paused: i64,
+    ) -> DbResult<()> {
+        let lesson_progress_for_p = LessonProgressForPause {
+            state: LessonProgressState::Pause.to_string(),
+            date_paused: from_unix_timestamp(date_paused).map_err(DbError::DateError)?,
+        };
+
+        let fields = lesson_progress_for_p.not_none_fields().for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(LessonProgressIden::UserId).eq(user_id))
+            .and_where(Expr::col(LessonProgressIden::LessonId).eq(lesson_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await?;
+
+        Ok(())
+    }
+
+    /// Only a progress paused by the student is resumed, so a concurrent resume fails.
+    pub async fn resume(
+        _: &Ctx,
+        dbm: &DbManager,
+        lesson_id: i64,
+        user_id: i64,
+    ) -> DbResult<()> {
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .value(LessonProgressIden::State, LessonProgressState::InProgress.to_string())
+            .value(LessonProgressIden::DatePaused, SimpleExpr::Keyword(Keyword::Null))
+            .and_where(Expr::col(LessonProgressIden::UserId).eq(user_id))
+            .and_where(Expr::col(LessonProgressIden::LessonId).eq(lesson_id))
+            .and_where(Expr::col(LessonProgressIden::State).eq(LessonProgressState::Pause.to_string()))
+            .and_where(Expr::col(LessonProgressIden::DatePaused).is_not_null());
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        let count = dbm.dbx().execute(sqlx_query).await?;
+
+        if count == 0 {
+            return Err(DbError::LessonProgressMustBePaused { lesson_id, user_id });
+        }
+
+        Ok(())
+    }
+
+    /// Only a paused progress keeps its pause date, a progress that left the previous state is left alone.
+    pub async fn update_state(
+        _: &Ctx,
+        dbm: &DbManager,
+        change: LessonProgressStateChange,
+    ) -> DbResult<()> {
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .value(LessonProgressIden::State, change.state.to_string())
+            .and_where(Expr::col(LessonProgressIden::UserId).eq(change.user_id))
+            .and_where(Expr::col(LessonProgressIden::LessonId).eq(change.lesson_id))
+            .and_where(Expr::col(LessonProgressIden::State).eq(change.previous_state.to_string()));
+
+        if change.state != LessonProgressState::Pause {
+            query.value(LessonProgressIden::DatePaused, SimpleExpr::Keyword(Keyword::Null));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await?;
+
+        Ok(())
+    }
+
     pub async fn get_lessons_progresses(
         dbm: &DbManager,
         _: &Ctx, 
@@ -112,4 +251,33 @@ impl LessonProgressCommandRepository {
 
         Ok(lesson_progresses)
     }
+
+    pub async fn get_course_lessons_progresses(
+        dbm: &DbManager,
+        _: &Ctx, 
+        course_id: i64,
+    ) -> DbResult<Vec<LessonProgressData>> {
+        let mut query = Query::select();
+        query
+            .columns(LessonProgressData::field_column_refs())
+            .from(Self::table_ref())
+            .join(
+                sea_query::JoinType::InnerJoin,
+                base::table_ref::get_lesson_table_ref(),
+                Expr::col((LessonProgressIden::LessonProgress, LessonProgressIden::LessonId))
+                    .equals((LessonIden::Lesson, CommonIden::Id)),
+            )
+            .and_where(Expr::col(LessonIden::CourseId).eq(course_id));
+    
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, LessonProgressData, _>(&sql, values);
+    
+        let lesson_progresses: Vec<LessonProgressData> = 
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        Ok(lesson_progresses)
+    }
 }
\ No newline at end of file
@@ -4,4 +4,16 @@ pub mod users_courses;
 pub mod lesson;
 pub mod lesson_progress;
 pub mod exercise;
-pub mod exercise_completion;
\ No newline at end of file
+pub mod exercise_completion;
+pub mod peer_review;
+pub mod similarity_flag;
+pub mod difficulty_change;
+pub mod exam;
+pub mod placement_test;
+pub mod placement_attempt;
+pub mod unlock_policy;
+pub mod course_revision;
+pub mod course_transfer;
+pub mod section;
+pub mod content_block;
+pub mod attachment;
\ No newline at end of file
@@ -0,0 +1,211 @@
+use lib_core::{ctx::Ctx, interactors::error::ExerciseError, interfaces::exercise::ExerciseResult, models::peer_review::{PeerReview, PeerReviewForCreate, PeerReviewForSubmitCommand, PeerReviewState}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, OnConflict, PostgresQueryBuilder, Query, Value};
+use sea_query_binder::SqlxBinder;
+use sqlx::{postgres::PgRow, prelude::FromRow};
+
+use crate::{base::{idens::PeerReviewIden, prep_fields_for_create, prep_fields_for_update, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+#[derive(Fields)]
+struct PeerReviewData {
+    pub exercise_id: i64,
+    pub exercise_completion_id: i64,
+    pub reviewer_id: i64,
+}
+
+#[derive(Fields)]
+struct PeerReviewForSubmit {
+    pub score: f32,
+    pub rubric_scores: Value,
+    pub comment: Option<String>,
+    pub state: String,
+}
+
+#[derive(Fields, FromRow)]
+struct PeerReviewQuery {
+    pub peer_review_id: i64,
+    pub exercise_id: i64,
+    pub exercise_completion_id: i64,
+    pub reviewer_id: i64,
+    pub score: Option<f32>,
+    pub rubric_scores: Option<serde_json::Value>,
+    pub comment: Option<String>,
+    pub state: String,
+}
+
+impl TryFrom<PeerReviewQuery> for PeerReview {
+    type Error = ExerciseError;
+
+    fn try_from(value: PeerReviewQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.peer_review_id,
+            exercise_id: value.exercise_id,
+            exercise_completion_id: value.exercise_completion_id,
+            reviewer_id: value.reviewer_id,
+            score: value.score,
+            rubric_scores: value.rubric_scores,
+            comment: value.comment,
+            state: value.state.try_into()?,
+        })
+    }
+}
+
+pub trait PeerReviewBy: HasFields + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
+
+impl PeerReviewBy for PeerReviewQuery {}
+
+pub struct PeerReviewCommandRepository;
+
+impl DbRepository for PeerReviewCommandRepository {
+    const TABLE: &'static str = "peer_review";
+}
+
+impl PeerReviewCommandRepository {
+    pub async fn get(
+        _: &Ctx,
+        dbm: &DbManager,
+        id: i64,
+    ) -> ExerciseResult<PeerReview> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(PeerReviewQuery::field_column_refs())
+            .and_where(Expr::col(PeerReviewIden::PeerReviewId).eq(id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, PeerReviewQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?
+                .ok_or(DbError::EntityNotFound {
+                    entity: Self::TABLE.to_string(),
+                    id,
+                })?;
+
+        Ok(entity.try_into()?)
+    }
+
+    pub async fn get_exercise_peer_reviews(
+        _: &Ctx,
+        dbm: &DbManager,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<PeerReview>> {
+        Self::get_peer_reviews_by(dbm, PeerReviewIden::ExerciseId, exercise_id).await
+    }
+
+    pub async fn get_exercise_completion_peer_reviews(
+        _: &Ctx,
+        dbm: &DbManager,
+        ex_comp_id: i64,
+    ) -> ExerciseResult<Vec<PeerReview>> {
+        Self::get_peer_reviews_by(dbm, PeerReviewIden::ExerciseCompletionId, ex_comp_id).await
+    }
+
+    async fn get_peer_reviews_by(
+        dbm: &DbManager,
+        column: PeerReviewIden,
+        id: i64,
+    ) -> ExerciseResult<Vec<PeerReview>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(PeerReviewQuery::field_column_refs())
+            .and_where(Expr::col((PeerReviewIden::PeerReview, column)).eq(id))
+            .order_by((PeerReviewIden::PeerReview, PeerReviewIden::PeerReviewId), sea_query::Order::Asc);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, PeerReviewQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        let mut result = Vec::new();
+
+        for peer_review in entities {
+            result.push(peer_review.try_into()?);
+        }
+
+        Ok(result)
+    }
+
+    pub async fn create_peer_reviews(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        peer_reviews_for_c: Vec<PeerReviewForCreate>,
+    ) -> ExerciseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        for peer_review_for_c in peer_reviews_for_c {
+            let peer_review_data = PeerReviewData {
+                exercise_id: peer_review_for_c.exercise_id,
+                exercise_completion_id: peer_review_for_c.exercise_completion_id,
+                reviewer_id: peer_review_for_c.reviewer_id,
+            };
+
+            let mut fields = peer_review_data.not_none_fields();
+            prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+            let (columns, sea_values) = fields.for_sea_insert();
+            let mut query = Query::insert();
+            query
+                .into_table(Self::table_ref())
+                .columns(columns)
+                .values(sea_values)
+                .map_err(DbxError::SeaQuery)
+                .map_err(Into::<DbError>::into)?
+                // Two requests of the same reviewer may select the same submission.
+                .on_conflict(
+                    OnConflict::columns([PeerReviewIden::ExerciseCompletionId, PeerReviewIden::ReviewerId])
+                        .do_nothing()
+                        .to_owned()
+                );
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn submit_peer_review(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        peer_review_for_s: PeerReviewForSubmitCommand,
+    ) -> ExerciseResult<()> {
+        let peer_review_for_s_req = PeerReviewForSubmit {
+            score: peer_review_for_s.score,
+            rubric_scores: Value::Json(Some(Box::new(peer_review_for_s.rubric_scores))),
+            comment: peer_review_for_s.comment,
+            state: PeerReviewState::Submitted.to_string(),
+        };
+
+        let mut fields = peer_review_for_s_req.not_none_fields();
+        prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+        let fields = fields.for_sea_update();
+        let mut query = Query::update();
+        query
+            .table(Self::table_ref())
+            .values(fields)
+            .and_where(Expr::col(PeerReviewIden::PeerReviewId).eq(peer_review_for_s.id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        let count = dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        if count == 0 {
+            Err(DbError::EntityNotFound {
+                entity: Self::TABLE.to_string(),
+                id: peer_review_for_s.id,
+            }.into())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use lib_core::{ctx::Ctx, interfaces::course::CourseResult, models::placement_test::{PlacementAttempt, PlacementAttemptForCreate}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{idens::PlacementAttemptIden, prep_fields_for_create, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+use super::lesson_progress::LessonProgressCommandRepository;
+
+#[derive(Fields, FromRow)]
+struct PlacementAttemptData {
+    pub course_id: i64,
+    pub user_id: i64,
+    pub score: f32,
+}
+
+impl From<PlacementAttemptData> for PlacementAttempt {
+    fn from(value: PlacementAttemptData) -> Self {
+        Self {
+            course_id: value.course_id,
+            user_id: value.user_id,
+            score: value.score,
+        }
+    }
+}
+
+pub struct PlacementAttemptCommandRepository;
+
+impl DbRepository for PlacementAttemptCommandRepository {
+    const TABLE: &'static str = "placement_attempt";
+}
+
+impl PlacementAttemptCommandRepository {
+    pub async fn get_placement_attempt(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+        user_id: i64,
+    ) -> CourseResult<Option<PlacementAttempt>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(PlacementAttemptData::field_column_refs())
+            .and_where(Expr::col(PlacementAttemptIden::CourseId).eq(course_id))
+            .and_where(Expr::col(PlacementAttemptIden::UserId).eq(user_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, PlacementAttemptData, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entity.map(Into::into))
+    }
+
+    /// The unique key of the table turns a concurrent second attempt into an error.
+    pub async fn create_placement_attempt(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        attempt_for_c: PlacementAttemptForCreate,
+    ) -> CourseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let attempt_data = PlacementAttemptData {
+            course_id: attempt_for_c.course_id,
+            user_id: attempt_for_c.user_id,
+            score: attempt_for_c.score,
+        };
+
+        let mut fields = attempt_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?;
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        LessonProgressCommandRepository::create_skipped(ctx, &dbm, attempt_for_c.user_id, attempt_for_c.skipped_lesson_ids).await?;
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::course::CourseResult, models::placement_test::PlacementTest};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{idens::PlacementTestIden, prep_fields_for_create, prep_fields_for_update, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+#[derive(Fields)]
+struct PlacementTestData {
+    pub course_id: i64,
+    pub exercise_ids: sea_query::Value,
+    pub levels: sea_query::Value,
+}
+
+#[derive(Fields)]
+struct PlacementTestForUpdate {
+    pub exercise_ids: sea_query::Value,
+    pub levels: sea_query::Value,
+}
+
+#[derive(Fields, FromRow)]
+struct PlacementTestQuery {
+    pub course_id: i64,
+    pub exercise_ids: serde_json::Value,
+    pub levels: serde_json::Value,
+}
+
+impl TryFrom<PlacementTestQuery> for PlacementTest {
+    type Error = CoreError;
+
+    fn try_from(value: PlacementTestQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            course_id: value.course_id,
+            exercise_ids: serde_json::from_value(value.exercise_ids)?,
+            levels: serde_json::from_value(value.levels)?,
+        })
+    }
+}
+
+pub struct PlacementTestCommandRepository;
+
+impl DbRepository for PlacementTestCommandRepository {
+    const TABLE: &'static str = "placement_test";
+}
+
+impl PlacementTestCommandRepository {
+    pub async fn get_placement_test(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Option<PlacementTest>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(PlacementTestQuery::field_column_refs())
+            .and_where(Expr::col(PlacementTestIden::CourseId).eq(course_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, PlacementTestQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        match entity {
+            Some(entity) => Ok(Some(entity.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_placement_test(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        placement_test: PlacementTest,
+    ) -> CourseResult<()> {
+        let exercise_ids = serde_json::to_value(&placement_test.exercise_ids)?;
+        let levels = serde_json::to_value(&placement_test.levels)?;
+
+        if Self::get_placement_test(ctx, dbm, placement_test.course_id).await?.is_some() {
+            let placement_test_for_u = PlacementTestForUpdate {
+                exercise_ids: sea_query::Value::Json(Some(Box::new(exercise_ids))),
+                levels: sea_query::Value::Json(Some(Box::new(levels))),
+            };
+
+            let mut fields = placement_test_for_u.not_none_fields();
+            prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+            let fields = fields.for_sea_update();
+            let mut query = Query::update();
+            query
+                .table(Self::table_ref())
+                .values(fields)
+                .and_where(Expr::col(PlacementTestIden::CourseId).eq(placement_test.course_id));
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+            return Ok(());
+        }
+
+        let placement_test_data = PlacementTestData {
+            course_id: placement_test.course_id,
+            exercise_ids: sea_query::Value::Json(Some(Box::new(exercise_ids))),
+            levels: sea_query::Value::Json(Some(Box::new(levels))),
+        };
+
+        let mut fields = placement_test_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?;
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use lib_core::{ctx::Ctx, interfaces::lesson::LessonResult, models::section::{Section, SectionForChangeOrder, SectionForCreateCommand, SectionForUpdate}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{self, idens::SectionIden, DbRepository}, store::{db_manager::DbManager, error::DbError}};
+
+#[derive(Clone, Fields, FromRow, Debug)]
+struct SectionData {
+    pub id: i64,
+    pub course_id: i64,
+    pub title: String,
+    pub section_order: i32,
+}
+
+#[derive(Fields)]
+struct SectionForInsert {
+    pub course_id: i64,
+    pub title: String,
+    pub description: String,
+    pub section_order: i32,
+}
+
+#[derive(Fields)]
+struct SectionForUpdateOrder {
+    pub section_order: i32,
+}
+
+#[derive(Fields)]
+struct SectionForUpdateData {
+    pub title: String,
+    pub description: String,
+}
+
+pub struct SectionCommandRepository;
+
+impl DbRepository for SectionCommandRepository {
+    const TABLE: &'static str = "section";
+}
+
+impl SectionCommandRepository {
+    pub async fn get_section(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        section_id: i64,
+    ) -> LessonResult<Section> {
+        let section = base::get::<Self, SectionData>(ctx, dbm, section_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(Section {
+            id: section.id,
+            course_id: section.course_id,
+            title: section.title,
+            section_order: section.section_order,
+        })
+    }
+
+    pub async fn get_course_sections_ordered(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> LessonResult<Vec<SectionForChangeOrder>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(SectionData::field_column_refs())
+            .and_where(Expr::col(SectionIden::CourseId).eq(course_id))
+            .order_by(SectionIden::SectionOrder, sea_query::Order::Asc);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, SectionData, _>(&sql, values);
+        let sections =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        let result = sections.iter().map(|section| SectionForChangeOrder {
+            id: section.id,
+            order: section.section_order,
+        }).collect();
+
+        Ok(result)
+    }
+
+    pub async fn create_section(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        section_for_c: SectionForCreateCommand,
+    ) -> LessonResult<i64> {
+        let section_fi = SectionForInsert {
+            course_id: section_for_c.course_id,
+            title: section_for_c.title,
+            description: section_for_c.description,
+            section_order: section_for_c.order,
+        };
+
+        let section_id = base::create::<Self, _>(ctx, dbm, section_fi)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(section_id)
+    }
+
+    pub async fn update_section(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        section_for_u: SectionForUpdate,
+    ) -> LessonResult<()> {
+        let section_for_u_data = SectionForUpdateData {
+            title: section_for_u.title,
+            description: section_for_u.description,
+        };
+
+        base::update::<Self, _>(ctx, dbm, section_for_u.id, section_for_u_data)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn delete_section(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        section_id: i64,
+    ) -> LessonResult<()> {
+        base::delete::<Self>(ctx, dbm, section_id)
+            .await
+            .map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+
+    pub async fn update_section_orders(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        sections_for_u_order: Vec<SectionForChangeOrder>,
+    ) -> LessonResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        for section in &sections_for_u_order {
+            let section_for_u_order = SectionForUpdateOrder {
+                section_order: section.order,
+            };
+
+            base::update::<Self, SectionForUpdateOrder>(ctx, &dbm, section.id, section_for_u_order)
+                .await
+                .map_err(Into::<DbError>::into)?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
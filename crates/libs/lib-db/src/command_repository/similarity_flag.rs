@@ -0,0 +1,136 @@
+use lib_core::{ctx::Ctx, interfaces::exercise::ExerciseResult, models::similarity_flag::{SimilarityFlag, SimilarityFlagForCreate}};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, Func, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{idens::{ExerciseCompletionIden, SimilarityFlagIden}, prep_fields_for_create, table_ref::get_exercise_completion_table_ref, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+#[derive(Fields)]
+struct SimilarityFlagData {
+    pub exercise_id: i64,
+    pub first_exercise_completion_id: i64,
+    pub second_exercise_completion_id: i64,
+    pub score: f32,
+}
+
+#[derive(Fields, FromRow)]
+struct SimilarityFlagQuery {
+    pub similarity_flag_id: i64,
+    pub exercise_id: i64,
+    pub first_exercise_completion_id: i64,
+    pub second_exercise_completion_id: i64,
+    pub score: f32,
+}
+
+impl From<SimilarityFlagQuery> for SimilarityFlag {
+    fn from(value: SimilarityFlagQuery) -> Self {
+        Self {
+            id: value.similarity_flag_id,
+            exercise_id: value.exercise_id,
+            first_exercise_completion_id: value.first_exercise_completion_id,
+            second_exercise_completion_id: value.second_exercise_completion_id,
+            score: value.score,
+        }
+    }
+}
+
+pub struct SimilarityFlagCommandRepository;
+
+impl DbRepository for SimilarityFlagCommandRepository {
+    const TABLE: &'static str = "similarity_flag";
+}
+
+impl SimilarityFlagCommandRepository {
+    pub async fn get_exercises_for_similarity_check(
+        _: &Ctx,
+        dbm: &DbManager,
+    ) -> ExerciseResult<Vec<i64>> {
+        let mut query = Query::select();
+        query
+            .from(get_exercise_completion_table_ref())
+            .column(ExerciseCompletionIden::ExerciseId)
+            .and_where(Expr::col(ExerciseCompletionIden::State).ne("InProgress"))
+            .group_by_col(ExerciseCompletionIden::ExerciseId)
+            .and_having(Expr::expr(Func::count_distinct(Expr::col(ExerciseCompletionIden::UserId))).gte(2));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, (i64,), _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(|(exercise_id,)| exercise_id).collect())
+    }
+
+    pub async fn get_exercise_similarity_flags(
+        _: &Ctx,
+        dbm: &DbManager,
+        exercise_id: i64,
+    ) -> ExerciseResult<Vec<SimilarityFlag>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(SimilarityFlagQuery::field_column_refs())
+            .and_where(Expr::col(SimilarityFlagIden::ExerciseId).eq(exercise_id))
+            .order_by(SimilarityFlagIden::Score, sea_query::Order::Desc);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, SimilarityFlagQuery, _>(&sql, values);
+        let entities =
+            dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        Ok(entities.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn replace_similarity_flags(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        exercise_id: i64,
+        flags_for_c: Vec<SimilarityFlagForCreate>,
+    ) -> ExerciseResult<()> {
+        let dbm = dbm.new_with_txn()?;
+        dbm.dbx().begin_txn().await.map_err(Into::<DbError>::into)?;
+
+        let mut query = Query::delete();
+        query
+            .from_table(Self::table_ref())
+            .and_where(Expr::col(SimilarityFlagIden::ExerciseId).eq(exercise_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        for flag_for_c in flags_for_c {
+            let flag_data = SimilarityFlagData {
+                exercise_id: flag_for_c.exercise_id,
+                first_exercise_completion_id: flag_for_c.first_exercise_completion_id,
+                second_exercise_completion_id: flag_for_c.second_exercise_completion_id,
+                score: flag_for_c.score,
+            };
+
+            let mut fields = flag_data.not_none_fields();
+            prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+            let (columns, sea_values) = fields.for_sea_insert();
+            let mut query = Query::insert();
+            query
+                .into_table(Self::table_ref())
+                .columns(columns)
+                .values(sea_values)
+                .map_err(DbxError::SeaQuery)
+                .map_err(Into::<DbError>::into)?;
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+        }
+
+        dbm.dbx().commit_txn().await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
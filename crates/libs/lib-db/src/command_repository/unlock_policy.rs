@@ -0,0 +1,127 @@
+use lib_core::{ctx::Ctx, interactors::error::CoreError, interfaces::course::CourseResult, models::unlock_policy::UnlockPolicy};
+use modql::field::{Fields, HasFields};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::prelude::FromRow;
+
+use crate::{base::{idens::UnlockPolicyIden, prep_fields_for_create, prep_fields_for_update, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::DbError}};
+
+#[derive(Fields)]
+struct UnlockPolicyData {
+    pub course_id: i64,
+    pub policy: String,
+    pub drip_releases: sea_query::Value,
+}
+
+#[derive(Fields)]
+struct UnlockPolicyForUpdate {
+    pub policy: String,
+    pub drip_releases: sea_query::Value,
+}
+
+#[derive(Fields, FromRow)]
+struct UnlockPolicyQuery {
+    pub course_id: i64,
+    pub policy: String,
+    pub drip_releases: serde_json::Value,
+}
+
+impl TryFrom<UnlockPolicyQuery> for UnlockPolicy {
+    type Error = CoreError;
+
+    fn try_from(value: UnlockPolicyQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            course_id: value.course_id,
+            kind: value.policy.try_into()?,
+            drip_releases: serde_json::from_value(value.drip_releases)?,
+        })
+    }
+}
+
+pub struct UnlockPolicyCommandRepository;
+
+impl DbRepository for UnlockPolicyCommandRepository {
+    const TABLE: &'static str = "unlock_policy";
+}
+
+impl UnlockPolicyCommandRepository {
+    pub async fn get_unlock_policy(
+        _: &Ctx,
+        dbm: &DbManager,
+        course_id: i64,
+    ) -> CourseResult<Option<UnlockPolicy>> {
+        let mut query = Query::select();
+        query
+            .from(Self::table_ref())
+            .columns(UnlockPolicyQuery::field_column_refs())
+            .and_where(Expr::col(UnlockPolicyIden::CourseId).eq(course_id));
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, UnlockPolicyQuery, _>(&sql, values);
+        let entity =
+            dbm.dbx()
+                .fetch_optional(sqlx_query)
+                .await.map_err(Into::<DbError>::into)?;
+
+        match entity {
+            Some(entity) => Ok(Some(entity.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_unlock_policy(
+        ctx: &Ctx,
+        dbm: &DbManager,
+        unlock_policy: UnlockPolicy,
+    ) -> CourseResult<()> {
+        let policy = unlock_policy.kind.to_string();
+        let drip_releases = serde_json::to_value(&unlock_policy.drip_releases)?;
+
+        if Self::get_unlock_policy(ctx, dbm, unlock_policy.course_id).await?.is_some() {
+            let unlock_policy_for_u = UnlockPolicyForUpdate {
+                policy,
+                drip_releases: sea_query::Value::Json(Some(Box::new(drip_releases))),
+            };
+
+            let mut fields = unlock_policy_for_u.not_none_fields();
+            prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+            let fields = fields.for_sea_update();
+            let mut query = Query::update();
+            query
+                .table(Self::table_ref())
+                .values(fields)
+                .and_where(Expr::col(UnlockPolicyIden::CourseId).eq(unlock_policy.course_id));
+
+            let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+            let sqlx_query = sqlx::query_with(&sql, values);
+            dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+            return Ok(());
+        }
+
+        let unlock_policy_data = UnlockPolicyData {
+            course_id: unlock_policy.course_id,
+            policy,
+            drip_releases: sea_query::Value::Json(Some(Box::new(drip_releases))),
+        };
+
+        let mut fields = unlock_policy_data.not_none_fields();
+        prep_fields_for_create::<Self>(&mut fields, ctx.user_id());
+
+        let (columns, sea_values) = fields.for_sea_insert();
+        let mut query = Query::insert();
+        query
+            .into_table(Self::table_ref())
+            .columns(columns)
+            .values(sea_values)
+            .map_err(DbxError::SeaQuery)
+            .map_err(Into::<DbError>::into)?;
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_with(&sql, values);
+        dbm.dbx().execute(sqlx_query).await.map_err(Into::<DbError>::into)?;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
-use lib_core::{ctx::Ctx, interactors::error::CoreError, models::course::UserCourse};
+use lib_core::{ctx::Ctx, interactors::error::CoreError, models::course::{UserCourse, UserCourseRole}};
 use modql::field::{Fields, HasFields};
 use sea_query::{Expr, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use sqlx::prelude::FromRow;
 use time::OffsetDateTime;
-use crate::{base::{idens::UserCourseIden, prep_fields_for_create, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::{DbError, DbResult}}};
+use crate::{base::{idens::{CommonIden, ExerciseCompletionIden, ExerciseIden, LessonIden, LessonProgressIden, UserCourseIden}, prep_fields_for_create, prep_fields_for_update, table_ref::{get_exercise_completion_table_ref, get_exercise_table_ref, get_lesson_progress_table_ref, get_lesson_table_ref}, DbRepository}, store::{db_manager::DbManager, dbx::error::DbxError, error::{DbError, DbResult}}};
 
 #[derive(Fields, FromRow)]
 pub struct UsersCoursesRequest {
@@ -31,6 +31,16 @@ impl TryFrom<UsersCoursesRequest> for UserCourse {
 	}
 }
 
+#[derive(Fields)]
+struct UsersCoursesForUpdateRole {
+    pub user_role: String,
+}
+
+#[derive(Fields)]
+struct UsersCoursesForUpdateUser {
+    pub user_id: i64,
+}
+
 pub struct UsersCoursesForDelete {
     pub user_id: i64,
     pub course_id: i64,
@@ -146,4 +156,163 @@ impl UsersCoursesCommandRepository {
 			Ok(())
 		}
 	}
-}
\ No newline at end of file
+
+	pub async fn get_course_staff(
+		dbm: &DbManager,
+		course_id: i64,
+	) -> DbResult<Vec<UsersCoursesRequest>> {
+		let mut query = Query::select();
+		query
+			.from(Self::table_ref())
+			.columns(UsersCoursesRequest::field_column_refs())
+			.and_where(Expr::col(UserCourseIden::CourseId).eq(course_id))
+			.and_where(Expr::col(UserCourseIden::UserRole).ne(UserCourseRole::Student.to_string()))
+			.order_by(UserCourseIden::UserId, sea_query::Order::Asc);
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, UsersCoursesRequest, _>(&sql, values);
+		let entities = dbm.dbx().fetch_all(sqlx_query).await?;
+
+		Ok(entities)
+	}
+
+	pub async fn update_role(
+		ctx: &Ctx,
+		dbm: &DbManager,
+		user_id: i64,
+		course_id: i64,
+		user_role: UserCourseRole,
+	) -> DbResult<()> {
+		let users_courses_u = UsersCoursesForUpdateRole {
+			user_role: user_role.to_string(),
+		};
+
+		let mut fields = users_courses_u.not_none_fields();
+		prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+		let fields = fields.for_sea_update();
+		let mut query = Query::update();
+		query
+			.table(Self::table_ref())
+			.values(fields)
+			.and_where(Expr::col(UserCourseIden::UserId).eq(user_id))
+			.and_where(Expr::col(UserCourseIden::CourseId).eq(course_id));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_with(&sql, values);
+		let count = dbm.dbx().execute(sqlx_query).await?;
+
+		if count == 0 {
+			return Err(DbError::UserCourseNotFound {
+				entity: Self::TABLE.to_string(),
+				user_id,
+				course_id,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Hands the `Creator` row over to another user, whatever row that user already had
+	/// in the course, a student or a staff one, is removed first.
+	pub async fn move_creator(
+		ctx: &Ctx,
+		dbm: &DbManager,
+		course_id: i64,
+		from_user_id: i64,
+		to_user_id: i64,
+	) -> DbResult<()> {
+		if Self::get_optional(dbm, to_user_id, course_id).await?.is_some() {
+			Self::delete(dbm, UsersCoursesForDelete { user_id: to_user_id, course_id }).await?;
+			Self::delete_progress(dbm, to_user_id, course_id).await?;
+		}
+
+		let users_courses_u = UsersCoursesForUpdateUser {
+			user_id: to_user_id,
+		};
+
+		let mut fields = users_courses_u.not_none_fields();
+		prep_fields_for_update::<Self>(&mut fields, ctx.user_id());
+
+		let fields = fields.for_sea_update();
+		let mut query = Query::update();
+		query
+			.table(Self::table_ref())
+			.values(fields)
+			.and_where(Expr::col(UserCourseIden::UserId).eq(from_user_id))
+			.and_where(Expr::col(UserCourseIden::CourseId).eq(course_id))
+			.and_where(Expr::col(UserCourseIden::UserRole).eq(UserCourseRole::Creator.to_string()));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_with(&sql, values);
+		let count = dbm.dbx().execute(sqlx_query).await?;
+
+		if count == 0 {
+			return Err(DbError::UserCourseNotFound {
+				entity: Self::TABLE.to_string(),
+				user_id: from_user_id,
+				course_id,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Removes the lesson progresses and the exercise attempts of the user in the course.
+	async fn delete_progress(
+		dbm: &DbManager,
+		user_id: i64,
+		course_id: i64,
+	) -> DbResult<()> {
+		let mut lesson_ids = Query::select();
+		lesson_ids
+			.from(get_lesson_table_ref())
+			.column(CommonIden::Id)
+			.and_where(Expr::col(LessonIden::CourseId).eq(course_id));
+
+		let mut exercise_ids = Query::select();
+		exercise_ids
+			.from(get_exercise_table_ref())
+			.column(CommonIden::Id)
+			.and_where(Expr::col(ExerciseIden::LessonId).in_subquery(lesson_ids.clone()));
+
+		let queries = [
+			Query::delete()
+				.from_table(get_exercise_completion_table_ref())
+				.and_where(Expr::col(ExerciseCompletionIden::UserId).eq(user_id))
+				.and_where(Expr::col(ExerciseCompletionIden::ExerciseId).in_subquery(exercise_ids))
+				.to_owned(),
+			Query::delete()
+				.from_table(get_lesson_progress_table_ref())
+				.and_where(Expr::col(LessonProgressIden::UserId).eq(user_id))
+				.and_where(Expr::col(LessonProgressIden::LessonId).in_subquery(lesson_ids))
+				.to_owned(),
+		];
+
+		for query in queries {
+			let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+			let sqlx_query = sqlx::query_with(&sql, values);
+			dbm.dbx().execute(sqlx_query).await?;
+		}
+
+		Ok(())
+	}
+
+	pub async fn count_students(
+		dbm: &DbManager,
+		course_id: i64,
+	) -> DbResult<i64> {
+		let mut query = Query::select();
+		query
+			.from(Self::table_ref())
+			.expr(Expr::col(UserCourseIden::UserId).count())
+			.and_where(Expr::col(UserCourseIden::CourseId).eq(course_id))
+			.and_where(Expr::col(UserCourseIden::UserRole).eq(UserCourseRole::Student.to_string()));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_scalar_with::<_, i64, _>(&sql, values);
+		let count = dbm.dbx().fetch_one_scalar(sqlx_query).await?;
+
+		Ok(count)
+	}
+}
@@ -8,8 +8,8 @@ use serde_with::serde_as;
 use sqlx::FromRow;
 use time::OffsetDateTime;
 use crate::base::compute_list_options;
-use crate::base::idens::{CommonIden, CourseIden, UserCourseIden};
-use crate::base::table_ref::get_users_courses_table_ref;
+use crate::base::idens::{CommonIden, CourseIden, PlacementTestIden, UnlockPolicyIden, UserCourseIden};
+use crate::base::table_ref::{get_placement_test_table_ref, get_unlock_policy_table_ref, get_users_courses_table_ref};
 use crate::query_repository::modql_utils::time_to_sea_value;
 use crate::store::db_manager::DbManager;
 use crate::store::dbx::error::DbxError;
@@ -31,7 +31,12 @@ pub struct CourseQuery {
 	#[serde_as(as = "Option<Rfc3339>")]
 	pub published_date: Option<OffsetDateTime>,
 	pub img_url: Option<String>,
+	pub img_variants: Option<serde_json::Value>,
 	pub state: String,
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub scheduled_publish_date: Option<OffsetDateTime>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub scheduled_archive_date: Option<OffsetDateTime>,
 }
 
 #[derive(FilterNodes, Deserialize, Default, Debug)]
@@ -55,6 +60,20 @@ pub struct CourseFilter {
 	pub mtime: Option<OpValsValue>,
 }
 
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct PlacementTestData {
+	pub course_id: i64,
+	pub exercise_ids: serde_json::Value,
+	pub levels: serde_json::Value,
+}
+
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct UnlockPolicyData {
+	pub course_id: i64,
+	pub policy: String,
+	pub drip_releases: serde_json::Value,
+}
+
 #[derive(Clone)]
 pub struct CourseQueryRepository {
 	dbm: DbManager,
@@ -80,7 +99,7 @@ impl CourseQueryRepository {
 		filter: Option<Vec<CourseFilter>>,
 		list_options: Option<ListOptions>,
 	) -> DbResult<Vec<CourseQuery>> {
-		let user_courses = self.get_courses_by_role(user_id, UserCourseRoleQuery::Creator).await?;
+		let user_courses = self.get_staff_courses(user_id).await?;
 
 		let mut query = Query::select();
 		query.from(Self::table_ref())
@@ -116,7 +135,7 @@ impl CourseQueryRepository {
 		user_id: i64,
 		filter: Option<Vec<CourseFilter>>,
 	) -> DbResult<i64> {
-		let courses_ids = self.get_courses_by_role(user_id, UserCourseRoleQuery::Creator).await?;
+		let courses_ids = self.get_staff_courses(user_id).await?;
 
     	let mut subquery = Query::select();
     	subquery.from(Self::table_ref())
@@ -156,6 +175,42 @@ impl CourseQueryRepository {
 		Ok(result)
 	}
 
+	pub async fn get_placement_test(&self, _: &Ctx, course_id: i64) -> DbResult<Option<PlacementTestData>> {
+		let mut query = Query::select();
+		query
+			.from(get_placement_test_table_ref())
+			.columns(PlacementTestData::field_column_refs())
+			.and_where(Expr::col(PlacementTestIden::CourseId).eq(course_id));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, PlacementTestData, _>(&sql, values);
+		let placement_test =
+			self.dbm.dbx()
+				.fetch_optional(sqlx_query)
+				.await
+				.map_err(Into::<DbError>::into)?;
+
+		Ok(placement_test)
+	}
+
+	pub async fn get_unlock_policy(&self, _: &Ctx, course_id: i64) -> DbResult<Option<UnlockPolicyData>> {
+		let mut query = Query::select();
+		query
+			.from(get_unlock_policy_table_ref())
+			.columns(UnlockPolicyData::field_column_refs())
+			.and_where(Expr::col(UnlockPolicyIden::CourseId).eq(course_id));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, UnlockPolicyData, _>(&sql, values);
+		let unlock_policy =
+			self.dbm.dbx()
+				.fetch_optional(sqlx_query)
+				.await
+				.map_err(Into::<DbError>::into)?;
+
+		Ok(unlock_policy)
+	}
+
 	pub async fn get_user_courses_registered(
 		&self,
 		_ctx: &Ctx,
@@ -207,6 +262,17 @@ impl CourseQueryRepository {
 			.collect())
 	}
 
+	/// Courses the user works on as the creator, a co-author or an assistant.
+	async fn get_staff_courses(&self, user_id: i64) -> DbResult<Vec<i64>> {
+		let staff_roles = vec![UserCourseRoleQuery::Creator, UserCourseRoleQuery::CoAuthor, UserCourseRoleQuery::Assistant];
+
+		Ok(UsersCoursesQueryRepository::get_user_courses_by_roles(&self.dbm, user_id, staff_roles)
+			.await?
+			.iter()
+			.map(|user_course| user_course.course_id)
+			.collect())
+	}
+
 	pub async fn get_user_courses_registered_count(
 		&self,
 		_ctx: &Ctx,
@@ -241,7 +307,7 @@ impl CourseQueryRepository {
 		filter: Option<Vec<CourseFilter>>,
 		list_options: Option<ListOptions>,
 	) -> DbResult<Vec<CourseQuery>> {
-		let courses_ids = self.get_courses_by_role(user_id, UserCourseRoleQuery::Creator).await?;
+		let courses_ids = self.get_staff_courses(user_id).await?;
 
 		let mut query = Query::select();
 		query
@@ -273,7 +339,7 @@ impl CourseQueryRepository {
 		user_id: i64,
 		filter: Option<Vec<CourseFilter>>,
 	) -> DbResult<i64> {
-		let courses_ids = self.get_courses_by_role(user_id, UserCourseRoleQuery::Creator).await?;
+		let courses_ids = self.get_staff_courses(user_id).await?;
 
 		let mut query = Query::select();
 		query
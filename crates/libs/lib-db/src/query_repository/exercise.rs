@@ -21,6 +21,10 @@ pub struct ExerciseQuery {
     pub answer_body: Value,
     pub difficult: String,
     pub time_to_complete: Option<i32>, 
+    pub manual_review: bool,
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
 #[derive(Clone, Fields, FromRow, Debug)]
@@ -130,7 +134,8 @@ impl ExerciseQueryRepository {
         	)
         	.and_where(Expr::col((ExerciseIden::Exercise, ExerciseIden::LessonId)).eq(lesson_id))
         	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::UserId)).eq(user_id))
-        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"));
+        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"))
+        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::IsPractice)).eq(false));
 
     	let mut query = Query::select();
     	query.expr(Expr::col(Alias::new("subquery")).count())
@@ -192,6 +197,7 @@ impl ExerciseQueryRepository {
         	)
             .and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::ExerciseId)).is_in(ex_ids))
         	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::State)).eq("Succeeded"))
+        	.and_where(Expr::col((ExerciseCompletionIden::ExerciseCompletion, ExerciseCompletionIden::IsPractice)).eq(false))
             .distinct();
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
@@ -19,6 +19,9 @@ pub struct ExerciseCompletionQuery {
     pub date_last_changes: Option<OffsetDateTime>,
     pub state: String,   
     pub body: Option<serde_json::Value>,
+    pub review_comment: Option<String>,
+    pub is_practice: bool,
+    pub paused_seconds: i64,
 }
 
 #[derive(Clone)]
@@ -44,13 +47,15 @@ impl ExerciseCompletionQueryRepository {
         _: &Ctx, 
         exercise_id: i64,
         user_id: i64,
+        is_practice: bool,
     ) -> DbResult<Vec<ExerciseCompletionQuery>> {
         let mut query = Query::select();
         query
             .from(Self::table_ref())
             .columns(ExerciseCompletionQuery::field_column_refs())
             .and_where(Expr::col(ExerciseCompletionIden::ExerciseId).eq(exercise_id))
-            .and_where(Expr::col(ExerciseCompletionIden::UserId).eq(user_id));
+            .and_where(Expr::col(ExerciseCompletionIden::UserId).eq(user_id))
+            .and_where(Expr::col(ExerciseCompletionIden::IsPractice).eq(is_practice));
     
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
         let sqlx_query = sqlx::query_as_with::<_, ExerciseCompletionQuery, _>(&sql, values);
@@ -79,6 +84,7 @@ impl ExerciseCompletionQueryRepository {
                 .equals((ExerciseIden::Exercise, CommonIden::Id))
             )
             .and_where(Expr::col(ExerciseCompletionIden::UserId).eq(user_id))
+            .and_where(Expr::col(ExerciseCompletionIden::IsPractice).eq(false))
             .and_where(Expr::col(ExerciseIden::LessonId).eq(lesson_id));
     
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
@@ -3,19 +3,57 @@ use modql::field::{Fields, HasFields};
 use sea_query::{Expr, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use sqlx::prelude::FromRow;
+use time::OffsetDateTime;
 
-use crate::{base::{self, idens::LessonIden, DbRepository}, store::{db_manager::DbManager, error::{DbError, DbResult}}};
+use crate::{base::{self, idens::{AttachmentIden, CommonIden, ContentBlockIden, ExamIden, LessonIden, SectionIden}, table_ref::{get_attachment_table_ref, get_content_block_table_ref, get_exam_table_ref, get_section_table_ref}, DbRepository}, store::{db_manager::DbManager, error::{DbError, DbResult}}};
 
 
 #[derive(Clone, Fields, FromRow, Debug)]
 pub struct LessonData {
 	pub id: i64,
 	pub course_id: i64,
+	pub section_id: Option<i64>,
     pub title: String,
     pub lesson_order: i32,
     pub description: String,
 }
 
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct SectionData {
+	pub id: i64,
+	pub course_id: i64,
+	pub title: String,
+	pub description: String,
+	pub section_order: i32,
+}
+
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct ContentBlockData {
+	pub id: i64,
+	pub lesson_id: i64,
+	pub block_type: String,
+	pub body: serde_json::Value,
+	pub rendered_html: String,
+	pub block_order: i32,
+}
+
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct AttachmentData {
+	pub id: i64,
+	pub lesson_id: i64,
+	pub file_name: String,
+	pub content_type: String,
+	pub size: i64,
+}
+
+#[derive(Clone, Fields, FromRow, Debug)]
+pub struct ExamData {
+	pub lesson_id: i64,
+	pub opens_at: OffsetDateTime,
+	pub closes_at: OffsetDateTime,
+	pub results_released: bool,
+}
+
 #[derive(Clone)]
 pub struct LessonQueryRepository {
     dbm: DbManager,
@@ -34,9 +72,10 @@ impl DbRepository for LessonQueryRepository {
 }
 
 impl LessonQueryRepository {
+    /// Lessons outside sections first, then section by section.
     pub async fn get_course_lessons_ordered(
         &self, 
-        _: &Ctx, 
+        ctx: &Ctx, 
         course_id: i64
     ) -> DbResult<Vec<LessonData>> {
         let mut query = Query::select();
@@ -48,14 +87,43 @@ impl LessonQueryRepository {
     
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
         let sqlx_query = sqlx::query_as_with::<_, LessonData, _>(&sql, values);
-        let lessons =
+        let mut lessons =
             self.dbm.dbx()
                 .fetch_all(sqlx_query)
                 .await
                 .map_err(Into::<DbError>::into)?;
 
+        let sections = self.get_course_sections_ordered(ctx, course_id).await?;
+
+        lessons.sort_by_key(|lesson| {
+            lesson.section_id.and_then(|section_id| sections.iter().position(|section| section.id == section_id))
+        });
+
         Ok(lessons)
     }
+
+    pub async fn get_course_sections_ordered(
+        &self, 
+        _: &Ctx, 
+        course_id: i64
+    ) -> DbResult<Vec<SectionData>> {
+        let mut query = Query::select();
+        query
+            .from(get_section_table_ref())
+            .columns(SectionData::field_column_refs())
+            .and_where(Expr::col(SectionIden::CourseId).eq(course_id))
+            .order_by(SectionIden::SectionOrder, sea_query::Order::Asc);
+    
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let sqlx_query = sqlx::query_as_with::<_, SectionData, _>(&sql, values);
+        let sections =
+            self.dbm.dbx()
+                .fetch_all(sqlx_query)
+                .await
+                .map_err(Into::<DbError>::into)?;
+
+        Ok(sections)
+    }
     
     pub async fn get(&self, ctx: &Ctx, id: i64) -> DbResult<LessonData>
 	{
@@ -65,4 +133,60 @@ impl LessonQueryRepository {
 
 		Ok(result)
 	}
+
+	pub async fn get_exam(&self, _: &Ctx, lesson_id: i64) -> DbResult<Option<ExamData>> {
+		let mut query = Query::select();
+		query
+			.from(get_exam_table_ref())
+			.columns(ExamData::field_column_refs())
+			.and_where(Expr::col(ExamIden::LessonId).eq(lesson_id));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, ExamData, _>(&sql, values);
+		let exam =
+			self.dbm.dbx()
+				.fetch_optional(sqlx_query)
+				.await
+				.map_err(Into::<DbError>::into)?;
+
+		Ok(exam)
+	}
+
+	pub async fn get_lesson_content_blocks(&self, _: &Ctx, lesson_id: i64) -> DbResult<Vec<ContentBlockData>> {
+		let mut query = Query::select();
+		query
+			.from(get_content_block_table_ref())
+			.columns(ContentBlockData::field_column_refs())
+			.and_where(Expr::col(ContentBlockIden::LessonId).eq(lesson_id))
+			.order_by(ContentBlockIden::BlockOrder, sea_query::Order::Asc);
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, ContentBlockData, _>(&sql, values);
+		let content_blocks =
+			self.dbm.dbx()
+				.fetch_all(sqlx_query)
+				.await
+				.map_err(Into::<DbError>::into)?;
+
+		Ok(content_blocks)
+	}
+
+	pub async fn get_lesson_attachments(&self, _: &Ctx, lesson_id: i64) -> DbResult<Vec<AttachmentData>> {
+		let mut query = Query::select();
+		query
+			.from(get_attachment_table_ref())
+			.columns(AttachmentData::field_column_refs())
+			.and_where(Expr::col(AttachmentIden::LessonId).eq(lesson_id))
+			.order_by(CommonIden::Id, sea_query::Order::Asc);
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, AttachmentData, _>(&sql, values);
+		let attachments =
+			self.dbm.dbx()
+				.fetch_all(sqlx_query)
+				.await
+				.map_err(Into::<DbError>::into)?;
+
+		Ok(attachments)
+	}
 }
\ No newline at end of file
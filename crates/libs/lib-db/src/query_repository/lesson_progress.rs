@@ -16,6 +16,8 @@ pub struct LessonProgressData {
     pub date_complete: Option<OffsetDateTime>,  
 
     pub state: String,
+    pub skipped_by_placement: bool,
+    pub date_paused: Option<OffsetDateTime>,
 }
 
 impl TryFrom<&LessonProgressData> for LessonProgress {
@@ -28,6 +30,8 @@ impl TryFrom<&LessonProgressData> for LessonProgress {
             date_started: value.date_started.unix_timestamp(),
             date_complete: value.date_complete.and_then(|date| Some(date.unix_timestamp())),
             state: value.state.clone().try_into()?,
+            skipped_by_placement: value.skipped_by_placement,
+            date_paused: value.date_paused.map(|date| date.unix_timestamp()),
         })
     }
 }
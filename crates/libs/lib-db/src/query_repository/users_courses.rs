@@ -11,12 +11,16 @@ use crate::{base::{idens::UserCourseIden, DbRepository}, store::{db_manager::DbM
 pub enum UserCourseRoleQuery {
     Student,
     Creator,
+    CoAuthor,
+    Assistant,
 }
 
 impl From<String> for UserCourseRoleQuery {
 	fn from(value: String) -> Self {
 		match value.as_str() {
 			"Creator" => Self::Creator,
+			"CoAuthor" => Self::CoAuthor,
+			"Assistant" => Self::Assistant,
 			_ => Self::Student,
 		}
 	}
@@ -27,6 +31,8 @@ impl From<UserCourseRoleQuery> for sea_query::Value {
 		match value {
 			UserCourseRoleQuery::Student => "Student".into(),
 			UserCourseRoleQuery::Creator => "Creator".into(),
+			UserCourseRoleQuery::CoAuthor => "CoAuthor".into(),
+			UserCourseRoleQuery::Assistant => "Assistant".into(),
 		}
 	}
 }
@@ -67,4 +73,27 @@ impl UsersCoursesQueryRepository {
 
 		Ok(entity)
     }
+
+    pub async fn get_user_courses_by_roles(
+        dbm: &DbManager,
+        user_id: i64,
+        user_roles: Vec<UserCourseRoleQuery>,
+    ) -> DbResult<Vec<UsersCoursesQuery>> {
+		let mut query = Query::select();
+		
+		query
+			.from(Self::table_ref())
+			.columns(UsersCoursesQuery::field_column_refs())
+			.and_where(Expr::col(UserCourseIden::UserId).eq(user_id))
+			.and_where(Expr::col(UserCourseIden::UserRole).is_in(user_roles.iter().map(ToString::to_string)));
+
+		let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+		let sqlx_query = sqlx::query_as_with::<_, UsersCoursesQuery, _>(&sql, values);
+		let entity =
+			dbm.dbx()
+				.fetch_all(sqlx_query)
+				.await?;
+
+		Ok(entity)
+    }
 }
\ No newline at end of file
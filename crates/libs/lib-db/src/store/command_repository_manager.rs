@@ -1,4 +1,6 @@
-use lib_core::interfaces::{command_repository_manager::ICommandRepositoryManager, course::ICourseCommandRepository, exercise::IExerciseCommandRepository, lesson::ILessonCommandRepository, user::IUserCommandRepository};
+use std::sync::Arc;
+
+use lib_core::interfaces::{command_repository_manager::ICommandRepositoryManager, course::ICourseCommandRepository, exercise::IExerciseCommandRepository, file_storage::IFileStorage, lesson::ILessonCommandRepository, user::IUserCommandRepository};
 
 use crate::command_repository::{course::CourseCommandRepository, exercise::ExerciseCommandRepository, lesson::LessonCommandRepository, user::UserCommandRepository};
 
@@ -9,6 +11,8 @@ pub struct CommandRepositoryManager {
     course_repository: CourseCommandRepository,
     lesson_repository: LessonCommandRepository,
     exercise_repository: ExerciseCommandRepository,
+    file_storage: Arc<dyn IFileStorage + Send + Sync>,
+    legacy_upload_storage: Arc<dyn IFileStorage + Send + Sync>,
 }
 
 impl CommandRepositoryManager {
@@ -19,12 +23,16 @@ impl CommandRepositoryManager {
         let course_repository = CourseCommandRepository::new(dbm.clone());
         let lesson_repository = LessonCommandRepository::new(dbm.clone());
         let exercise_repository = ExerciseCommandRepository::new(dbm);
+        let file_storage = lib_storage::new_file_storage();
+        let legacy_upload_storage = lib_storage::new_legacy_upload_storage();
 
         let result = Self {
             user_repository,
             course_repository,
             lesson_repository,
             exercise_repository,
+            file_storage,
+            legacy_upload_storage,
         };
 
         Ok(result)
@@ -47,4 +55,12 @@ impl<'a> ICommandRepositoryManager for CommandRepositoryManager {
     fn get_exercise_repository(&self) -> Box<dyn IExerciseCommandRepository + Send + Sync> {
         Box::new(self.exercise_repository.clone())
     }
+
+    fn get_file_storage(&self) -> Arc<dyn IFileStorage + Send + Sync> {
+        self.file_storage.clone()
+    }
+
+    fn get_legacy_upload_storage(&self) -> Arc<dyn IFileStorage + Send + Sync> {
+        self.legacy_upload_storage.clone()
+    }
 }
\ No newline at end of file
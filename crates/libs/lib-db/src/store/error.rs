@@ -39,6 +39,16 @@ pub enum DbError {
 	CourseStateMustBePublished {
 		course_id: i64,
 	},
+	CourseTransferMustBePending {
+		transfer_id: i64,
+	},
+	LessonProgressMustBePaused {
+		lesson_id: i64,
+		user_id: i64,
+	},
+	ExamAttemptSubmittedMeanwhile {
+		lesson_id: i64,
+	},
 	MissingFieldError {
 		entity: String,
 		field: String,
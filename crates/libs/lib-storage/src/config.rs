@@ -0,0 +1,70 @@
+use lib_utils::envs::{get_env, Error};
+use std::sync::OnceLock;
+
+pub fn storage_config() -> &'static StorageConfig {
+	static INSTANCE: OnceLock<StorageConfig> = OnceLock::new();
+
+	INSTANCE.get_or_init(|| {
+		StorageConfig::load_from_env().unwrap_or_else(|ex| {
+			panic!("FATAL - WHILE LOADING CONF - Cause: {ex:?}")
+		})
+	})
+}
+
+#[allow(non_snake_case)]
+pub struct StorageConfig {
+	/// `local` or `s3`
+	pub STORAGE_BACKEND: String,
+
+	// -- Local
+	pub STORAGE_LOCAL_DIR: String,
+
+	// -- S3, required for the `s3` backend only
+	pub S3: Option<S3Config>,
+}
+
+#[allow(non_snake_case)]
+pub struct S3Config {
+	pub S3_ENDPOINT: String,
+	pub S3_REGION: String,
+	pub S3_BUCKET: String,
+	pub S3_ACCESS_KEY: String,
+	pub S3_SECRET_KEY: String,
+	/// Base URL the `public/` keys of the bucket are readable at.
+	pub S3_PUBLIC_URL: String,
+}
+
+impl StorageConfig {
+	fn load_from_env() -> lib_utils::envs::Result<StorageConfig> {
+		let storage_backend = get_env("SERVICE_STORAGE_BACKEND")?;
+
+		let s3 = match storage_backend.as_str() {
+			"local" => None,
+			"s3" => Some(S3Config::load_from_env()?),
+			_ => return Err(Error::WrongFormat("SERVICE_STORAGE_BACKEND")),
+		};
+
+		Ok(StorageConfig {
+			STORAGE_BACKEND: storage_backend,
+
+			// -- Local
+			STORAGE_LOCAL_DIR: get_env("SERVICE_STORAGE_LOCAL_DIR")?,
+
+			// -- S3
+			S3: s3,
+		})
+	}
+}
+
+impl S3Config {
+	fn load_from_env() -> lib_utils::envs::Result<S3Config> {
+		Ok(S3Config {
+			S3_ENDPOINT: get_env("SERVICE_S3_ENDPOINT")?,
+			S3_REGION: get_env("SERVICE_S3_REGION")?,
+			S3_BUCKET: get_env("SERVICE_S3_BUCKET")?,
+			S3_ACCESS_KEY: get_env("SERVICE_S3_ACCESS_KEY")?,
+			S3_SECRET_KEY: get_env("SERVICE_S3_SECRET_KEY")?,
+			S3_PUBLIC_URL: get_env("SERVICE_S3_PUBLIC_URL")?,
+		})
+	}
+}
@@ -0,0 +1,32 @@
+pub mod config;
+pub mod local;
+pub mod s3;
+
+use std::sync::Arc;
+
+use lib_core::interfaces::file_storage::{IFileStorage, PUBLIC_PREFIX};
+
+use crate::{config::storage_config, local::LocalFileStorage, s3::S3FileStorage};
+
+/// Backend picked by `SERVICE_STORAGE_BACKEND`.
+pub fn new_file_storage() -> Arc<dyn IFileStorage + Send + Sync> {
+	let config = storage_config();
+
+	match &config.S3 {
+		Some(s3_config) => Arc::new(S3FileStorage::new(s3_config)),
+		None => Arc::new(LocalFileStorage::new(&config.STORAGE_LOCAL_DIR)),
+	}
+}
+
+/// Directory the web server serves at the root, the legacy uploads live in its `uploads/`.
+pub const LEGACY_PUBLIC_DIR: &str = "public";
+
+/// The legacy uploads stay on the local disk whatever the backend is.
+pub fn new_legacy_upload_storage() -> Arc<dyn IFileStorage + Send + Sync> {
+	Arc::new(LocalFileStorage::new(LEGACY_PUBLIC_DIR))
+}
+
+/// Directory of the public files of the local backend, served by the web server as is.
+pub fn local_public_dir() -> String {
+	format!("{}/{}", storage_config().STORAGE_LOCAL_DIR, PUBLIC_PREFIX)
+}
@@ -0,0 +1,108 @@
+use std::{io::ErrorKind, path::{Component, Path, PathBuf}, time::UNIX_EPOCH};
+
+use async_trait::async_trait;
+use lib_core::{interactors::error::CoreError, interfaces::file_storage::{FileStorageResult, IFileStorage}, models::file::{StoredFile, StoredFileInfo}};
+use tokio::fs;
+
+/// Files on the local disk under `root`. 
+/// The public URL of a key is its path relative to the working directory.
+pub struct LocalFileStorage {
+    root: String,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: &str) -> Self {
+        Self {
+            root: root.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn path(&self, key: &str) -> FileStorageResult<PathBuf> {
+        let is_relative = Path::new(key)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        if key.is_empty() || !is_relative {
+            return Err(CoreError::FileStorageError { 
+                key: key.to_string(), 
+                description: "key must be a relative path".to_string(),
+            });
+        }
+
+        Ok(Path::new(&self.root).join(key))
+    }
+}
+
+#[async_trait]
+impl IFileStorage for LocalFileStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, _: &str) -> FileStorageResult<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+
+        fs::write(path, data).await?;
+
+        Ok(())
+    }
+
+    /// The content type is recovered from the extension of the key.
+    async fn get(&self, key: &str) -> FileStorageResult<StoredFile> {
+        let data = fs::read(self.path(key)?).await?;
+        let content_type = mime_guess::from_path(key).first_or_octet_stream().to_string();
+
+        Ok(StoredFile {
+            data,
+            content_type,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> FileStorageResult<()> {
+        fs::remove_file(self.path(key)?).await?;
+
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> FileStorageResult<Vec<StoredFileInfo>> {
+        let mut result = Vec::new();
+        let mut dirs = vec![prefix.trim_end_matches('/').to_string()];
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match fs::read_dir(self.path(&dir)?).await {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            while let Some(entry) = entries.next_entry().await? {
+                let key = format!("{}/{}", dir, entry.file_name().to_string_lossy());
+                let metadata = entry.metadata().await?;
+
+                if metadata.is_dir() {
+                    dirs.push(key);
+                    continue;
+                }
+
+                let modified_at = metadata.modified()?
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs() as i64)
+                    .unwrap_or_default();
+
+                result.push(StoredFileInfo {
+                    key,
+                    modified_at,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.root, key)
+    }
+
+    fn key_from_public_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(&format!("{}/", self.root)).map(ToString::to_string)
+    }
+}
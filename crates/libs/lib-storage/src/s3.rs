@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use aws_sdk_s3::{config::{Credentials, Region}, error::DisplayErrorContext, primitives::ByteStream, Client};
+use lib_core::{interactors::error::CoreError, interfaces::file_storage::{FileStorageResult, IFileStorage}, models::file::{StoredFile, StoredFileInfo}};
+
+use crate::config::S3Config;
+
+/// Any S3-compatible service (AWS, MinIO, ...), addressed path-style.
+pub struct S3FileStorage {
+    client: Client,
+    bucket: String,
+    public_url: String,
+}
+
+impl S3FileStorage {
+    pub fn new(config: &S3Config) -> Self {
+        let credentials = Credentials::new(
+            &config.S3_ACCESS_KEY, 
+            &config.S3_SECRET_KEY, 
+            None, 
+            None, 
+            "lqrl-config",
+        );
+
+        let s3_config = aws_sdk_s3::Config::builder()
+            .endpoint_url(&config.S3_ENDPOINT)
+            .region(Region::new(config.S3_REGION.clone()))
+            .credentials_provider(credentials)
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: Client::from_conf(s3_config),
+            bucket: config.S3_BUCKET.clone(),
+            public_url: config.S3_PUBLIC_URL.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn error(key: &str, err: impl std::error::Error) -> CoreError {
+        CoreError::FileStorageError {
+            key: key.to_string(),
+            description: DisplayErrorContext(err).to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl IFileStorage for S3FileStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> FileStorageResult<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|err| Self::error(key, err))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> FileStorageResult<StoredFile> {
+        let object = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| Self::error(key, err))?;
+
+        let content_type = object.content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+
+        let data = object.body
+            .collect()
+            .await
+            .map_err(|err| Self::error(key, err))?
+            .into_bytes()
+            .to_vec();
+
+        Ok(StoredFile {
+            data,
+            content_type,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> FileStorageResult<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| Self::error(key, err))?;
+
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> FileStorageResult<Vec<StoredFileInfo>> {
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+
+        let mut pages = self.client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&prefix)
+            .into_paginator()
+            .send();
+
+        let mut result = Vec::new();
+        while let Some(page) = pages.try_next().await.map_err(|err| Self::error(&prefix, err))? {
+            for object in page.contents() {
+                let Some(key) = object.key() else {
+                    continue;
+                };
+
+                result.push(StoredFileInfo {
+                    key: key.to_string(),
+                    modified_at: object.last_modified().map(|date| date.secs()).unwrap_or_default(),
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+
+    fn key_from_public_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(&format!("{}/", self.public_url)).map(ToString::to_string)
+    }
+}
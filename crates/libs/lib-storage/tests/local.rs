@@ -0,0 +1,62 @@
+use lib_core::{interactors::error::CoreError, interfaces::file_storage::IFileStorage};
+use lib_storage::local::LocalFileStorage;
+use uuid::Uuid;
+
+fn storage() -> LocalFileStorage {
+    let root = std::env::temp_dir().join(format!("lqrl-storage-{}", Uuid::new_v4()));
+
+    LocalFileStorage::new(root.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_put_get_delete() {
+    let storage = storage();
+    let key = "public/course_images/cover.webp";
+
+    storage.put(key, b"RIFF".to_vec(), "image/webp").await.unwrap();
+
+    let file = storage.get(key).await.unwrap();
+    assert_eq!(file.data, b"RIFF");
+    assert_eq!(file.content_type, "image/webp");
+
+    let url = storage.public_url(key);
+    assert!(url.ends_with("/public/course_images/cover.webp"));
+    assert_eq!(storage.key_from_public_url(&url).as_deref(), Some(key));
+    assert_eq!(storage.key_from_public_url("uploads/old.png"), None);
+
+    storage.delete(key).await.unwrap();
+    assert!(storage.get(key).await.is_err());
+}
+
+#[tokio::test]
+async fn test_key_cannot_leave_root() {
+    let storage = storage();
+
+    for key in ["../outside.png", "/etc/passwd", "public/../../outside.png", ""] {
+        let result = storage.put(key, b"data".to_vec(), "image/png").await;
+        assert!(matches!(result, Err(CoreError::FileStorageError { .. })), "{key}");
+    }
+}
+
+#[tokio::test]
+async fn test_list() {
+    let storage = storage();
+
+    assert!(storage.list("public").await.unwrap().is_empty());
+
+    for key in ["public/course_images/1/hero.png", "public/course_images/2/hero.png", "private/attachments/1.pdf"] {
+        storage.put(key, b"data".to_vec(), "application/octet-stream").await.unwrap();
+    }
+
+    let mut keys: Vec<String> = storage.list("public").await.unwrap()
+        .into_iter()
+        .map(|file| file.key)
+        .collect();
+    keys.sort();
+
+    assert_eq!(keys, vec!["public/course_images/1/hero.png", "public/course_images/2/hero.png"]);
+
+    let files = storage.list("private").await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].modified_at > 0);
+}
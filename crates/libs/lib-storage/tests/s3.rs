@@ -0,0 +1,39 @@
+//! Runs against a real S3-compatible service, e.g. the `minio` service of docker-compose:
+//! `cargo test -p lib-storage --test s3 -- --ignored`
+//! The bucket from `SERVICE_S3_BUCKET` must exist.
+
+use lib_core::interfaces::file_storage::IFileStorage;
+use lib_storage::{config::S3Config, s3::S3FileStorage};
+use uuid::Uuid;
+
+fn s3_config() -> S3Config {
+    let env = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("{name} is not set"));
+
+    S3Config {
+        S3_ENDPOINT: env("SERVICE_S3_ENDPOINT"),
+        S3_REGION: env("SERVICE_S3_REGION"),
+        S3_BUCKET: env("SERVICE_S3_BUCKET"),
+        S3_ACCESS_KEY: env("SERVICE_S3_ACCESS_KEY"),
+        S3_SECRET_KEY: env("SERVICE_S3_SECRET_KEY"),
+        S3_PUBLIC_URL: env("SERVICE_S3_PUBLIC_URL"),
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a running MinIO"]
+async fn test_put_get_delete() {
+    let storage = S3FileStorage::new(&s3_config());
+    let key = format!("private/test/{}.pdf", Uuid::new_v4());
+
+    storage.put(&key, b"%PDF-1.".to_vec(), "application/pdf").await.unwrap();
+
+    let file = storage.get(&key).await.unwrap();
+    assert_eq!(file.data, b"%PDF-1.");
+    assert_eq!(file.content_type, "application/pdf");
+
+    let files = storage.list("private/test").await.unwrap();
+    assert!(files.iter().any(|file| file.key == key));
+
+    storage.delete(&key).await.unwrap();
+    assert!(storage.get(&key).await.is_err());
+}
@@ -1,43 +1,126 @@
-use std::{str::FromStr, sync::Arc};
+use std::{future::Future, str::FromStr, sync::Arc};
 
 use chrono::{Local, Utc};
 use cron::Schedule;
-use lib_core::interactors::cron_job_exercise::CronJobExercise;
+use lib_core::interactors::{cron_job_course_schedule::CronJobCourseSchedule, cron_job_difficulty::CronJobDifficulty, cron_job_exercise::CronJobExercise, cron_job_similarity::CronJobSimilarity, cron_job_upload_gc::CronJobUploadGc};
 use lib_db::store::command_repository_manager::CommandRepositoryManager;
 
 #[tokio::main]
 async fn main() {
 	let command_repository = Arc::new(CommandRepositoryManager::new().await.unwrap());
 
-    let expression = "0/60 * * * * *";
+    let jobs = [
+        tokio::spawn(run_on_schedule("0/60 * * * * *", command_repository.clone(), complete_overdue_exercises)),
+        tokio::spawn(run_on_schedule("0/60 * * * * *", command_repository.clone(), run_course_schedules)),
+        tokio::spawn(run_on_schedule("0 0 * * * *", command_repository.clone(), flag_similar_submissions)),
+        tokio::spawn(run_on_schedule("0 30 3 * * *", command_repository.clone(), calibrate_difficulties)),
+        tokio::spawn(run_on_schedule("0 0 4 * * *", command_repository.clone(), delete_orphaned_uploads)),
+    ];
+
+    for job in jobs {
+        job.await.unwrap();
+    }
+}
+
+/// Runs the job at every time of the CRON expression and prints the lines it reports.
+/// A run is awaited before the next one is scheduled, so runs of one job never overlap.
+async fn run_on_schedule<F, Fut>(expression: &str, command_repository: Arc<CommandRepositoryManager>, job: F)
+where
+    F: Fn(Arc<CommandRepositoryManager>) -> Fut,
+    Fut: Future<Output = Vec<String>>,
+{
     let schedule = Schedule::from_str(expression).expect("Failed to parse CRON expression");
 
     loop {
         let now = Utc::now();
         if let Some(next) = schedule.upcoming(Utc).take(1).next() {
-            let cron_ex = CronJobExercise::new(command_repository.clone());
-
             let until_next = next - now;
-
-            tokio::spawn(async move {
-                match cron_ex.complete_overdue_exercises().await {
-                    Ok(completed_exercises) => {
-                        println!(
-                            "Successfully completed {} exercises. Current time: {}",
-                            completed_exercises,
-                            Local::now().format("%Y-%m-%d %H:%M:%S"),
-                        );
-                    },
-                    Err(_) => {
-                        println!(
-                            "Failed. Current time: {}",
-                            Local::now().format("%Y-%m-%d %H:%M:%S")
-                        );
-                    },
-                }
-            }).await.unwrap();  
-
             tokio::time::sleep(until_next.to_std().unwrap()).await;
+
+            for line in job(command_repository.clone()).await {
+                println!(
+                    "{line} Current time: {}",
+                    Local::now().format("%Y-%m-%d %H:%M:%S"),
+                );
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+async fn complete_overdue_exercises(command_repository: Arc<CommandRepositoryManager>) -> Vec<String> {
+    let cron_ex = CronJobExercise::new(command_repository);
+
+    let completed = match cron_ex.complete_overdue_exercises().await {
+        Ok(completed_exercises) => format!("Successfully completed {completed_exercises} exercises."),
+        Err(_) => "Failed.".to_string(),
+    };
+
+    let submitted = match cron_ex.submit_overdue_exams().await {
+        Ok(submitted_exams) => format!("Successfully submitted {submitted_exams} exams."),
+        Err(_) => "Exam submission failed.".to_string(),
+    };
+
+    vec![completed, submitted]
+}
+
+async fn run_course_schedules(command_repository: Arc<CommandRepositoryManager>) -> Vec<String> {
+    let cron_course_schedule = CronJobCourseSchedule::new(command_repository);
+
+    let report = match cron_course_schedule.run_due_schedules().await {
+        Ok(report) => format!(
+            "Successfully published {} and archived {} scheduled courses (not ready: {:?}, failed: {:?}).",
+            report.published,
+            report.archived,
+            report.not_ready_course_ids,
+            report.failed_course_ids,
+        ),
+        Err(_) => "Scheduled course transitions failed.".to_string(),
+    };
+
+    vec![report]
+}
+
+async fn flag_similar_submissions(command_repository: Arc<CommandRepositoryManager>) -> Vec<String> {
+    let cron_similarity = CronJobSimilarity::new(command_repository);
+
+    let report = match cron_similarity.flag_similar_submissions().await {
+        Ok(report) => format!(
+            "Successfully flagged {} similar pairs (failed exercises: {:?}).",
+            report.flagged_pairs,
+            report.failed_exercise_ids,
+        ),
+        Err(_) => "Similarity check failed.".to_string(),
+    };
+
+    vec![report]
+}
+
+async fn calibrate_difficulties(command_repository: Arc<CommandRepositoryManager>) -> Vec<String> {
+    let cron_difficulty = CronJobDifficulty::new(command_repository);
+
+    let report = match cron_difficulty.calibrate_difficulties().await {
+        Ok(report) => format!(
+            "Successfully recalibrated {} exercises (failed exercises: {:?}).",
+            report.changed_exercises,
+            report.failed_exercise_ids,
+        ),
+        Err(_) => "Difficulty calibration failed.".to_string(),
+    };
+
+    vec![report]
+}
+
+async fn delete_orphaned_uploads(command_repository: Arc<CommandRepositoryManager>) -> Vec<String> {
+    let cron_upload_gc = CronJobUploadGc::new(command_repository);
+
+    let report = match cron_upload_gc.delete_orphaned_uploads().await {
+        Ok(report) => format!(
+            "Successfully deleted {} orphaned uploads ({} failed).",
+            report.orphaned_keys.len(),
+            report.failed_keys.len(),
+        ),
+        Err(_) => "Upload collection failed.".to_string(),
+    };
+
+    vec![report]
+}
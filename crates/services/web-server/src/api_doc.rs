@@ -5,9 +5,9 @@ use utoipa::{
 
 use utoipa::openapi::security::Http;
 use crate::routes::models;
-use crate::routes::user::{login, register, user, course as user_course, lesson as user_lesson, exercise as user_exercise};
-use crate::routes::student::{course as student_course, lesson as student_lesson, lesson_progress as student_lesson_progress, exercise as student_exercise};
-use crate::routes::creator::{course as creator_course, lesson as creator_lesson, exercise as creator_exercise};
+use crate::routes::user::{attachment, login, register, user, course as user_course, lesson as user_lesson, exercise as user_exercise};
+use crate::routes::student::{course as student_course, lesson as student_lesson, lesson_progress as student_lesson_progress, exercise as student_exercise, peer_review as student_peer_review};
+use crate::routes::creator::{course as creator_course, lesson as creator_lesson, section as creator_section, content_block as creator_content_block, exercise as creator_exercise};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -25,45 +25,112 @@ use crate::routes::creator::{course as creator_course, lesson as creator_lesson,
 		// Course user 
 		user_course::api_get_course_handler,
 		user_course::api_get_courses_handler,
+		user_course::api_get_placement_test_handler,
+		user_course::api_get_unlock_policy_handler,
 
 		// Course creator
 		creator_course::api_set_course_img_handler,
 		creator_course::api_create_course_draft_handler,
 		creator_course::api_update_course_handler,
+		creator_course::api_get_publish_readiness_handler,
 		creator_course::api_publish_course_handler,
+		creator_course::api_unpublish_course_handler,
 		creator_course::api_archive_course_handler,
+		creator_course::api_restore_course_handler,
+		creator_course::api_delete_course_handler,
+		creator_course::api_set_course_schedule_handler,
+		creator_course::api_clear_course_schedule_handler,
+		creator_course::api_get_course_revision_handler,
+		creator_course::api_publish_course_revision_handler,
+		creator_course::api_discard_course_revision_handler,
+		creator_course::api_get_course_staff_handler,
+		creator_course::api_invite_staff_member_handler,
+		creator_course::api_change_staff_role_handler,
+		creator_course::api_remove_staff_member_handler,
+		creator_course::api_propose_course_transfer_handler,
+		creator_course::api_accept_course_transfer_handler,
+		creator_course::api_decline_course_transfer_handler,
+		creator_course::api_cancel_course_transfer_handler,
+		creator_course::api_force_course_transfer_handler,
+		creator_course::api_get_course_transfers_handler,
+		creator_course::api_get_incoming_course_transfers_handler,
 		creator_course::api_get_created_courses_handler,
 		creator_course::api_get_attendants,
 		creator_course::api_get_point_statistics_handler,
+		creator_course::api_get_mistake_statistics_handler,
+		creator_course::api_get_difficulty_calibration_handler,
+		creator_course::api_update_placement_test_handler,
+		creator_course::api_update_unlock_policy_handler,
+		creator_course::api_reconcile_progress_handler,
+		creator_course::api_collect_orphaned_uploads_handler,
 
 		// Course student
 		student_course::api_register_for_course_handler,
 		student_course::api_unsubscribe_from_course_handler,
 		student_course::api_get_user_courses_registered_handler,
+		student_course::api_take_placement_test_handler,
 
 		// Lesson
 		creator_lesson::api_create_lesson_handler,
 		creator_lesson::api_update_lesson_handler,
 		creator_lesson::api_lesson_change_order_handler,
+		creator_lesson::api_lesson_change_section_handler,
         creator_lesson::api_delete_lesson_handler,
+		creator_lesson::api_update_exam_handler,
+		creator_lesson::api_remove_exam_handler,
+		creator_lesson::api_release_exam_results_handler,
+		creator_lesson::api_preview_lesson_handler,
+		creator_lesson::api_upload_attachment_handler,
+		creator_lesson::api_delete_attachment_handler,
 
 		user_lesson::api_get_lessons_handler,
+		user_lesson::api_get_sections_handler,
 		user_lesson::api_get_lesson_handler,
+		user_lesson::api_get_exam_handler,
+		user_lesson::api_get_attachments_handler,
 
 		student_lesson::api_start_lesson_handler,
+		student_lesson::api_submit_exam_handler,
+		student_lesson::api_pause_lesson_handler,
+		student_lesson::api_resume_lesson_handler,
+		student_lesson::api_get_attachment_link_handler,
+		attachment::api_download_attachment_handler,
 		student_lesson_progress::api_get_lesson_progresses_handler,
+		student_lesson_progress::api_get_lesson_locks_handler,
+
+		// Section
+		creator_section::api_create_section_handler,
+		creator_section::api_delete_section_handler,
+		creator_section::api_update_section_handler,
+		creator_section::api_section_change_order_handler,
+
+		// Content block
+		creator_content_block::api_create_content_block_handler,
+		creator_content_block::api_delete_content_block_handler,
+		creator_content_block::api_update_content_block_handler,
+		creator_content_block::api_content_block_change_order_handler,
 
 		// Exercise
 		creator_exercise::api_create_exercise_handler,
 		creator_exercise::api_update_exercise_handler,
 		creator_exercise::api_exercise_change_order_handler,
+		creator_exercise::api_layout_exercise_body_handler,
+		creator_exercise::api_get_student_attempt_diff_handler,
+		creator_exercise::api_get_review_queue_handler,
+		creator_exercise::api_get_similarity_flags_handler,
+		creator_exercise::api_send_to_review_handler,
+		creator_exercise::api_review_attempt_handler,
 
 		student_exercise::api_start_exercise_handler,
 		student_exercise::api_save_changes_handler,
 		student_exercise::api_complete_attempt_handler,
+		student_exercise::api_get_attempt_diff_handler,
+		student_peer_review::api_get_peer_review_assignments_handler,
+		student_peer_review::api_submit_peer_review_handler,
 		user_exercise::api_get_lesson_exercises_handler,
 		user_exercise::api_get_exercise_handler,
 		user_exercise::api_get_exercise_completions_handler,
+		user_exercise::api_get_exercise_practice_completions_handler,
 		user_exercise::api_get_exercises_completions_handler,
 		user_exercise::api_get_number_of_lesson_completed_exercises_handler,
     ),
@@ -85,11 +152,31 @@ use crate::routes::creator::{course as creator_course, lesson as creator_lesson,
 			models::course::CourseUpdatePayload,
 			models::course::CoursePayload,
 			models::course::CourseStatePayload,
+			models::course::CourseImageUrlsPayload,
+			models::course::CourseImageVariantsPayload,
 			models::course::CourseId,
+			models::course::CourseSchedulePayload,
+			models::course::CourseStaffPayload,
+			models::course::CourseStaffMemberId,
+			models::course::CourseStaffMemberPayload,
+			models::course::CourseTransferProposalPayload,
+			models::course::CourseTransferId,
+			models::course::CourseTransferPayload,
 			models::course::CourseFilterPayload,
 			models::course::CoursesPayload,
 			models::course::CoursePointStatisticsPayload,
 			models::course::UserPointsPayload,
+			models::course::PlacementLevelPayload,
+			models::course::PlacementTestPayload,
+			models::course::PlacementAnswerPayload,
+			models::course::PlacementTestTakePayload,
+			models::course::PlacementResultPayload,
+			models::course::DripReleasePayload,
+			models::course::UnlockPolicyPayload,
+			models::course::ReadinessIssuePayload,
+			models::course::PublishReadinessPayload,
+			models::course::StagedEditPayload,
+			models::course::CourseRevisionPayload,
 
 			// Lesson
 			models::lesson::LessonCreatedPayload,
@@ -97,25 +184,79 @@ use crate::routes::creator::{course as creator_course, lesson as creator_lesson,
   			models::lesson::LessonDeletePayload,
 			models::lesson::LessonUpdatePayload,
 			models::lesson::LessonChangeOrderPayload,
+			models::lesson::LessonChangeSectionPayload,
 			models::lesson::LessonDataPayload,
+			models::lesson::LessonPreviewPayload,
 			models::lesson::StartLessonPayload,
+			models::lesson::ExamUpdatePayload,
+			models::lesson::ExamRemovePayload,
+			models::lesson::ExamReleaseResultsPayload,
+			models::lesson::ExamSubmitPayload,
+			models::lesson::ExamPayload,
+
+			// Attachment
+			models::attachment::AttachmentCreatedPayload,
+			models::attachment::AttachmentDeletePayload,
+			models::attachment::AttachmentPayload,
+			models::attachment::AttachmentLinkPayload,
+
+			// Section
+			models::section::SectionCreatePayload,
+			models::section::SectionCreatedPayload,
+			models::section::SectionDeletePayload,
+			models::section::SectionUpdatePayload,
+			models::section::SectionChangeOrderPayload,
+			models::section::SectionDataPayload,
+
+			// Content block
+			models::content_block::ContentBlockCreatePayload,
+			models::content_block::ContentBlockCreatedPayload,
+			models::content_block::ContentBlockDeletePayload,
+			models::content_block::ContentBlockUpdatePayload,
+			models::content_block::ContentBlockChangeOrderPayload,
+			models::content_block::ContentBlockPayload,
+			models::lesson::LessonContentPayload,
 
 			// Lesson progress
 			models::lesson_progress::GetLessonProgressesPayload,
 			models::lesson_progress::LessonProgressPayload,
+			models::lesson_progress::LessonLockPayload,
+			models::lesson_progress::ProgressReconciliationPayload,
+			models::file::CollectOrphanedUploadsPayload,
+			models::file::UploadGcReportPayload,
 
 			// Exercise
 			models::exercise::ExerciseCreatePayload,
 			models::exercise::ExerciseCreatedPayload,
 			models::exercise::ExerciseForUpdatePayload,
 			models::exercise::ExerciseChangeOrderPayload,
+			models::exercise::ExerciseLayoutPayload,
+			models::exercise::ExerciseBodyPayload,
+			models::exercise::ConspectDiffPayload,
+			models::exercise::ConnectionDiffPayload,
+			models::exercise::NodeDiffPayload,
+			models::exercise::StageDiffPayload,
+			models::exercise::ExerciseCompletionReviewPayload,
+			models::exercise::ExerciseMistakeStatisticsPayload,
+			models::exercise::ConnectionMistakePayload,
+			models::exercise::StageMistakePayload,
+			models::exercise::NodeMistakePayload,
+			models::exercise::DifficultyCalibrationPayload,
+			models::exercise::DifficultyChangePayload,
 
-			models::exercise::ExerciseId,
+			models::exercise::ExerciseForStart,
 			models::exercise::ExercisePayload,
 			models::exercise::ExerciseCompletionForSaveChanges,
 			models::exercise::ExerciseCompletionId,
 			models::exercise::ExerciseEstimatePayload,
 			models::exercise_completion::ExerciseCompletionPayload,
+
+			// Peer review
+			models::peer_review::PeerReviewAssignmentPayload,
+			models::peer_review::PeerReviewSubmitPayload,
+
+			// Similarity
+			models::similarity_flag::SimilarityComparisonPayload,
 		)
     ),
     modifiers(&SecurityAddon),
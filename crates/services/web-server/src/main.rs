@@ -19,7 +19,7 @@ use crate::middleware::mw_auth::{mw_ctx_require, mw_ctx_resolver};
 use crate::middleware::mw_req_stamp::mw_req_stamp_resolver;
 use crate::middleware::mw_res_map::mw_reponse_map;
 use crate::routes::routes_static;
-use crate::routes::user::{login, register};
+use crate::routes::user::{attachment, login, register};
 use axum::{middleware as axum_middleware, Router};
 use tokio::net::TcpListener;
 use tracing::info;
@@ -59,6 +59,12 @@ async fn main() -> AppResult<()> {
 	let routes_creator_lesson = routes::creator::lesson::routes(app_state.clone())
 		.route_layer(axum_middleware::from_fn(mw_ctx_require));
 
+	let routes_creator_section = routes::creator::section::routes(app_state.clone())
+		.route_layer(axum_middleware::from_fn(mw_ctx_require));
+
+	let routes_creator_content_block = routes::creator::content_block::routes(app_state.clone())
+		.route_layer(axum_middleware::from_fn(mw_ctx_require));
+
 	let routes_user_lesson = routes::user::lesson::routes(app_state.clone())
 		.route_layer(axum_middleware::from_fn(mw_ctx_require));
 
@@ -77,24 +83,35 @@ async fn main() -> AppResult<()> {
 	let routes_user_exercise = routes::user::exercise::routes(app_state.clone())
 		.route_layer(axum_middleware::from_fn(mw_ctx_require));
 
+	let routes_student_peer_review = routes::student::peer_review::routes(app_state.clone())
+		.route_layer(axum_middleware::from_fn(mw_ctx_require));
+
+	// Public files of the local storage backend, their URLs are their paths.
+	let local_public_dir = lib_storage::local_public_dir();
+
 	let routes_all = Router::new()
 		.nest("/api/course", routes_user_course)
 		.nest("/api/course", routes_student_course)
 		.nest("/api/course", routes_creator_course)
 		.nest("/api/course/lesson", routes_creator_lesson)
+		.nest("/api/course/section", routes_creator_section)
+		.nest("/api/course/lesson/content_block", routes_creator_content_block)
 		.nest("/api/course/lesson", routes_user_lesson)
 		.nest("/api/course/lesson", routes_student_lesson)
 		.nest("/api/course/lesson", routes_student_lesson_progress)
 		.nest("/api/course/lesson/exercise", routes_creator_exercise)
 		.nest("/api/course/lesson/exercise", routes_student_exercise)
 		.nest("/api/course/lesson/exercise", routes_user_exercise)
+		.nest("/api/course/lesson/exercise/peer_review", routes_student_peer_review)
 		.nest("/api/user", routes_user)
         .layer(axum_middleware::from_fn_with_state(app_state.clone(), mw_ctx_resolver))
 		.merge(login::routes(app_state.clone()))
 		.merge(register::routes(app_state.clone()))
+		.merge(attachment::routes(app_state.clone()))
 		.layer(axum_middleware::map_response(mw_reponse_map))
 		.layer(axum_middleware::from_fn(mw_req_stamp_resolver))
-		.nest_service("/", ServeDir::new("public"))
+		.nest_service("/", ServeDir::new(lib_storage::LEGACY_PUBLIC_DIR))
+		.nest_service(&format!("/{}", local_public_dir), ServeDir::new(&local_public_dir))
 		.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
 		.fallback_service(routes_static::serve_dir())
 		.layer(cors);
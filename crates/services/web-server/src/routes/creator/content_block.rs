@@ -0,0 +1,152 @@
+use axum::{extract::State, routing::{delete, post, put}, Json, Router};
+use lib_core::{interactors::creator::content_block::CreatorContentBlockInteractor, models::content_block::{ContentBlockForChangeOrder, ContentBlockForCreate, ContentBlockForUpdate}};
+use serde_json::{json, Value};
+
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::content_block::{ContentBlockChangeOrderPayload, ContentBlockCreatePayload, ContentBlockCreatedPayload, ContentBlockDeletePayload, ContentBlockUpdatePayload}};
+
+pub fn routes(app_state: AppState) -> Router {
+	Router::new()
+		.route("/create", post(api_create_content_block_handler))
+		.route("/delete", delete(api_delete_content_block_handler))
+		.route("/update", put(api_update_content_block_handler))
+		.route("/change_order", put(api_content_block_change_order_handler))
+		.with_state(app_state)
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/lesson/content_block/create",
+	request_body = ContentBlockCreatePayload,
+	responses(
+		(status = 200, description = "Content block created successfully", body = ContentBlockCreatedPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_create_content_block_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ContentBlockCreatePayload>,
+) -> AppResult<Json<ContentBlockCreatedPayload>> {
+	let ctx = ctx.0;
+
+	let content_block_c = ContentBlockForCreate {
+		lesson_id: paylod.lesson_id,
+		block_type: paylod.block_type,
+		body: paylod.body,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let content_block_interactor = CreatorContentBlockInteractor::new(command_repository_manager);
+
+	let content_block_id = content_block_interactor.create_content_block(&ctx, content_block_c).await?;
+
+	Ok(Json(ContentBlockCreatedPayload { content_block_id }))
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/lesson/content_block/delete",
+	request_body = ContentBlockDeletePayload,
+	responses(
+		(status = 200, description = "Content block deleted successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_delete_content_block_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ContentBlockDeletePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let content_block_interactor = CreatorContentBlockInteractor::new(command_repository_manager);
+
+	content_block_interactor.delete_content_block(&ctx, paylod.content_block_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/content_block/update",
+	request_body = ContentBlockUpdatePayload,
+	responses(
+		(status = 200, description = "Content block updated successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_update_content_block_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ContentBlockUpdatePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let content_block_u = ContentBlockForUpdate {
+		id: paylod.content_block_id,
+		body: paylod.body,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let content_block_interactor = CreatorContentBlockInteractor::new(command_repository_manager);
+
+	content_block_interactor.update_content_block(&ctx, content_block_u).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/content_block/change_order",
+	request_body = ContentBlockChangeOrderPayload,
+	responses(
+		(status = 200, description = "Content block moved successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_content_block_change_order_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ContentBlockChangeOrderPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let content_block_c_o = ContentBlockForChangeOrder {
+		id: paylod.content_block_id,
+		order: paylod.order,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let content_block_interactor = CreatorContentBlockInteractor::new(command_repository_manager);
+
+	content_block_interactor.change_order(&ctx, content_block_c_o).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
@@ -1,22 +1,51 @@
-use axum::{extract::{Multipart, Path, Query, State}, routing::{get, post, put}, Json, Router};
-use lib_core::{interactors::creator::course::CreatorCourseInteractor, models::course::{CourseForCreate, CourseForUpdate}};
+use axum::{extract::{DefaultBodyLimit, Multipart, Path, Query, State}, routing::{delete, get, post, put}, Json, Router};
+use lib_core::{interactors::creator::{course::CreatorCourseInteractor, course_transfer::CreatorCourseTransferInteractor, exercise::CreatorExerciseInteractor}, models::{course::{CourseForCreate, CourseForUpdate, CoursePermission}, course_image::MAX_COURSE_IMG_SIZE}};
 use lib_db::query_repository::course::CourseQuery;
 use lib_utils::time::now_utc_sec;
 use serde_json::{json, Value};
 use tracing::info;
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{course::{CourseCreateDraftPayload, CourseFilterPayload, CourseId, CoursePayload, CoursePointStatisticsPayload, CourseUpdatePayload, CoursesPayload, CreatedCourseDraft}, user::{GetAttendatsPayload, UserPayload, UsersPayload}}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{course::{CourseCreateDraftPayload, CourseFilterPayload, CourseId, CourseImageVariantsPayload, CoursePayload, CourseRevisionPayload, CourseSchedulePayload, CourseStaffMemberId, CourseStaffMemberPayload, CourseStaffPayload, CourseTransferId, CourseTransferPayload, CourseTransferProposalPayload, CoursePointStatisticsPayload, CourseUpdatePayload, CoursesPayload, CreatedCourseDraft, PlacementTestPayload, PublishReadinessPayload, UnlockPolicyPayload}, exercise::{DifficultyCalibrationPayload, ExerciseMistakeStatisticsPayload}, file::{CollectOrphanedUploadsPayload, UploadGcReportPayload}, lesson_progress::ProgressReconciliationPayload, user::{GetAttendatsPayload, UserPayload, UsersPayload}}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/create_course_draft", post(api_create_course_draft_handler))
 		.route("/update", put(api_update_course_handler))
-		.route("/set_course_img/:i64", put(api_set_course_img_handler))
+		.route(
+			"/set_course_img/:i64", 
+			put(api_set_course_img_handler).layer(DefaultBodyLimit::max(MAX_COURSE_IMG_SIZE + 64 * 1024))
+		)
+		.route("/get_publish_readiness/:i64", get(api_get_publish_readiness_handler))
 		.route("/publish_course", put(api_publish_course_handler))
+		.route("/unpublish_course", put(api_unpublish_course_handler))
 		.route("/archive_course", put(api_archive_course_handler))
+		.route("/restore_course", put(api_restore_course_handler))
+		.route("/delete_course", delete(api_delete_course_handler))
+		.route("/set_course_schedule", put(api_set_course_schedule_handler))
+		.route("/clear_course_schedule", put(api_clear_course_schedule_handler))
+		.route("/get_course_revision/:i64", get(api_get_course_revision_handler))
+		.route("/publish_course_revision", put(api_publish_course_revision_handler))
+		.route("/discard_course_revision", delete(api_discard_course_revision_handler))
+		.route("/get_course_staff/:i64", get(api_get_course_staff_handler))
+		.route("/invite_staff_member", post(api_invite_staff_member_handler))
+		.route("/change_staff_role", put(api_change_staff_role_handler))
+		.route("/remove_staff_member", delete(api_remove_staff_member_handler))
+		.route("/propose_course_transfer", post(api_propose_course_transfer_handler))
+		.route("/accept_course_transfer", put(api_accept_course_transfer_handler))
+		.route("/decline_course_transfer", put(api_decline_course_transfer_handler))
+		.route("/cancel_course_transfer", put(api_cancel_course_transfer_handler))
+		.route("/force_course_transfer", put(api_force_course_transfer_handler))
+		.route("/get_course_transfers/:i64", get(api_get_course_transfers_handler))
+		.route("/get_incoming_course_transfers", get(api_get_incoming_course_transfers_handler))
 		.route("/get_created_courses", get(api_get_created_courses_handler))
 		.route("/get_attendants", get(api_get_attendants))
 		.route("/get_point_statistics/:i64", get(api_get_point_statistics_handler))
+		.route("/get_mistake_statistics/:i64", get(api_get_mistake_statistics_handler))
+		.route("/get_difficulty_calibration/:i64", get(api_get_difficulty_calibration_handler))
+		.route("/update_placement_test", put(api_update_placement_test_handler))
+		.route("/update_unlock_policy", put(api_update_unlock_policy_handler))
+		.route("/reconcile_progress", put(api_reconcile_progress_handler))
+		.route("/collect_orphaned_uploads", put(api_collect_orphaned_uploads_handler))
 		.with_state(app_state)
 }
 
@@ -103,28 +132,584 @@ async fn api_update_course_handler(
 	Ok(body)
 }
 
+#[utoipa::path(
+	get,
+	path = "/api/course/get_publish_readiness/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = PublishReadinessPayload, description = "Blocking errors and warnings of the course"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_publish_readiness_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<PublishReadinessPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	let readiness = course_interactor.check_publish_readiness(&ctx, course_id).await?;
+
+	Ok(Json(readiness.into()))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/publish_course",
+	request_body = CourseId,
+	responses(
+		(status = 200),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_publish_course_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.publish_course(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/unpublish_course",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Course without students moved back to Draft"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_unpublish_course_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.unpublish_course(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/archive_course",
+	request_body = CourseId,
+	responses(
+		(status = 200),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_archive_course_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.archive_course(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/restore_course",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Archived course published again"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_restore_course_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.restore_course(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/delete_course",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Draft deleted with its lessons, exercises and files"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_delete_course_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.delete_course(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/set_course_img/{course_id}",
+	params(
+		("course_id", description = "ID of the course for which we set an avatar")
+	),
+	request_body(content_type = "multipart/formdata", content = Vec<u8>),
+	responses(
+		(status = 200, description = "Course image successfully set"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_set_course_img_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+	mut multipart: Multipart,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+    while let Some(field) = multipart.next_field().await? {
+        let field_name = if let Some(field_name) = field.name() {
+			field_name.to_string()
+		} else {
+			continue;
+		};
+		
+        if field_name == "image" {
+            let data = field.bytes().await?;
+			let img_variants = course_interactor.set_course_img(&ctx, course_id, &data).await?;
+			let img_url = img_variants.hero.png.clone();
+
+			let body = Json(json!({
+				"result": {
+					"img_url": img_url,
+					"img_variants": CourseImageVariantsPayload::from(img_variants),
+				}
+			}));
+
+			return Ok(body);
+        }
+    }
+
+	let body = Json(json!({
+		"result": {
+			"error": "file error"
+		}
+	}));
+
+    Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/set_course_schedule",
+	request_body = CourseSchedulePayload,
+	responses(
+		(status = 200, description = "Schedule of the course replaced, a missing date is not scheduled"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_set_course_schedule_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(schedule): Json<CourseSchedulePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.set_course_schedule(&ctx, schedule.into()).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/clear_course_schedule",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Scheduled publishing and archiving cancelled"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_clear_course_schedule_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.clear_course_schedule(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_course_revision/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = CourseRevisionPayload, description = "Staged edits of the published course, empty when there are none"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_course_revision_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<CourseRevisionPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	let revision = course_interactor.get_course_revision(&ctx, course_id).await?;
+
+	Ok(Json(revision.try_into()?))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/publish_course_revision",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Staged edits applied at once"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_publish_course_revision_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.publish_course_revision(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/discard_course_revision",
+	request_body = CourseId,
+	responses(
+		(status = 200, description = "Staged edits dropped"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_discard_course_revision_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.discard_course_revision(&ctx, course_id.course_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_course_staff/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Vec<CourseStaffMemberPayload>, description = "Creator, co-authors and assistants of the course"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_course_staff_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<CourseStaffMemberPayload>>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	let result = course_interactor
+		.get_course_staff(&ctx, course_id)
+		.await?
+		.into_iter()
+		.map(|member| member.into())
+		.collect();
+
+	Ok(Json(result))
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/invite_staff_member",
+	request_body = CourseStaffPayload,
+	responses(
+		(status = 200, description = "User joined the course staff"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_invite_staff_member_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CourseStaffPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.invite_staff_member(&ctx, payload.try_into()?).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/change_staff_role",
+	request_body = CourseStaffPayload,
+	responses(
+		(status = 200, description = "Role of the staff member changed"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_change_staff_role_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CourseStaffPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.change_staff_role(&ctx, payload.try_into()?).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/remove_staff_member",
+	request_body = CourseStaffMemberId,
+	responses(
+		(status = 200, description = "User removed from the course staff"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_remove_staff_member_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CourseStaffMemberId>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.remove_staff_member(&ctx, payload.course_id, payload.user_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/propose_course_transfer",
+	request_body = CourseTransferProposalPayload,
+	responses(
+		(status = 200, body = CourseTransferId, description = "Transfer waits for the recipient to accept it"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_propose_course_transfer_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CourseTransferProposalPayload>,
+) -> AppResult<Json<CourseTransferId>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
+
+	let transfer_id = transfer_interactor.propose_course_transfer(&ctx, payload.course_id, payload.to_user_id).await?;
+
+	Ok(Json(CourseTransferId { transfer_id }))
+}
+
 #[utoipa::path(
 	put,
-	path = "/api/course/publish_course",
-	request_body = CourseId,
+	path = "/api/course/accept_course_transfer",
+	request_body = CourseTransferId,
 	responses(
-		(status = 200),
+		(status = 200, description = "Course moved to the recipient"),
 	),
 	security(
 		("bearerAuth" = [])
 	)
 )]
-async fn api_publish_course_handler(
+async fn api_accept_course_transfer_handler(
 	ctx: CtxW,
 	State(app_state): State<AppState>,
-	Json(course_id): Json<CourseId>,
+	Json(payload): Json<CourseTransferId>,
 ) -> AppResult<Json<Value>> {
 	let ctx = ctx.0;
 
 	let command_repository_manager = app_state.command_repository_manager;
-	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
 
-	course_interactor.publish_course(&ctx, course_id.course_id).await?;
+	transfer_interactor.accept_course_transfer(&ctx, payload.transfer_id).await?;
 
 	let body = Json(json!({
 		"result": {
@@ -137,26 +722,26 @@ async fn api_publish_course_handler(
 
 #[utoipa::path(
 	put,
-	path = "/api/course/archive_course",
-	request_body = CourseId,
+	path = "/api/course/decline_course_transfer",
+	request_body = CourseTransferId,
 	responses(
-		(status = 200),
+		(status = 200, description = "Transfer declined by the recipient"),
 	),
 	security(
 		("bearerAuth" = [])
 	)
 )]
-async fn api_archive_course_handler(
+async fn api_decline_course_transfer_handler(
 	ctx: CtxW,
 	State(app_state): State<AppState>,
-	Json(course_id): Json<CourseId>,
+	Json(payload): Json<CourseTransferId>,
 ) -> AppResult<Json<Value>> {
 	let ctx = ctx.0;
 
 	let command_repository_manager = app_state.command_repository_manager;
-	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
 
-	course_interactor.archive_course(&ctx, course_id.course_id).await?;
+	transfer_interactor.decline_course_transfer(&ctx, payload.transfer_id).await?;
 
 	let body = Json(json!({
 		"result": {
@@ -169,57 +754,128 @@ async fn api_archive_course_handler(
 
 #[utoipa::path(
 	put,
-	path = "/api/course/set_course_img/{course_id}",
-	params(
-		("course_id", description = "ID of the course for which we set an avatar")
-	),
-	request_body(content_type = "multipart/formdata", content = Vec<u8>),
+	path = "/api/course/cancel_course_transfer",
+	request_body = CourseTransferId,
 	responses(
-		(status = 200, description = "Course image successfully set"),
+		(status = 200, description = "Transfer cancelled by the creator"),
 	),
 	security(
 		("bearerAuth" = [])
 	)
 )]
-async fn api_set_course_img_handler(
+async fn api_cancel_course_transfer_handler(
 	ctx: CtxW,
 	State(app_state): State<AppState>,
-	Path(course_id): Path<i64>,
-	mut multipart: Multipart,
+	Json(payload): Json<CourseTransferId>,
 ) -> AppResult<Json<Value>> {
 	let ctx = ctx.0;
 
 	let command_repository_manager = app_state.command_repository_manager;
-	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
 
-    while let Some(field) = multipart.next_field().await? {
-        let field_name = if let Some(field_name) = field.name() {
-			field_name.to_string()
-		} else {
-			continue;
-		};
-		
-        if field_name == "image" {
-            let data = field.bytes().await?;
-			let img_url = course_interactor.set_course_img(&ctx, course_id, &data).await?;
+	transfer_interactor.cancel_course_transfer(&ctx, payload.transfer_id).await?;
 
-			let body = Json(json!({
-				"result": {
-					"img_url": img_url,
-				}
-			}));
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
 
-			return Ok(body);
-        }
-    }
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/force_course_transfer",
+	request_body = CourseTransferProposalPayload,
+	responses(
+		(status = 200, description = "Admin only. Course moved without asking the recipient"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_force_course_transfer_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CourseTransferProposalPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
+
+	transfer_interactor.force_course_transfer(&ctx, payload.course_id, payload.to_user_id).await?;
 
 	let body = Json(json!({
 		"result": {
-			"error": "file error"
+			"success": true,
 		}
 	}));
 
-    Ok(body)
+	Ok(body)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_course_transfers/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Vec<CourseTransferPayload>, description = "Every ownership transfer of the course, the oldest first"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_course_transfers_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<CourseTransferPayload>>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
+
+	let result = transfer_interactor
+		.get_course_transfers(&ctx, course_id)
+		.await?
+		.into_iter()
+		.map(|transfer| transfer.into())
+		.collect();
+
+	Ok(Json(result))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_incoming_course_transfers",
+	responses(
+		(status = 200, body = Vec<CourseTransferPayload>, description = "Pending transfers offered to the user"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_incoming_course_transfers_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<CourseTransferPayload>>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let transfer_interactor = CreatorCourseTransferInteractor::new(command_repository_manager);
+
+	let result = transfer_interactor
+		.get_incoming_course_transfers(&ctx)
+		.await?
+		.into_iter()
+		.map(|transfer| transfer.into())
+		.collect();
+
+	Ok(Json(result))
 }
 
 #[utoipa::path(
@@ -302,7 +958,7 @@ async fn api_get_attendants(
 	let ctx = ctx.0;
 	let course_id = payload.course_id;
 
-	app_state.permission_manager.check_course_creator_permission(&ctx, course_id).await?;
+	app_state.permission_manager.check_course_permission(&ctx, course_id, CoursePermission::ViewStatistics).await?;
 
 	let list_options = if let Some(list_options) = payload.list_options {
 		serde_json::from_str(&list_options)?
@@ -351,9 +1007,195 @@ async fn api_get_point_statistics_handler(
 ) -> AppResult<Json<CoursePointStatisticsPayload>> {
 	let ctx = ctx.0;
 
+	app_state.permission_manager.check_course_permission(&ctx, course_id, CoursePermission::ViewStatistics).await?;
+
 	let exercise_repository = app_state.query_repository_manager.get_exercise_repository();
 
 	let course_point_statistics = exercise_repository.get_course_point_statistics(&ctx, course_id).await?;
 
 	Ok(Json(course_point_statistics.into()))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_mistake_statistics/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Vec<ExerciseMistakeStatisticsPayload>, description = "Most frequent mistakes of every exercise"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_mistake_statistics_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<ExerciseMistakeStatisticsPayload>>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+	let result = exercise_interactor
+		.get_mistake_statistics(&ctx, course_id)
+		.await?
+		.into_iter()
+		.map(|statistics| statistics.into())
+		.collect();
+
+	Ok(Json(result))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_difficulty_calibration/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Vec<DifficultyCalibrationPayload>, description = "Suggested versus assigned difficulty of every exercise"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_difficulty_calibration_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<DifficultyCalibrationPayload>>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+	let result = exercise_interactor
+		.get_difficulty_calibration(&ctx, course_id)
+		.await?
+		.into_iter()
+		.map(|calibration| calibration.into())
+		.collect();
+
+	Ok(Json(result))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/update_placement_test",
+	request_body = PlacementTestPayload,
+	responses(
+		(status = 200, description = "Placement test of the course saved successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_update_placement_test_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<PlacementTestPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.update_placement_test(&ctx, payload.into()).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/update_unlock_policy",
+	request_body = UnlockPolicyPayload,
+	responses(
+		(status = 200, description = "Unlock policy of the course saved successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_update_unlock_policy_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<UnlockPolicyPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	course_interactor.update_unlock_policy(&ctx, payload.try_into()?).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+/// Admin only.
+#[utoipa::path(
+	put,
+	path = "/api/course/reconcile_progress",
+	request_body = CourseId,
+	responses(
+		(status = 200, body = ProgressReconciliationPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_reconcile_progress_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(course_id): Json<CourseId>,
+) -> AppResult<Json<ProgressReconciliationPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	let progress_reconciliation = course_interactor.reconcile_progress(&ctx, course_id.course_id).await?;
+
+	Ok(Json(progress_reconciliation.into()))
+}
+
+/// Admin only.
+#[utoipa::path(
+	put,
+	path = "/api/course/collect_orphaned_uploads",
+	request_body = CollectOrphanedUploadsPayload,
+	responses(
+		(status = 200, body = UploadGcReportPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_collect_orphaned_uploads_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<CollectOrphanedUploadsPayload>,
+) -> AppResult<Json<UploadGcReportPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let course_interactor = CreatorCourseInteractor::new(command_repository_manager);
+
+	let report = course_interactor.collect_orphaned_uploads(&ctx, payload.dry_run).await?;
+
+	Ok(Json(report.into()))
 }
\ No newline at end of file
@@ -1,14 +1,20 @@
-use axum::{extract::State, routing::{post, put}, Json, Router};
-use lib_core::{interactors::creator::exercise::CreatorExerciseInteractor, models::exercise::{ExerciseForChangeOrder, ExerciseForCreate, ExerciseForUpdate}};
+use axum::{extract::{Path, State}, routing::{get, post, put}, Json, Router};
+use lib_core::{interactors::creator::exercise::CreatorExerciseInteractor, models::{exercise::{ConspectLayoutAlgorithm, ExerciseForChangeOrder, ExerciseForCreate, ExerciseForUpdate}, exercise_completion::ExerciseCompletionForReview}};
 use serde_json::{json, Value};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::exercise::{ExerciseChangeOrderPayload, ExerciseCreatePayload, ExerciseCreatedPayload, ExerciseForUpdatePayload}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{exercise::{ConspectDiffPayload, ExerciseBodyPayload, ExerciseChangeOrderPayload, ExerciseCompletionId, ExerciseCompletionReviewPayload, ExerciseCreatePayload, ExerciseCreatedPayload, ExerciseForUpdatePayload, ExerciseLayoutPayload}, exercise_completion::ExerciseCompletionPayload, similarity_flag::SimilarityComparisonPayload}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/create", post(api_create_exercise_handler))
 		.route("/update", put(api_update_exercise_handler))
 		.route("/change_order", put(api_exercise_change_order_handler))
+		.route("/layout_body", post(api_layout_exercise_body_handler))
+		.route("/get_student_attempt_diff/:i64", get(api_get_student_attempt_diff_handler))
+		.route("/get_review_queue/:i64", get(api_get_review_queue_handler))
+		.route("/get_similarity_flags/:i64", get(api_get_similarity_flags_handler))
+		.route("/send_to_review", put(api_send_to_review_handler))
+		.route("/review_attempt", put(api_review_attempt_handler))
 		.with_state(app_state)
 }
 
@@ -37,6 +43,10 @@ async fn api_create_exercise_handler(
         exercise_type: payload.exercise_type.try_into()?, 
         difficult: payload.difficult.try_into()?, 
         time_to_complete: payload.time_to_complete,
+        manual_review: payload.manual_review,
+        auto_difficulty: payload.auto_difficulty,
+        peer_review_count: payload.peer_review_count,
+        peer_review_rubric: payload.peer_review_rubric,
 		answer_body: payload.answer_body.clone(),
 		exercise_body: payload.exercise_body.clone(),
     };
@@ -93,6 +103,10 @@ async fn api_update_exercise_handler(
         answer_body: payload.answer_body.clone(), 
         difficult, 
         time_to_complete: payload.time_to_complete,
+        manual_review: payload.manual_review,
+        auto_difficulty: payload.auto_difficulty,
+        peer_review_count: payload.peer_review_count,
+        peer_review_rubric: payload.peer_review_rubric,
     };
 
 	let command_repository_manager = app_state.command_repository_manager;
@@ -144,4 +158,202 @@ async fn api_exercise_change_order_handler(
 	}));
 
     Ok(body)
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/lesson/exercise/layout_body",
+	request_body = ExerciseLayoutPayload,
+	responses(
+		(status = 200, description = "Body with computed node positions", body = ExerciseBodyPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_layout_exercise_body_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<ExerciseLayoutPayload>,
+) -> AppResult<Json<ExerciseBodyPayload>> {
+    let ctx = ctx.0;
+
+    let algorithm = match payload.algorithm {
+        Some(algorithm) => algorithm.try_into()?,
+        None => ConspectLayoutAlgorithm::Auto,
+    };
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    let body = exercise_interactor
+        .layout_exercise_body(&ctx, payload.lesson_id, &payload.exercise_type.try_into()?, payload.body, algorithm)
+        .await?;
+
+    Ok(Json(ExerciseBodyPayload { body }))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/get_student_attempt_diff/{exercise_completion_id}",
+	params(
+		("exercise_completion_id", description = "ID of the student's exercise attempt")
+	),
+	responses(
+		(status = 200, body = ConspectDiffPayload, description = "Diff between the attempt and the answer"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_student_attempt_diff_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(exercise_completion_id): Path<i64>,
+) -> AppResult<Json<ConspectDiffPayload>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    let result = exercise_interactor.get_exercise_completion_diff(&ctx, exercise_completion_id).await?;
+
+    Ok(Json(result.into()))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/get_review_queue/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Vec<ExerciseCompletionPayload>, description = "Attempts waiting for manual review"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_review_queue_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<ExerciseCompletionPayload>>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    let result = exercise_interactor
+        .get_review_queue(&ctx, course_id)
+        .await?
+        .into_iter()
+        .map(|ex_comp| ex_comp.into())
+        .collect();
+
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/get_similarity_flags/{exercise_id}",
+	params(
+		("exercise_id", description = "ID of the exercise")
+	),
+	responses(
+		(status = 200, body = Vec<SimilarityComparisonPayload>, description = "Pairs of suspiciously similar attempts, most similar first"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_similarity_flags_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(exercise_id): Path<i64>,
+) -> AppResult<Json<Vec<SimilarityComparisonPayload>>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    let result = exercise_interactor
+        .get_similarity_flags(&ctx, exercise_id)
+        .await?
+        .into_iter()
+        .map(|comparison| comparison.into())
+        .collect();
+
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/exercise/send_to_review",
+	request_body = ExerciseCompletionId,
+	responses(
+		(status = 200, description = "Attempt sent to manual review"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_send_to_review_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<ExerciseCompletionId>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    exercise_interactor.send_exercise_completion_to_review(&ctx, payload.exercise_completion_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/exercise/review_attempt",
+	request_body = ExerciseCompletionReviewPayload,
+	responses(
+		(status = 200, description = "Attempt reviewed successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_review_attempt_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<ExerciseCompletionReviewPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+    let review = ExerciseCompletionForReview {
+        id: payload.exercise_completion_id,
+        points_scored: payload.points_scored,
+        comment: payload.comment,
+        approved: payload.approved,
+    };
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let exercise_interactor = CreatorExerciseInteractor::new(command_repository_manager);
+
+    exercise_interactor.review_exercise_completion(&ctx, review).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
-use axum::{extract::State, routing::{delete, post, put}, Json, Router};
-use lib_core::{interactors::creator::lesson::CreatorLessonInteractor, models::lesson::{LessonForChangeOreder, LessonForCreate, LessonForUpdate}};
+use axum::{extract::{DefaultBodyLimit, Multipart, Path, State}, routing::{delete, get, post, put}, Json, Router};
+use lib_core::{interactors::creator::{attachment::CreatorAttachmentInteractor, lesson::CreatorLessonInteractor}, models::{attachment::{AttachmentForCreate, MAX_ATTACHMENT_SIZE}, exam::ExamForUpdate, lesson::{LessonForChangeOreder, LessonForChangeSection, LessonForCreate, LessonForUpdate}}};
 use serde_json::{json, Value};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::lesson::{LessonChangeOrderPayload, LessonCreatePayload, LessonCreatedPayload, LessonDeletePayload, LessonUpdatePayload}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{attachment::{AttachmentCreatedPayload, AttachmentDeletePayload}, lesson::{ExamReleaseResultsPayload, ExamRemovePayload, ExamUpdatePayload, LessonChangeOrderPayload, LessonChangeSectionPayload, LessonCreatePayload, LessonCreatedPayload, LessonDeletePayload, LessonPreviewPayload, LessonUpdatePayload}}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
@@ -10,6 +10,17 @@ pub fn routes(app_state: AppState) -> Router {
 		.route("/delete", delete(api_delete_lesson_handler))
 		.route("/update", put(api_update_lesson_handler))
 		.route("/change_order", put(api_lesson_change_order_handler))
+		.route("/change_section", put(api_lesson_change_section_handler))
+		.route("/update_exam", put(api_update_exam_handler))
+		.route("/remove_exam", delete(api_remove_exam_handler))
+		.route("/release_exam_results", put(api_release_exam_results_handler))
+		.route("/preview_lesson/:i64", get(api_preview_lesson_handler))
+		.route(
+			"/upload_attachment/:i64", 
+			// The multipart envelope is allowed on top of the file itself.
+			put(api_upload_attachment_handler).layer(DefaultBodyLimit::max(MAX_ATTACHMENT_SIZE + 64 * 1024))
+		)
+		.route("/delete_attachment", delete(api_delete_attachment_handler))
 		.with_state(app_state)
 }
 
@@ -33,6 +44,7 @@ async fn api_create_lesson_handler(
 
     let lesson_c = LessonForCreate {
         course_id: paylod.course_id,
+		section_id: paylod.section_id,
         title: paylod.title,
 		description: paylod.description,
     };
@@ -156,4 +168,256 @@ async fn api_lesson_change_order_handler(
 	}));
 
     Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/change_section",
+	request_body = LessonChangeSectionPayload,
+	responses(
+		(status = 200, description = "Lesson moved to the end of the section successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_lesson_change_section_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<LessonChangeSectionPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+    let lesson_c_s = LessonForChangeSection {
+        id: paylod.lesson_id,
+		section_id: paylod.section_id,
+    };
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = CreatorLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.change_section(&ctx, lesson_c_s).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+    Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/update_exam",
+	request_body = ExamUpdatePayload,
+	responses(
+		(status = 200, description = "Lesson turned into an exam or exam window updated successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_update_exam_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ExamUpdatePayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+    let exam_u = ExamForUpdate {
+        lesson_id: paylod.lesson_id,
+        opens_at: paylod.opens_at,
+        closes_at: paylod.closes_at,
+    };
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = CreatorLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.update_exam(&ctx, exam_u).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+    Ok(body)
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/lesson/remove_exam",
+	request_body = ExamRemovePayload,
+	responses(
+		(status = 200, description = "Exam turned back into a regular lesson"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_remove_exam_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ExamRemovePayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = CreatorLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.remove_exam(&ctx, paylod.lesson_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+    Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/release_exam_results",
+	request_body = ExamReleaseResultsPayload,
+	responses(
+		(status = 200, description = "Exam results released successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_release_exam_results_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ExamReleaseResultsPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = CreatorLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.release_exam_results(&ctx, paylod.lesson_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+    Ok(body)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/preview_lesson/{lesson_id}",
+	params(
+		("lesson_id", description = "ID of the lesson")
+	),
+	responses(
+		(status = 200, body = LessonPreviewPayload, description = "The lesson as students will see it once the course revision is published"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_preview_lesson_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(lesson_id): Path<i64>,
+) -> AppResult<Json<LessonPreviewPayload>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = CreatorLessonInteractor::new(command_repository_manager);
+
+    let preview = lesson_interactor.preview_lesson(&ctx, lesson_id).await?;
+
+    Ok(Json(preview.into()))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/upload_attachment/{lesson_id}",
+	params(
+		("lesson_id", description = "ID of the lesson the file is attached to")
+	),
+	request_body(content_type = "multipart/formdata", content = Vec<u8>, description = "PDF, slides or a document in the `file` field, up to 20 MB"),
+	responses(
+		(status = 200, description = "Attachment uploaded successfully", body = AttachmentCreatedPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_upload_attachment_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(lesson_id): Path<i64>,
+	mut multipart: Multipart,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let attachment_interactor = CreatorAttachmentInteractor::new(command_repository_manager);
+
+	while let Some(field) = multipart.next_field().await? {
+		if field.name() != Some("file") {
+			continue;
+		}
+
+		let attachment_c = AttachmentForCreate {
+			lesson_id,
+			file_name: field.file_name().unwrap_or_default().to_string(),
+			content_type: field.content_type().unwrap_or_default().to_string(),
+		};
+
+		let data = field.bytes().await?;
+		let attachment_id = attachment_interactor.upload_attachment(&ctx, attachment_c, &data).await?;
+
+		let body = Json(json!(AttachmentCreatedPayload { attachment_id }));
+
+		return Ok(body);
+	}
+
+	let body = Json(json!({
+		"result": {
+			"error": "file error"
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/lesson/delete_attachment",
+	request_body = AttachmentDeletePayload,
+	responses(
+		(status = 200, description = "Attachment deleted successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_delete_attachment_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<AttachmentDeletePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let attachment_interactor = CreatorAttachmentInteractor::new(command_repository_manager);
+
+	attachment_interactor.delete_attachment(&ctx, paylod.attachment_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
 pub mod course;
 pub mod lesson;
-pub mod exercise;
\ No newline at end of file
+pub mod exercise;
+pub mod section;
+pub mod content_block;
\ No newline at end of file
@@ -0,0 +1,153 @@
+use axum::{extract::State, routing::{delete, post, put}, Json, Router};
+use lib_core::{interactors::creator::section::CreatorSectionInteractor, models::section::{SectionForChangeOrder, SectionForCreate, SectionForUpdate}};
+use serde_json::{json, Value};
+
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::section::{SectionChangeOrderPayload, SectionCreatePayload, SectionCreatedPayload, SectionDeletePayload, SectionUpdatePayload}};
+
+pub fn routes(app_state: AppState) -> Router {
+	Router::new()
+		.route("/create", post(api_create_section_handler))
+		.route("/delete", delete(api_delete_section_handler))
+		.route("/update", put(api_update_section_handler))
+		.route("/change_order", put(api_section_change_order_handler))
+		.with_state(app_state)
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/section/create",
+	request_body = SectionCreatePayload,
+	responses(
+		(status = 200, description = "Section created successfully", body = SectionCreatedPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_create_section_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<SectionCreatePayload>,
+) -> AppResult<Json<SectionCreatedPayload>> {
+	let ctx = ctx.0;
+
+	let section_c = SectionForCreate {
+		course_id: paylod.course_id,
+		title: paylod.title,
+		description: paylod.description,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let section_interactor = CreatorSectionInteractor::new(command_repository_manager);
+
+	let section_id = section_interactor.create_section(&ctx, section_c).await?;
+
+	Ok(Json(SectionCreatedPayload { section_id }))
+}
+
+#[utoipa::path(
+	delete,
+	path = "/api/course/section/delete",
+	request_body = SectionDeletePayload,
+	responses(
+		(status = 200, description = "Empty section deleted successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_delete_section_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<SectionDeletePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let section_interactor = CreatorSectionInteractor::new(command_repository_manager);
+
+	section_interactor.delete_section(&ctx, paylod.section_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/section/update",
+	request_body = SectionUpdatePayload,
+	responses(
+		(status = 200, description = "Section updated successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_update_section_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<SectionUpdatePayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let section_u = SectionForUpdate {
+		id: paylod.section_id,
+		title: paylod.title,
+		description: paylod.description,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let section_interactor = CreatorSectionInteractor::new(command_repository_manager);
+
+	section_interactor.update_section(&ctx, section_u).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/section/change_order",
+	request_body = SectionChangeOrderPayload,
+	responses(
+		(status = 200, description = "Section moved with its lessons successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_section_change_order_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<SectionChangeOrderPayload>,
+) -> AppResult<Json<Value>> {
+	let ctx = ctx.0;
+
+	let section_c_o = SectionForChangeOrder {
+		id: paylod.section_id,
+		order: paylod.order,
+	};
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let section_interactor = CreatorSectionInteractor::new(command_repository_manager);
+
+	section_interactor.change_order(&ctx, section_c_o).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
@@ -0,0 +1,44 @@
+use lib_db::query_repository::lesson::AttachmentData;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentCreatedPayload {
+    pub attachment_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AttachmentDeletePayload {
+    pub attachment_id: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentPayload {
+    pub id: i64,
+    pub lesson_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    /// Bytes
+    pub size: i64,
+}
+
+impl From<AttachmentData> for AttachmentPayload {
+    fn from(attachment_data: AttachmentData) -> Self {
+        Self {
+            id: attachment_data.id,
+            lesson_id: attachment_data.lesson_id,
+            file_name: attachment_data.file_name,
+            content_type: attachment_data.content_type,
+            size: attachment_data.size,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentLinkPayload {
+    /// Download link for the current user only
+    pub url: String,
+    /// Rfc3339
+    pub expires_at: String,
+}
@@ -0,0 +1,72 @@
+use lib_core::models::content_block::ContentBlock;
+use lib_db::query_repository::lesson::ContentBlockData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentBlockCreatedPayload {
+    pub content_block_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ContentBlockCreatePayload {
+    pub lesson_id: i64,
+    /// Markdown, Image, Video, Attachment or Callout
+    pub block_type: String,
+    /// Markdown: { text }, Image: { url, alt }, Video: { url }, 
+    /// Attachment: { url, file_name }, Callout: { style: Info | Tip | Warning, text }
+    pub body: Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ContentBlockDeletePayload {
+    pub content_block_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ContentBlockUpdatePayload {
+    pub content_block_id: i64,
+    pub body: Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ContentBlockChangeOrderPayload {
+    pub content_block_id: i64,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentBlockPayload {
+    pub id: i64,
+    pub block_type: String,
+    pub body: Value,
+    /// Sanitized HTML of Markdown and Callout blocks, empty for the others
+    pub html: String,
+    pub block_order: i32,
+}
+
+impl From<ContentBlockData> for ContentBlockPayload {
+    fn from(content_block_data: ContentBlockData) -> Self {
+        Self {
+            id: content_block_data.id,
+            block_type: content_block_data.block_type,
+            body: content_block_data.body,
+            html: content_block_data.rendered_html,
+            block_order: content_block_data.block_order,
+        }
+    }
+}
+
+impl From<ContentBlock> for ContentBlockPayload {
+    fn from(content_block: ContentBlock) -> Self {
+        Self {
+            id: content_block.id,
+            block_type: content_block.block_type.to_string(),
+            body: content_block.body,
+            html: content_block.rendered_html,
+            block_order: content_block.block_order,
+        }
+    }
+}
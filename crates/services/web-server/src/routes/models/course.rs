@@ -1,6 +1,7 @@
-use lib_core::interactors::error::{CoreError, CourseError};
-use lib_db::query_repository::{course::CourseQuery, exercise::{CoursePointStatistics, UserPoints}};
+use lib_core::{interactors::error::{CoreError, CourseError}, models::{course::{CourseSchedule, CourseStaffForUpdate, UserCourse, UserCourseRole}, course_image::{CourseImageUrls, CourseImageVariants}, course_revision::{CourseRevision, StagedEdit}, course_transfer::CourseTransfer, placement_test::{PlacementAnswer, PlacementLevel, PlacementResult, PlacementTest}, publish_readiness::{PublishReadiness, ReadinessIssue}, unlock_policy::{DripRelease, LessonRelease, UnlockPolicy}}};
+use lib_db::query_repository::{course::{CourseQuery, PlacementTestData, UnlockPolicyData}, exercise::{CoursePointStatistics, UserPoints}};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serde_with::serde_as;
 use utoipa::{IntoParams, ToSchema};
 
@@ -34,6 +35,107 @@ pub struct CourseId {
 	pub course_id: i64,
 }
 
+#[derive(ToSchema, Deserialize)]
+pub struct CourseSchedulePayload {
+	pub course_id: i64,
+	/// Unix seconds, the course is published at this date
+	pub publish_date: Option<i64>,
+	/// Unix seconds, the course is archived at this date
+	pub archive_date: Option<i64>,
+}
+
+impl From<CourseSchedulePayload> for CourseSchedule {
+	fn from(value: CourseSchedulePayload) -> Self {
+		Self {
+			course_id: value.course_id,
+			publish_date: value.publish_date,
+			archive_date: value.archive_date,
+		}
+	}
+}
+
+#[derive(ToSchema, Deserialize)]
+pub struct CourseStaffPayload {
+	pub course_id: i64,
+	pub user_id: i64,
+	/// CoAuthor or Assistant
+	pub user_role: String,
+}
+
+impl TryFrom<CourseStaffPayload> for CourseStaffForUpdate {
+	type Error = CoreError;
+
+	fn try_from(value: CourseStaffPayload) -> Result<Self, Self::Error> {
+		Ok(Self {
+			course_id: value.course_id,
+			user_id: value.user_id,
+			user_role: UserCourseRole::try_from(value.user_role)?,
+		})
+	}
+}
+
+#[derive(ToSchema, Deserialize)]
+pub struct CourseStaffMemberId {
+	pub course_id: i64,
+	pub user_id: i64,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct CourseStaffMemberPayload {
+	pub user_id: i64,
+	pub user_role: String,
+	pub date_registered: i64,
+}
+
+impl From<UserCourse> for CourseStaffMemberPayload {
+	fn from(value: UserCourse) -> Self {
+		Self {
+			user_id: value.user_id,
+			user_role: value.user_role.to_string(),
+			date_registered: value.date_registered,
+		}
+	}
+}
+
+#[derive(ToSchema, Deserialize)]
+pub struct CourseTransferProposalPayload {
+	pub course_id: i64,
+	pub to_user_id: i64,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct CourseTransferId {
+	pub transfer_id: i64,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct CourseTransferPayload {
+	pub id: i64,
+	pub course_id: i64,
+	pub from_user_id: i64,
+	pub to_user_id: i64,
+	/// Pending, Accepted, Declined or Cancelled
+	pub state: String,
+	pub forced: bool,
+	pub date_proposed: i64,
+	pub date_resolved: Option<i64>,
+}
+
+impl From<CourseTransfer> for CourseTransferPayload {
+	fn from(value: CourseTransfer) -> Self {
+		Self {
+			id: value.id,
+			course_id: value.course_id,
+			from_user_id: value.from_user_id,
+			to_user_id: value.to_user_id,
+			state: value.state.to_string(),
+			forced: value.forced,
+			date_proposed: value.date_proposed,
+			date_resolved: value.date_resolved,
+		}
+	}
+}
+
 #[derive(ToSchema, Serialize)]
 pub struct CoursesPayload {
 	pub courses: Vec<CoursePayload>,
@@ -51,7 +153,42 @@ pub struct CoursePayload {
 	pub color: String,
 	pub published_date: Option<i64>,
 	pub img_url: Option<String>,
+	pub img_variants: Option<CourseImageVariantsPayload>,
 	pub state: CourseStatePayload,
+	pub scheduled_publish_date: Option<i64>,
+	pub scheduled_archive_date: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CourseImageUrlsPayload {
+	pub webp: String,
+	pub png: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CourseImageVariantsPayload {
+	pub thumbnail: CourseImageUrlsPayload,
+	pub card: CourseImageUrlsPayload,
+	pub hero: CourseImageUrlsPayload,
+}
+
+impl From<CourseImageUrls> for CourseImageUrlsPayload {
+	fn from(value: CourseImageUrls) -> Self {
+		Self {
+			webp: value.webp,
+			png: value.png,
+		}
+	}
+}
+
+impl From<CourseImageVariants> for CourseImageVariantsPayload {
+	fn from(value: CourseImageVariants) -> Self {
+		Self {
+			thumbnail: value.thumbnail.into(),
+			card: value.card.into(),
+			hero: value.hero.into(),
+		}
+	}
 }
 
 #[serde_as]
@@ -80,6 +217,7 @@ impl TryFrom<CourseQuery> for CoursePayload {
 	
 	fn try_from(value: CourseQuery) -> Result<Self, Self::Error> {
 		let published_date = value.published_date.and_then(|date| Some(date.unix_timestamp()));
+		let img_variants = value.img_variants.map(serde_json::from_value).transpose()?;
 
 		let result = Self {
     		id: value.id,
@@ -90,7 +228,10 @@ impl TryFrom<CourseQuery> for CoursePayload {
     		color: value.color,
     		published_date,
     		img_url: value.img_url,
+    		img_variants,
     		state: value.state.try_into()?,
+    		scheduled_publish_date: value.scheduled_publish_date.map(|date| date.unix_timestamp()),
+    		scheduled_archive_date: value.scheduled_archive_date.map(|date| date.unix_timestamp()),
 		};
 
 		Ok(result)
@@ -135,4 +276,265 @@ impl From<CoursePointStatistics> for CoursePointStatisticsPayload {
     		users_points: value.users_points.iter().map(|user_points| user_points.into()).collect(),
 		}
 	}
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PlacementLevelPayload {
+	/// Share of the points, `(0, 1]`
+	pub min_score: f32,
+	pub lessons_to_skip: i32,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PlacementTestPayload {
+	pub course_id: i64,
+	pub exercise_ids: Vec<i64>,
+	pub levels: Vec<PlacementLevelPayload>,
+}
+
+impl From<PlacementTestPayload> for PlacementTest {
+	fn from(value: PlacementTestPayload) -> Self {
+		Self {
+			course_id: value.course_id,
+			exercise_ids: value.exercise_ids,
+			levels: value.levels
+				.into_iter()
+				.map(|level| PlacementLevel { min_score: level.min_score, lessons_to_skip: level.lessons_to_skip })
+				.collect(),
+		}
+	}
+}
+
+impl TryFrom<PlacementTestData> for PlacementTestPayload {
+	type Error = CoreError;
+
+	fn try_from(value: PlacementTestData) -> Result<Self, Self::Error> {
+		Ok(Self {
+			course_id: value.course_id,
+			exercise_ids: serde_json::from_value(value.exercise_ids)?,
+			levels: serde_json::from_value(value.levels)?,
+		})
+	}
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PlacementAnswerPayload {
+	pub exercise_id: i64,
+	pub body: Value,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PlacementTestTakePayload {
+	pub course_id: i64,
+	pub answers: Vec<PlacementAnswerPayload>,
+}
+
+impl From<PlacementAnswerPayload> for PlacementAnswer {
+	fn from(value: PlacementAnswerPayload) -> Self {
+		Self {
+			exercise_id: value.exercise_id,
+			body: value.body,
+		}
+	}
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PlacementResultPayload {
+	pub score: f32,
+	pub skipped_lesson_ids: Vec<i64>,
+}
+
+impl From<PlacementResult> for PlacementResultPayload {
+	fn from(value: PlacementResult) -> Self {
+		Self {
+			score: value.score,
+			skipped_lesson_ids: value.skipped_lesson_ids,
+		}
+	}
+}
+
+/// Exactly one of `days_after_enrollment` and `date` is set.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DripReleasePayload {
+	pub lesson_id: i64,
+	pub days_after_enrollment: Option<i32>,
+	/// Unix timestamp
+	pub date: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct UnlockPolicyPayload {
+	pub course_id: i64,
+	/// Sequential, Free or Drip
+	pub policy: String,
+	#[serde(default)]
+	pub drip_releases: Vec<DripReleasePayload>,
+}
+
+impl TryFrom<DripReleasePayload> for DripRelease {
+	type Error = CoreError;
+
+	fn try_from(value: DripReleasePayload) -> Result<Self, Self::Error> {
+		let release = match (value.days_after_enrollment, value.date) {
+			(Some(days), None) => LessonRelease::DaysAfterEnrollment { days },
+			(None, Some(date)) => LessonRelease::Date { date },
+			_ => return Err(CourseError::IncorrectDripRelease { lesson_id: value.lesson_id }.into()),
+		};
+
+		Ok(Self {
+			lesson_id: value.lesson_id,
+			release,
+		})
+	}
+}
+
+impl From<DripRelease> for DripReleasePayload {
+	fn from(value: DripRelease) -> Self {
+		let (days_after_enrollment, date) = match value.release {
+			LessonRelease::DaysAfterEnrollment { days } => (Some(days), None),
+			LessonRelease::Date { date } => (None, Some(date)),
+		};
+
+		Self {
+			lesson_id: value.lesson_id,
+			days_after_enrollment,
+			date,
+		}
+	}
+}
+
+impl TryFrom<UnlockPolicyPayload> for UnlockPolicy {
+	type Error = CoreError;
+
+	fn try_from(value: UnlockPolicyPayload) -> Result<Self, Self::Error> {
+		let mut drip_releases = Vec::new();
+		for drip_release in value.drip_releases {
+			drip_releases.push(drip_release.try_into()?);
+		}
+
+		Ok(Self {
+			course_id: value.course_id,
+			kind: value.policy.try_into()?,
+			drip_releases,
+		})
+	}
+}
+
+impl TryFrom<UnlockPolicyData> for UnlockPolicyPayload {
+	type Error = CoreError;
+
+	fn try_from(value: UnlockPolicyData) -> Result<Self, Self::Error> {
+		let drip_releases: Vec<DripRelease> = serde_json::from_value(value.drip_releases)?;
+
+		Ok(Self {
+			course_id: value.course_id,
+			policy: value.policy,
+			drip_releases: drip_releases.into_iter().map(Into::into).collect(),
+		})
+	}
+}
+
+impl From<UnlockPolicy> for UnlockPolicyPayload {
+	fn from(value: UnlockPolicy) -> Self {
+		Self {
+			course_id: value.course_id,
+			policy: value.kind.to_string(),
+			drip_releases: value.drip_releases.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessIssuePayload {
+	/// missing_description, missing_image, no_lessons, empty_lesson, invalid_exercise or empty_section
+	pub code: String,
+	pub section_id: Option<i64>,
+	pub lesson_id: Option<i64>,
+	pub exercise_id: Option<i64>,
+	pub message: String,
+}
+
+impl From<ReadinessIssue> for ReadinessIssuePayload {
+	fn from(value: ReadinessIssue) -> Self {
+		Self {
+			code: value.kind.code().to_string(),
+			section_id: value.section_id,
+			lesson_id: value.lesson_id,
+			exercise_id: value.exercise_id,
+			message: value.message,
+		}
+	}
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PublishReadinessPayload {
+	pub course_id: i64,
+	/// The course can be published, warnings do not block it
+	pub ready: bool,
+	pub errors: Vec<ReadinessIssuePayload>,
+	pub warnings: Vec<ReadinessIssuePayload>,
+}
+
+impl From<PublishReadiness> for PublishReadinessPayload {
+	fn from(value: PublishReadiness) -> Self {
+		Self {
+			course_id: value.course_id,
+			ready: value.is_ready(),
+			errors: value.errors.into_iter().map(Into::into).collect(),
+			warnings: value.warnings.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StagedEditPayload {
+	/// section, lesson, exam, exercise or content_block
+	pub kind: String,
+	pub entity_id: i64,
+	/// Null for section edits
+	pub lesson_id: Option<i64>,
+	/// Attempts of the exercise are removed on publishing
+	pub retake: bool,
+	/// The staged fields, unchanged exercise fields are null
+	pub changes: Value,
+}
+
+impl TryFrom<StagedEdit> for StagedEditPayload {
+	type Error = CoreError;
+
+	fn try_from(value: StagedEdit) -> Result<Self, Self::Error> {
+		let (kind, entity_id, lesson_id, retake, changes) = match value {
+			StagedEdit::Lesson(edit) => ("lesson", edit.lesson_id, Some(edit.lesson_id), false, serde_json::to_value(edit)?),
+			StagedEdit::Exercise(edit) => ("exercise", edit.exercise_id, Some(edit.lesson_id), edit.retake, serde_json::to_value(edit)?),
+			StagedEdit::ContentBlock(edit) => ("content_block", edit.content_block_id, Some(edit.lesson_id), false, serde_json::to_value(edit)?),
+			StagedEdit::Section(edit) => ("section", edit.section_id, None, false, serde_json::to_value(edit)?),
+			StagedEdit::Exam(edit) => ("exam", edit.lesson_id, Some(edit.lesson_id), false, serde_json::to_value(edit)?),
+		};
+
+		Ok(Self {
+			kind: kind.to_string(),
+			entity_id,
+			lesson_id,
+			retake,
+			changes,
+		})
+	}
+}
+
+/// Edits of the published course students don't see yet.
+#[derive(Serialize, ToSchema)]
+pub struct CourseRevisionPayload {
+	pub course_id: i64,
+	pub edits: Vec<StagedEditPayload>,
+}
+
+impl TryFrom<CourseRevision> for CourseRevisionPayload {
+	type Error = CoreError;
+
+	fn try_from(value: CourseRevision) -> Result<Self, Self::Error> {
+		Ok(Self {
+			course_id: value.course_id,
+			edits: value.edits.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+		})
+	}
+}
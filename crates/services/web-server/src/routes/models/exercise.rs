@@ -1,4 +1,4 @@
-use lib_core::models::exercise::ExerciseEstimate;
+use lib_core::models::{difficulty_calibration::{DifficultyCalibration, DifficultyChange}, exercise::{ConnectionDiff, ConnectionMistake, ConspectDiff, Exercise, ExerciseEstimate, ExerciseMistakeStatistics, NodeDiff, NodeMistake, StageDiff, StageMistake}};
 use lib_db::query_repository::exercise::ExerciseQuery;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -16,6 +16,10 @@ pub struct ExercisePayload {
     pub exercise_order: i32,
     pub difficult: String,
     pub time_to_complete: Option<i32>,  
+    pub manual_review: bool,
+    pub auto_difficulty: bool,
+    pub peer_review_count: i32,
+    pub peer_review_rubric: Value,
 }
 
 impl From<ExerciseQuery> for ExercisePayload {
@@ -31,6 +35,31 @@ impl From<ExerciseQuery> for ExercisePayload {
             exercise_order: value.exercise_order,
             difficult: value.difficult,
             time_to_complete: value.time_to_complete,
+            manual_review: value.manual_review,
+            auto_difficulty: value.auto_difficulty,
+            peer_review_count: value.peer_review_count,
+            peer_review_rubric: value.peer_review_rubric,
+        }
+    }
+}
+
+impl From<(i64, Exercise)> for ExercisePayload {
+    fn from((exercise_id, exercise): (i64, Exercise)) -> Self {
+        Self {
+            exercise_id,
+            lesson_id: exercise.lesson_id,
+            title: exercise.title,
+            description: exercise.description,
+            exercise_type: exercise.exercise_type.to_string(),
+            exercise_body: exercise.exercise_body,
+            answer_body: exercise.answer_body,
+            exercise_order: exercise.exercise_order,
+            difficult: exercise.difficult.to_string(),
+            time_to_complete: exercise.time_to_complete,
+            manual_review: exercise.manual_review,
+            auto_difficulty: exercise.auto_difficulty,
+            peer_review_count: exercise.peer_review_count,
+            peer_review_rubric: exercise.peer_review_rubric,
         }
     }
 }
@@ -45,6 +74,21 @@ pub struct ExerciseCreatePayload {
     pub answer_body: Value,
     pub difficult: String,
     pub time_to_complete: Option<i32>,  
+    #[serde(default)]
+    pub manual_review: bool,
+    /// Lets the calibration job apply the observed difficulty.
+    #[serde(default)]
+    pub auto_difficulty: bool,
+    /// Number of classmates' attempts every student reviews, 0 disables peer review.
+    #[serde(default)]
+    pub peer_review_count: i32,
+    /// List of `{ "name": String, "max_points": f32 }` criteria.
+    #[serde(default = "empty_rubric")]
+    pub peer_review_rubric: Value,
+}
+
+fn empty_rubric() -> Value {
+    Value::Array(Vec::new())
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -62,6 +106,10 @@ pub struct ExerciseForUpdatePayload {
     pub answer_body: Option<Value>,
     pub difficult: Option<String>,
     pub time_to_complete: Option<i64>,  
+    pub manual_review: Option<bool>,
+    pub auto_difficulty: Option<bool>,
+    pub peer_review_count: Option<i32>,
+    pub peer_review_rubric: Option<Value>,
     pub is_retake_exercise: bool,
 }
 
@@ -72,8 +120,25 @@ pub struct ExerciseChangeOrderPayload {
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct ExerciseId {
+pub struct ExerciseLayoutPayload {
+    pub lesson_id: i64,
+    pub exercise_type: String,
+    pub body: Value,
+    /// One of `Auto`, `Layered`, `ForceDirected`. Defaults to `Auto`.
+    pub algorithm: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExerciseBodyPayload {
+    pub body: Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExerciseForStart {
     pub exercise_id: i64,
+    /// Practice attempts don't affect grades, available once the exercise is passed.
+    #[serde(default)]
+    pub is_practice: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -87,6 +152,14 @@ pub struct ExerciseCompletionForSaveChanges {
     pub body: Value,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExerciseCompletionReviewPayload {
+    pub exercise_completion_id: i64,
+    pub points_scored: f32,
+    pub comment: Option<String>,
+    pub approved: bool,
+}
+
 #[derive(Serialize, ToSchema)] 
 pub struct ExerciseEstimatePayload {
     pub points: f32,
@@ -104,4 +177,204 @@ impl From<ExerciseEstimate> for ExerciseEstimatePayload {
             state: value.state.to_string(),
         }
     }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConspectDiffPayload {
+    pub added_connections: Vec<ConnectionDiffPayload>,
+    pub removed_connections: Vec<ConnectionDiffPayload>,
+    pub nodes: Vec<NodeDiffPayload>,
+}
+
+impl From<ConspectDiff> for ConspectDiffPayload {
+    fn from(value: ConspectDiff) -> Self {
+        Self {
+            added_connections: value.added_connections.into_iter().map(|connection| connection.into()).collect(),
+            removed_connections: value.removed_connections.into_iter().map(|connection| connection.into()).collect(),
+            nodes: value.nodes.into_iter().map(|node| node.into()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConnectionDiffPayload {
+    pub from: String,
+    pub to: String,
+}
+
+impl From<ConnectionDiff> for ConnectionDiffPayload {
+    fn from(value: ConnectionDiff) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NodeDiffPayload {
+    pub id: String,
+    pub x: i64,
+    pub y: i64,
+    pub state: String,
+    pub answer_body: Option<Value>,
+    pub solution_body: Option<Value>,
+    pub stages: Vec<StageDiffPayload>,
+}
+
+impl From<NodeDiff> for NodeDiffPayload {
+    fn from(value: NodeDiff) -> Self {
+        Self {
+            id: value.id,
+            x: value.x,
+            y: value.y,
+            state: value.state.to_string(),
+            answer_body: value.answer_body,
+            solution_body: value.solution_body,
+            stages: value.stages.into_iter().map(|stage| stage.into()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StageDiffPayload {
+    pub position: usize,
+    pub answer_stage_id: Option<i64>,
+    pub solution_stage_id: Option<i64>,
+}
+
+impl From<StageDiff> for StageDiffPayload {
+    fn from(value: StageDiff) -> Self {
+        Self {
+            position: value.position,
+            answer_stage_id: value.answer_stage_id,
+            solution_stage_id: value.solution_stage_id,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ExerciseMistakeStatisticsPayload {
+    pub exercise_id: i64,
+    pub title: String,
+    pub number_of_completions: i64,
+    pub missing_connections: Vec<ConnectionMistakePayload>,
+    pub extra_connections: Vec<ConnectionMistakePayload>,
+    pub wrong_stage_positions: Vec<StageMistakePayload>,
+    pub blank_nodes: Vec<NodeMistakePayload>,
+}
+
+impl From<ExerciseMistakeStatistics> for ExerciseMistakeStatisticsPayload {
+    fn from(value: ExerciseMistakeStatistics) -> Self {
+        Self {
+            exercise_id: value.exercise_id,
+            title: value.title,
+            number_of_completions: value.number_of_completions,
+            missing_connections: value.missing_connections.into_iter().map(|mistake| mistake.into()).collect(),
+            extra_connections: value.extra_connections.into_iter().map(|mistake| mistake.into()).collect(),
+            wrong_stage_positions: value.wrong_stage_positions.into_iter().map(|mistake| mistake.into()).collect(),
+            blank_nodes: value.blank_nodes.into_iter().map(|mistake| mistake.into()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConnectionMistakePayload {
+    pub from: String,
+    pub to: String,
+    pub count: i64,
+}
+
+impl From<ConnectionMistake> for ConnectionMistakePayload {
+    fn from(value: ConnectionMistake) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StageMistakePayload {
+    pub node_id: String,
+    pub position: usize,
+    pub count: i64,
+}
+
+impl From<StageMistake> for StageMistakePayload {
+    fn from(value: StageMistake) -> Self {
+        Self {
+            node_id: value.node_id,
+            position: value.position,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NodeMistakePayload {
+    pub node_id: String,
+    pub count: i64,
+}
+
+impl From<NodeMistake> for NodeMistakePayload {
+    fn from(value: NodeMistake) -> Self {
+        Self {
+            node_id: value.node_id,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DifficultyCalibrationPayload {
+    pub exercise_id: i64,
+    pub title: String,
+    pub assigned_difficulty: String,
+    /// Missing for reading exercises and until enough students finished the exercise.
+    pub suggested_difficulty: Option<String>,
+    pub auto_difficulty: bool,
+    pub number_of_students: i64,
+    pub pass_rate: f32,
+    pub average_attempts: f32,
+    /// Seconds.
+    pub average_time_spent: Option<f32>,
+    pub history: Vec<DifficultyChangePayload>,
+}
+
+impl From<DifficultyCalibration> for DifficultyCalibrationPayload {
+    fn from(value: DifficultyCalibration) -> Self {
+        Self {
+            exercise_id: value.exercise_id,
+            title: value.title,
+            assigned_difficulty: value.assigned_difficulty.to_string(),
+            suggested_difficulty: value.suggested_difficulty.map(|difficulty| difficulty.to_string()),
+            auto_difficulty: value.auto_difficulty,
+            number_of_students: value.observations.number_of_students,
+            pass_rate: value.observations.pass_rate,
+            average_attempts: value.observations.average_attempts,
+            average_time_spent: value.observations.average_time_spent,
+            history: value.history.into_iter().map(|change| change.into()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DifficultyChangePayload {
+    pub old_difficulty: String,
+    pub new_difficulty: String,
+    pub pass_rate: f32,
+    pub date_changed: i64,
+}
+
+impl From<DifficultyChange> for DifficultyChangePayload {
+    fn from(value: DifficultyChange) -> Self {
+        Self {
+            old_difficulty: value.old_difficulty.to_string(),
+            new_difficulty: value.new_difficulty.to_string(),
+            pass_rate: value.pass_rate,
+            date_changed: value.date_changed,
+        }
+    }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use lib_core::models::exercise_completion::ExerciseCompletion;
 use lib_db::query_repository::exercise_completion::ExerciseCompletionQuery;
 use serde::Serialize;
 use utoipa::ToSchema;
@@ -14,6 +15,10 @@ pub struct ExerciseCompletionPayload {
     pub date_last_changes: Option<i64>,
     pub state: String,   
     pub body: Option<serde_json::Value>,
+    pub review_comment: Option<String>,
+    pub is_practice: bool,
+    /// Time the lesson spent paused while the attempt was running, added to the deadline
+    pub paused_seconds: i64,
 }
 
 impl From<ExerciseCompletionQuery> for ExerciseCompletionPayload {
@@ -29,6 +34,29 @@ impl From<ExerciseCompletionQuery> for ExerciseCompletionPayload {
             date_last_changes: value.date_last_changes.and_then(|date| Some(date.unix_timestamp())),
             state: value.state,
             body: value.body,
+            review_comment: value.review_comment,
+            is_practice: value.is_practice,
+            paused_seconds: value.paused_seconds,
+        }
+    }
+}
+
+impl From<ExerciseCompletion> for ExerciseCompletionPayload {
+    fn from(value: ExerciseCompletion) -> Self {
+        Self {
+            exercise_completion_id: value.id,
+            exercise_id: value.exercise_id,
+            user_id: value.user_id,
+            points_scored: value.points_scored,
+            max_points: value.max_points,
+            number_of_attempts: value.number_of_attempts,
+            date_started: value.date_started,
+            date_last_changes: value.date_last_changes,
+            state: value.state.to_string(),
+            body: value.body,
+            review_comment: value.review_comment,
+            is_practice: value.is_practice,
+            paused_seconds: value.paused_seconds,
         }
     }
 }
@@ -0,0 +1,33 @@
+use lib_core::models::file::UploadGcReport;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CollectOrphanedUploadsPayload {
+    /// Only report the orphaned files
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UploadGcReportPayload {
+    pub dry_run: bool,
+    pub scanned: usize,
+    pub referenced: usize,
+    /// Unreferenced files kept because of the grace period
+    pub recent: usize,
+    pub orphaned_keys: Vec<String>,
+    pub failed_keys: Vec<String>,
+}
+
+impl From<UploadGcReport> for UploadGcReportPayload {
+    fn from(value: UploadGcReport) -> Self {
+        Self {
+            dry_run: value.dry_run,
+            scanned: value.scanned,
+            referenced: value.referenced,
+            recent: value.recent,
+            orphaned_keys: value.orphaned_keys,
+            failed_keys: value.failed_keys,
+        }
+    }
+}
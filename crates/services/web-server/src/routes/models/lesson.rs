@@ -1,7 +1,10 @@
-use lib_db::query_repository::lesson::LessonData;
+use lib_core::models::course_revision::LessonPreview;
+use lib_db::query_repository::lesson::{ExamData, LessonData};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::{content_block::ContentBlockPayload, exercise::ExercisePayload};
+
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct LessonCreatedPayload {
@@ -16,6 +19,8 @@ pub struct StartLessonPayload {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LessonCreatePayload {
     pub course_id: i64,
+    /// Lessons without a section come before the sections
+    pub section_id: Option<i64>,
     pub title: String,
 	pub description: String,
 }
@@ -35,26 +40,116 @@ pub struct LessonUpdatePayload {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LessonChangeOrderPayload {
     pub lesson_id: i64,
+    /// Place within the section of the lesson
     pub order: i32,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LessonChangeSectionPayload {
+    pub lesson_id: i64,
+    /// null takes the lesson out of sections
+    pub section_id: Option<i64>,
+}
+
 #[derive(Debug, Serialize, ToSchema)] 
 pub struct LessonDataPayload {
 	pub id: i64,
 	pub course_id: i64,
+	pub section_id: Option<i64>,
     pub title: String,
     pub lesson_order: i32,
     pub description: String,
 }
 
+/// Lesson with its content blocks in order.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LessonContentPayload {
+    #[serde(flatten)]
+    pub lesson: LessonDataPayload,
+    pub content_blocks: Vec<ContentBlockPayload>,
+}
+
+/// Lesson with the staged edits of its course applied.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LessonPreviewPayload {
+	pub id: i64,
+	pub course_id: i64,
+	pub section_id: Option<i64>,
+    pub title: String,
+    pub lesson_order: i32,
+    /// Only set when the description has a staged edit
+    pub description: Option<String>,
+    pub content_blocks: Vec<ContentBlockPayload>,
+    pub exercises: Vec<ExercisePayload>,
+}
+
+impl From<LessonPreview> for LessonPreviewPayload {
+    fn from(preview: LessonPreview) -> Self {
+        Self {
+            id: preview.lesson.id,
+            course_id: preview.lesson.course_id,
+            section_id: preview.lesson.section_id,
+            title: preview.lesson.title,
+            lesson_order: preview.lesson.lesson_order,
+            description: preview.description,
+            content_blocks: preview.content_blocks.into_iter().map(Into::into).collect(),
+            exercises: preview.exercises.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 impl From<LessonData> for LessonDataPayload {
     fn from(lesson_data: LessonData) -> Self {
         Self {
             id: lesson_data.id,
             course_id: lesson_data.course_id,
+            section_id: lesson_data.section_id,
             title: lesson_data.title.clone(),
             lesson_order: lesson_data.lesson_order,
             description: lesson_data.description,
         }
     }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExamUpdatePayload {
+    pub lesson_id: i64,
+    /// Unix timestamp
+    pub opens_at: i64,
+    /// Unix timestamp
+    pub closes_at: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExamRemovePayload {
+    pub lesson_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExamReleaseResultsPayload {
+    pub lesson_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExamSubmitPayload {
+    pub lesson_id: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExamPayload {
+    pub lesson_id: i64,
+    pub opens_at: i64,
+    pub closes_at: i64,
+    pub results_released: bool,
+}
+
+impl From<ExamData> for ExamPayload {
+    fn from(exam_data: ExamData) -> Self {
+        Self {
+            lesson_id: exam_data.lesson_id,
+            opens_at: exam_data.opens_at.unix_timestamp(),
+            closes_at: exam_data.closes_at.unix_timestamp(),
+            results_released: exam_data.results_released,
+        }
+    }
 }
\ No newline at end of file
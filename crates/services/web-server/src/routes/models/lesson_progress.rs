@@ -1,3 +1,4 @@
+use lib_core::models::{lesson_progress::ProgressReconciliation, unlock_policy::LessonLock};
 use lib_db::query_repository::lesson_progress::LessonProgressData;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
@@ -16,6 +17,8 @@ pub struct LessonProgressPayload {
     pub date_complete: Option<i64>,  
 
     pub state: String,
+    pub skipped_by_placement: bool,
+    pub date_paused: Option<i64>,
 }
 
 impl From<&LessonProgressData> for LessonProgressPayload {
@@ -26,6 +29,44 @@ impl From<&LessonProgressData> for LessonProgressPayload {
             date_started: value.date_started.unix_timestamp(),
             date_complete: value.date_complete.and_then(|date| Some(date.unix_timestamp())),
             state: value.state.clone(),
+            skipped_by_placement: value.skipped_by_placement,
+            date_paused: value.date_paused.map(|date| date.unix_timestamp()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LessonLockPayload {
+    pub lesson_id: i64,
+    /// Release date of a drip lesson, null while the previous lesson isn't completed
+    pub locked_until: Option<i64>,
+}
+
+impl From<LessonLock> for LessonLockPayload {
+    fn from(value: LessonLock) -> Self {
+        Self {
+            lesson_id: value.lesson_id,
+            locked_until: value.locked_until,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProgressReconciliationPayload {
+    pub course_id: i64,
+    pub lessons_completed: i64,
+    pub lessons_reopened: i64,
+    /// Students with every lesson of the course done after the reconciliation
+    pub students_completed_course: i64,
+}
+
+impl From<ProgressReconciliation> for ProgressReconciliationPayload {
+    fn from(value: ProgressReconciliation) -> Self {
+        Self {
+            course_id: value.course_id,
+            lessons_completed: value.lessons_completed,
+            lessons_reopened: value.lessons_reopened,
+            students_completed_course: value.students_completed_course,
         }
     }
 }
\ No newline at end of file
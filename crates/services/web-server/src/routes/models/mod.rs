@@ -3,4 +3,10 @@ pub mod lesson;
 pub mod user;
 pub mod lesson_progress;
 pub mod exercise;
-pub mod exercise_completion;
\ No newline at end of file
+pub mod exercise_completion;
+pub mod peer_review;
+pub mod similarity_flag;
+pub mod section;
+pub mod content_block;
+pub mod attachment;
+pub mod file;
\ No newline at end of file
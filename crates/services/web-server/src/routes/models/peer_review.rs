@@ -0,0 +1,33 @@
+use lib_core::models::peer_review::PeerReviewAssignment;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct PeerReviewAssignmentPayload {
+    pub peer_review_id: i64,
+    pub exercise_id: i64,
+    pub body: Option<Value>,
+    pub rubric: Value,
+    pub state: String,
+}
+
+impl From<PeerReviewAssignment> for PeerReviewAssignmentPayload {
+    fn from(value: PeerReviewAssignment) -> Self {
+        Self {
+            peer_review_id: value.peer_review_id,
+            exercise_id: value.exercise_id,
+            body: value.body,
+            rubric: value.rubric,
+            state: value.state.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PeerReviewSubmitPayload {
+    pub peer_review_id: i64,
+    /// Points for every rubric criterion, in rubric order.
+    pub rubric_scores: Vec<f32>,
+    pub comment: Option<String>,
+}
@@ -0,0 +1,55 @@
+use lib_db::query_repository::lesson::SectionData;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SectionCreatedPayload {
+    pub section_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SectionCreatePayload {
+    pub course_id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SectionDeletePayload {
+    pub section_id: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SectionUpdatePayload {
+    pub section_id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SectionChangeOrderPayload {
+    pub section_id: i64,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SectionDataPayload {
+    pub id: i64,
+    pub course_id: i64,
+    pub title: String,
+    pub description: String,
+    pub section_order: i32,
+}
+
+impl From<SectionData> for SectionDataPayload {
+    fn from(section_data: SectionData) -> Self {
+        Self {
+            id: section_data.id,
+            course_id: section_data.course_id,
+            title: section_data.title,
+            description: section_data.description,
+            section_order: section_data.section_order,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use lib_core::models::similarity_flag::SimilarityComparison;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::exercise_completion::ExerciseCompletionPayload;
+
+#[derive(Serialize, ToSchema)]
+pub struct SimilarityComparisonPayload {
+    pub similarity_flag_id: i64,
+    /// Similarity in `[0, 1]`.
+    pub score: f32,
+    pub first: ExerciseCompletionPayload,
+    pub second: ExerciseCompletionPayload,
+}
+
+impl From<SimilarityComparison> for SimilarityComparisonPayload {
+    fn from(value: SimilarityComparison) -> Self {
+        Self {
+            similarity_flag_id: value.id,
+            score: value.score,
+            first: value.first.into(),
+            second: value.second.into(),
+        }
+    }
+}
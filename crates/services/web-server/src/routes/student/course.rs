@@ -1,15 +1,16 @@
-use axum::{extract::{Query, State}, routing::{get, put}, Json, Router};
-use lib_core::interactors::student::course::StudentCourseInteractor;
+use axum::{extract::{Query, State}, routing::{get, post, put}, Json, Router};
+use lib_core::interactors::student::{course::StudentCourseInteractor, lesson::StudentLessonInteractor};
 use lib_db::query_repository::course::CourseQuery;
 use serde_json::{json, Value};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::course::{CourseFilterPayload, CourseId, CoursePayload, CoursesPayload}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::course::{CourseFilterPayload, CourseId, CoursePayload, CoursesPayload, PlacementResultPayload, PlacementTestTakePayload}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/register_for_course", put(api_register_for_course_handler))
 		.route("/unsubscribe_from_course", put(api_unsubscribe_from_course_handler))
 		.route("/get_user_courses_registered/", get(api_get_user_courses_registered_handler))
+		.route("/take_placement_test", post(api_take_placement_test_handler))
 		.with_state(app_state)
 }
 
@@ -134,4 +135,31 @@ async fn api_get_user_courses_registered_handler(
 	};
 
 	Ok(Json(result))
+}
+
+#[utoipa::path(
+	post,
+	path = "/api/course/take_placement_test",
+	request_body = PlacementTestTakePayload,
+	responses(
+		(status = 200, body = PlacementResultPayload, description = "Score of the placement test and the lessons it skipped"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_take_placement_test_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<PlacementTestTakePayload>,
+) -> AppResult<Json<PlacementResultPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = StudentLessonInteractor::new(command_repository_manager);
+
+	let answers = payload.answers.into_iter().map(|answer| answer.into()).collect();
+	let result = lesson_interactor.take_placement_test(&ctx, payload.course_id, answers).await?;
+
+	Ok(Json(result.into()))
 }
\ No newline at end of file
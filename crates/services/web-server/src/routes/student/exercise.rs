@@ -1,21 +1,22 @@
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{extract::{Path, State}, routing::{get, post}, Json, Router};
 use lib_core::interactors::student::exercise::StudentExerciseInteractor;
 use serde_json::{json, Value};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::exercise::{ExerciseCompletionForSaveChanges, ExerciseCompletionId, ExerciseEstimatePayload, ExerciseId}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::exercise::{ConspectDiffPayload, ExerciseCompletionForSaveChanges, ExerciseCompletionId, ExerciseEstimatePayload, ExerciseForStart}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/start_exercise", post(api_start_exercise_handler))
 		.route("/save_changes", post(api_save_changes_handler))
 		.route("/complete_attempt", post(api_complete_attempt_handler))
+		.route("/get_attempt_diff/:i64", get(api_get_attempt_diff_handler))
 		.with_state(app_state)
 }
 
 #[utoipa::path(
 	post,
 	path = "/api/course/lesson/exercise/start_exercise",
-	request_body = ExerciseId,
+	request_body = ExerciseForStart,
 	responses(
 		(status = 200, body = ExerciseCompletionId, description = "Exercise started successfully"),
 	),
@@ -26,14 +27,14 @@ pub fn routes(app_state: AppState) -> Router {
 async fn api_start_exercise_handler(
     ctx: CtxW,
 	State(app_state): State<AppState>,
-	Json(payload): Json<ExerciseId>,
+	Json(payload): Json<ExerciseForStart>,
 ) -> AppResult<Json<ExerciseCompletionId>> {
     let ctx = ctx.0;
 
     let repository_manager = app_state.command_repository_manager;
     let exercise_interactor = StudentExerciseInteractor::new(repository_manager);
 
-    let id = exercise_interactor.start_exercise(&ctx, payload.exercise_id).await?;
+    let id = exercise_interactor.start_exercise(&ctx, payload.exercise_id, payload.is_practice).await?;
 	
 	let result = ExerciseCompletionId { 
 		exercise_completion_id: id,
@@ -98,5 +99,33 @@ async fn api_complete_attempt_handler(
     let result = exercise_interactor.complete_exercise(&ctx, payload.exercise_completion_id).await?;
 	
 
+	Ok(Json(result.into()))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/get_attempt_diff/{exercise_completion_id}",
+	params(
+		("exercise_completion_id", description = "ID of the completed exercise attempt")
+	),
+	responses(
+		(status = 200, body = ConspectDiffPayload, description = "Diff between the attempt and the answer"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_attempt_diff_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(exercise_completion_id): Path<i64>,
+) -> AppResult<Json<ConspectDiffPayload>> {
+    let ctx = ctx.0;
+
+    let repository_manager = app_state.command_repository_manager;
+    let exercise_interactor = StudentExerciseInteractor::new(repository_manager);
+
+    let result = exercise_interactor.get_exercise_completion_diff(&ctx, exercise_completion_id).await?;
+
 	Ok(Json(result.into()))
 }
\ No newline at end of file
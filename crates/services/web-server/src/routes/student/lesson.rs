@@ -1,12 +1,16 @@
-use axum::{extract::State, routing::put, Json, Router};
-use lib_core::interactors::student::lesson::StudentLessonInteractor;
+use axum::{extract::{Path, State}, routing::{get, put}, Json, Router};
+use lib_core::interactors::student::{attachment::StudentAttachmentInteractor, lesson::StudentLessonInteractor};
 use serde_json::{json, Value};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::lesson::StartLessonPayload};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{attachment::AttachmentLinkPayload, lesson::{ExamSubmitPayload, StartLessonPayload}}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/start_lesson", put(api_start_lesson_handler))
+		.route("/submit_exam", put(api_submit_exam_handler))
+		.route("/pause_lesson", put(api_pause_lesson_handler))
+		.route("/resume_lesson", put(api_resume_lesson_handler))
+		.route("/get_attachment_link/:i64", get(api_get_attachment_link_handler))
 		.with_state(app_state)
 }
 
@@ -41,3 +45,130 @@ async fn api_start_lesson_handler(
 
 	Ok(body)
 }
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/submit_exam",
+	request_body = ExamSubmitPayload,
+	responses(
+		(status = 200, description = "Exam submitted successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_submit_exam_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<ExamSubmitPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = StudentLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.submit_exam(&ctx, paylod.lesson_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/pause_lesson",
+	request_body = StartLessonPayload,
+	responses(
+		(status = 200, description = "Lesson paused successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_pause_lesson_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<StartLessonPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = StudentLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.pause_lesson(&ctx, paylod.lesson_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/resume_lesson",
+	request_body = StartLessonPayload,
+	responses(
+		(status = 200, description = "Lesson resumed successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_resume_lesson_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(paylod): Json<StartLessonPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = StudentLessonInteractor::new(command_repository_manager);
+
+    lesson_interactor.resume_lesson(&ctx, paylod.lesson_id).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/get_attachment_link/{attachment_id}",
+	params(
+		("attachment_id", description = "ID of the attachment")
+	),
+	responses(
+		(status = 200, description = "Short-lived download link issued to the current user", body = AttachmentLinkPayload),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_attachment_link_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(attachment_id): Path<i64>,
+) -> AppResult<Json<AttachmentLinkPayload>> {
+	let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let attachment_interactor = StudentAttachmentInteractor::new(command_repository_manager);
+
+	let link = attachment_interactor.get_download_link(&ctx, attachment_id).await?;
+
+	Ok(Json(AttachmentLinkPayload {
+		url: format!("/api/attachment/{}", link.token),
+		expires_at: link.expires_at,
+	}))
+}
\ No newline at end of file
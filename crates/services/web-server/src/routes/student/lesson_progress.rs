@@ -1,10 +1,12 @@
 use axum::{extract::{Query, State}, routing::get, Json, Router};
+use lib_core::interactors::student::lesson::StudentLessonInteractor;
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::lesson_progress::{GetLessonProgressesPayload, LessonProgressPayload}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::lesson_progress::{GetLessonProgressesPayload, LessonLockPayload, LessonProgressPayload}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/get_lesson_progresses", get(api_get_lesson_progresses_handler))
+		.route("/get_lesson_locks", get(api_get_lesson_locks_handler))
 		.with_state(app_state)
 }
 
@@ -39,3 +41,37 @@ async fn api_get_lesson_progresses_handler(
 
 	Ok(Json(lesson_progresses_data))
 }
+
+/// Lessons without a progress that the student can't start yet.
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/get_lesson_locks",
+	params(
+		GetLessonProgressesPayload
+	),
+	responses(
+		(status = 200, body = Vec<LessonLockPayload>),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_lesson_locks_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Query(paylod): Query<GetLessonProgressesPayload>,
+) -> AppResult<Json<Vec<LessonLockPayload>>> {
+    let ctx = ctx.0;
+
+	let command_repository_manager = app_state.command_repository_manager;
+	let lesson_interactor = StudentLessonInteractor::new(command_repository_manager);
+
+	let lesson_locks = lesson_interactor
+		.get_lesson_locks(&ctx, paylod.course_id)
+		.await?
+		.into_iter()
+		.map(|lesson_lock| lesson_lock.into())
+		.collect();
+
+	Ok(Json(lesson_locks))
+}
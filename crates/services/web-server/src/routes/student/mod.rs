@@ -1,4 +1,5 @@
 pub mod course;
 pub mod lesson;
 pub mod lesson_progress;
-pub mod exercise;
\ No newline at end of file
+pub mod exercise;
+pub mod peer_review;
\ No newline at end of file
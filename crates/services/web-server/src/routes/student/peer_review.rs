@@ -0,0 +1,83 @@
+use axum::{extract::{Path, State}, routing::{get, put}, Json, Router};
+use lib_core::{interactors::student::peer_review::StudentPeerReviewInteractor, models::peer_review::PeerReviewForSubmit};
+use serde_json::{json, Value};
+
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::peer_review::{PeerReviewAssignmentPayload, PeerReviewSubmitPayload}};
+
+pub fn routes(app_state: AppState) -> Router {
+	Router::new()
+		.route("/get_assignments/:i64", get(api_get_peer_review_assignments_handler))
+		.route("/submit", put(api_submit_peer_review_handler))
+		.with_state(app_state)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/peer_review/get_assignments/{exercise_id}",
+	params(
+		("exercise_id", description = "ID of the exercise")
+	),
+	responses(
+		(status = 200, body = Vec<PeerReviewAssignmentPayload>, description = "Anonymized attempts assigned to the student"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_peer_review_assignments_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(exercise_id): Path<i64>,
+) -> AppResult<Json<Vec<PeerReviewAssignmentPayload>>> {
+    let ctx = ctx.0;
+
+    let repository_manager = app_state.command_repository_manager;
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager);
+
+    let result = peer_review_interactor
+		.get_peer_review_assignments(&ctx, exercise_id)
+		.await?
+		.into_iter()
+		.map(|assignment| assignment.into())
+		.collect();
+
+	Ok(Json(result))
+}
+
+#[utoipa::path(
+	put,
+	path = "/api/course/lesson/exercise/peer_review/submit",
+	request_body = PeerReviewSubmitPayload,
+	responses(
+		(status = 200, description = "Peer review submitted successfully"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_submit_peer_review_handler(
+    ctx: CtxW,
+	State(app_state): State<AppState>,
+	Json(payload): Json<PeerReviewSubmitPayload>,
+) -> AppResult<Json<Value>> {
+    let ctx = ctx.0;
+
+	let peer_review_for_s = PeerReviewForSubmit {
+		id: payload.peer_review_id,
+		rubric_scores: payload.rubric_scores,
+		comment: payload.comment,
+	};
+
+    let repository_manager = app_state.command_repository_manager;
+    let peer_review_interactor = StudentPeerReviewInteractor::new(repository_manager);
+
+    peer_review_interactor.submit_peer_review(&ctx, peer_review_for_s).await?;
+
+	let body = Json(json!({
+		"result": {
+			"success": true,
+		}
+	}));
+
+	Ok(body)
+}
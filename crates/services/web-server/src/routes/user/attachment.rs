@@ -0,0 +1,62 @@
+use axum::{extract::{Path, State}, http::header, response::IntoResponse, routing::get, Router};
+use lib_core::interactors::student::attachment::StudentAttachmentInteractor;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::{app_state::AppState, error::AppResult};
+
+/// Everything but the `attr-char` of RFC 5987.
+const FILE_NAME_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+	.remove(b'!').remove(b'#').remove(b'$').remove(b'&').remove(b'+').remove(b'-')
+	.remove(b'.').remove(b'^').remove(b'_').remove(b'`').remove(b'|').remove(b'~');
+
+/// Opened straight from the browser, so the route is not behind the bearer auth, 
+/// the signed token in the path is the credential.
+pub fn routes(app_state: AppState) -> Router {
+	Router::new()
+		.route("/api/attachment/:token", get(api_download_attachment_handler))
+		.with_state(app_state)
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/attachment/{token}",
+	params(
+		("token", description = "Signed token from get_attachment_link")
+	),
+	responses(
+		(status = 200, description = "File content", content_type = "application/octet-stream", body = Vec<u8>),
+	)
+)]
+async fn api_download_attachment_handler(
+	State(app_state): State<AppState>,
+	Path(token): Path<String>,
+) -> AppResult<impl IntoResponse> {
+	let command_repository_manager = app_state.command_repository_manager;
+	let attachment_interactor = StudentAttachmentInteractor::new(command_repository_manager);
+
+	let attachment = attachment_interactor.download_attachment(&token).await?;
+
+	let headers = [
+		(header::CONTENT_TYPE, attachment.content_type),
+		(header::CONTENT_DISPOSITION, content_disposition(&attachment.file_name)),
+	];
+
+	Ok((headers, attachment.data))
+}
+
+/// A quoted ASCII `filename` for old clients and the exact name in `filename*`.
+fn content_disposition(file_name: &str) -> String {
+	let ascii_file_name: String = file_name
+		.chars()
+		.map(|c| if c.is_ascii() && !c.is_ascii_control() { c } else { '_' })
+		.collect();
+	let quoted_file_name = ascii_file_name.replace('\\', "\\\\").replace('"', "\\\"");
+
+	if ascii_file_name == file_name {
+		return format!("attachment; filename=\"{quoted_file_name}\"");
+	}
+
+	let encoded_file_name = utf8_percent_encode(file_name, FILE_NAME_ENCODE_SET);
+
+	format!("attachment; filename=\"{quoted_file_name}\"; filename*=UTF-8''{encoded_file_name}")
+}
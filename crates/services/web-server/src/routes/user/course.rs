@@ -1,13 +1,16 @@
 use axum::{extract::{Path, Query, State}, routing::get, Json, Router};
+use lib_core::models::unlock_policy::UnlockPolicy;
 use lib_db::query_repository::course::CourseQuery;
 use tracing::info;
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::course::{CourseFilterPayload, CoursePayload, CoursesPayload}};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::course::{CourseFilterPayload, CoursePayload, CoursesPayload, PlacementTestPayload, UnlockPolicyPayload}};
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/get_course/:i64", get(api_get_course_handler))
 		.route("/get_courses/", get(api_get_courses_handler))
+		.route("/get_placement_test/:i64", get(api_get_placement_test_handler))
+		.route("/get_unlock_policy/:i64", get(api_get_unlock_policy_handler))
 		.with_state(app_state)
 }
 
@@ -93,4 +96,62 @@ async fn api_get_courses_handler(
 	};
 
 	Ok(Json(result))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_placement_test/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = Option<PlacementTestPayload>, description = "Placement test of the course, null if there is none"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_placement_test_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Option<PlacementTestPayload>>> {
+	let ctx = ctx.0;
+
+	let course_query_repository = app_state.query_repository_manager.get_course_repository();
+	let placement_test = match course_query_repository.get_placement_test(&ctx, course_id).await? {
+		Some(placement_test) => Some(placement_test.try_into()?),
+		None => None,
+	};
+
+	Ok(Json(placement_test))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/get_unlock_policy/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body = UnlockPolicyPayload, description = "Unlock policy of the course, Sequential if it was never set"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_unlock_policy_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<UnlockPolicyPayload>> {
+	let ctx = ctx.0;
+
+	let course_query_repository = app_state.query_repository_manager.get_course_repository();
+	let unlock_policy = match course_query_repository.get_unlock_policy(&ctx, course_id).await? {
+		Some(unlock_policy) => unlock_policy.try_into()?,
+		None => UnlockPolicy::sequential(course_id).into(),
+	};
+
+	Ok(Json(unlock_policy))
 }
\ No newline at end of file
@@ -8,6 +8,7 @@ pub fn routes(app_state: AppState) -> Router {
 		.route("/get_lesson_exercises/:i64", get(api_get_lesson_exercises_handler))
 		.route("/get_exercise/:i64", get(api_get_exercise_handler))
 		.route("/get_exercise_completions/:i64", get(api_get_exercise_completions_handler))
+		.route("/get_exercise_practice_completions/:i64", get(api_get_exercise_practice_completions_handler))
 		.route("/get_exercises_completions/:i64", get(api_get_exercises_completions_handler))
 		.route("/get_number_of_lesson_completed_exercises/:i64", get(api_get_number_of_lesson_completed_exercises_handler))
 		.with_state(app_state)
@@ -99,7 +100,41 @@ async fn api_get_exercise_completions_handler(
         .get_exercise_completion_repository();
 
 	let exercise_completions = exercise_completion_query_repository
-        .get_exercise_completions(&ctx, exercise_id, user_id)
+        .get_exercise_completions(&ctx, exercise_id, user_id, false)
+        .await?
+        .iter()
+        .map(|exercise| exercise.clone().into()).collect();
+
+	Ok(Json(exercise_completions))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/exercise/get_exercise_practice_completions/{exercise_id}",
+	params(
+		("exercise_id", description = "ID of the exercise")
+	),
+	responses(
+		(status = 200, body=Vec<ExerciseCompletionPayload>, description = "Practice attempts, they are not part of the graded history"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_exercise_practice_completions_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(exercise_id): Path<i64>,
+) -> AppResult<Json<Vec<ExerciseCompletionPayload>>> {
+	let ctx = ctx.0;
+    let user_id = ctx.user_id();
+
+	let exercise_completion_query_repository = app_state
+        .query_repository_manager
+        .get_exercise_completion_repository();
+
+	let exercise_completions = exercise_completion_query_repository
+        .get_exercise_completions(&ctx, exercise_id, user_id, true)
         .await?
         .iter()
         .map(|exercise| exercise.clone().into()).collect();
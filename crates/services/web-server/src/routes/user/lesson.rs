@@ -1,12 +1,15 @@
 use axum::{extract::{Path, State}, routing::get, Json, Router};
 
-use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::lesson::LessonDataPayload};
+use crate::{app_state::AppState, error::AppResult, middleware::mw_auth::CtxW, routes::models::{attachment::AttachmentPayload, lesson::{ExamPayload, LessonContentPayload, LessonDataPayload}, section::SectionDataPayload}};
 
 
 pub fn routes(app_state: AppState) -> Router {
 	Router::new()
 		.route("/get_lessons/:i64", get(api_get_lessons_handler))
+		.route("/get_sections/:i64", get(api_get_sections_handler))
 		.route("/get_lesson/:i64", get(api_get_lesson_handler))
+		.route("/get_exam/:i64", get(api_get_exam_handler))
+		.route("/get_attachments/:i64", get(api_get_attachments_handler))
 		.with_state(app_state)
 }
 
@@ -40,6 +43,36 @@ async fn api_get_lessons_handler(
 	Ok(Json(lessons))
 }
 
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/get_sections/{course_id}",
+	params(
+		("course_id", description = "ID of the course")
+	),
+	responses(
+		(status = 200, body=Vec<SectionDataPayload>),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_sections_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(course_id): Path<i64>,
+) -> AppResult<Json<Vec<SectionDataPayload>>> {
+	let ctx = ctx.0;
+
+	let lesson_query_repository = app_state.query_repository_manager.get_lesson_repository();
+	let sections = lesson_query_repository
+        .get_course_sections_ordered(&ctx, course_id)
+        .await?
+        .into_iter()
+        .map(|section| section.into()).collect();
+
+	Ok(Json(sections))
+}
+
 #[utoipa::path(
 	get,
 	path = "/api/course/lesson/get_lesson/{lesson_id}",
@@ -47,7 +80,7 @@ async fn api_get_lessons_handler(
 		("lesson_id", description = "ID of the lesson")
 	),
 	responses(
-		(status = 200, body=LessonDataPayload),
+		(status = 200, body=LessonContentPayload),
 	),
 	security(
 		("bearerAuth" = [])
@@ -57,13 +90,82 @@ async fn api_get_lesson_handler(
 	ctx: CtxW,
 	State(app_state): State<AppState>,
 	Path(lesson_id): Path<i64>,
-) -> AppResult<Json<LessonDataPayload>> {
+) -> AppResult<Json<LessonContentPayload>> {
 	let ctx = ctx.0;
 
 	let lesson_query_repository = app_state.query_repository_manager.get_lesson_repository();
-	let lessons = lesson_query_repository
+	let lesson = lesson_query_repository
         .get(&ctx, lesson_id)
         .await?;
 
-	Ok(Json(lessons.into()))
+	let content_blocks = lesson_query_repository
+        .get_lesson_content_blocks(&ctx, lesson_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+	Ok(Json(LessonContentPayload {
+		lesson: lesson.into(),
+		content_blocks,
+	}))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/get_exam/{lesson_id}",
+	params(
+		("lesson_id", description = "ID of the lesson")
+	),
+	responses(
+		(status = 200, body=Option<ExamPayload>, description = "Exam settings, null for a regular lesson"),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_exam_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(lesson_id): Path<i64>,
+) -> AppResult<Json<Option<ExamPayload>>> {
+	let ctx = ctx.0;
+
+	let lesson_query_repository = app_state.query_repository_manager.get_lesson_repository();
+	let exam = lesson_query_repository
+        .get_exam(&ctx, lesson_id)
+        .await?;
+
+	Ok(Json(exam.map(Into::into)))
+}
+
+#[utoipa::path(
+	get,
+	path = "/api/course/lesson/get_attachments/{lesson_id}",
+	params(
+		("lesson_id", description = "ID of the lesson")
+	),
+	responses(
+		(status = 200, body=Vec<AttachmentPayload>),
+	),
+	security(
+		("bearerAuth" = [])
+	)
+)]
+async fn api_get_attachments_handler(
+	ctx: CtxW,
+	State(app_state): State<AppState>,
+	Path(lesson_id): Path<i64>,
+) -> AppResult<Json<Vec<AttachmentPayload>>> {
+	let ctx = ctx.0;
+
+	let lesson_query_repository = app_state.query_repository_manager.get_lesson_repository();
+	let attachments = lesson_query_repository
+        .get_lesson_attachments(&ctx, lesson_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+	Ok(Json(attachments))
 }
\ No newline at end of file
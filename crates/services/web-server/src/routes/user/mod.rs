@@ -3,4 +3,5 @@ pub mod login;
 pub mod register;
 pub mod course;
 pub mod lesson;
-pub mod exercise;
\ No newline at end of file
+pub mod exercise;
+pub mod attachment;
\ No newline at end of file
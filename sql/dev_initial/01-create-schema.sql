@@ -31,7 +31,10 @@ CREATE TABLE course (
   color varchar(256) NOT NULL,
   published_date timestamp with time zone,
   img_url varchar(256),
+  img_variants jsonb,
   state varchar(256) NOT NULL default 'Draft',
+  scheduled_publish_date timestamp with time zone,
+  scheduled_archive_date timestamp with time zone,
 
   -- Timestamps
   cid bigint NOT NULL,
@@ -60,15 +63,75 @@ CREATE TABLE users_courses (
   mtime timestamp with time zone NOT NULL  
 );
 
+-- Section grouping lessons of a course
+CREATE TABLE section (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  course_id BIGINT NOT NULL,
+
+  title varchar(256) NOT NULL,
+  description varchar(256) NOT NULL,
+  section_order integer NOT NULL,
+
+  CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES course(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
 -- Lesson
 CREATE TABLE lesson (
   id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
   course_id BIGINT NOT NULL,
+  -- Lessons without a section come before the sections
+  section_id BIGINT,
 
   title varchar(256) NOT NULL,
   description varchar(256) NOT NULL,
+  -- Order within the section
   lesson_order integer NOT NULL,
 
+  CONSTRAINT fk_section FOREIGN KEY (section_id) REFERENCES section(id),
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+-- Teaching material of a lesson
+CREATE TABLE content_block (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  lesson_id BIGINT NOT NULL,
+
+  -- Markdown, Image, Video, Attachment, Callout
+  block_type varchar(256) NOT NULL,
+  body jsonb NOT NULL,
+  -- Sanitized HTML rendered from the Markdown text of the block
+  rendered_html text NOT NULL,
+  block_order integer NOT NULL,
+
+  CONSTRAINT fk_lesson FOREIGN KEY (lesson_id) REFERENCES lesson(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+-- Files shared with a lesson, served by signed links only
+CREATE TABLE attachment (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  lesson_id BIGINT NOT NULL,
+
+  file_name varchar(256) NOT NULL,
+  content_type varchar(256) NOT NULL,
+  size bigint NOT NULL,
+  storage_key varchar(256) NOT NULL,
+
+  CONSTRAINT fk_lesson FOREIGN KEY (lesson_id) REFERENCES lesson(id) ON DELETE CASCADE,
+
   cid bigint NOT NULL,
   ctime timestamp with time zone NOT NULL,
   mid bigint NOT NULL,
@@ -86,6 +149,8 @@ CREATE TABLE lesson_progress (
   date_complete timestamp with time zone,  
 
   state varchar(256) NOT NULL default 'InProgress', 
+  skipped_by_placement boolean NOT NULL default false,
+  date_paused timestamp with time zone,
 
   -- PRIMARY KEY (user_id, lesson_id),
   CONSTRAINT fk_users_courses FOREIGN KEY (user_id) REFERENCES "user"(id) ON DELETE CASCADE,
@@ -110,6 +175,10 @@ CREATE TABLE exercise (
   answer_body jsonb NOT NULL,
   difficult varchar(256) NOT NULL default 'Read',
   time_to_complete integer,  
+  manual_review boolean NOT NULL default false,
+  auto_difficulty boolean NOT NULL default false,
+  peer_review_count integer NOT NULL default 0,
+  peer_review_rubric jsonb NOT NULL default '[]',
 
   CONSTRAINT fk_lesson FOREIGN KEY (lesson_id) REFERENCES lesson(id) ON DELETE CASCADE,
 
@@ -135,6 +204,9 @@ CREATE TABLE exercise_completion (
 
   state varchar(256) NOT NULL default 'InProgress', 
   body jsonb,
+  review_comment varchar(1024),
+  is_practice boolean NOT NULL default false,
+  paused_seconds bigint NOT NULL default 0,
 
   CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user"(id),
   CONSTRAINT fk_exercise FOREIGN KEY (exercise_id) REFERENCES exercise(id),
@@ -143,4 +215,170 @@ CREATE TABLE exercise_completion (
   ctime timestamp with time zone NOT NULL,
   mid bigint NOT NULL,
   mtime timestamp with time zone NOT NULL  
-);
\ No newline at end of file
+);
+
+-- Peer reviews of exercise completions
+CREATE TABLE peer_review (
+  peer_review_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  exercise_id BIGINT NOT NULL,
+  exercise_completion_id BIGINT NOT NULL,
+  reviewer_id BIGINT NOT NULL,
+
+  score float4,
+  rubric_scores jsonb,
+  comment varchar(1024),
+
+  state varchar(256) NOT NULL default 'Assigned',
+
+  CONSTRAINT fk_exercise FOREIGN KEY (exercise_id) REFERENCES exercise(id) ON DELETE CASCADE,
+  CONSTRAINT fk_exercise_completion FOREIGN KEY (exercise_completion_id) REFERENCES exercise_completion(exercise_completion_id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer FOREIGN KEY (reviewer_id) REFERENCES "user"(id) ON DELETE CASCADE,
+  CONSTRAINT unique_reviewer_completion UNIQUE (exercise_completion_id, reviewer_id),
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE exam (
+  exam_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  lesson_id BIGINT NOT NULL UNIQUE,
+
+  opens_at timestamp with time zone NOT NULL,
+  closes_at timestamp with time zone NOT NULL,
+  results_released boolean NOT NULL default false,
+
+  CONSTRAINT fk_lesson FOREIGN KEY (lesson_id) REFERENCES lesson(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE placement_test (
+  placement_test_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  course_id BIGINT NOT NULL UNIQUE,
+
+  exercise_ids jsonb NOT NULL,
+  levels jsonb NOT NULL,
+
+  CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES course(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE placement_attempt (
+  placement_attempt_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  course_id BIGINT NOT NULL,
+  user_id BIGINT NOT NULL,
+
+  score real NOT NULL,
+
+  UNIQUE (course_id, user_id),
+  CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES course(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user"(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE unlock_policy (
+  unlock_policy_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  course_id BIGINT NOT NULL UNIQUE,
+
+  policy varchar(256) NOT NULL default 'Sequential',
+  drip_releases jsonb NOT NULL default '[]',
+
+  CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES course(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE course_revision (
+  course_revision_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  course_id BIGINT NOT NULL UNIQUE,
+
+  edits jsonb NOT NULL default '[]',
+
+  CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES course(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE course_transfer (
+  course_transfer_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  course_id BIGINT NOT NULL,
+  from_user_id BIGINT NOT NULL,
+  to_user_id BIGINT NOT NULL,
+
+  state varchar(256) NOT NULL default 'Pending',
+  forced boolean NOT NULL default false,
+  date_resolved timestamp with time zone,
+
+  -- No foreign keys, the audit trail keeps the ids after the course or the users are deleted.
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+-- At most one pending transfer per course.
+CREATE UNIQUE INDEX course_transfer_pending_idx ON course_transfer (course_id) WHERE state = 'Pending';
+
+CREATE TABLE similarity_flag (
+  similarity_flag_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  exercise_id BIGINT NOT NULL,
+  first_exercise_completion_id BIGINT NOT NULL,
+  second_exercise_completion_id BIGINT NOT NULL,
+
+  score float4 NOT NULL,
+
+  CONSTRAINT fk_exercise FOREIGN KEY (exercise_id) REFERENCES exercise(id) ON DELETE CASCADE,
+  CONSTRAINT fk_first_exercise_completion FOREIGN KEY (first_exercise_completion_id) REFERENCES exercise_completion(exercise_completion_id) ON DELETE CASCADE,
+  CONSTRAINT fk_second_exercise_completion FOREIGN KEY (second_exercise_completion_id) REFERENCES exercise_completion(exercise_completion_id) ON DELETE CASCADE,
+  CONSTRAINT unique_similarity_pair UNIQUE (first_exercise_completion_id, second_exercise_completion_id),
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+CREATE TABLE difficulty_change (
+  difficulty_change_id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  exercise_id BIGINT NOT NULL,
+
+  old_difficulty varchar(256) NOT NULL,
+  new_difficulty varchar(256) NOT NULL,
+  pass_rate float4 NOT NULL,
+
+  CONSTRAINT fk_exercise FOREIGN KEY (exercise_id) REFERENCES exercise(id) ON DELETE CASCADE,
+
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);